@@ -1,24 +1,31 @@
 //! The build script requires that a user has provided a valid
-//! feature flag. If they provide too many feature flags, we fail.
+//! chip feature flag. If they provide too many chip feature flags, we fail.
+//!
+//! Other features, like `std`, don't select a chip.
 
 use std::env;
 
-// Keep this in sync with the available features
+// Keep this in sync with the available chip features
 static SUPPORTED_FEATURES: &[&str] = &[
     "imxrt1010",
+    "imxrt1015",
     "imxrt1020",
+    "imxrt1024",
     "imxrt1040",
     "imxrt1050",
     "imxrt1060",
     "imxrt1064",
     "imxrt1170",
     "imxrt1180",
+    "imxrt500",
+    "imxrt600",
 ];
 
 fn main() {
     let features: Vec<_> = env::vars()
         .map(|(key, _)| key)
         .flat_map(|key| key.strip_prefix("CARGO_FEATURE_").map(str::to_lowercase))
+        .filter(|feature| SUPPORTED_FEATURES.contains(&feature.as_str()))
         .collect();
 
     let feature_count = features.len();
@@ -0,0 +1,278 @@
+//! Generate, inspect, and compare serial NOR configuration blocks
+//!
+//! Like the library, the CLI supports one chip, selected by a feature:
+//!
+//! ```text
+//! cargo install --path cli --features imxrt1060
+//! ```
+//!
+//! `generate` lowers a description into a 512-byte configuration block. A
+//! `.toml` description is a `description::FlashPart`, and a `.json`
+//! description is a `description::SerialNor`. `dump` prints the report for a
+//! configuration block in a binary or ELF file, or decompiles it into Rust
+//! source with `--rust`. `diff` prints the fields that differ between two
+//! configuration blocks.
+//!
+//! In a binary file, `--offset` is a byte offset. In an ELF file, `--offset`
+//! is a load address.
+
+use clap::{Parser, Subcommand};
+use imxrt_boot_gen::{
+    description::{FlashPart, SerialNor},
+    hex::{self, Segment},
+    serial_flash::nor,
+    uf2, Imxrt,
+};
+use object::{Object, ObjectSegment};
+use std::{error::Error, fs, path::PathBuf, process::ExitCode};
+
+type Result<T> = std::result::Result<T, Box<dyn Error>>;
+
+#[derive(Parser)]
+#[command(name = "imxrt-boot-gen", version, about)]
+struct Cli {
+    #[command(subcommand)]
+    command: Cmd,
+}
+
+#[derive(Subcommand)]
+enum Cmd {
+    /// Write a configuration block from a TOML part or JSON description.
+    Generate {
+        /// The description file.
+        description: PathBuf,
+        /// The output file. A `.hex` file is Intel HEX, a `.srec` file is
+        /// S-records, and a `.uf2` file is UF2, all at the chip's
+        /// configuration block address. Anything else is a binary.
+        #[arg(short, long)]
+        output: PathBuf,
+        /// Also print Rust source for a TOML part.
+        #[arg(long)]
+        rust: bool,
+    },
+    /// Print the report for a configuration block.
+    Dump {
+        /// A binary or ELF file.
+        file: PathBuf,
+        /// The configuration block's offset, or its load address in an ELF file.
+        #[arg(long, default_value = "0", value_parser = parse_number)]
+        offset: u64,
+        /// Print Rust source that reproduces the configuration block.
+        #[arg(long)]
+        rust: bool,
+    },
+    /// Print the fields that differ between two configuration blocks.
+    Diff {
+        /// A binary or ELF file.
+        a: PathBuf,
+        /// Another binary or ELF file.
+        b: PathBuf,
+        /// The configuration block's offset in `a`.
+        #[arg(long, default_value = "0", value_parser = parse_number)]
+        offset_a: u64,
+        /// The configuration block's offset in `b`.
+        #[arg(long, default_value = "0", value_parser = parse_number)]
+        offset_b: u64,
+    },
+}
+
+/// Parse a decimal or `0x`-prefixed hexadecimal number.
+fn parse_number(number: &str) -> std::result::Result<u64, std::num::ParseIntError> {
+    let number = number.replace('_', "");
+    match number.strip_prefix("0x").or(number.strip_prefix("0X")) {
+        Some(hex) => u64::from_str_radix(hex, 16),
+        None => number.parse(),
+    }
+}
+
+fn generate(description: &PathBuf, rust: bool) -> Result<nor::ConfigurationBlock> {
+    let text = fs::read_to_string(description)?;
+    match description.extension().and_then(|ext| ext.to_str()) {
+        Some("toml") => {
+            let part = FlashPart::from_toml(&text)?;
+            if rust {
+                print!("{}", part.to_rust()?);
+            }
+            Ok(part.to_block()?)
+        }
+        Some("json") if !rust => Ok(serde_json::from_str::<SerialNor>(&text)?.to_block()?),
+        Some("json") => Err("--rust requires a TOML part".into()),
+        _ => Err("the description must be a .toml or .json file".into()),
+    }
+}
+
+/// Read the configuration block at `offset` in a binary or ELF file.
+fn read_block(file: &PathBuf, offset: u64) -> Result<nor::ConfigurationBlock> {
+    let data = fs::read(file)?;
+    let bytes = if data.starts_with(b"\x7FELF") {
+        elf_bytes(&data, offset)?
+    } else {
+        usize::try_from(offset)
+            .ok()
+            .and_then(|offset| data.get(offset..)?.get(..nor::ConfigurationBlock::SIZE))
+            .ok_or("the configuration block extends beyond the end of the file")?
+    };
+    Ok(nor::ConfigurationBlock::from_bytes(bytes.try_into()?)?)
+}
+
+/// Find the configuration block's bytes in the ELF segment that loads `address`.
+fn elf_bytes(data: &[u8], address: u64) -> Result<&[u8]> {
+    let elf = object::File::parse(data)?;
+    for segment in elf.segments() {
+        let (start, size) = segment.file_range();
+        let load = segment.address();
+        if (load..load + size).contains(&address) {
+            let offset = usize::try_from(start + address - load)?;
+            return data
+                .get(offset..offset + nor::ConfigurationBlock::SIZE)
+                .ok_or_else(|| "the configuration block extends beyond the ELF file".into());
+        }
+    }
+    Err(format!("no ELF segment loads address {address:#X}").into())
+}
+
+/// Split a report into keyed lines. Field lines are keyed by their offset and
+/// name, and lookup table lines by their index.
+fn report_lines(block: &nor::ConfigurationBlock) -> Vec<(String, String)> {
+    block
+        .to_string()
+        .lines()
+        .map(|line| {
+            let (key, value) = match line.trim_start().split_once("] ") {
+                Some((index, value)) if line.starts_with(' ') => (format!("{index}]"), value),
+                _ => {
+                    let mut parts = line.splitn(2, "  ");
+                    let offset = parts.next().unwrap_or_default();
+                    let (name, value) = parts
+                        .next()
+                        .unwrap_or_default()
+                        .split_once(' ')
+                        .unwrap_or((line, ""));
+                    (format!("{offset}  {name}"), value)
+                }
+            };
+            (key, value.trim().to_string())
+        })
+        .collect()
+}
+
+/// Returns the report lines that differ between `a` and `b`.
+fn diff(a: &nor::ConfigurationBlock, b: &nor::ConfigurationBlock) -> Vec<String> {
+    let (a, b) = (report_lines(a), report_lines(b));
+    let mut keys: Vec<&String> = Vec::new();
+    for (key, _) in a.iter().chain(&b) {
+        if !keys.contains(&key) {
+            keys.push(key);
+        }
+    }
+    let find = |lines: &[(String, String)], key: &str| {
+        lines
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, value)| value.clone())
+    };
+    let mut lines = Vec::new();
+    for key in keys {
+        let (a, b) = (find(&a, key), find(&b, key));
+        if a != b {
+            if let Some(a) = a {
+                lines.push(format!("- {key:<31}{a}"));
+            }
+            if let Some(b) = b {
+                lines.push(format!("+ {key:<31}{b}"));
+            }
+        }
+    }
+    lines
+}
+
+fn run(cli: Cli) -> Result<ExitCode> {
+    match cli.command {
+        Cmd::Generate {
+            description,
+            output,
+            rust,
+        } => {
+            let block = generate(&description, rust)?;
+            let segments = [Segment::serial_nor_fcb(&block)];
+            match output.extension().and_then(|ext| ext.to_str()) {
+                Some("hex") => fs::write(output, hex::to_intel_hex(&segments))?,
+                Some("srec" | "s19" | "s28" | "s37") => fs::write(output, hex::to_srec(&segments))?,
+                Some("uf2") => {
+                    let family_id = Imxrt::SELECTED
+                        .uf2_family_id()
+                        .ok_or("the chip doesn't have a UF2 family ID")?;
+                    fs::write(output, uf2::to_uf2(&segments, family_id))?
+                }
+                _ => fs::write(output, block.to_bytes())?,
+            }
+        }
+        Cmd::Dump { file, offset, rust } => {
+            let block = read_block(&file, offset)?;
+            if rust {
+                print!("{}", SerialNor::from_block(&block).to_rust()?);
+            } else {
+                print!("{block}");
+            }
+        }
+        Cmd::Diff {
+            a,
+            b,
+            offset_a,
+            offset_b,
+        } => {
+            let lines = diff(&read_block(&a, offset_a)?, &read_block(&b, offset_b)?);
+            for line in &lines {
+                println!("{line}");
+            }
+            if !lines.is_empty() {
+                return Ok(ExitCode::FAILURE);
+            }
+        }
+    }
+    Ok(ExitCode::SUCCESS)
+}
+
+fn main() -> ExitCode {
+    match run(Cli::parse()) {
+        Ok(code) => code,
+        Err(err) => {
+            eprintln!("error: {err}");
+            ExitCode::from(2)
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{diff, parse_number};
+    use imxrt_boot_gen::{
+        flexspi::{self, LookupTable},
+        serial_flash::nor,
+    };
+
+    #[test]
+    fn numbers() {
+        assert_eq!(parse_number("1024"), Ok(1024));
+        assert_eq!(parse_number("0x6000_0000"), Ok(0x6000_0000));
+        assert!(parse_number("0xG").is_err());
+    }
+
+    #[test]
+    fn diff_fields() {
+        let block =
+            nor::ConfigurationBlock::new(flexspi::ConfigurationBlock::new(LookupTable::new()))
+                .page_size(256)
+                .sector_size(4096);
+        assert!(diff(&block, &block).is_empty());
+
+        let other = block.sector_size(65536);
+        assert_eq!(
+            diff(&block, &other),
+            [
+                "- 0x1C4  sectorSize              4096 bytes",
+                "+ 0x1C4  sectorSize              65536 bytes",
+            ]
+        );
+    }
+}
@@ -6,11 +6,9 @@
 
 pub use nor::ConfigurationBlock;
 
-use imxrt_boot_gen::flexspi::{self, opcodes::sdr::*, *};
-use imxrt_boot_gen::serial_flash::*;
+use imxrt_boot_gen::prelude::*;
 
-const DENSITY_BITS: u32 = 128 * 1024 * 1024;
-const DENSITY_BYTES: u32 = DENSITY_BITS / 8;
+const DENSITY: Density = Density::from_megabits(128);
 
 const SEQ_READ: Sequence = SequenceBuilder::new()
     .instr(Instr::new(CMD, Pads::One, 0xEB))
@@ -48,13 +46,11 @@ const LUT: LookupTable = LookupTable::new()
 
 const COMMON_CONFIGURATION_BLOCK: flexspi::ConfigurationBlock =
     flexspi::ConfigurationBlock::new(LUT)
-        .read_sample_clk_src(ReadSampleClockSource::LoopbackFromDQSPad)
-        .cs_hold_time(0x03)
-        .cs_setup_time(0x03)
+        .profile(Profile::IMXRT1010)
         .column_address_width(ColumnAddressWidth::OtherDevices)
         .device_mode_configuration(DeviceModeConfiguration::Disabled)
         .wait_time_cfg_commands(WaitTimeConfigurationCommands::disable())
-        .flash_size(SerialFlashRegion::A1, DENSITY_BYTES)
+        .flash_size(SerialFlashRegion::A1, DENSITY)
         .serial_clk_freq(SerialClockFrequency::MHz120)
         .serial_flash_pad_type(FlashPadType::Quad);
 
@@ -62,8 +58,29 @@ pub const SERIAL_NOR_CONFIGURATION_BLOCK: nor::ConfigurationBlock =
     nor::ConfigurationBlock::new(COMMON_CONFIGURATION_BLOCK)
         .page_size(256)
         .sector_size(4096)
-        .ip_cmd_serial_clk_freq(nor::SerialClockFrequency::MHz30);
+        .ip_cmd_serial_clk_freq(nor::SerialClockFrequency::MHz30)
+        .finish();
 
 #[no_mangle]
 #[cfg_attr(all(target_arch = "arm", target_os = "none"), link_section = ".fcb")]
 pub static FLEXSPI_CONFIGURATION_BLOCK: nor::ConfigurationBlock = SERIAL_NOR_CONFIGURATION_BLOCK;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The board's read sequence is the AT25SF preset's read.
+    ///
+    /// The board keeps its own lookup table so that its bytes don't change:
+    /// the preset also places a quad enable sequence and an erase block
+    /// sequence in the table.
+    #[test]
+    fn read_matches_preset() {
+        assert_eq!(
+            LUT.get_command(Command::Read),
+            nor::presets::adesto::AT25SF
+                .lookup_table()
+                .get_command(Command::Read)
+        );
+    }
+}
@@ -0,0 +1,96 @@
+//! FlexSPI configuration block (FCB) for the iMXRT1024EVK.
+//!
+//! This FCB is compatible with the 4MiB QuadSPI flash inside the iMXRT1024
+//! package. The iMXRT1024EVK boots from that flash.
+#![no_std]
+
+pub use nor::ConfigurationBlock;
+
+use imxrt_boot_gen::prelude::*;
+
+const DENSITY: Density = Density::from_mebibytes(4);
+
+const SEQ_READ: Sequence = SequenceBuilder::new()
+    .instr(Instr::new(CMD, Pads::One, 0xEB))
+    .instr(Instr::new(RADDR, Pads::Four, 0x18))
+    .instr(Instr::new(DUMMY, Pads::Four, 0x06))
+    .instr(Instr::new(READ, Pads::Four, 0x04))
+    .build();
+
+// Like the 1020 EVK's block, this block only describes the read. It doesn't enable
+// the device mode configuration, so the internal flash's quad enable bit must
+// already be set.
+const LUT: LookupTable = LookupTable::new().command(Command::Read, SEQ_READ);
+
+const COMMON_CONFIGURATION_BLOCK: flexspi::ConfigurationBlock =
+    flexspi::ConfigurationBlock::new(LUT)
+        .profile(Profile::IMXRT1024)
+        .column_address_width(ColumnAddressWidth::OtherDevices)
+        .device_mode_configuration(DeviceModeConfiguration::Disabled)
+        .wait_time_cfg_commands(WaitTimeConfigurationCommands::disable())
+        .flash_size(SerialFlashRegion::A1, DENSITY)
+        .serial_clk_freq(SerialClockFrequency::MHz100)
+        .serial_flash_pad_type(FlashPadType::Quad);
+
+pub const SERIAL_NOR_CONFIGURATION_BLOCK: nor::ConfigurationBlock =
+    nor::ConfigurationBlock::new(COMMON_CONFIGURATION_BLOCK)
+        .page_size(256)
+        .sector_size(4096)
+        .ip_cmd_serial_clk_freq(nor::SerialClockFrequency::NoChange)
+        .finish();
+
+#[no_mangle]
+#[cfg_attr(all(target_arch = "arm", target_os = "none"), link_section = ".fcb")]
+pub static FLEXSPI_CONFIGURATION_BLOCK: nor::ConfigurationBlock = SERIAL_NOR_CONFIGURATION_BLOCK;
+
+#[cfg(test)]
+mod tests {
+    use super::{DENSITY, SERIAL_NOR_CONFIGURATION_BLOCK};
+    use imxrt_boot_gen::Imxrt;
+
+    /// The 1020 EVK's words, resized for the 1024's internal flash.
+    ///
+    /// These words weren't dumped from a 1024 SDK build. They're the 1020 EVK
+    /// block from `tests/imxrt1020-evk.rs`, with a 4MiB flash size. Like that
+    /// test, they expect a serial NOR `deviceType` and a zero `blockSize`,
+    /// where the 1020 SDK has zero and 256KiB.
+    const EXPECTED: [u32; 128] = [
+        0x46434642, 0x00040156, 0x00000000, 0x01030300, 0x00000000, 0x00000000, 0x00000000,
+        0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+        0x00000000, 0x00000000, 0x00000000, 0x01040600, 0x00000000, 0x00000000, 0x00004000,
+        0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+        0x00000000, 0x00000000, 0x00000000, 0x00000000, 0xeb04180a, 0x06320426, 0x00000000,
+        0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+        0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+        0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+        0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+        0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+        0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+        0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+        0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+        0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+        0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+        0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+        0x00010000, 0x00100000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+        0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+        0x00000000, 0x00000000,
+    ];
+
+    #[test]
+    fn imxrt1024evk() {
+        let actual: [u32; 128] = unsafe { core::mem::transmute(SERIAL_NOR_CONFIGURATION_BLOCK) };
+        for (i, (a, e)) in actual.iter().zip(EXPECTED).enumerate() {
+            let offset = i * 4;
+            assert_eq!(
+                a.to_be_bytes(),
+                e.to_le_bytes(),
+                "Offset {offset:#X}\nACTUAL: {actual:?}\nEXPECTED: {EXPECTED:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn internal_flash_density() {
+        assert_eq!(Imxrt::Imxrt1024.internal_flash(), Some(DENSITY));
+    }
+}
@@ -0,0 +1,210 @@
+//! FlexSPI configuration block (FCB) for the iMXRT1050EVKB.
+//!
+//! This FCB is compatible with the S26KS512S HyperFlash storage found on the
+//! iMXRT1050EVKB. The board boots from HyperFlash by default.
+#![no_std]
+
+pub use nor::ConfigurationBlock;
+
+use imxrt_boot_gen::prelude::*;
+
+pub const SERIAL_NOR_CONFIGURATION_BLOCK: nor::ConfigurationBlock =
+    nor::presets::infineon::s26ks_fcb(nor::presets::infineon::S26KS512S);
+
+#[no_mangle]
+#[cfg_attr(all(target_arch = "arm", target_os = "none"), link_section = ".fcb")]
+pub static FLEXSPI_CONFIGURATION_BLOCK: nor::ConfigurationBlock = SERIAL_NOR_CONFIGURATION_BLOCK;
+
+#[cfg(test)]
+mod tests {
+    use super::SERIAL_NOR_CONFIGURATION_BLOCK;
+    use imxrt_boot_gen::prelude::*;
+
+    /// The S26KS512S configuration for the 1050 EVKB, one field at a time.
+    ///
+    /// The words are written by hand from the field values in NXP's 1050 EVKB
+    /// HyperFlash configuration. The lookup table words use the SDK's
+    /// `FLEXSPI_LUT_SEQ` encoding. They weren't dumped from an SDK build, and
+    /// they don't come from this crate's builders.
+    ///
+    /// The SDK's block has a 256KiB `blockSize` at offset 0x1D0. The 1050 boot
+    /// ROM doesn't use it, and this crate can't express it for the 1050, so
+    /// it's zero here.
+    #[rustfmt::skip]
+    const EXPECTED: [u32; 128] = [
+    /* 0x000 */ 0x4246_4346, // Tag
+    /*       */ 0x5601_0400, // Version
+    /*       */ 0,           // reserved
+    /*       */ 0x0303_0303, // columnAddressWidth = 3, csSetupTime = 3, csHoldTime = 3, readSampleClkSrc = DQS pad
+    /* 0x010 */ 0,           // waitTimeCfgCommands, deviceModeCfgEnable
+    /*       */ 0,           // deviceModeSeq
+    /*       */ 0,           // deviceModeArg
+    /*       */ 0,           // configCmdEnable
+    /* 0x020 */ 0,           // configCmdSeqs
+    /*       */ 0,
+    /*       */ 0,
+    /*       */ 0,           // reserved
+    /* 0x030 */ 0,           // cfgCmdArgs
+    /*       */ 0,
+    /*       */ 0,
+    /*       */ 0,           // reserved
+    /* 0x040 */ 0x0000_0059, // controllerMiscOption: DDR, word addressable, safe configuration, differential clock
+    /*       */ 0x0107_0801, // lutCustomSeqEnable = 1, serialClkFreq = 133MHz, sflashPadType = 8 pads, deviceType = serial NOR
+    /*       */ 0,           // reserved
+    /*       */ 0,           // reserved
+    /* 0x050 */ 0x0400_0000, // sflashA1Size: 64MiB
+    /*       */ 0,           // sflashA2Size
+    /*       */ 0,           // sflashB1Size
+    /*       */ 0,           // sflashB2Size
+    /* 0x060 */ 0,           // csPadSettingOverride
+    /*       */ 0,           // sclkPadSettingOverride
+    /*       */ 0,           // dataPadSettingOverride
+    /*       */ 0,           // dqsPadSettingOverride
+    /* 0x070 */ 0,           // timeoutInMs
+    /*       */ 0,           // commandInterval
+    /*       */ 0x0000_1010, // dataValidTime = {16u, 16u}: port A time_100ps = 16, delay_cells = 16
+    /*       */ 0x0001_000F, // busyBitPolarity = 1, busyOffset = 15
+    // lookupTable[0]: Read
+    /* 0x080 */ 0x8B18_87A0, // CMD_DDR 0xA0, RADDR_DDR 0x18
+    /*       */ 0xA704_8F10, // CADDR_DDR 0x10, READ_DDR 0x04
+    /*       */ 0x0000_0000,
+    /*       */ 0x0000_0000,
+    // lookupTable[1]: Read status: write 0x70 to word 0x555
+    /* 0x090 */ 0x8700_8700, // CMD_DDR 0x00, CMD_DDR 0x00
+    /*       */ 0x87AA_8700, // CMD_DDR 0x00, CMD_DDR 0xAA
+    /*       */ 0x8705_8700, // CMD_DDR 0x00, CMD_DDR 0x05
+    /*       */ 0x8770_8700, // CMD_DDR 0x00, CMD_DDR 0x70
+    // lookupTable[2]: Read status: read the status register
+    /* 0x0a0 */ 0x8B18_8780, // CMD_DDR 0x80, RADDR_DDR 0x18
+    /*       */ 0xB70B_8F10, // CADDR_DDR 0x10, DUMMY_RWDS_DDR 0x0B
+    /*       */ 0x0000_A704, // READ_DDR 0x04, STOP 0x00
+    /*       */ 0x0000_0000,
+    // lookupTable[3]: Write enable: write 0xAA to word 0x555
+    /* 0x0b0 */ 0x8700_8700, // CMD_DDR 0x00, CMD_DDR 0x00
+    /*       */ 0x87AA_8700, // CMD_DDR 0x00, CMD_DDR 0xAA
+    /*       */ 0x8705_8700, // CMD_DDR 0x00, CMD_DDR 0x05
+    /*       */ 0x87AA_8700, // CMD_DDR 0x00, CMD_DDR 0xAA
+    // lookupTable[4]: Write enable: write 0x55 to word 0x2AA
+    /* 0x0c0 */ 0x8700_8700, // CMD_DDR 0x00, CMD_DDR 0x00
+    /*       */ 0x8755_8700, // CMD_DDR 0x00, CMD_DDR 0x55
+    /*       */ 0x8702_8700, // CMD_DDR 0x00, CMD_DDR 0x02
+    /*       */ 0x8755_8700, // CMD_DDR 0x00, CMD_DDR 0x55
+    // lookupTable[5]: Erase sector: write 0x80 to word 0x555
+    /* 0x0d0 */ 0x8700_8700, // CMD_DDR 0x00, CMD_DDR 0x00
+    /*       */ 0x87AA_8700, // CMD_DDR 0x00, CMD_DDR 0xAA
+    /*       */ 0x8705_8700, // CMD_DDR 0x00, CMD_DDR 0x05
+    /*       */ 0x8780_8700, // CMD_DDR 0x00, CMD_DDR 0x80
+    // lookupTable[6]: Erase sector: write 0xAA to word 0x555
+    /* 0x0e0 */ 0x8700_8700, // CMD_DDR 0x00, CMD_DDR 0x00
+    /*       */ 0x87AA_8700, // CMD_DDR 0x00, CMD_DDR 0xAA
+    /*       */ 0x8705_8700, // CMD_DDR 0x00, CMD_DDR 0x05
+    /*       */ 0x87AA_8700, // CMD_DDR 0x00, CMD_DDR 0xAA
+    // lookupTable[7]: Erase sector: write 0x55 to word 0x2AA
+    /* 0x0f0 */ 0x8700_8700, // CMD_DDR 0x00, CMD_DDR 0x00
+    /*       */ 0x8755_8700, // CMD_DDR 0x00, CMD_DDR 0x55
+    /*       */ 0x8702_8700, // CMD_DDR 0x00, CMD_DDR 0x02
+    /*       */ 0x8755_8700, // CMD_DDR 0x00, CMD_DDR 0x55
+    // lookupTable[8]: Erase sector: write 0x30 to the sector
+    /* 0x100 */ 0x8B18_8700, // CMD_DDR 0x00, RADDR_DDR 0x18
+    /*       */ 0x8700_8F10, // CADDR_DDR 0x10, CMD_DDR 0x00
+    /*       */ 0x0000_8730, // CMD_DDR 0x30, STOP 0x00
+    /*       */ 0x0000_0000,
+    // lookupTable[9]: Page program: write 0xA0 to word 0x555
+    /* 0x110 */ 0x8700_8700, // CMD_DDR 0x00, CMD_DDR 0x00
+    /*       */ 0x87AA_8700, // CMD_DDR 0x00, CMD_DDR 0xAA
+    /*       */ 0x8705_8700, // CMD_DDR 0x00, CMD_DDR 0x05
+    /*       */ 0x87A0_8700, // CMD_DDR 0x00, CMD_DDR 0xA0
+    // lookupTable[10]: Page program: write the data
+    /* 0x120 */ 0x8B18_8700, // CMD_DDR 0x00, RADDR_DDR 0x18
+    /*       */ 0xA380_8F10, // CADDR_DDR 0x10, WRITE_DDR 0x80
+    /*       */ 0x0000_0000,
+    /*       */ 0x0000_0000,
+    // lookupTable[11]: Chip erase: write 0x80 to word 0x555
+    /* 0x130 */ 0x8700_8700, // CMD_DDR 0x00, CMD_DDR 0x00
+    /*       */ 0x87AA_8700, // CMD_DDR 0x00, CMD_DDR 0xAA
+    /*       */ 0x8705_8700, // CMD_DDR 0x00, CMD_DDR 0x05
+    /*       */ 0x8780_8700, // CMD_DDR 0x00, CMD_DDR 0x80
+    // lookupTable[12]: Chip erase: write 0xAA to word 0x555
+    /* 0x140 */ 0x8700_8700, // CMD_DDR 0x00, CMD_DDR 0x00
+    /*       */ 0x87AA_8700, // CMD_DDR 0x00, CMD_DDR 0xAA
+    /*       */ 0x8705_8700, // CMD_DDR 0x00, CMD_DDR 0x05
+    /*       */ 0x87AA_8700, // CMD_DDR 0x00, CMD_DDR 0xAA
+    // lookupTable[13]: Chip erase: write 0x55 to word 0x2AA
+    /* 0x150 */ 0x8700_8700, // CMD_DDR 0x00, CMD_DDR 0x00
+    /*       */ 0x8755_8700, // CMD_DDR 0x00, CMD_DDR 0x55
+    /*       */ 0x8702_8700, // CMD_DDR 0x00, CMD_DDR 0x02
+    /*       */ 0x8755_8700, // CMD_DDR 0x00, CMD_DDR 0x55
+    // lookupTable[14]: Chip erase: write 0x10 to word 0x555
+    /* 0x160 */ 0x8700_8700, // CMD_DDR 0x00, CMD_DDR 0x00
+    /*       */ 0x87AA_8700, // CMD_DDR 0x00, CMD_DDR 0xAA
+    /*       */ 0x8705_8700, // CMD_DDR 0x00, CMD_DDR 0x05
+    /*       */ 0x8710_8700, // CMD_DDR 0x00, CMD_DDR 0x10
+    // lookupTable[15]
+    /* 0x170 */ 0x0000_0000,
+    /*       */ 0x0000_0000,
+    /*       */ 0x0000_0000,
+    /*       */ 0x0000_0000,
+    /* 0x180 */ 0,           // lutCustomSeq[0]
+    /*       */ 0x0000_0102, // lutCustomSeq[1]: read status, two sequences from 1
+    /*       */ 0x0000_0302, // lutCustomSeq[2]: write enable, two sequences from 3
+    /*       */ 0x0000_0504, // lutCustomSeq[3]: erase sector, four sequences from 5
+    /* 0x190 */ 0x0000_0902, // lutCustomSeq[4]: page program, two sequences from 9
+    /*       */ 0x0000_0B04, // lutCustomSeq[5]: chip erase, four sequences from 11
+    /*       */ 0,
+    /*       */ 0,
+    /* 0x1a0 */ 0,
+    /*       */ 0,
+    /*       */ 0,
+    /*       */ 0,
+    /* 0x1b0 */ 0,           // reserved
+    /*       */ 0,
+    /*       */ 0,
+    /*       */ 0,
+    /* 0x1c0 */ 512,         // pageSize
+    /*       */ 256 * 1024,  // sectorSize
+    /*       */ 0x0000_0001, // ipCmdSerialClkFreq = 30MHz
+    /*       */ 0,           // reserved
+    /* 0x1d0 */ 0,           // reserved (blockSize = 256KiB in the SDK)
+    /*       */ 0,
+    /*       */ 0,
+    /*       */ 0,
+    /* 0x1e0 */ 0,
+    /*       */ 0,
+    /*       */ 0,
+    /*       */ 0,
+    /* 0x1f0 */ 0,
+    /*       */ 0,
+    /*       */ 0,
+    /*       */ 0,
+    ];
+
+    #[test]
+    fn imxrt1050evkb() {
+        let actual: [u32; 128] = unsafe { core::mem::transmute(SERIAL_NOR_CONFIGURATION_BLOCK) };
+        for (i, (a, e)) in actual.iter().zip(EXPECTED).enumerate() {
+            let offset = i * 4;
+            assert_eq!(
+                *a, e,
+                "Offset {offset:#X}\nACTUAL: {actual:#X?}\nEXPECTED: {EXPECTED:#X?}"
+            );
+        }
+    }
+
+    #[test]
+    fn hyperflash_read() {
+        let mem_cfg = SERIAL_NOR_CONFIGURATION_BLOCK.mem_cfg();
+        assert_eq!(
+            mem_cfg.get_column_address_width(),
+            ColumnAddressWidth::Hyperflash
+        );
+        assert_eq!(
+            mem_cfg.get_lookup_table().get_command(Command::Read),
+            SequenceBuilder::new()
+                .instr(Instr::new(ddr::CMD, Pads::Eight, 0xA0))
+                .instr(Instr::new(ddr::RADDR, Pads::Eight, 0x18))
+                .instr(Instr::new(ddr::CADDR, Pads::Eight, 0x10))
+                .instr(Instr::new(ddr::READ, Pads::Eight, 0x04))
+                .build()
+        );
+    }
+}
@@ -1,16 +1,14 @@
 //! FlexSPI configuration block (FCB) for the iMXRT1060EVK.
 //!
 //! This FCB is compatible with the IS25WP QuadSPI flash storage found on the
-//! iMXRT1060EVK.
+//! iMXRT1060EVK and iMXRT1060EVKB. The boards can also be reworked to boot from
+//! an S26KS512S HyperFlash; enable the `"hyperflash"` feature to use the
+//! HyperFlash FCB instead.
 #![no_std]
 
 pub use nor::ConfigurationBlock;
 
-use imxrt_boot_gen::flexspi::{self, opcodes::sdr::*, *};
-use imxrt_boot_gen::flexspi::{
-    FlashPadType, ReadSampleClockSource, SerialClockFrequency, SerialFlashRegion,
-};
-use imxrt_boot_gen::serial_flash::*;
+use imxrt_boot_gen::prelude::*;
 
 const SEQ_READ: Sequence = SequenceBuilder::new()
     .instr(Instr::new(CMD, Pads::One, 0xEB))
@@ -48,21 +46,99 @@ const LUT: LookupTable = LookupTable::new()
 
 const COMMON_CONFIGURATION_BLOCK: flexspi::ConfigurationBlock =
     flexspi::ConfigurationBlock::new(LUT)
-        .version(Version::new(1, 4, 0))
-        .read_sample_clk_src(ReadSampleClockSource::LoopbackFromDQSPad)
-        .cs_hold_time(3)
-        .cs_setup_time(3)
-        .controller_misc_options(0x10)
+        .profile(Profile::IMXRT1060)
         .serial_flash_pad_type(FlashPadType::Quad)
         .serial_clk_freq(SerialClockFrequency::MHz133)
-        .flash_size(SerialFlashRegion::A1, 8 * 1024 * 1024);
+        .flash_size(SerialFlashRegion::A1, Density::from_mebibytes(8));
 
-pub const SERIAL_NOR_CONFIGURATION_BLOCK: nor::ConfigurationBlock =
+/// The FCB for the IS25WP064 QuadSPI flash.
+pub const QSPI_CONFIGURATION_BLOCK: nor::ConfigurationBlock =
     nor::ConfigurationBlock::new(COMMON_CONFIGURATION_BLOCK)
         .page_size(256)
         .sector_size(4096)
-        .ip_cmd_serial_clk_freq(nor::SerialClockFrequency::MHz30);
+        .ip_cmd_serial_clk_freq(nor::SerialClockFrequency::MHz30)
+        .finish();
+
+/// The FCB for the S26KS512S HyperFlash.
+pub const HYPERFLASH_CONFIGURATION_BLOCK: nor::ConfigurationBlock =
+    nor::presets::infineon::s26ks_fcb(nor::presets::infineon::S26KS512S);
+
+#[cfg(not(feature = "hyperflash"))]
+pub const SERIAL_NOR_CONFIGURATION_BLOCK: nor::ConfigurationBlock = QSPI_CONFIGURATION_BLOCK;
+#[cfg(feature = "hyperflash")]
+pub const SERIAL_NOR_CONFIGURATION_BLOCK: nor::ConfigurationBlock = HYPERFLASH_CONFIGURATION_BLOCK;
 
 #[no_mangle]
 #[cfg_attr(all(target_arch = "arm", target_os = "none"), link_section = ".fcb")]
 pub static FLEXSPI_CONFIGURATION_BLOCK: nor::ConfigurationBlock = SERIAL_NOR_CONFIGURATION_BLOCK;
+
+#[cfg(test)]
+mod tests {
+    use super::{HYPERFLASH_CONFIGURATION_BLOCK, QSPI_CONFIGURATION_BLOCK};
+
+    /// The QuadSPI configuration, written by hand.
+    ///
+    /// The words weren't dumped from an SDK build. They're transcribed from the
+    /// field values in the 1060 EVK SDK's QuadSPI configuration, except for two
+    /// fields that are zero here. The SDK has an erase block sequence at offset
+    /// 0x100 and a 64KiB `blockSize` at offset 0x1D0. The 1060 boot ROM doesn't
+    /// use either, and this crate can't express them for the 1060.
+    const EXPECTED: [u32; 128] = [
+        0x46434642, 0x00040156, 0x00000000, 0x01030300, 0x00000000, 0x00000000, 0x00000000,
+        0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+        0x00000000, 0x00000000, 0x10000000, 0x01040800, 0x00000000, 0x00000000, 0x00008000,
+        0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+        0x00000000, 0x00000000, 0x00000000, 0x00000000, 0xeb04180a, 0x06320426, 0x00000000,
+        0x00000000, 0x05040424, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+        0x00000000, 0x00000000, 0x06040000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+        0x00000000, 0x00000000, 0x00000000, 0x20041808, 0x00000000, 0x00000000, 0x00000000,
+        0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+        0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x02041808, 0x04200000,
+        0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x60040000,
+        0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+        0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+        0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+        0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+        0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+        0x00010000, 0x00100000, 0x01000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+        0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+        0x00000000, 0x00000000,
+    ];
+
+    #[test]
+    fn imxrt1060evk() {
+        let actual: [u32; 128] = unsafe { core::mem::transmute(QSPI_CONFIGURATION_BLOCK) };
+        for (i, (a, e)) in actual.iter().zip(EXPECTED).enumerate() {
+            let offset = i * 4;
+            assert_eq!(
+                a.to_be_bytes(),
+                e.to_le_bytes(),
+                "Offset {offset:#X}\nACTUAL: {actual:?}\nEXPECTED: {EXPECTED:?}"
+            );
+        }
+    }
+
+    /// The HyperFlash fields, written by hand from the 1060 EVK SDK's
+    /// HyperFlash configuration.
+    ///
+    /// The SDK's HyperFlash configuration is the same as the 1050 EVKB's.
+    #[test]
+    fn imxrt1060evk_hyperflash() {
+        let actual = HYPERFLASH_CONFIGURATION_BLOCK.to_bytes();
+        let word =
+            |offset: usize| u32::from_le_bytes(actual[offset..offset + 4].try_into().unwrap());
+
+        // readSampleClkSrc, columnAddressWidth, controllerMiscOption
+        assert_eq!(actual[0x0C], 3);
+        assert_eq!(actual[0x0F], 3);
+        assert_eq!(word(0x40), 0x59);
+        // Read, and the status read command and status read.
+        assert_eq!(word(0x80), 0x8B18_87A0);
+        assert_eq!(word(0x84), 0xA704_8F10);
+        assert_eq!(word(0xA0), 0x8B18_8780);
+        assert_eq!(word(0xA4), 0xB70B_8F10);
+        assert_eq!(word(0xA8), 0x0000_A704);
+        assert_eq!(word(0x1C0), 512);
+        assert_eq!(word(0x1C4), 256 * 1024);
+    }
+}
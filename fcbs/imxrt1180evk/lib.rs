@@ -6,11 +6,7 @@
 
 pub use nor::ConfigurationBlock;
 
-use imxrt_boot_gen::flexspi::{self, opcodes::sdr::*, *};
-use imxrt_boot_gen::flexspi::{
-    FlashPadType, ReadSampleClockSource, SerialClockFrequency, SerialFlashRegion,
-};
-use imxrt_boot_gen::serial_flash::*;
+use imxrt_boot_gen::prelude::*;
 
 const SEQ_READ: Sequence = SequenceBuilder::new()
     .instr(Instr::new(CMD, Pads::One, 0xEB))
@@ -29,6 +25,10 @@ const SEQ_ERASE_SECTOR: Sequence = SequenceBuilder::new()
     .instr(Instr::new(CMD, Pads::One, 0x20))
     .instr(Instr::new(RADDR, Pads::One, 0x18))
     .build();
+const SEQ_ERASE_BLOCK: Sequence = SequenceBuilder::new()
+    .instr(Instr::new(CMD, Pads::One, 0xD8))
+    .instr(Instr::new(RADDR, Pads::One, 0x18))
+    .build();
 const SEQ_PAGE_PROGRAM: Sequence = SequenceBuilder::new()
     .instr(Instr::new(CMD, Pads::One, 0x02))
     .instr(Instr::new(RADDR, Pads::One, 0x18))
@@ -43,26 +43,24 @@ const LUT: LookupTable = LookupTable::new()
     .command(Command::ReadStatus, SEQ_READ_STATUS)
     .command(Command::WriteEnable, SEQ_WRITE_ENABLE)
     .command(Command::EraseSector, SEQ_ERASE_SECTOR)
+    .command(Command::EraseBlock, SEQ_ERASE_BLOCK)
     .command(Command::PageProgram, SEQ_PAGE_PROGRAM)
     .command(Command::ChipErase, SEQ_CHIP_ERASE);
 
 const COMMON_CONFIGURATION_BLOCK: flexspi::ConfigurationBlock =
     flexspi::ConfigurationBlock::new(LUT)
-        .version(Version::new(1, 4, 0))
-        .read_sample_clk_src(ReadSampleClockSource::LoopbackFromDQSPad)
-        .cs_hold_time(3)
-        .cs_setup_time(3)
-        .controller_misc_options(0x10)
+        .profile(Profile::IMXRT1180)
         .serial_flash_pad_type(FlashPadType::Quad)
         .serial_clk_freq(SerialClockFrequency::MHz133)
-        .flash_size(SerialFlashRegion::A1, 16 * 1024 * 1024);
+        .flash_size(SerialFlashRegion::A1, Density::from_mebibytes(16));
 
 pub const SERIAL_NOR_CONFIGURATION_BLOCK: nor::ConfigurationBlock =
     nor::ConfigurationBlock::new(COMMON_CONFIGURATION_BLOCK)
         .page_size(256)
         .sector_size(4 * 1024)
         .ip_cmd_serial_clk_freq(nor::SerialClockFrequency::MHz30)
-        .block_size(64 * 1024);
+        .block_size(64 * 1024)
+        .finish();
 
 #[no_mangle]
 #[cfg_attr(all(target_arch = "arm", target_os = "none"), link_section = ".fcb")]
@@ -74,10 +72,8 @@ mod tests {
 
     /// Magic numbers extracted from a build of the 1180 EVK's SDK.
     ///
-    /// The actual configuration has an instruction sequence at offset 0x100.
-    /// I dropped it when copying over the raw values, since we don't have
-    /// support for custom instruction sequences. Erase sector and erase chip
-    /// are both implemented.
+    /// This includes the SDK's erase block sequence at offset 0x100,
+    /// `Command::EraseBlock`, and its 64KiB `blockSize` at offset 0x1D0.
     const EXPECTED: [u32; 128] = [
         0x46434642, 0x00040156, 0x00000000, 0x01030300, 0x00000000, 0x00000000, 0x00000000,
         0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
@@ -88,7 +84,7 @@ mod tests {
         0x00000000, 0x00000000, 0x06040000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
         0x00000000, 0x00000000, 0x00000000, 0x20041808, 0x00000000, 0x00000000, 0x00000000,
         0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
-        0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x02041808, 0x04200000,
+        0x00000000, 0xd8041808, 0x00000000, 0x00000000, 0x00000000, 0x02041808, 0x04200000,
         0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x60040000,
         0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
         0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
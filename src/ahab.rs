@@ -0,0 +1,415 @@
+//! Advanced high assurance boot (AHAB) containers
+//!
+//! The 1180 boots from a container, not an image vector table. The container
+//! header describes up to [`MAX_IMAGES`] images, and it's followed by image
+//! array entries and an optional signature block. The boot ROM loads each
+//! image, checks its hash, and jumps to the entry point of the executable
+//! image.
+//!
+//! Like the other boot data structures, you're responsible for placing the
+//! container where the boot ROM expects it. Image offsets are relative to the
+//! start of the container.
+//!
+//! ```
+//! use imxrt_boot_gen::ahab::{Container, Image, ImageType};
+//!
+//! const CONTAINER: Container = Container::new()
+//!     .sw_version(1)
+//!     .image(
+//!         Image::new(ImageType::Executable, 0x2000, 0x1_0000)
+//!             .load_address(0x2800_2000)
+//!             .entry_point(0x2800_2000),
+//!     );
+//!
+//! #[link_section = ".container"]
+//! static CONTAINER_BYTES: [u8; CONTAINER.len()] = CONTAINER.to_array();
+//! ```
+//!
+//! # Signed images
+//!
+//! To sign the container, reserve a signature block with
+//! [`signature_block`](Container::signature_block). The signature block has
+//! zeroed placeholders for the SRK table and the signature, and a signing tool
+//! fills them in. The builder doesn't compute image hashes; the signing tool
+//! computes those, too.
+
+/// The most images that a container describes.
+pub const MAX_IMAGES: usize = 8;
+
+const HEADER_SIZE: usize = 16;
+const IMAGE_SIZE: usize = 128;
+const SIGNATURE_BLOCK_HEADER_SIZE: usize = 16;
+
+const CONTAINER_TAG: u8 = 0x87;
+const SIGNATURE_BLOCK_TAG: u8 = 0x90;
+const VERSION: u8 = 0x00;
+
+/// The kind of image
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum ImageType {
+    /// Executable code, with an entry point.
+    Executable = 0x3,
+    /// Data that's loaded, but not executed.
+    Data = 0x4,
+}
+
+/// The hash algorithm that checks an image
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum HashType {
+    /// SHA-256. This is the default.
+    Sha256 = 0,
+    /// SHA-384.
+    Sha384 = 1,
+    /// SHA-512.
+    Sha512 = 2,
+}
+
+/// The super root key set that authenticates the container
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum SrkSet {
+    /// The container isn't authenticated. This is the default.
+    None = 0,
+    /// NXP's keys.
+    Nxp = 1,
+    /// The OEM's keys.
+    Oem = 2,
+}
+
+/// An image array entry
+///
+/// Unless otherwise specified, the image loads to address zero, its entry point
+/// is zero, its core ID is zero, and it's checked with SHA-256.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Image {
+    image_type: ImageType,
+    offset: u32,
+    size: u32,
+    load_address: u64,
+    entry_point: u64,
+    core_id: u8,
+    hash_type: HashType,
+    encrypted: bool,
+    hash: [u8; 64],
+    iv: [u8; 32],
+}
+
+impl Image {
+    /// Describe an image that starts `offset` bytes after the start of the
+    /// container, and spans `size` bytes.
+    pub const fn new(image_type: ImageType, offset: u32, size: u32) -> Self {
+        Image {
+            image_type,
+            offset,
+            size,
+            load_address: 0,
+            entry_point: 0,
+            core_id: 0,
+            hash_type: HashType::Sha256,
+            encrypted: false,
+            hash: [0; 64],
+            iv: [0; 32],
+        }
+    }
+    /// Set the address where the boot ROM loads the image.
+    pub const fn load_address(mut self, load_address: u64) -> Self {
+        self.load_address = load_address;
+        self
+    }
+    /// Set the image's entry point.
+    pub const fn entry_point(mut self, entry_point: u64) -> Self {
+        self.entry_point = entry_point;
+        self
+    }
+    /// Set the ID of the core that runs the image.
+    ///
+    /// See your chip's reference manual for the core IDs.
+    pub const fn core_id(mut self, core_id: u8) -> Self {
+        assert!(core_id < 16, "The core ID must be less than 16");
+        self.core_id = core_id;
+        self
+    }
+    /// Set the hash algorithm.
+    pub const fn hash_type(mut self, hash_type: HashType) -> Self {
+        self.hash_type = hash_type;
+        self
+    }
+    /// Set the image's hash.
+    ///
+    /// A SHA-256 or SHA-384 hash occupies the start of the array.
+    pub const fn hash(mut self, hash: [u8; 64]) -> Self {
+        self.hash = hash;
+        self
+    }
+    /// Mark the image as encrypted, with the initialization vector `iv`.
+    pub const fn encrypted(mut self, iv: [u8; 32]) -> Self {
+        self.encrypted = true;
+        self.iv = iv;
+        self
+    }
+    /// Returns the image's offset from the start of the container.
+    pub const fn get_offset(&self) -> u32 {
+        self.offset
+    }
+    /// Returns the image's size, in bytes.
+    pub const fn get_size(&self) -> u32 {
+        self.size
+    }
+    /// Returns the image's flags.
+    pub const fn flags(&self) -> u32 {
+        (self.image_type as u32)
+            | ((self.core_id as u32) << 4)
+            | ((self.hash_type as u32) << 8)
+            | ((self.encrypted as u32) << 11)
+    }
+
+    const fn to_bytes(self) -> [u8; IMAGE_SIZE] {
+        use crate::raw::put;
+
+        let mut bytes = [0; IMAGE_SIZE];
+        put(&mut bytes, 0x00, &self.offset.to_le_bytes());
+        put(&mut bytes, 0x04, &self.size.to_le_bytes());
+        put(&mut bytes, 0x08, &self.load_address.to_le_bytes());
+        put(&mut bytes, 0x10, &self.entry_point.to_le_bytes());
+        put(&mut bytes, 0x18, &self.flags().to_le_bytes());
+        put(&mut bytes, 0x20, &self.hash);
+        put(&mut bytes, 0x60, &self.iv);
+        bytes
+    }
+}
+
+/// An AHAB container
+///
+/// Unless otherwise specified, the container isn't authenticated, and its
+/// software and fuse versions are zero.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Container {
+    srk_set: SrkSet,
+    srk_selection: u8,
+    revoke_mask: u8,
+    sw_version: u16,
+    fuse_version: u8,
+    images: [Image; MAX_IMAGES],
+    num_images: usize,
+    signature_block: Option<(u16, u16)>,
+}
+
+impl Default for Container {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Container {
+    /// Create a container without any images.
+    pub const fn new() -> Self {
+        Container {
+            srk_set: SrkSet::None,
+            srk_selection: 0,
+            revoke_mask: 0,
+            sw_version: 0,
+            fuse_version: 0,
+            images: [Image::new(ImageType::Data, 0, 0); MAX_IMAGES],
+            num_images: 0,
+            signature_block: None,
+        }
+    }
+    /// Set the software version.
+    pub const fn sw_version(mut self, sw_version: u16) -> Self {
+        self.sw_version = sw_version;
+        self
+    }
+    /// Set the fuse version, which the boot ROM compares with the anti-rollback
+    /// fuses.
+    pub const fn fuse_version(mut self, fuse_version: u8) -> Self {
+        self.fuse_version = fuse_version;
+        self
+    }
+    /// Authenticate the container with `srk_set`, using the key at
+    /// `srk_selection`.
+    pub const fn srk(mut self, srk_set: SrkSet, srk_selection: u8) -> Self {
+        assert!(srk_selection < 4, "The SRK selection must be less than 4");
+        self.srk_set = srk_set;
+        self.srk_selection = srk_selection;
+        self
+    }
+    /// Revoke the keys in `revoke_mask`, one bit per key.
+    pub const fn revoke_mask(mut self, revoke_mask: u8) -> Self {
+        assert!(revoke_mask < 16, "The revoke mask has four bits");
+        self.revoke_mask = revoke_mask;
+        self
+    }
+    /// Add an image array entry.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the container already has [`MAX_IMAGES`] images.
+    pub const fn image(mut self, image: Image) -> Self {
+        assert!(
+            self.num_images < MAX_IMAGES,
+            "The container has too many images"
+        );
+        self.images[self.num_images] = image;
+        self.num_images += 1;
+        self
+    }
+    /// Reserve a signature block, with `srk_table_len` bytes for the SRK table
+    /// and `signature_len` bytes for the signature.
+    ///
+    /// The sizes depend on the key type. Use the sizes from your signing tool.
+    pub const fn signature_block(mut self, srk_table_len: u16, signature_len: u16) -> Self {
+        self.signature_block = Some((srk_table_len, signature_len));
+        self
+    }
+    /// Returns the container's images.
+    pub const fn images(&self) -> &[Image] {
+        self.images.split_at(self.num_images).0
+    }
+    /// Returns the container header's flags.
+    pub const fn flags(&self) -> u32 {
+        (self.srk_set as u32)
+            | ((self.srk_selection as u32) << 4)
+            | ((self.revoke_mask as u32) << 8)
+    }
+    /// Returns the offset of the signature block from the start of the
+    /// container.
+    pub const fn signature_block_offset(&self) -> usize {
+        HEADER_SIZE + self.num_images * IMAGE_SIZE
+    }
+    /// Returns the offset of the SRK table from the start of the container, or
+    /// `None` if there's no signature block.
+    pub const fn srk_table_offset(&self) -> Option<usize> {
+        match self.signature_block {
+            Some(_) => Some(self.signature_block_offset() + SIGNATURE_BLOCK_HEADER_SIZE),
+            None => None,
+        }
+    }
+    /// Returns the offset of the signature from the start of the container, or
+    /// `None` if there's no signature block.
+    pub const fn signature_offset(&self) -> Option<usize> {
+        match (self.srk_table_offset(), self.signature_block) {
+            (Some(offset), Some((srk_table_len, _))) => Some(offset + srk_table_len as usize),
+            _ => None,
+        }
+    }
+    /// Returns the size of the container, including the signature block, in
+    /// bytes.
+    #[allow(clippy::len_without_is_empty)]
+    pub const fn len(&self) -> usize {
+        self.signature_block_offset() + self.signature_block_len()
+    }
+    /// Serialize the container into an array that's exactly [`len`](Self::len)
+    /// bytes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `N` isn't the container's length.
+    pub const fn to_array<const N: usize>(&self) -> [u8; N] {
+        use crate::raw::put;
+
+        assert!(
+            N == self.len(),
+            "The array length must equal the container length"
+        );
+        let mut bytes = [0; N];
+        put(&mut bytes, 0x0, &[VERSION]);
+        put(&mut bytes, 0x1, &(self.len() as u16).to_le_bytes());
+        put(&mut bytes, 0x3, &[CONTAINER_TAG]);
+        put(&mut bytes, 0x4, &self.flags().to_le_bytes());
+        put(&mut bytes, 0x8, &self.sw_version.to_le_bytes());
+        put(&mut bytes, 0xA, &[self.fuse_version, self.num_images as u8]);
+        if self.signature_block.is_some() {
+            let offset = self.signature_block_offset() as u16;
+            put(&mut bytes, 0xC, &offset.to_le_bytes());
+        }
+        let mut idx = 0;
+        while idx < self.num_images {
+            put(
+                &mut bytes,
+                HEADER_SIZE + idx * IMAGE_SIZE,
+                &self.images[idx].to_bytes(),
+            );
+            idx += 1;
+        }
+        if let Some((srk_table_len, _)) = self.signature_block {
+            let offset = self.signature_block_offset();
+            let srk_table_offset = SIGNATURE_BLOCK_HEADER_SIZE as u16;
+            let signature_offset = srk_table_offset + srk_table_len;
+            put(&mut bytes, offset, &[VERSION]);
+            put(
+                &mut bytes,
+                offset + 1,
+                &(self.signature_block_len() as u16).to_le_bytes(),
+            );
+            put(&mut bytes, offset + 3, &[SIGNATURE_BLOCK_TAG]);
+            put(&mut bytes, offset + 4, &srk_table_offset.to_le_bytes());
+            put(&mut bytes, offset + 10, &signature_offset.to_le_bytes());
+        }
+        bytes
+    }
+
+    const fn signature_block_len(&self) -> usize {
+        match self.signature_block {
+            Some((srk_table_len, signature_len)) => {
+                SIGNATURE_BLOCK_HEADER_SIZE + srk_table_len as usize + signature_len as usize
+            }
+            None => 0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Container, HashType, Image, ImageType, SrkSet};
+
+    const IMAGE: Image = Image::new(ImageType::Executable, 0x2000, 0x1_0000)
+        .load_address(0x2800_2000)
+        .entry_point(0x2800_2000)
+        .core_id(1)
+        .hash_type(HashType::Sha512);
+
+    #[test]
+    fn unsigned() {
+        const CONTAINER: Container = Container::new().sw_version(3).image(IMAGE);
+        const BYTES: [u8; CONTAINER.len()] = CONTAINER.to_array();
+        assert_eq!(BYTES.len(), 16 + 128);
+        assert_eq!(
+            &BYTES[..16],
+            &[0x00, 0x90, 0x00, 0x87, 0, 0, 0, 0, 3, 0, 0, 1, 0, 0, 0, 0]
+        );
+        assert_eq!(&BYTES[16..20], &0x2000u32.to_le_bytes());
+        assert_eq!(&BYTES[24..32], &0x2800_2000u64.to_le_bytes());
+        assert_eq!(&BYTES[40..44], &0x0213u32.to_le_bytes());
+    }
+
+    #[test]
+    fn signed() {
+        const CONTAINER: Container = Container::new()
+            .srk(SrkSet::Oem, 1)
+            .image(IMAGE)
+            .signature_block(0x110, 0x48);
+        const BYTES: [u8; CONTAINER.len()] = CONTAINER.to_array();
+        assert_eq!(CONTAINER.flags(), 0x12);
+        assert_eq!(CONTAINER.signature_block_offset(), 0x90);
+        assert_eq!(CONTAINER.srk_table_offset(), Some(0xA0));
+        assert_eq!(CONTAINER.signature_offset(), Some(0x1B0));
+        assert_eq!(BYTES.len(), 0x1F8);
+        assert_eq!(&BYTES[0x1..0x3], &[0xF8, 0x01]);
+        assert_eq!(&BYTES[0xC..0xE], &[0x90, 0x00]);
+        assert_eq!(
+            &BYTES[0x90..0xA0],
+            &[0x00, 0x68, 0x01, 0x90, 0x10, 0, 0, 0, 0, 0, 0x20, 0x01, 0, 0, 0, 0]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "too many images")]
+    fn too_many_images() {
+        let mut container = Container::new();
+        for _ in 0..9 {
+            container = container.image(IMAGE);
+        }
+    }
+}
@@ -0,0 +1,296 @@
+//! Bus encryption engine (BEE) headers for encrypted XIP
+//!
+//! The 1050 and 1060 can execute images that are encrypted in FlexSPI flash.
+//! The BEE has two regions. Each region has a key info block (KIB) and a
+//! protection region descriptor block (PRDB). The boot ROM reads the encrypted
+//! blocks, the EKIB and EPRDB, from fixed offsets in flash, then configures
+//! the BEE from them.
+//!
+//! Use [`Kib`] and [`Prdb`] to describe a region, and [`Region`] to find where
+//! the encrypted blocks belong. This module produces the plain blocks. You must
+//! encrypt them before you program them into flash:
+//!
+//! - encrypt the KIB with AES-128-ECB, using the key that the region's
+//!   `BEE_KEYn_SEL` fuse selects.
+//! - encrypt the PRDB with AES-128-CBC, using the KIB's key and IV.
+//!
+//! This crate doesn't implement encryption.
+//!
+//! ```
+//! use imxrt_boot_gen::bee::{Kib, Mode, Prdb, Region};
+//!
+//! const KIB: Kib = Kib::new([0x11; 16], [0x22; 16]);
+//! const PRDB: Prdb = Prdb::new(Mode::Ctr, 0x6000_2000, 0x6010_0000)
+//!     .counter([0x33; 12])
+//!     .fac(0x6000_2000, 0x6010_0000, 0);
+//!
+//! assert_eq!(Region::Zero.kib_offset(), 0x400);
+//! assert_eq!(Region::Zero.prdb_offset(), 0x480);
+//! let plain_kib = KIB.to_bytes();
+//! let plain_prdb = PRDB.to_bytes();
+//! # assert_eq!(plain_kib.len(), 32);
+//! # assert_eq!(plain_prdb.len(), 256);
+//! ```
+
+/// The size of a KIB, in bytes.
+pub const KIB_SIZE: usize = 32;
+/// The size of a PRDB, in bytes.
+pub const PRDB_SIZE: usize = 256;
+/// The maximum number of FAC regions in a PRDB.
+pub const MAX_FAC_REGIONS: usize = 4;
+
+const TAGL: u32 = 0x5F47_4154;
+const TAGH: u32 = 0x5244_4845;
+const VERSION: u32 = 0x5601_0000;
+const FAC_OFFSET: usize = 48;
+const FAC_SIZE: usize = 16;
+
+/// A BEE region
+///
+/// Each region has its own key. The `BEE_KEY0_SEL` and `BEE_KEY1_SEL` fuses
+/// select the key that decrypts the region's EKIB.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum Region {
+    /// Region 0, using the `BEE_KEY0_SEL` key.
+    Zero = 0,
+    /// Region 1, using the `BEE_KEY1_SEL` key.
+    One = 1,
+}
+
+impl Region {
+    /// Returns the offset of the region's EKIB, relative to the start of
+    /// flash.
+    pub const fn kib_offset(self) -> u32 {
+        0x400 * (self as u32 + 1)
+    }
+    /// Returns the offset of the region's EPRDB, relative to the start of
+    /// flash.
+    pub const fn prdb_offset(self) -> u32 {
+        self.kib_offset() + 0x80
+    }
+}
+
+/// A key info block
+///
+/// The BEE decrypts the region's PRDB with this key and IV.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Kib {
+    key: [u8; 16],
+    iv: [u8; 16],
+}
+
+impl Kib {
+    /// Create a KIB with an AES-128 key and IV.
+    pub const fn new(key: [u8; 16], iv: [u8; 16]) -> Self {
+        Kib { key, iv }
+    }
+    /// Returns the key.
+    pub const fn get_key(&self) -> [u8; 16] {
+        self.key
+    }
+    /// Returns the IV.
+    pub const fn get_iv(&self) -> [u8; 16] {
+        self.iv
+    }
+    /// Returns the plain KIB.
+    pub const fn to_bytes(&self) -> [u8; KIB_SIZE] {
+        let mut bytes = [0; KIB_SIZE];
+        crate::raw::put(&mut bytes, 0, &self.key);
+        crate::raw::put(&mut bytes, 16, &self.iv);
+        bytes
+    }
+}
+
+/// The AES mode that decrypts the protected region
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u32)]
+pub enum Mode {
+    /// AES-128-ECB.
+    Ecb = 0,
+    /// AES-128-CTR.
+    Ctr = 1,
+}
+
+/// A FAC region, which sets the protection level for part of a BEE region
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Fac {
+    start: u32,
+    end: u32,
+    level: u32,
+}
+
+/// A protection region descriptor block
+///
+/// The PRDB describes the region's address range, AES mode, and counter, and
+/// up to [`MAX_FAC_REGIONS`] FAC regions. The region spans from `start` up to,
+/// but excluding, `end`. Unless otherwise specified, the counter nonce and the
+/// lock options are zero.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Prdb {
+    mode: Mode,
+    start: u32,
+    end: u32,
+    lock_options: u32,
+    counter: [u8; 12],
+    facs: [Fac; MAX_FAC_REGIONS],
+    fac_count: usize,
+}
+
+impl Prdb {
+    /// Describe a region that decrypts `start..end` with `mode`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `start` or `end` isn't 1KiB aligned, or if the region is
+    /// empty.
+    pub const fn new(mode: Mode, start: u32, end: u32) -> Self {
+        assert!(
+            start.is_multiple_of(1024) && end.is_multiple_of(1024),
+            "The BEE region must be 1KiB aligned"
+        );
+        assert!(end > start, "The BEE region must not be empty");
+        Prdb {
+            mode,
+            start,
+            end,
+            lock_options: 0,
+            counter: [0; 12],
+            facs: [Fac {
+                start: 0,
+                end: 0,
+                level: 0,
+            }; MAX_FAC_REGIONS],
+            fac_count: 0,
+        }
+    }
+    /// Set the AES-CTR nonce.
+    ///
+    /// The nonce is the counter's upper 96 bits. The boot ROM fills the lower
+    /// 32 bits with the block address.
+    pub const fn counter(mut self, nonce: [u8; 12]) -> Self {
+        self.counter = nonce;
+        self
+    }
+    /// Set the lock options.
+    pub const fn lock_options(mut self, lock_options: u32) -> Self {
+        self.lock_options = lock_options;
+        self
+    }
+    /// Add a FAC region that spans `start..end`, with a protection `level`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are already [`MAX_FAC_REGIONS`] FAC regions, if the FAC
+    /// region isn't within the BEE region, or if `level` is greater than 3.
+    pub const fn fac(mut self, start: u32, end: u32, level: u32) -> Self {
+        assert!(
+            self.fac_count < MAX_FAC_REGIONS,
+            "The PRDB has too many FAC regions"
+        );
+        assert!(
+            self.start <= start && start < end && end <= self.end,
+            "The FAC region must be within the BEE region"
+        );
+        assert!(level <= 3, "The FAC protection level must be 0 through 3");
+        self.facs[self.fac_count] = Fac { start, end, level };
+        self.fac_count += 1;
+        self
+    }
+    /// Returns the AES mode.
+    pub const fn get_mode(&self) -> Mode {
+        self.mode
+    }
+    /// Returns the region's start address.
+    pub const fn get_start(&self) -> u32 {
+        self.start
+    }
+    /// Returns the region's end address, excluding the end.
+    pub const fn get_end(&self) -> u32 {
+        self.end
+    }
+    /// Returns the plain PRDB.
+    pub const fn to_bytes(&self) -> [u8; PRDB_SIZE] {
+        use crate::raw::put;
+
+        let mut bytes = [0; PRDB_SIZE];
+        put(&mut bytes, 0, &TAGL.to_le_bytes());
+        put(&mut bytes, 4, &TAGH.to_le_bytes());
+        put(&mut bytes, 8, &VERSION.to_le_bytes());
+        put(&mut bytes, 12, &(self.fac_count as u32).to_le_bytes());
+        put(&mut bytes, 16, &self.start.to_le_bytes());
+        put(&mut bytes, 20, &self.end.to_le_bytes());
+        put(&mut bytes, 24, &(self.mode as u32).to_le_bytes());
+        put(&mut bytes, 28, &self.lock_options.to_le_bytes());
+        put(&mut bytes, 32, &self.counter);
+
+        let mut idx = 0;
+        while idx < self.fac_count {
+            let fac = &self.facs[idx];
+            let offset = FAC_OFFSET + idx * FAC_SIZE;
+            put(&mut bytes, offset, &fac.start.to_le_bytes());
+            put(&mut bytes, offset + 4, &fac.end.to_le_bytes());
+            put(&mut bytes, offset + 8, &fac.level.to_le_bytes());
+            idx += 1;
+        }
+        bytes
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Kib, Mode, Prdb, Region};
+
+    #[test]
+    fn offsets() {
+        assert_eq!(Region::Zero.kib_offset(), 0x400);
+        assert_eq!(Region::Zero.prdb_offset(), 0x480);
+        assert_eq!(Region::One.kib_offset(), 0x800);
+        assert_eq!(Region::One.prdb_offset(), 0x880);
+    }
+
+    #[test]
+    fn kib() {
+        let bytes = Kib::new([0xAA; 16], [0xBB; 16]).to_bytes();
+        assert_eq!(&bytes[..16], &[0xAA; 16]);
+        assert_eq!(&bytes[16..], &[0xBB; 16]);
+    }
+
+    #[test]
+    fn prdb() {
+        let bytes = Prdb::new(Mode::Ctr, 0x6000_1000, 0x6000_8000)
+            .counter([0xCC; 12])
+            .lock_options(0xE)
+            .fac(0x6000_1000, 0x6000_4000, 2)
+            .to_bytes();
+        assert_eq!(&bytes[..4], b"TAG_");
+        assert_eq!(&bytes[4..8], b"EHDR");
+        assert_eq!(&bytes[8..12], &[0x00, 0x00, 0x01, 0x56]);
+        assert_eq!(&bytes[12..16], &1u32.to_le_bytes());
+        assert_eq!(&bytes[16..20], &0x6000_1000u32.to_le_bytes());
+        assert_eq!(&bytes[20..24], &0x6000_8000u32.to_le_bytes());
+        assert_eq!(&bytes[24..28], &1u32.to_le_bytes());
+        assert_eq!(&bytes[28..32], &0xEu32.to_le_bytes());
+        assert_eq!(&bytes[32..44], &[0xCC; 12]);
+        assert_eq!(&bytes[44..48], &[0; 4]);
+        assert_eq!(&bytes[48..52], &0x6000_1000u32.to_le_bytes());
+        assert_eq!(&bytes[52..56], &0x6000_4000u32.to_le_bytes());
+        assert_eq!(&bytes[56..60], &2u32.to_le_bytes());
+        assert!(bytes[60..].iter().all(|byte| *byte == 0));
+    }
+
+    #[test]
+    #[should_panic(expected = "within the BEE region")]
+    fn fac_outside_region() {
+        let _ = Prdb::new(Mode::Ecb, 0x6000_1000, 0x6000_2000).fac(0x6000_0000, 0x6000_2000, 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "too many FAC regions")]
+    fn too_many_facs() {
+        let mut prdb = Prdb::new(Mode::Ecb, 0x6000_0000, 0x6001_0000);
+        for idx in 0..5 {
+            prdb = prdb.fac(0x6000_0000 + idx * 0x1000, 0x6000_1000 + idx * 0x1000, 0);
+        }
+    }
+}
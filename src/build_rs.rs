@@ -0,0 +1,267 @@
+//! Build script helpers
+//!
+//! Some projects can't place the configuration block in a `.fcb` link
+//! section, like projects that flash the block with an external tool. Call
+//! [`write_fcb`] from your build script to write the block to `OUT_DIR`:
+//!
+//! ```no_run
+//! // build.rs
+//! use imxrt_boot_gen::flexspi::{self, LookupTable};
+//! use imxrt_boot_gen::serial_flash::nor;
+//!
+//! # const FCB: nor::ConfigurationBlock = nor::ConfigurationBlock::new(flexspi::ConfigurationBlock::new(LookupTable::new()));
+//! fn main() {
+//!     let out_dir = std::env::var("OUT_DIR").unwrap();
+//!     imxrt_boot_gen::build_rs::write_fcb(&FCB, &out_dir).unwrap();
+//! }
+//! ```
+//!
+//! Then, include the generated Rust source in your crate:
+//!
+//! ```ignore
+//! include!(concat!(env!("OUT_DIR"), "/fcb.rs"));
+//! ```
+//!
+//! If you're writing your own linker script, [`write_linker_script`] writes
+//! a fragment that places the `.fcb` section at the chip's configuration
+//! block address, and the `.ivt` and `.boot_data` sections after it.
+//!
+//! This module requires the `"std"` feature.
+
+use crate::serial_flash::nor;
+use crate::{ivt, FlexSpiInstance, Imxrt};
+use std::{fs, io, path::Path};
+
+/// The name of the configuration block binary.
+pub const FCB_BIN: &str = "fcb.bin";
+
+/// The name of the Rust source that includes the binary.
+pub const FCB_RS: &str = "fcb.rs";
+
+/// Write the configuration block to `fcb.bin` in `out_dir`, along with an
+/// `fcb.rs` that includes it.
+///
+/// `fcb.rs` defines `FCB`, a `static` byte array that includes `fcb.bin`
+/// by its absolute path.
+pub fn write_fcb(block: &nor::ConfigurationBlock, out_dir: impl AsRef<Path>) -> io::Result<()> {
+    let out_dir = out_dir.as_ref();
+    let bin = out_dir.join(FCB_BIN);
+    fs::write(&bin, block.to_bytes())?;
+    let bin = bin
+        .to_str()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "OUT_DIR is not valid UTF-8"))?;
+    let source = format!(
+        "/// The serial NOR configuration block generated by imxrt-boot-gen\n\
+         pub static FCB: [u8; {}] = *include_bytes!({bin:?});\n",
+        nor::ConfigurationBlock::SIZE
+    );
+    fs::write(out_dir.join(FCB_RS), source)
+}
+
+/// The name of the linker script fragment.
+pub const FCB_LD: &str = "imxrt-boot-gen.x";
+
+/// Returns a GNU LD fragment that places the boot structure sections for
+/// `chip`.
+///
+/// The fragment defines `__imxrt_boot_gen_flexspi_base` and
+/// `__imxrt_boot_gen_fcb_address`, then places input `.fcb` sections at the
+/// configuration block address. It asserts that the section is exactly one
+/// serial NOR configuration block.
+///
+/// If the chip boots with an [IVT](Imxrt::ivt_offset), the fragment also
+/// defines `__imxrt_boot_gen_ivt_address` and
+/// `__imxrt_boot_gen_boot_data_address`. It places input `.ivt` sections at
+/// the IVT address, and input `.boot_data` sections right after the IVT. The
+/// fragment asserts that each section is either empty or exactly one
+/// [`Ivt`](ivt::Ivt) or [`BootData`](ivt::BootData).
+///
+/// Your `FLASH` memory region must not overlap the boot structures.
+///
+/// ```
+/// use imxrt_boot_gen::{build_rs, Imxrt};
+///
+/// let fragment = build_rs::linker_script(Imxrt::Imxrt1170);
+/// assert!(fragment.contains("__imxrt_boot_gen_fcb_address = 0x30000400;"));
+/// ```
+pub fn linker_script(chip: Imxrt) -> String {
+    fragment(chip, chip.flexspi_base())
+}
+
+/// Returns a GNU LD fragment that places the `.fcb` section for `chip`
+/// booting from the FlexSPI `instance`, or `None` if the chip doesn't boot
+/// from that instance.
+///
+/// The fragment is otherwise the same as the [`linker_script`] fragment.
+///
+/// ```
+/// use imxrt_boot_gen::{build_rs, FlexSpiInstance, Imxrt};
+///
+/// let fragment = build_rs::instance_linker_script(Imxrt::Imxrt1180, FlexSpiInstance::FlexSpi2);
+/// assert!(fragment.unwrap().contains("__imxrt_boot_gen_fcb_address = 0x04000400;"));
+/// ```
+pub fn instance_linker_script(chip: Imxrt, instance: FlexSpiInstance) -> Option<String> {
+    chip.instance_base(instance)
+        .map(|flexspi_base| fragment(chip, flexspi_base))
+}
+
+fn fragment(chip: Imxrt, flexspi_base: u32) -> String {
+    let mut lines = vec![
+        format!("/* Boot structures for the {chip:?}, generated by imxrt-boot-gen */"),
+        format!("__imxrt_boot_gen_flexspi_base = 0x{flexspi_base:08X};"),
+        format!(
+            "__imxrt_boot_gen_fcb_address = 0x{:08X};",
+            flexspi_base + chip.fcb_offset()
+        ),
+    ];
+    let ivt_address = chip
+        .ivt_offset()
+        .map(|ivt_offset| flexspi_base + ivt_offset);
+    if let Some(ivt_address) = ivt_address {
+        lines.extend([
+            format!("__imxrt_boot_gen_ivt_address = 0x{ivt_address:08X};"),
+            format!(
+                "__imxrt_boot_gen_boot_data_address = 0x{:08X};",
+                ivt_address + ivt::Ivt::SIZE as u32
+            ),
+        ]);
+    }
+    lines.extend([
+        String::new(),
+        "SECTIONS".into(),
+        "{".into(),
+        "    .fcb __imxrt_boot_gen_fcb_address :".into(),
+        "    {".into(),
+        "        KEEP(*(.fcb))".into(),
+        "    }".into(),
+    ]);
+    if ivt_address.is_some() {
+        lines.extend([
+            "    .ivt __imxrt_boot_gen_ivt_address :".into(),
+            "    {".into(),
+            "        KEEP(*(.ivt))".into(),
+            "    }".into(),
+            "    .boot_data __imxrt_boot_gen_boot_data_address :".into(),
+            "    {".into(),
+            "        KEEP(*(.boot_data))".into(),
+            "    }".into(),
+        ]);
+    }
+    lines.extend([
+        "}".into(),
+        String::new(),
+        format!(
+            "ASSERT(SIZEOF(.fcb) == {}, \"The .fcb section is not one serial NOR configuration block\");",
+            nor::ConfigurationBlock::SIZE
+        ),
+    ]);
+    if ivt_address.is_some() {
+        lines.extend([
+            format!(
+                "ASSERT(SIZEOF(.ivt) == 0 || SIZEOF(.ivt) == {}, \"The .ivt section is not one IVT\");",
+                ivt::Ivt::SIZE
+            ),
+            format!(
+                "ASSERT(SIZEOF(.boot_data) == 0 || SIZEOF(.boot_data) == {}, \"The .boot_data section is not one boot data\");",
+                ivt::BootData::SIZE
+            ),
+        ]);
+    }
+    lines.into_iter().map(|line| line + "\n").collect()
+}
+
+/// Write the [`linker_script`] fragment to `imxrt-boot-gen.x` in `out_dir`.
+///
+/// Add `out_dir` to the linker search path, and `INCLUDE imxrt-boot-gen.x`
+/// in your linker script:
+///
+/// ```no_run
+/// // build.rs
+/// use imxrt_boot_gen::{build_rs, Imxrt};
+///
+/// let out_dir = std::env::var("OUT_DIR").unwrap();
+/// build_rs::write_linker_script(Imxrt::SELECTED, &out_dir).unwrap();
+/// println!("cargo:rustc-link-search={out_dir}");
+/// ```
+pub fn write_linker_script(chip: Imxrt, out_dir: impl AsRef<Path>) -> io::Result<()> {
+    fs::write(out_dir.as_ref().join(FCB_LD), linker_script(chip))
+}
+
+#[cfg(test)]
+mod test {
+    use super::{instance_linker_script, linker_script, write_fcb, FCB_BIN, FCB_RS};
+    use crate::flexspi::{self, LookupTable};
+    use crate::serial_flash::nor;
+    use crate::{FlexSpiInstance, Imxrt};
+
+    #[test]
+    fn write() {
+        let out_dir = std::env::temp_dir().join(format!("imxrt-boot-gen-{}", std::process::id()));
+        std::fs::create_dir_all(&out_dir).unwrap();
+        let block =
+            nor::ConfigurationBlock::new(flexspi::ConfigurationBlock::new(LookupTable::new()))
+                .page_size(256);
+        write_fcb(&block, &out_dir).unwrap();
+
+        let bin = std::fs::read(out_dir.join(FCB_BIN)).unwrap();
+        assert_eq!(bin, block.to_bytes());
+        let source = std::fs::read_to_string(out_dir.join(FCB_RS)).unwrap();
+        assert!(source.contains("pub static FCB: [u8; 512] = *include_bytes!("));
+        assert!(source.contains(FCB_BIN));
+
+        std::fs::remove_dir_all(out_dir).unwrap();
+    }
+
+    #[test]
+    fn linker_script_addresses() {
+        assert_eq!(
+            linker_script(Imxrt::Imxrt1060),
+            "\
+/* Boot structures for the Imxrt1060, generated by imxrt-boot-gen */
+__imxrt_boot_gen_flexspi_base = 0x60000000;
+__imxrt_boot_gen_fcb_address = 0x60000000;
+__imxrt_boot_gen_ivt_address = 0x60001000;
+__imxrt_boot_gen_boot_data_address = 0x60001020;
+
+SECTIONS
+{
+    .fcb __imxrt_boot_gen_fcb_address :
+    {
+        KEEP(*(.fcb))
+    }
+    .ivt __imxrt_boot_gen_ivt_address :
+    {
+        KEEP(*(.ivt))
+    }
+    .boot_data __imxrt_boot_gen_boot_data_address :
+    {
+        KEEP(*(.boot_data))
+    }
+}
+
+ASSERT(SIZEOF(.fcb) == 512, \"The .fcb section is not one serial NOR configuration block\");
+ASSERT(SIZEOF(.ivt) == 0 || SIZEOF(.ivt) == 32, \"The .ivt section is not one IVT\");
+ASSERT(SIZEOF(.boot_data) == 0 || SIZEOF(.boot_data) == 12, \"The .boot_data section is not one boot data\");
+"
+        );
+        let fragment = linker_script(Imxrt::Imxrt1180);
+        assert!(fragment.contains("_fcb_address = 0x28000400;"));
+        assert!(!fragment.contains(".ivt"));
+        assert!(linker_script(Imxrt::Imxrt1170).contains("_ivt_address = 0x30001000;"));
+    }
+
+    #[test]
+    fn instance_linker_script_addresses() {
+        assert_eq!(
+            instance_linker_script(Imxrt::Imxrt1180, FlexSpiInstance::FlexSpi1),
+            Some(linker_script(Imxrt::Imxrt1180))
+        );
+        let fragment = instance_linker_script(Imxrt::Imxrt1180, FlexSpiInstance::FlexSpi2).unwrap();
+        assert!(fragment.contains("__imxrt_boot_gen_flexspi_base = 0x04000000;"));
+        assert!(fragment.contains("__imxrt_boot_gen_fcb_address = 0x04000400;"));
+        assert_eq!(
+            instance_linker_script(Imxrt::Imxrt1060, FlexSpiInstance::FlexSpi2),
+            None
+        );
+    }
+}
@@ -0,0 +1,127 @@
+//! Export configuration blocks as C arrays
+//!
+//! The array has one little-endian `uint32_t` per word. Each word's comment
+//! has its byte offset and the names of the fields that start in the word.
+//! Lookup table words also describe the sequence that starts in the word.
+
+use crate::flexspi::{self, Field, Sequence};
+use crate::report::COMMANDS;
+use crate::serial_flash::{nand, nor};
+use std::fmt::Write;
+
+fn c_array(name: &str, bytes: &[u8], fields: &[Field]) -> String {
+    let lookup_table = fields
+        .iter()
+        .find(|field| field.name == "lookupTable")
+        .unwrap();
+    let mut source = format!(
+        "/* Generated by imxrt-boot-gen */\nconst uint32_t {name}[{}] = {{\n",
+        bytes.len() / 4
+    );
+    for (idx, word) in bytes.chunks_exact(4).enumerate() {
+        let offset = idx * 4;
+        let mut names: Vec<String> = fields
+            .iter()
+            .filter(|field| (offset..offset + 4).contains(&field.offset))
+            .map(|field| field.name.into())
+            .collect();
+        let lut_offset = offset.wrapping_sub(lookup_table.offset);
+        if lut_offset < lookup_table.size && lut_offset % 16 == 0 {
+            let index = lut_offset / 16;
+            let sequence = Sequence::from_bytes(bytes[offset..offset + 16].try_into().unwrap());
+            if sequence != Sequence::stopped() {
+                let command = COMMANDS
+                    .iter()
+                    .find(|command| **command as usize == index)
+                    .map(|command| format!(" {command:?}"))
+                    .unwrap_or_default();
+                names.push(format!("[{index}]{command}: {sequence}"));
+            }
+        }
+        let word = u32::from_le_bytes(word.try_into().unwrap());
+        write!(source, "    {word:#010X}, /* {offset:#05X}").unwrap();
+        for name in names {
+            write!(source, " {name}").unwrap();
+        }
+        source.push_str(" */\n");
+    }
+    source.push_str("};\n");
+    source
+}
+
+impl flexspi::ConfigurationBlock {
+    /// Returns C source that defines a `const uint32_t` array named `name`
+    /// with the block's contents.
+    ///
+    /// A comment on each word names the fields that start in the word, and
+    /// describes the lookup table sequences. This requires the `"std"`
+    /// feature.
+    pub fn to_c_array(&self, name: &str) -> String {
+        c_array(name, &self.to_bytes(), &flexspi::FIELDS)
+    }
+}
+
+impl nor::ConfigurationBlock {
+    /// Returns C source that defines a `const uint32_t` array named `name`
+    /// with the block's contents.
+    ///
+    /// A comment on each word names the fields that start in the word, and
+    /// describes the lookup table sequences. This requires the `"std"`
+    /// feature.
+    ///
+    /// ```
+    /// use imxrt_boot_gen::flexspi::{self, LookupTable};
+    /// use imxrt_boot_gen::serial_flash::nor;
+    ///
+    /// let block = nor::ConfigurationBlock::new(flexspi::ConfigurationBlock::new(LookupTable::new()));
+    /// let source = block.to_c_array("fcb");
+    /// assert!(source.contains("const uint32_t fcb[128] = {"));
+    /// assert!(source.contains("    0x42464346, /* 0x000 tag */"));
+    /// ```
+    pub fn to_c_array(&self, name: &str) -> String {
+        c_array(name, &self.to_bytes(), &nor::FIELDS)
+    }
+}
+
+impl nand::ConfigurationBlock {
+    /// Returns C source that defines a `const uint32_t` array named `name`
+    /// with the block's contents.
+    ///
+    /// See [`nor::ConfigurationBlock::to_c_array`] for more information.
+    pub fn to_c_array(&self, name: &str) -> String {
+        c_array(name, &self.to_bytes(), &nand::FIELDS)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::flexspi::{
+        self, opcodes::sdr::*, Command, Instr, LookupTable, Pads, SequenceBuilder,
+    };
+    use crate::serial_flash::nor;
+
+    #[test]
+    fn c_array() {
+        let lut = LookupTable::new().command(
+            Command::ReadStatus,
+            SequenceBuilder::new()
+                .instr(Instr::new(CMD, Pads::One, 0x05))
+                .instr(Instr::new(READ, Pads::One, 0x04))
+                .build(),
+        );
+        let block = nor::ConfigurationBlock::new(flexspi::ConfigurationBlock::new(lut))
+            .page_size(256)
+            .sector_size(4096);
+        let source = block.to_c_array("fcb");
+        let lines: Vec<_> = source.lines().collect();
+
+        assert_eq!(lines[1], "const uint32_t fcb[128] = {");
+        assert_eq!(lines.len(), 128 + 3);
+        assert_eq!(lines[130], "};");
+        assert!(lines.contains(&"    0x00000100, /* 0x1C0 pageSize */"));
+        assert!(lines.contains(
+            &"    0x24040405, /* 0x090 [1] ReadStatus: CMD_SDR SINGLE 0x05; READ_SDR SINGLE 0x04 */"
+        ));
+        assert!(lines.contains(&"    0x00000000, /* 0x094 */"));
+    }
+}
@@ -0,0 +1,153 @@
+//! Secondary core images for the 1170
+//!
+//! The 1170's CM7 boots first. To run the CM4, the CM7 image carries the CM4
+//! image in flash. The CM7 copies the CM4 image into memory that the CM4 can
+//! fetch from, sets the CM4's initial vector table address, then releases the
+//! CM4 from reset. The `IOMUXC_LPSR_GPR` registers `GPR0` and `GPR1` hold the
+//! low and high halves of the vector table address.
+//!
+//! [`Image`] describes the copy, and computes the vector table register values.
+//! Write them from your CM7 startup code before you release the CM4, or add
+//! them to a [`Dcd`].
+//!
+//! ```
+//! use imxrt_boot_gen::cm4::Image;
+//!
+//! // Copy 64KiB from flash into the CM4's ITCM.
+//! const CM4: Image = Image::new(0x3010_0000, 0x1FFE_0000, 0x1_0000);
+//!
+//! for (address, value) in CM4.registers() {
+//!     // Write value to address...
+//! #   let _ = (address, value);
+//! }
+//! ```
+
+use crate::dcd::Dcd;
+
+/// `IOMUXC_LPSR_GPR_GPR0`, the low half of the CM4 vector table address.
+pub const LPSR_GPR0: u32 = 0x40C0_C000;
+/// `IOMUXC_LPSR_GPR_GPR1`, the high half of the CM4 vector table address.
+pub const LPSR_GPR1: u32 = 0x40C0_C004;
+
+/// The vector table address granularity, in bytes.
+const VECTOR_TABLE_ALIGNMENT: u32 = 8;
+
+/// A CM4 image that the CM7 image carries
+///
+/// The CM7 copies `length` bytes from `source` to `destination`. Unless
+/// otherwise specified, the CM4's vector table is at the start of the copy.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Image {
+    source: u32,
+    destination: u32,
+    length: u32,
+    vector_table: u32,
+}
+
+impl Image {
+    /// Describe a CM4 image that's copied from `source` to `destination`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the image is empty, if either range extends beyond the 32-bit
+    /// address space, if the ranges overlap, or if `destination` isn't 8 byte
+    /// aligned.
+    pub const fn new(source: u32, destination: u32, length: u32) -> Self {
+        assert!(length > 0, "The CM4 image must not be empty");
+        assert!(
+            source.checked_add(length).is_some() && destination.checked_add(length).is_some(),
+            "The CM4 image extends beyond the 32-bit address space"
+        );
+        assert!(
+            source + length <= destination || destination + length <= source,
+            "The CM4 image's source and destination overlap"
+        );
+        Image {
+            source,
+            destination,
+            length,
+            vector_table: destination,
+        }
+        .vector_table(destination)
+    }
+    /// Set the address of the CM4's vector table.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `vector_table` isn't 8 byte aligned, or if it's outside of
+    /// the copied image.
+    pub const fn vector_table(mut self, vector_table: u32) -> Self {
+        assert!(
+            vector_table.is_multiple_of(VECTOR_TABLE_ALIGNMENT),
+            "The CM4 vector table must be 8 byte aligned"
+        );
+        assert!(
+            self.destination <= vector_table && vector_table < self.destination + self.length,
+            "The CM4 vector table must be within the copied image"
+        );
+        self.vector_table = vector_table;
+        self
+    }
+    /// Returns the address of the image in the CM7 image.
+    pub const fn get_source(&self) -> u32 {
+        self.source
+    }
+    /// Returns the address that the image runs from.
+    pub const fn get_destination(&self) -> u32 {
+        self.destination
+    }
+    /// Returns the image size, in bytes.
+    pub const fn get_length(&self) -> u32 {
+        self.length
+    }
+    /// Returns the address of the CM4's vector table.
+    pub const fn get_vector_table(&self) -> u32 {
+        self.vector_table
+    }
+    /// Returns the register addresses and values that set the CM4's vector
+    /// table.
+    pub const fn registers(&self) -> [(u32, u32); 2] {
+        [
+            (LPSR_GPR0, self.vector_table & 0xFFFF),
+            (LPSR_GPR1, self.vector_table >> 16),
+        ]
+    }
+    /// Append the register writes to `dcd`.
+    ///
+    /// The DCD can't copy the image. Your CM7 startup code must still copy
+    /// the image before it releases the CM4.
+    pub const fn dcd(&self, dcd: Dcd) -> Dcd {
+        dcd.write_words(&self.registers())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Image, LPSR_GPR0, LPSR_GPR1};
+    use crate::dcd::Dcd;
+
+    const IMAGE: Image = Image::new(0x3010_0000, 0x2020_0000, 0x8000);
+
+    #[test]
+    fn registers() {
+        assert_eq!(IMAGE.get_vector_table(), 0x2020_0000);
+        assert_eq!(
+            IMAGE.vector_table(0x2020_0400).registers(),
+            [(LPSR_GPR0, 0x0400), (LPSR_GPR1, 0x2020)]
+        );
+        // Header, one write command header, and two address-value pairs.
+        assert_eq!(IMAGE.dcd(Dcd::new()).len(), 4 + 4 + 2 * 8);
+    }
+
+    #[test]
+    #[should_panic(expected = "within the copied image")]
+    fn vector_table_outside_image() {
+        let _ = IMAGE.vector_table(0x2020_8000);
+    }
+
+    #[test]
+    #[should_panic(expected = "overlap")]
+    fn overlapping_copy() {
+        let _ = Image::new(0x2020_0000, 0x2020_4000, 0x8000);
+    }
+}
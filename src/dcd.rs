@@ -0,0 +1,344 @@
+//! Device configuration data (DCD)
+//!
+//! The boot ROM executes the DCD before it jumps to your program. Use the DCD
+//! to initialize clocks and external memory, like SEMC SDRAM, so that your
+//! program can be loaded into that memory.
+//!
+//! Build the DCD in a `const` expression, then copy its commands into a `static`
+//! array that's exactly [`len`](Dcd::len) bytes long. Point the IVT's
+//! [`dcd`](crate::ivt::Ivt::dcd) at that array.
+//!
+//! ```
+//! use imxrt_boot_gen::dcd::{Condition, Dcd, Width};
+//! use imxrt_boot_gen::ivt::{Ivt, Pointer};
+//!
+//! const COMMANDS: Dcd = Dcd::new()
+//!     .write(Width::Word, 0x400F_C068, 0xFFFF_FFFF)
+//!     .write(Width::Word, 0x400F_C06C, 0xFFFF_FFFF)
+//!     .check(Width::Word, 0x402F_003C, 0x1, Condition::AnySet);
+//!
+//! static DCD: [u8; COMMANDS.len()] = COMMANDS.to_array();
+//!
+//! # unsafe extern "C" fn reset() {}
+//! static IVT: Ivt = Ivt::new(Pointer::from_fn(reset))
+//!     .dcd(Pointer::from_static(&DCD))
+//!     .self_ptr(Pointer::from_static(&IVT));
+//! ```
+//!
+//! The boot ROM rejects a DCD that's larger than [`MAX_SIZE`] bytes. The builder
+//! panics if you exceed that size, so a DCD that's too large fails to compile.
+//!
+//! All DCD values are big endian. The builder handles the byte order for you.
+
+#[cfg(any(
+    feature = "imxrt1020",
+    feature = "imxrt1024",
+    feature = "imxrt1040",
+    feature = "imxrt1050",
+    feature = "imxrt1060",
+    feature = "imxrt1064"
+))]
+pub mod sdram;
+
+/// The largest DCD that the boot ROM accepts, in bytes.
+pub const MAX_SIZE: usize = 1768;
+
+const HEADER_TAG: u8 = 0xD2;
+const VERSION: u8 = 0x41;
+const WRITE_DATA_TAG: u8 = 0xCC;
+const CHECK_DATA_TAG: u8 = 0xCF;
+const NOP_TAG: u8 = 0xC0;
+
+const MASK: u8 = 1 << 3;
+const SET: u8 = 1 << 4;
+
+/// The width of a DCD access
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum Width {
+    /// One byte.
+    Byte = 1,
+    /// Two bytes.
+    HalfWord = 2,
+    /// Four bytes.
+    Word = 4,
+}
+
+/// The condition that ends a check data command
+///
+/// The boot ROM polls the address until the condition holds for the bits in
+/// the mask.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum Condition {
+    /// All bits in the mask are clear.
+    AllClear = 0,
+    /// All bits in the mask are set.
+    AllSet = SET,
+    /// Any bit in the mask is clear.
+    AnyClear = MASK,
+    /// Any bit in the mask is set.
+    AnySet = MASK | SET,
+}
+
+/// Device configuration data
+///
+/// The DCD starts with a header, followed by commands. Consecutive writes with
+/// the same width and operation share one write data command.
+#[derive(Debug, Clone, Copy)]
+pub struct Dcd {
+    bytes: [u8; MAX_SIZE],
+    len: usize,
+    /// The offset of the last write data command, if it was the last command.
+    last_write: Option<usize>,
+}
+
+impl Default for Dcd {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Dcd {
+    /// Create a DCD without any commands.
+    pub const fn new() -> Self {
+        let mut dcd = Dcd {
+            bytes: [0; MAX_SIZE],
+            len: 4,
+            last_write: None,
+        };
+        dcd.bytes[0] = HEADER_TAG;
+        dcd.bytes[3] = VERSION;
+        dcd.set_length(0, 4);
+        dcd
+    }
+    /// Write `value` to `address`.
+    pub const fn write(self, width: Width, address: u32, value: u32) -> Self {
+        self.write_data(width as u8, address, value)
+    }
+    /// Write each `(address, value)` pair as a word, in order.
+    pub const fn write_words(self, writes: &[(u32, u32)]) -> Self {
+        let mut dcd = self;
+        let mut idx = 0;
+        while idx < writes.len() {
+            let (address, value) = writes[idx];
+            dcd = dcd.write(Width::Word, address, value);
+            idx += 1;
+        }
+        dcd
+    }
+    /// Clear the bits of `mask` at `address`, leaving the other bits unchanged.
+    pub const fn clear_bits(self, width: Width, address: u32, mask: u32) -> Self {
+        self.write_data(width as u8 | MASK, address, mask)
+    }
+    /// Set the bits of `mask` at `address`, leaving the other bits unchanged.
+    pub const fn set_bits(self, width: Width, address: u32, mask: u32) -> Self {
+        self.write_data(width as u8 | MASK | SET, address, mask)
+    }
+    /// Poll `address` until `condition` holds for the bits in `mask`.
+    ///
+    /// The boot ROM polls forever. Use [`check_count`](Self::check_count) to
+    /// limit the number of polls.
+    pub const fn check(self, width: Width, address: u32, mask: u32, condition: Condition) -> Self {
+        self.check_data(width as u8 | condition as u8, address, mask, None)
+    }
+    /// Poll `address` until `condition` holds for the bits in `mask`, or until
+    /// the boot ROM polls `count` times.
+    pub const fn check_count(
+        self,
+        width: Width,
+        address: u32,
+        mask: u32,
+        condition: Condition,
+        count: u32,
+    ) -> Self {
+        self.check_data(width as u8 | condition as u8, address, mask, Some(count))
+    }
+    /// Do nothing.
+    pub const fn nop(mut self) -> Self {
+        self.command(NOP_TAG, 0, 4);
+        self.last_write = None;
+        self
+    }
+    /// Returns the size of the DCD, including its header, in bytes.
+    #[allow(clippy::len_without_is_empty)]
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+    /// Returns the DCD's bytes.
+    pub const fn as_bytes(&self) -> &[u8] {
+        self.bytes.split_at(self.len).0
+    }
+    /// Copy the DCD into an array that's exactly [`len`](Self::len) bytes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `N` isn't the DCD's length.
+    pub const fn to_array<const N: usize>(&self) -> [u8; N] {
+        assert!(N == self.len, "The array length must equal the DCD length");
+        let mut array = [0; N];
+        crate::raw::put(&mut array, 0, self.as_bytes());
+        array
+    }
+
+    const fn write_data(mut self, params: u8, address: u32, value: u32) -> Self {
+        let offset = match self.last_write {
+            Some(offset) if self.bytes[offset + 3] == params => offset,
+            _ => self.command(WRITE_DATA_TAG, params, 4),
+        };
+        self.push(address);
+        self.push(value);
+        let length = self.len - offset;
+        self.set_length(offset, length);
+        self.last_write = Some(offset);
+        self
+    }
+    const fn check_data(mut self, params: u8, address: u32, mask: u32, count: Option<u32>) -> Self {
+        let offset = self.command(CHECK_DATA_TAG, params, 4);
+        self.push(address);
+        self.push(mask);
+        if let Some(count) = count {
+            self.push(count);
+        }
+        let length = self.len - offset;
+        self.set_length(offset, length);
+        self.last_write = None;
+        self
+    }
+    /// Append a command header, and return its offset.
+    const fn command(&mut self, tag: u8, params: u8, length: usize) -> usize {
+        let offset = self.len;
+        self.push(u32::from_be_bytes([tag, 0, 0, params]));
+        self.set_length(offset, length);
+        offset
+    }
+    const fn push(&mut self, word: u32) {
+        assert!(
+            self.len + 4 <= MAX_SIZE,
+            "The DCD exceeds the 1768 byte limit"
+        );
+        crate::raw::put(&mut self.bytes, self.len, &word.to_be_bytes());
+        self.len += 4;
+        self.set_length(0, self.len);
+    }
+    /// Write the big-endian length of the header or command at `offset`.
+    const fn set_length(&mut self, offset: usize, length: usize) {
+        let length = (length as u16).to_be_bytes();
+        self.bytes[offset + 1] = length[0];
+        self.bytes[offset + 2] = length[1];
+    }
+}
+
+// Keep these two tests in sync
+//
+// The first one lets you know if the second one is failing to compile
+// in the way we expect.
+//
+
+/// ```
+/// use imxrt_boot_gen::dcd::{Dcd, Width};
+/// const DCD: Dcd = {
+///     let mut dcd = Dcd::new();
+///     let mut idx = 0;
+///     while idx < 220 {
+///         dcd = dcd.write(Width::Word, 0x2000_0000, 0);
+///         idx += 1;
+///     }
+///     dcd
+/// };
+/// ```
+#[cfg(doctest)]
+struct DcdWithinLimit;
+
+/// ```compile_fail
+/// use imxrt_boot_gen::dcd::{Dcd, Width};
+/// const DCD: Dcd = {
+///     let mut dcd = Dcd::new();
+///     let mut idx = 0;
+///     while idx < 221 {
+///         dcd = dcd.write(Width::Word, 0x2000_0000, 0);
+///         idx += 1;
+///     }
+///     dcd
+/// };
+/// ```
+#[cfg(doctest)]
+struct DcdExceedsLimit;
+
+#[cfg(test)]
+mod test {
+    use super::{Condition, Dcd, Width, MAX_SIZE};
+
+    #[test]
+    fn empty() {
+        assert_eq!(Dcd::new().as_bytes(), &[0xD2, 0x00, 0x04, 0x41]);
+    }
+
+    #[test]
+    fn merged_writes() {
+        let dcd = Dcd::new()
+            .write(Width::Word, 0x400F_C068, 0xFFFF_FFFF)
+            .write(Width::Word, 0x400F_C06C, 0x1234_5678);
+        assert_eq!(
+            dcd.as_bytes(),
+            &[
+                0xD2, 0x00, 0x18, 0x41, // header
+                0xCC, 0x00, 0x14, 0x04, // write data
+                0x40, 0x0F, 0xC0, 0x68, 0xFF, 0xFF, 0xFF, 0xFF, //
+                0x40, 0x0F, 0xC0, 0x6C, 0x12, 0x34, 0x56, 0x78,
+            ]
+        );
+    }
+
+    #[test]
+    fn write_words() {
+        let dcd = Dcd::new().write_words(&[(0x400F_C068, 0xFFFF_FFFF), (0x400F_C06C, 0x1234_5678)]);
+        let expected = Dcd::new()
+            .write(Width::Word, 0x400F_C068, 0xFFFF_FFFF)
+            .write(Width::Word, 0x400F_C06C, 0x1234_5678);
+        assert_eq!(dcd.as_bytes(), expected.as_bytes());
+    }
+
+    #[test]
+    fn separate_commands() {
+        let dcd = Dcd::new()
+            .write(Width::Word, 0x1000, 1)
+            .set_bits(Width::Word, 0x1004, 2)
+            .nop()
+            .write(Width::Word, 0x1008, 3)
+            .check_count(Width::HalfWord, 0x100C, 0x8000, Condition::AnySet, 100);
+        let bytes = dcd.as_bytes();
+        assert_eq!(dcd.len(), 4 + 12 + 12 + 4 + 12 + 16);
+        assert_eq!(&bytes[4..8], &[0xCC, 0x00, 0x0C, 0x04]);
+        assert_eq!(&bytes[16..20], &[0xCC, 0x00, 0x0C, 0x1C]);
+        assert_eq!(&bytes[28..32], &[0xC0, 0x00, 0x04, 0x00]);
+        assert_eq!(&bytes[32..36], &[0xCC, 0x00, 0x0C, 0x04]);
+        assert_eq!(&bytes[44..48], &[0xCF, 0x00, 0x10, 0x1A]);
+        assert_eq!(&bytes[56..60], &100u32.to_be_bytes());
+    }
+
+    #[test]
+    fn to_array() {
+        const DCD: Dcd = Dcd::new().write(Width::Byte, 0x1000, 0xAB);
+        const BYTES: [u8; DCD.len()] = DCD.to_array();
+        assert_eq!(&BYTES[..], DCD.as_bytes());
+    }
+
+    #[test]
+    fn fills_to_the_limit() {
+        let mut dcd = Dcd::new().write(Width::Word, 0, 0);
+        while dcd.len() < MAX_SIZE {
+            dcd = dcd.write(Width::Word, 0, 0);
+        }
+        assert_eq!(dcd.len(), MAX_SIZE);
+    }
+
+    #[test]
+    #[should_panic(expected = "1768 byte limit")]
+    fn exceeds_the_limit() {
+        let mut dcd = Dcd::new();
+        for _ in 0..MAX_SIZE / 8 {
+            dcd = dcd.write(Width::Word, 0, 0);
+        }
+    }
+}
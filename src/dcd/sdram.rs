@@ -0,0 +1,326 @@
+//! SEMC SDRAM initialization
+//!
+//! [`Dcd::semc_sdram`] appends the commands that configure the SEMC for an SDRAM
+//! on chip select 0, and that initialize the SDRAM. Describe the SDRAM with an
+//! [`Sdram`], or use one of the presets for the SDRAMs on NXP's EVKs.
+//!
+//! The commands don't configure the SEMC pins or the SEMC clock. Write those
+//! registers before you call `semc_sdram`, and pass the SEMC clock frequency so
+//! that the builder can convert the SDRAM timings into clock cycles.
+//!
+//! ```
+//! use imxrt_boot_gen::dcd::{sdram, Dcd, Width};
+//!
+//! const COMMANDS: Dcd = Dcd::new()
+//!     // Enable all clock gates.
+//!     .write(Width::Word, 0x400F_C068, 0xFFFF_FFFF)
+//!     // Configure the SEMC pins and clock here...
+//!     .semc_sdram(&sdram::IS42S16160J, 166);
+//!
+//! static DCD: [u8; COMMANDS.len()] = COMMANDS.to_array();
+//! ```
+
+use super::{Condition, Dcd, Width};
+
+/// The SEMC base address.
+const SEMC: u32 = 0x402F_0000;
+/// The address of chip select 0's SDRAM.
+pub const SDRAM_BASE: u32 = 0x8000_0000;
+
+const MCR: u32 = SEMC;
+const BMCR0: u32 = SEMC + 0x08;
+const BMCR1: u32 = SEMC + 0x0C;
+const BR0: u32 = SEMC + 0x10;
+const INTR: u32 = SEMC + 0x3C;
+const SDRAMCR0: u32 = SEMC + 0x40;
+const SDRAMCR1: u32 = SEMC + 0x44;
+const SDRAMCR2: u32 = SEMC + 0x48;
+const SDRAMCR3: u32 = SEMC + 0x4C;
+const IPCR0: u32 = SEMC + 0x90;
+const IPCR1: u32 = SEMC + 0x94;
+const IPCR2: u32 = SEMC + 0x98;
+const IPCMD: u32 = SEMC + 0x9C;
+const IPTXDAT: u32 = SEMC + 0xA0;
+
+/// `INTR[IPCMDDONE]`
+const IPCMDDONE: u32 = 1;
+
+/// SDRAM IP commands, with the key that the SEMC requires.
+const MODESET: u32 = 0xA55A_000A;
+const AUTO_REFRESH: u32 = 0xA55A_000C;
+const PRECHARGE_ALL: u32 = 0xA55A_000F;
+
+/// A 16-bit SDRAM
+///
+/// Unless otherwise specified, the SDRAM has 9 column address bits, a CAS
+/// latency of 3, and it refreshes 8192 rows every 64ms. All timings are in
+/// nanoseconds, as they appear in the SDRAM's datasheet.
+///
+/// ```
+/// use imxrt_boot_gen::dcd::sdram::Sdram;
+///
+/// const MY_SDRAM: Sdram = Sdram::new(32 * 1024 * 1024)
+///     .trp_ns(18)
+///     .trcd_ns(18)
+///     .trfc_ns(60)
+///     .twr_ns(12)
+///     .tras_ns(42)
+///     .trrd_ns(12)
+///     .txsr_ns(70);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Sdram {
+    size: u32,
+    column_bits: u8,
+    cas_latency: u8,
+    trp_ns: u32,
+    trcd_ns: u32,
+    trfc_ns: u32,
+    twr_ns: u32,
+    tras_ns: u32,
+    trrd_ns: u32,
+    txsr_ns: u32,
+    refresh_rows: u32,
+    refresh_period_ms: u32,
+}
+
+/// ISSI IS42S16160J-6, a 32MiB SDRAM on the 1050 and 1060 EVKs
+pub const IS42S16160J: Sdram = Sdram::new(32 * 1024 * 1024)
+    .trp_ns(18)
+    .trcd_ns(18)
+    .trfc_ns(60)
+    .twr_ns(12)
+    .tras_ns(42)
+    .trrd_ns(12)
+    .txsr_ns(70);
+
+/// Winbond W9825G6KH-6, a 32MiB SDRAM on newer EVKs, like the 1060 EVKB
+pub const W9825G6KH: Sdram = Sdram::new(32 * 1024 * 1024)
+    .trp_ns(15)
+    .trcd_ns(15)
+    .trfc_ns(60)
+    .twr_ns(12)
+    .tras_ns(42)
+    .trrd_ns(12)
+    .txsr_ns(72);
+
+impl Sdram {
+    /// Describe a 16-bit SDRAM with `size` bytes.
+    pub const fn new(size: u32) -> Self {
+        Sdram {
+            size,
+            column_bits: 9,
+            cas_latency: 3,
+            trp_ns: 0,
+            trcd_ns: 0,
+            trfc_ns: 0,
+            twr_ns: 0,
+            tras_ns: 0,
+            trrd_ns: 0,
+            txsr_ns: 0,
+            refresh_rows: 8192,
+            refresh_period_ms: 64,
+        }
+    }
+    /// Set the number of column address bits, from 9 to 12.
+    pub const fn column_bits(mut self, column_bits: u8) -> Self {
+        self.column_bits = column_bits;
+        self
+    }
+    /// Set the CAS latency, from 1 to 3 cycles.
+    pub const fn cas_latency(mut self, cas_latency: u8) -> Self {
+        self.cas_latency = cas_latency;
+        self
+    }
+    /// Set the precharge command period, tRP.
+    pub const fn trp_ns(mut self, trp_ns: u32) -> Self {
+        self.trp_ns = trp_ns;
+        self
+    }
+    /// Set the active to read or write delay, tRCD.
+    pub const fn trcd_ns(mut self, trcd_ns: u32) -> Self {
+        self.trcd_ns = trcd_ns;
+        self
+    }
+    /// Set the refresh cycle time, tRFC.
+    pub const fn trfc_ns(mut self, trfc_ns: u32) -> Self {
+        self.trfc_ns = trfc_ns;
+        self
+    }
+    /// Set the write recovery time, tWR.
+    pub const fn twr_ns(mut self, twr_ns: u32) -> Self {
+        self.twr_ns = twr_ns;
+        self
+    }
+    /// Set the active to precharge time, tRAS.
+    ///
+    /// The SEMC also uses this time as the minimum CKE off time.
+    pub const fn tras_ns(mut self, tras_ns: u32) -> Self {
+        self.tras_ns = tras_ns;
+        self
+    }
+    /// Set the active to active delay between banks, tRRD.
+    pub const fn trrd_ns(mut self, trrd_ns: u32) -> Self {
+        self.trrd_ns = trrd_ns;
+        self
+    }
+    /// Set the self refresh exit time, tXSR.
+    pub const fn txsr_ns(mut self, txsr_ns: u32) -> Self {
+        self.txsr_ns = txsr_ns;
+        self
+    }
+    /// Refresh `rows` rows every `period_ms` milliseconds.
+    pub const fn refresh(mut self, rows: u32, period_ms: u32) -> Self {
+        self.refresh_rows = rows;
+        self.refresh_period_ms = period_ms;
+        self
+    }
+
+    /// `BR0`, with the base address, memory size, and valid bit.
+    const fn br0(&self) -> u32 {
+        assert!(
+            self.size.is_power_of_two() && self.size >= 4 * 1024,
+            "The SDRAM size must be a power of two, and at least 4KiB"
+        );
+        let ms = (self.size / (4 * 1024)).trailing_zeros();
+        SDRAM_BASE | (ms << 1) | 1
+    }
+    /// `SDRAMCR0`, with a 16-bit port and a burst length of 8.
+    const fn sdramcr0(&self) -> u32 {
+        assert!(
+            self.column_bits >= 9 && self.column_bits <= 12,
+            "The SDRAM must have 9 to 12 column address bits"
+        );
+        assert!(
+            self.cas_latency >= 1 && self.cas_latency <= 3,
+            "The SDRAM CAS latency must be 1 to 3 cycles"
+        );
+        const PS_16BIT: u32 = 1;
+        const BL_8: u32 = 3;
+        let col = 12 - self.column_bits as u32;
+        PS_16BIT | (BL_8 << 4) | (col << 8) | ((self.cas_latency as u32) << 10)
+    }
+    const fn sdramcr1(&self, clock_mhz: u32) -> u32 {
+        field(self.trp_ns, clock_mhz, 4)
+            | (field(self.trcd_ns, clock_mhz, 4) << 4)
+            | (field(self.trfc_ns, clock_mhz, 5) << 8)
+            | (field(self.twr_ns, clock_mhz, 3) << 13)
+            | (field(self.tras_ns, clock_mhz, 4) << 16)
+            | (field(self.tras_ns, clock_mhz, 4) << 20)
+    }
+    const fn sdramcr2(&self, clock_mhz: u32) -> u32 {
+        field(self.txsr_ns, clock_mhz, 8)
+            | (field(self.trfc_ns, clock_mhz, 8) << 8)
+            | (field(self.trrd_ns, clock_mhz, 8) << 16)
+    }
+    /// `SDRAMCR3`, with a prescaler period of about one microsecond, and one
+    /// refresh per refresh period.
+    const fn sdramcr3(&self, clock_mhz: u32, refresh_enable: bool) -> u32 {
+        let prescale = clock_mhz.div_ceil(16);
+        assert!(prescale <= 0xFF, "The SEMC clock is too fast");
+        let row_ns = self.refresh_period_ms * 1_000_000 / self.refresh_rows;
+        let periods = row_ns as u64 * clock_mhz as u64 / 1000 / (16 * prescale as u64);
+        assert!(
+            periods >= 1 && periods <= 0x100,
+            "The SDRAM refresh period doesn't fit in SDRAMCR3"
+        );
+        let rt = periods as u32 - 1;
+        refresh_enable as u32 | (prescale << 8) | (rt << 16) | (rt << 24)
+    }
+    /// The mode register value, with a burst length of 8.
+    const fn mode(&self) -> u32 {
+        const BURST_LENGTH_8: u32 = 3;
+        BURST_LENGTH_8 | ((self.cas_latency as u32) << 4)
+    }
+}
+
+/// Convert `ns` into the SEMC's cycles - 1 encoding, rounding up.
+const fn field(ns: u32, clock_mhz: u32, bits: u32) -> u32 {
+    let cycles = (ns * clock_mhz).div_ceil(1000);
+    let value = cycles.saturating_sub(1);
+    assert!(
+        value < (1 << bits),
+        "An SDRAM timing doesn't fit in its SEMC field"
+    );
+    value
+}
+
+impl Dcd {
+    /// Configure the SEMC for `sdram` on chip select 0, then initialize the
+    /// SDRAM.
+    ///
+    /// `clock_mhz` is the SEMC clock frequency. The SDRAM is available at
+    /// [`SDRAM_BASE`] after the boot ROM executes the commands.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the SDRAM description is invalid, or if a timing doesn't fit
+    /// in its SEMC field at `clock_mhz`.
+    pub const fn semc_sdram(self, sdram: &Sdram, clock_mhz: u32) -> Self {
+        self.write(Width::Word, MCR, 0x1000_0004)
+            .write(Width::Word, BMCR0, 0x0003_0524)
+            .write(Width::Word, BMCR1, 0x0603_0524)
+            .write(Width::Word, BR0, sdram.br0())
+            .write(Width::Word, SDRAMCR0, sdram.sdramcr0())
+            .write(Width::Word, SDRAMCR1, sdram.sdramcr1(clock_mhz))
+            .write(Width::Word, SDRAMCR2, sdram.sdramcr2(clock_mhz))
+            .write(Width::Word, SDRAMCR3, sdram.sdramcr3(clock_mhz, false))
+            .write(Width::Word, IPCR1, 2)
+            .write(Width::Word, IPCR2, 0)
+            .ip_command(PRECHARGE_ALL)
+            .ip_command(AUTO_REFRESH)
+            .ip_command(AUTO_REFRESH)
+            .write(Width::Word, IPTXDAT, sdram.mode())
+            .ip_command(MODESET)
+            .write(Width::Word, SDRAMCR3, sdram.sdramcr3(clock_mhz, true))
+    }
+
+    /// Clear `IPCMDDONE`, send an IP command to the SDRAM, and wait for it to
+    /// finish.
+    const fn ip_command(self, command: u32) -> Self {
+        self.write(Width::Word, INTR, IPCMDDONE)
+            .write(Width::Word, IPCR0, SDRAM_BASE)
+            .write(Width::Word, IPCMD, command)
+            .check(Width::Word, INTR, IPCMDDONE, Condition::AnySet)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{IS42S16160J, W9825G6KH};
+    use crate::dcd::Dcd;
+
+    #[test]
+    fn registers() {
+        assert_eq!(IS42S16160J.br0(), 0x8000_001B);
+        assert_eq!(IS42S16160J.sdramcr0(), 0x0000_0F31);
+        assert_eq!(IS42S16160J.mode(), 0x33);
+        // tRP and tRCD are 3 cycles, tRFC is 10, tWR is 2, and tRAS is 7.
+        assert_eq!(IS42S16160J.sdramcr1(166), 0x0066_2922);
+        // tXSR is 12 cycles, tRFC is 10, and tRRD is 2.
+        assert_eq!(IS42S16160J.sdramcr2(166), 0x0001_090B);
+        // The prescaler period is 11 x 16 cycles, and a row refreshes every
+        // 7 periods.
+        assert_eq!(IS42S16160J.sdramcr3(166, true), 0x0606_0B01);
+        assert_eq!(W9825G6KH.sdramcr1(166), 0x0066_2922);
+    }
+
+    #[test]
+    fn commands() {
+        let dcd = Dcd::new().semc_sdram(&IS42S16160J, 166);
+        let bytes = dcd.as_bytes();
+        // The last write enables refresh.
+        assert_eq!(
+            &bytes[bytes.len() - 8..],
+            &[0x40, 0x2F, 0x00, 0x4C, 0x06, 0x06, 0x0B, 0x01]
+        );
+        assert_eq!(
+            bytes
+                .windows(4)
+                .filter(|word| *word == [0xCF, 0x00, 0x0C, 0x1C])
+                .count(),
+            4,
+            "Every IP command waits for IPCMDDONE"
+        );
+    }
+}
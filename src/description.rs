@@ -0,0 +1,833 @@
+//! Serializable descriptions of configuration blocks
+//!
+//! A description mirrors a configuration block, but it names every field,
+//! and it names the lookup table sequences. Host tools can read and write
+//! descriptions with any `serde` format, like JSON or YAML, then lower them
+//! into configuration blocks.
+//!
+//! ```
+//! use imxrt_boot_gen::description::SerialNor;
+//!
+//! let description: SerialNor = serde_json::from_str(r#"{
+//!     "flexspi": {
+//!         "serial_flash_pad_type": "Quad",
+//!         "serial_clk_freq": "MHz60",
+//!         "flash_sizes": { "a1": 16777216 },
+//!         "lookup_table": {
+//!             "Read": ["CMD_SDR SINGLE 0xEB", "RADDR_SDR QUAD 0x18", "DUMMY_SDR QUAD 0x06", "READ_SDR QUAD 0x04"],
+//!             "ReadStatus": ["CMD_SDR SINGLE 0x05", "READ_SDR SINGLE 0x04"]
+//!         }
+//!     },
+//!     "page_size": 256,
+//!     "sector_size": 4096
+//! }"#).unwrap();
+//!
+//! let block = description.to_block().unwrap();
+//! assert_eq!(block.get_page_size(), 256);
+//! assert_eq!(SerialNor::from_block(&block), description);
+//! ```
+//!
+//! Descriptions don't have reserved fields. Every field has a default, so a
+//! description only needs the fields that differ from a new configuration
+//! block. Instructions use the same format as their `Display` output.
+//!
+//! A [`FlashPart`] is a smaller description of a serial NOR flash part, from
+//! values in its data sheet. It produces the recommended configuration block,
+//! and it generates Rust source for that block. With the `"toml"` feature,
+//! parse a part from TOML.
+//!
+//! This module requires the `"serde"` feature.
+
+use crate::flexspi::{
+    self, ColumnAddressWidth, Command, ConfigurationCommand, DataValidTime,
+    DeviceModeConfiguration, DeviceModeSequence, FlashPadType, Instr, LookupTable,
+    LutCustomSequence, PadSettingOverride, ReadSampleClockSource, SequenceBuilder,
+    SerialClockFrequency, SerialFlashRegion, Version, WaitTimeConfigurationCommands,
+};
+use crate::serial_flash::nor;
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use std::{collections::BTreeMap, fmt};
+
+mod part;
+mod rust;
+
+pub use part::{Commands, FlashPart, Read};
+
+/// The number of sequences in a lookup table.
+const SEQUENCES: usize = 16;
+
+/// The lookup table commands that this crate names.
+const COMMANDS: &[Command] = &[
+    Command::Read,
+    Command::ReadStatus,
+    Command::WriteEnable,
+    Command::EraseSector,
+    #[cfg(any(
+        feature = "imxrt1170",
+        feature = "imxrt1180",
+        feature = "imxrt500",
+        feature = "imxrt600"
+    ))]
+    Command::EraseBlock,
+    Command::PageProgram,
+    Command::ChipErase,
+    Command::Dummy,
+];
+
+/// An error produced when lowering a description into a configuration block
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Error {
+    /// The field has more entries than the configuration block allows.
+    TooManyEntries {
+        /// The description field.
+        field: &'static str,
+        /// The maximum number of entries.
+        max: usize,
+    },
+    /// The field's sequences extend beyond the end of the lookup table.
+    SequenceRange {
+        /// The description field.
+        field: &'static str,
+    },
+    /// The busy offset isn't a bit in a 32-bit status register.
+    BusyOffset(u8),
+    /// A lookup table sequence has more than eight instructions.
+    TooManyInstructions {
+        /// The lookup table index.
+        index: usize,
+    },
+    /// The field's value isn't supported.
+    InvalidField {
+        /// The description field.
+        field: &'static str,
+    },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Error::TooManyEntries { field, max } => {
+                write!(f, "{field} has more than {max} entries")
+            }
+            Error::SequenceRange { field } => write!(
+                f,
+                "{field} has sequences beyond the end of the lookup table"
+            ),
+            Error::BusyOffset(offset) => write!(f, "busy offset {offset} is greater than 31"),
+            Error::TooManyInstructions { index } => write!(
+                f,
+                "lookup table sequence {index} has more than eight instructions"
+            ),
+            Error::InvalidField { field } => write!(f, "{field} has an unsupported value"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// A lookup table index, named by its [`Command`] if it has one
+///
+/// A slot serializes as the command name, like `"Read"`, or as the index, like
+/// `"2"`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Slot(usize);
+
+impl Slot {
+    /// Returns the slot for the lookup table `index`, or `None` if the index
+    /// is out of range.
+    pub const fn new(index: usize) -> Option<Self> {
+        if index < SEQUENCES {
+            Some(Slot(index))
+        } else {
+            None
+        }
+    }
+    /// Returns the lookup table index.
+    pub const fn index(self) -> usize {
+        self.0
+    }
+}
+
+impl From<Command> for Slot {
+    fn from(command: Command) -> Self {
+        Slot(command as usize)
+    }
+}
+
+impl Serialize for Slot {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match COMMANDS.iter().find(|command| **command as usize == self.0) {
+            Some(command) => serializer.collect_str(&format_args!("{command:?}")),
+            None => serializer.collect_str(&self.0),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Slot {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let name = String::deserialize(deserializer)?;
+        COMMANDS
+            .iter()
+            .find(|command| format!("{command:?}") == name)
+            .map(|command| Slot::from(*command))
+            .or_else(|| name.parse().ok().and_then(Slot::new))
+            .ok_or_else(|| de::Error::custom(format!("unknown lookup table slot {name:?}")))
+    }
+}
+
+impl Serialize for Instr {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Instr {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let text = String::deserialize(deserializer)?;
+        Instr::parse(&text)
+            .ok_or_else(|| de::Error::custom(format!("invalid instruction {text:?}")))
+    }
+}
+
+/// Serializes like `"V1.4.0"`.
+impl Serialize for Version {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Version {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let text = String::deserialize(deserializer)?;
+        let mut parts = text
+            .strip_prefix('V')
+            .into_iter()
+            .flat_map(|numbers| numbers.split('.'))
+            .map(str::parse::<u8>);
+        match (parts.next(), parts.next(), parts.next(), parts.next()) {
+            (Some(Ok(major)), Some(Ok(minor)), Some(Ok(bugfix)), None) => {
+                Ok(Version::new(major, minor, bugfix))
+            }
+            _ => Err(de::Error::custom(format!("invalid version {text:?}"))),
+        }
+    }
+}
+
+/// `deviceModeSeq` and `deviceModeArg`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct DeviceMode {
+    /// `deviceModeArg`
+    pub arg: u32,
+    /// The number of LUT sequences.
+    pub number_of_luts: u8,
+    /// The starting LUT index.
+    pub starting_lut_index: u8,
+}
+
+/// A `configCmdSeqs` slot and its `cfgCmdArgs` argument
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ConfigCommand {
+    /// The number of LUT sequences.
+    pub number_of_luts: u8,
+    /// The starting LUT index.
+    pub starting_lut_index: u8,
+    /// The argument.
+    pub arg: u32,
+}
+
+/// A `lutCustomSeq` slot
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct LutCustomSeq {
+    /// The number of LUT sequences.
+    pub number_of_luts: u8,
+    /// The starting LUT index.
+    pub starting_lut_index: u8,
+}
+
+/// The flash sizes, in bytes, for each region
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct FlashSizes {
+    /// `sflashA1Size`
+    pub a1: u32,
+    /// `sflashA2Size`
+    pub a2: u32,
+    /// `sflashB1Size`
+    pub b1: u32,
+    /// `sflashB2Size`
+    pub b2: u32,
+}
+
+/// A FlexSPI configuration block description
+///
+/// The defaults match [`flexspi::ConfigurationBlock::new`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct FlexSpi {
+    /// `version`
+    pub version: Version,
+    /// `readSampleClkSrc`
+    pub read_sample_clk_src: ReadSampleClockSource,
+    /// `csHoldTime`
+    pub cs_hold_time: u8,
+    /// `csSetupTime`
+    pub cs_setup_time: u8,
+    /// `columnAddressWidth`
+    pub column_address_width: ColumnAddressWidth,
+    /// The device mode configuration, or `None` if it's disabled.
+    pub device_mode: Option<DeviceMode>,
+    /// `deviceModeType`
+    #[cfg(any(
+        feature = "imxrt1170",
+        feature = "imxrt1180",
+        feature = "imxrt500",
+        feature = "imxrt600"
+    ))]
+    pub device_mode_type: flexspi::DeviceModeType,
+    /// `waitTimeCfgCommands`, in units of 100us.
+    pub wait_time_cfg_commands: u16,
+    /// The configuration commands. Empty if `configCmdEnable` is cleared.
+    pub config_commands: Vec<ConfigCommand>,
+    /// `controllerMiscOption`
+    pub controller_misc_options: u32,
+    /// `sFlashPad`
+    pub serial_flash_pad_type: FlashPadType,
+    /// `serialClkFreq`
+    pub serial_clk_freq: SerialClockFrequency,
+    /// The custom LUT sequences. Empty if `lutCustomSeqEnable` is cleared.
+    pub lut_custom_seqs: Vec<LutCustomSeq>,
+    /// The flash sizes.
+    pub flash_sizes: FlashSizes,
+    /// `csPadSettingOverride`
+    pub cs_pad_setting_override: u32,
+    /// `sclkPadSettingOverride`
+    pub sclk_pad_setting_override: u32,
+    /// `dataPadSettingOverride`
+    pub data_pad_setting_override: u32,
+    /// `dqsPadSettingOverride`
+    pub dqs_pad_setting_override: u32,
+    /// `timeoutInMs`
+    pub timeout_ms: u32,
+    /// `commandInterval`
+    pub command_interval: u32,
+    /// `dataValidTime`
+    pub data_valid_time: DataValidTime,
+    /// `busyOffset`
+    pub busy_offset: u8,
+    /// `busyBitPolarity`
+    pub busy_bit_polarity: flexspi::BusyBitPolarity,
+    /// The lookup table sequences that aren't empty.
+    pub lookup_table: BTreeMap<Slot, Vec<Instr>>,
+    /// `dll0CrVal`
+    #[cfg(any(feature = "imxrt500", feature = "imxrt600"))]
+    pub dll0_cr_val: u32,
+    /// `dll1CrVal`
+    #[cfg(any(feature = "imxrt500", feature = "imxrt600"))]
+    pub dll1_cr_val: u32,
+}
+
+impl Default for FlexSpi {
+    fn default() -> Self {
+        Self::from_block(&flexspi::ConfigurationBlock::new(LookupTable::new()))
+    }
+}
+
+impl FlexSpi {
+    /// Describe a FlexSPI configuration block.
+    pub fn from_block(block: &flexspi::ConfigurationBlock) -> Self {
+        let device_mode = match block.get_device_mode_configuration() {
+            DeviceModeConfiguration::Disabled => None,
+            DeviceModeConfiguration::Enabled {
+                device_mode_arg,
+                device_mode_seq,
+            } => Some(DeviceMode {
+                arg: device_mode_arg,
+                number_of_luts: device_mode_seq.number_of_luts(),
+                starting_lut_index: device_mode_seq.starting_lut_index(),
+            }),
+        };
+        let config_commands = (0..3)
+            .filter_map(|index| block.get_config_command(index))
+            .map(|command| ConfigCommand {
+                number_of_luts: command.number_of_luts(),
+                starting_lut_index: command.starting_lut_index(),
+                arg: command.arg(),
+            })
+            .collect();
+        let lut_custom_seqs = (0..12)
+            .filter_map(|index| block.get_lut_custom_seq(index))
+            .map(|sequence| LutCustomSeq {
+                number_of_luts: sequence.number_of_luts(),
+                starting_lut_index: sequence.starting_lut_index(),
+            })
+            .collect();
+        let lookup_table = block.get_lookup_table();
+        let lookup_table = (0..SEQUENCES)
+            .map(|index| (Slot(index), lookup_table.get_sequence(index)))
+            .filter(|(_, sequence)| *sequence != SequenceBuilder::new().build())
+            .map(|(slot, sequence)| {
+                let instrs = sequence.0.into_iter();
+                let count = instrs
+                    .clone()
+                    .rposition(|instr| instr != flexspi::STOP)
+                    .map_or(0, |last| last + 1);
+                (slot, instrs.take(count).collect())
+            })
+            .collect();
+        let busy_bit = block.get_busy_bit();
+
+        FlexSpi {
+            version: block.get_version(),
+            read_sample_clk_src: block.get_read_sample_clk_src(),
+            cs_hold_time: block.get_cs_hold_time(),
+            cs_setup_time: block.get_cs_setup_time(),
+            column_address_width: block.get_column_address_width(),
+            device_mode,
+            #[cfg(any(
+                feature = "imxrt1170",
+                feature = "imxrt1180",
+                feature = "imxrt500",
+                feature = "imxrt600"
+            ))]
+            device_mode_type: block.get_device_mode_type().unwrap_or_default(),
+            wait_time_cfg_commands: u16::from_le_bytes(
+                block.get_wait_time_cfg_commands().to_bytes(),
+            ),
+            config_commands,
+            controller_misc_options: block.get_controller_misc_options().bits(),
+            serial_flash_pad_type: block.get_serial_flash_pad_type(),
+            serial_clk_freq: block.get_serial_clk_freq(),
+            lut_custom_seqs,
+            flash_sizes: FlashSizes {
+                a1: block.get_flash_size(SerialFlashRegion::A1).bytes(),
+                a2: block.get_flash_size(SerialFlashRegion::A2).bytes(),
+                b1: block.get_flash_size(SerialFlashRegion::B1).bytes(),
+                b2: block.get_flash_size(SerialFlashRegion::B2).bytes(),
+            },
+            cs_pad_setting_override: block.get_cs_pad_setting_override().raw(),
+            sclk_pad_setting_override: block.get_sclk_pad_setting_override().raw(),
+            data_pad_setting_override: block.get_data_pad_setting_override().raw(),
+            dqs_pad_setting_override: block.get_dqs_pad_setting_override().raw(),
+            timeout_ms: block.get_timeout_ms(),
+            command_interval: block.get_command_interval(),
+            data_valid_time: block.get_data_valid_time(),
+            busy_offset: busy_bit.offset(),
+            busy_bit_polarity: busy_bit.polarity(),
+            lookup_table,
+            #[cfg(any(feature = "imxrt500", feature = "imxrt600"))]
+            dll0_cr_val: block.get_dll0_cr_val(),
+            #[cfg(any(feature = "imxrt500", feature = "imxrt600"))]
+            dll1_cr_val: block.get_dll1_cr_val(),
+        }
+    }
+
+    /// Lower the description into a FlexSPI configuration block.
+    pub fn to_block(&self) -> Result<flexspi::ConfigurationBlock, Error> {
+        let fits = |number_of_luts: u8, starting_lut_index: u8| {
+            usize::from(number_of_luts) + usize::from(starting_lut_index) <= SEQUENCES
+        };
+
+        let mut lookup_table = LookupTable::new();
+        for (slot, instrs) in &self.lookup_table {
+            if instrs.len() > 8 {
+                return Err(Error::TooManyInstructions { index: slot.0 });
+            }
+            let sequence = instrs
+                .iter()
+                .fold(SequenceBuilder::new(), |builder, instr| {
+                    builder.instr(*instr)
+                })
+                .build();
+            lookup_table = lookup_table.sequence(slot.0, sequence);
+        }
+
+        let mut block = flexspi::ConfigurationBlock::new(lookup_table)
+            .version(self.version)
+            .read_sample_clk_src(self.read_sample_clk_src)
+            .cs_hold_time(self.cs_hold_time)
+            .cs_setup_time(self.cs_setup_time)
+            .column_address_width(self.column_address_width)
+            .wait_time_cfg_commands(WaitTimeConfigurationCommands::from_bytes(
+                self.wait_time_cfg_commands.to_le_bytes(),
+            ))
+            .controller_misc_options(self.controller_misc_options)
+            .serial_flash_pad_type(self.serial_flash_pad_type)
+            .serial_clk_freq(self.serial_clk_freq)
+            .flash_size_bytes(SerialFlashRegion::A1, self.flash_sizes.a1)
+            .flash_size_bytes(SerialFlashRegion::A2, self.flash_sizes.a2)
+            .flash_size_bytes(SerialFlashRegion::B1, self.flash_sizes.b1)
+            .flash_size_bytes(SerialFlashRegion::B2, self.flash_sizes.b2)
+            .cs_pad_setting_override(PadSettingOverride::from_raw(self.cs_pad_setting_override))
+            .sclk_pad_setting_override(PadSettingOverride::from_raw(self.sclk_pad_setting_override))
+            .data_pad_setting_override(PadSettingOverride::from_raw(self.data_pad_setting_override))
+            .dqs_pad_setting_override(PadSettingOverride::from_raw(self.dqs_pad_setting_override))
+            .timeout_ms(self.timeout_ms)
+            .command_interval(self.command_interval)
+            .data_valid_time(self.data_valid_time);
+        #[cfg(any(
+            feature = "imxrt1170",
+            feature = "imxrt1180",
+            feature = "imxrt500",
+            feature = "imxrt600"
+        ))]
+        {
+            block = block.device_mode_type(self.device_mode_type);
+        }
+        #[cfg(any(feature = "imxrt500", feature = "imxrt600"))]
+        {
+            block = block
+                .dll0_cr_val(self.dll0_cr_val)
+                .dll1_cr_val(self.dll1_cr_val);
+        }
+
+        if let Some(device_mode) = self.device_mode {
+            block = block.device_mode_configuration(DeviceModeConfiguration::Enabled {
+                device_mode_arg: device_mode.arg,
+                device_mode_seq: DeviceModeSequence::new(
+                    device_mode.number_of_luts,
+                    device_mode.starting_lut_index,
+                ),
+            });
+        }
+
+        if self.config_commands.len() > 3 {
+            return Err(Error::TooManyEntries {
+                field: "config_commands",
+                max: 3,
+            });
+        }
+        for (index, command) in self.config_commands.iter().enumerate() {
+            if !fits(command.number_of_luts, command.starting_lut_index) {
+                return Err(Error::SequenceRange {
+                    field: "config_commands",
+                });
+            }
+            block = block.config_command(
+                index,
+                ConfigurationCommand::new(
+                    command.number_of_luts,
+                    command.starting_lut_index,
+                    command.arg,
+                ),
+            );
+        }
+
+        if self.lut_custom_seqs.len() > 12 {
+            return Err(Error::TooManyEntries {
+                field: "lut_custom_seqs",
+                max: 12,
+            });
+        }
+        for (index, sequence) in self.lut_custom_seqs.iter().enumerate() {
+            if !fits(sequence.number_of_luts, sequence.starting_lut_index) {
+                return Err(Error::SequenceRange {
+                    field: "lut_custom_seqs",
+                });
+            }
+            block = block.lut_custom_seq(
+                index,
+                LutCustomSequence::new(sequence.number_of_luts, sequence.starting_lut_index),
+            );
+        }
+
+        if self.busy_offset > 31 {
+            return Err(Error::BusyOffset(self.busy_offset));
+        }
+        Ok(block.busy_bit(flexspi::BusyBit::new(
+            self.busy_offset,
+            self.busy_bit_polarity,
+        )))
+    }
+}
+
+/// A serial NOR configuration block description
+///
+/// The defaults match [`nor::ConfigurationBlock::new`], so the page size and
+/// sector size are zero.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SerialNor {
+    /// The FlexSPI configuration block.
+    pub flexspi: FlexSpi,
+    /// `pageSize`
+    pub page_size: u32,
+    /// `sectorSize`
+    pub sector_size: u32,
+    /// `ipcmdSerialClkFreq`
+    pub ip_cmd_serial_clk_freq: nor::SerialClockFrequency,
+    /// The block size, or `None` if it equals the sector size.
+    #[cfg(any(
+        feature = "imxrt1170",
+        feature = "imxrt1180",
+        feature = "imxrt500",
+        feature = "imxrt600"
+    ))]
+    pub block_size: Option<u32>,
+    /// `isDataOrderSwapped`
+    #[cfg(any(
+        feature = "imxrt1170",
+        feature = "imxrt1180",
+        feature = "imxrt500",
+        feature = "imxrt600"
+    ))]
+    pub is_data_order_swapped: bool,
+    /// `serialNorType`
+    #[cfg(any(
+        feature = "imxrt1170",
+        feature = "imxrt1180",
+        feature = "imxrt500",
+        feature = "imxrt600"
+    ))]
+    pub serial_nor_type: nor::SerialNorType,
+    /// `needExitNoCmdMode`
+    #[cfg(any(
+        feature = "imxrt1170",
+        feature = "imxrt1180",
+        feature = "imxrt500",
+        feature = "imxrt600"
+    ))]
+    pub need_exit_no_cmd_mode: bool,
+    /// `halfClkForNonReadCmd`
+    #[cfg(any(
+        feature = "imxrt1170",
+        feature = "imxrt1180",
+        feature = "imxrt500",
+        feature = "imxrt600"
+    ))]
+    pub half_clk_for_non_read_cmd: bool,
+    /// `needRestoreNoCmdMode`
+    #[cfg(any(
+        feature = "imxrt1170",
+        feature = "imxrt1180",
+        feature = "imxrt500",
+        feature = "imxrt600"
+    ))]
+    pub need_restore_no_cmd_mode: bool,
+    /// `flashStateCtx`
+    #[cfg(any(
+        feature = "imxrt1170",
+        feature = "imxrt1180",
+        feature = "imxrt500",
+        feature = "imxrt600"
+    ))]
+    pub flash_state_ctx: u32,
+}
+
+impl Default for SerialNor {
+    fn default() -> Self {
+        Self::from_block(&nor::ConfigurationBlock::new(
+            flexspi::ConfigurationBlock::new(LookupTable::new()),
+        ))
+    }
+}
+
+impl SerialNor {
+    /// Describe a serial NOR configuration block.
+    pub fn from_block(block: &nor::ConfigurationBlock) -> Self {
+        #[cfg(any(
+            feature = "imxrt1170",
+            feature = "imxrt1180",
+            feature = "imxrt500",
+            feature = "imxrt600"
+        ))]
+        let extras = block.get_extras();
+        SerialNor {
+            flexspi: FlexSpi::from_block(block.mem_cfg()),
+            page_size: block.get_page_size(),
+            sector_size: block.get_sector_size(),
+            ip_cmd_serial_clk_freq: block.get_ip_cmd_serial_clk_freq(),
+            #[cfg(any(
+                feature = "imxrt1170",
+                feature = "imxrt1180",
+                feature = "imxrt500",
+                feature = "imxrt600"
+            ))]
+            block_size: extras.get_block_size(),
+            #[cfg(any(
+                feature = "imxrt1170",
+                feature = "imxrt1180",
+                feature = "imxrt500",
+                feature = "imxrt600"
+            ))]
+            is_data_order_swapped: extras.get_is_data_order_swapped(),
+            #[cfg(any(
+                feature = "imxrt1170",
+                feature = "imxrt1180",
+                feature = "imxrt500",
+                feature = "imxrt600"
+            ))]
+            serial_nor_type: extras.get_serial_nor_type().unwrap_or_default(),
+            #[cfg(any(
+                feature = "imxrt1170",
+                feature = "imxrt1180",
+                feature = "imxrt500",
+                feature = "imxrt600"
+            ))]
+            need_exit_no_cmd_mode: extras.get_need_exit_no_cmd_mode(),
+            #[cfg(any(
+                feature = "imxrt1170",
+                feature = "imxrt1180",
+                feature = "imxrt500",
+                feature = "imxrt600"
+            ))]
+            half_clk_for_non_read_cmd: extras.get_half_clk_for_non_read_cmd(),
+            #[cfg(any(
+                feature = "imxrt1170",
+                feature = "imxrt1180",
+                feature = "imxrt500",
+                feature = "imxrt600"
+            ))]
+            need_restore_no_cmd_mode: extras.get_need_restore_no_cmd_mode(),
+            #[cfg(any(
+                feature = "imxrt1170",
+                feature = "imxrt1180",
+                feature = "imxrt500",
+                feature = "imxrt600"
+            ))]
+            flash_state_ctx: extras.get_flash_state_ctx().raw(),
+        }
+    }
+
+    /// Generate Rust source that produces the described configuration block
+    /// with this crate's `const` API.
+    ///
+    /// The source defines a public `SERIAL_NOR_CONFIGURATION_BLOCK` constant.
+    /// It only calls the builder methods for fields that differ from a new
+    /// configuration block. Use this with [`from_block`](Self::from_block)
+    /// to port a configuration block from another tool:
+    ///
+    /// ```
+    /// use imxrt_boot_gen::description::SerialNor;
+    /// use imxrt_boot_gen::flexspi::{self, LookupTable};
+    /// use imxrt_boot_gen::serial_flash::nor;
+    ///
+    /// # let bytes = nor::ConfigurationBlock::new(flexspi::ConfigurationBlock::new(LookupTable::new()))
+    /// #     .page_size(256)
+    /// #     .sector_size(4096)
+    /// #     .to_bytes();
+    /// let block = nor::ConfigurationBlock::from_bytes(&bytes).unwrap();
+    /// let source = SerialNor::from_block(&block).to_rust().unwrap();
+    /// assert!(source.contains("    .page_size(256)\n    .sector_size(4096);"));
+    /// ```
+    pub fn to_rust(&self) -> Result<String, Error> {
+        rust::serial_nor(self)
+    }
+
+    /// Lower the description into a serial NOR configuration block.
+    ///
+    /// This doesn't check that the page size and sector size are set.
+    pub fn to_block(&self) -> Result<nor::ConfigurationBlock, Error> {
+        let block = nor::ConfigurationBlock::new(self.flexspi.to_block()?)
+            .page_size(self.page_size)
+            .sector_size(self.sector_size)
+            .ip_cmd_serial_clk_freq(self.ip_cmd_serial_clk_freq);
+        #[cfg(any(
+            feature = "imxrt1170",
+            feature = "imxrt1180",
+            feature = "imxrt500",
+            feature = "imxrt600"
+        ))]
+        let block = {
+            let mut extras = nor::Extras::new()
+                .is_data_order_swapped(self.is_data_order_swapped)
+                .serial_nor_type(self.serial_nor_type)
+                .need_exit_no_cmd_mode(self.need_exit_no_cmd_mode)
+                .half_clk_for_non_read_cmd(self.half_clk_for_non_read_cmd)
+                .need_restore_no_cmd_mode(self.need_restore_no_cmd_mode)
+                .flash_state_ctx(nor::FlashStateContext::from_raw(self.flash_state_ctx));
+            if let Some(block_size) = self.block_size {
+                extras = extras.block_size(block_size);
+            }
+            block.extras(extras)
+        };
+        Ok(block)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Error, FlexSpi, SerialNor, Slot};
+    use crate::flexspi::{
+        self, opcodes::sdr::*, Command, ConfigurationCommand, Density, FlashPadType, Instr,
+        LookupTable, Pads, SequenceBuilder, SerialFlashRegion,
+    };
+    use crate::serial_flash::nor;
+
+    #[test]
+    fn round_trip() {
+        let lut = LookupTable::new()
+            .command(
+                Command::Read,
+                SequenceBuilder::new()
+                    .instr(Instr::new(CMD, Pads::One, 0xEB))
+                    .instr(Instr::new(RADDR, Pads::Four, 0x18))
+                    .instr(Instr::new(DUMMY, Pads::Four, 0x06))
+                    .instr(Instr::new(READ, Pads::Four, 0x04))
+                    .build(),
+            )
+            .sequence(
+                2,
+                SequenceBuilder::new()
+                    .instr(Instr::new(CMD, Pads::One, 0x01))
+                    .instr(Instr::new(WRITE, Pads::One, 0x01))
+                    .build(),
+            );
+        let block = nor::ConfigurationBlock::new(
+            flexspi::ConfigurationBlock::new(lut)
+                .serial_flash_pad_type(FlashPadType::Quad)
+                .flash_size(SerialFlashRegion::A1, Density::from_mebibytes(16))
+                .config_command(0, ConfigurationCommand::new(1, 2, 0x40)),
+        )
+        .page_size(256)
+        .sector_size(4096);
+
+        let description = SerialNor::from_block(&block);
+        let json = serde_json::to_string(&description).unwrap();
+        assert!(json.contains(r#""lookup_table":{"Read":["CMD_SDR SINGLE 0xEB","#));
+        assert!(json.contains(r#""2":["CMD_SDR SINGLE 0x01","WRITE_SDR SINGLE 0x01"]"#));
+
+        let description: SerialNor = serde_json::from_str(&json).unwrap();
+        assert_eq!(description.to_block(), Ok(block));
+    }
+
+    #[test]
+    fn defaults() {
+        let block = flexspi::ConfigurationBlock::new(LookupTable::new());
+        let description: FlexSpi = serde_json::from_str("{}").unwrap();
+        assert_eq!(description.to_block(), Ok(block));
+        assert_eq!(description, FlexSpi::default());
+    }
+
+    #[test]
+    fn invalid() {
+        assert!(serde_json::from_str::<FlexSpi>(r#"{"cs_hold": 1}"#).is_err());
+        assert!(
+            serde_json::from_str::<FlexSpi>(r#"{"lookup_table": {"Read": ["CMD_SDR 0xEB"]}}"#)
+                .is_err()
+        );
+        assert!(serde_json::from_str::<FlexSpi>(r#"{"lookup_table": {"16": []}}"#).is_err());
+        assert!(serde_json::from_str::<FlexSpi>(r#"{"version": "1.4.0"}"#).is_err());
+
+        let description = FlexSpi {
+            busy_offset: 32,
+            ..FlexSpi::default()
+        };
+        assert_eq!(description.to_block(), Err(Error::BusyOffset(32)));
+    }
+
+    #[test]
+    fn slots() {
+        assert_eq!(
+            serde_json::to_string(&Slot::from(Command::ReadStatus)).unwrap(),
+            r#""ReadStatus""#
+        );
+        assert_eq!(
+            serde_json::from_str::<Slot>(r#""15""#).unwrap(),
+            Slot::from(Command::Dummy)
+        );
+    }
+}
@@ -0,0 +1,418 @@
+//! Flash part descriptions
+//!
+//! A [`FlashPart`] describes a serial NOR flash part in terms of its data
+//! sheet: the read command, the address and data pads, and the device's
+//! sizes. The description builds a conventional lookup table, then produces
+//! the [`recommended_fcb`](nor::recommended_fcb) for the selected chip.
+
+use super::{rust, Error};
+use crate::flexspi::{
+    opcodes::{ddr, sdr},
+    Command, FlashPadType, Instr, LookupTable, Pads, SequenceBuilder,
+};
+use crate::serial_flash::nor::{self, NorFlashDevice};
+use serde::{Deserialize, Serialize};
+use std::fmt::Write;
+
+/// A serial NOR flash part
+///
+/// The sequences for everything other than reads use one pad, and the erase
+/// and program sequences use the same address size as reads.
+///
+/// With the `"toml"` feature, parse a part from a TOML document:
+///
+/// ```
+/// # #[cfg(feature = "toml")] {
+/// use imxrt_boot_gen::description::FlashPart;
+///
+/// let part = FlashPart::from_toml(r#"
+///     name = "W25Q64JV"
+///     flash_size = 8388608
+///     page_size = 256
+///     sector_size = 4096
+///     max_frequency_mhz = 133
+///     pads = 4
+///
+///     [read]
+///     opcode = 0xEB
+///     dummy_cycles = 6
+/// "#).unwrap();
+///
+/// let block = part.to_block().unwrap();
+/// assert_eq!(block.get_sector_size(), 4096);
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct FlashPart {
+    /// The part's name, used in generated documentation.
+    pub name: String,
+    /// The part's capacity, in bytes.
+    pub flash_size: u32,
+    /// The page size, in bytes.
+    pub page_size: u32,
+    /// The sector size, in bytes.
+    pub sector_size: u32,
+    /// The maximum serial clock frequency, in MHz. Defaults to 30.
+    #[serde(default = "default_max_frequency_mhz")]
+    pub max_frequency_mhz: u32,
+    /// The number of data pads used for reads: 1, 2, 4, or 8. Defaults to 1.
+    #[serde(default = "default_pads")]
+    pub pads: u8,
+    /// The number of address bits. Defaults to 24.
+    #[serde(default = "default_address_bits")]
+    pub address_bits: u8,
+    /// Indicates that the part drives DQS.
+    #[serde(default)]
+    pub dqs: bool,
+    /// The read command.
+    pub read: Read,
+    /// The opcodes of all other commands.
+    #[serde(default)]
+    pub commands: Commands,
+}
+
+/// The read command of a [`FlashPart`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Read {
+    /// The read opcode, like `0xEB`.
+    pub opcode: u8,
+    /// The number of address pads. Defaults to the part's data pads.
+    #[serde(default)]
+    pub address_pads: Option<u8>,
+    /// The `DUMMY` instruction's operand. Zero omits the instruction.
+    #[serde(default)]
+    pub dummy_cycles: u8,
+    /// Indicates that the address, dummy cycles, and data are DDR.
+    #[serde(default)]
+    pub ddr: bool,
+}
+
+/// The opcodes of a [`FlashPart`]'s other commands
+///
+/// The defaults are the common single-pad SPI NOR opcodes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Commands {
+    /// Read status register. Defaults to `0x05`.
+    pub read_status: u8,
+    /// Write enable. Defaults to `0x06`.
+    pub write_enable: u8,
+    /// Erase a sector. Defaults to `0x20`.
+    pub erase_sector: u8,
+    /// Program a page. Defaults to `0x02`.
+    pub page_program: u8,
+    /// Erase the chip. Defaults to `0x60`.
+    pub chip_erase: u8,
+}
+
+impl Default for Commands {
+    fn default() -> Self {
+        Commands {
+            read_status: 0x05,
+            write_enable: 0x06,
+            erase_sector: 0x20,
+            page_program: 0x02,
+            chip_erase: 0x60,
+        }
+    }
+}
+
+fn default_max_frequency_mhz() -> u32 {
+    30
+}
+
+fn default_pads() -> u8 {
+    1
+}
+
+fn default_address_bits() -> u8 {
+    24
+}
+
+/// Returns the pads for a number of pads.
+fn pads(field: &'static str, pads: u8) -> Result<Pads, Error> {
+    Ok(match pads {
+        1 => Pads::One,
+        2 => Pads::Two,
+        4 => Pads::Four,
+        8 => Pads::Eight,
+        _ => return Err(Error::InvalidField { field }),
+    })
+}
+
+impl FlashPart {
+    /// Parse a part from a TOML document.
+    ///
+    /// This requires the `"toml"` feature.
+    #[cfg(feature = "toml")]
+    pub fn from_toml(toml: &str) -> Result<Self, toml::de::Error> {
+        toml::from_str(toml)
+    }
+
+    /// Build the part's lookup table.
+    pub fn lookup_table(&self) -> Result<LookupTable, Error> {
+        let data_pads = pads("pads", self.pads)?;
+        let address_pads = pads(
+            "read.address_pads",
+            self.read.address_pads.unwrap_or(self.pads),
+        )?;
+        if !matches!(self.address_bits, 24 | 32) {
+            return Err(Error::InvalidField {
+                field: "address_bits",
+            });
+        }
+
+        let (raddr, dummy, read) = if self.read.ddr {
+            (ddr::RADDR, ddr::DUMMY, ddr::READ)
+        } else {
+            (sdr::RADDR, sdr::DUMMY, sdr::READ)
+        };
+        let mut read_seq = SequenceBuilder::new()
+            .instr(Instr::new(sdr::CMD, Pads::One, self.read.opcode))
+            .instr(Instr::new(raddr, address_pads, self.address_bits));
+        if self.read.dummy_cycles != 0 {
+            read_seq = read_seq.instr(Instr::new(dummy, data_pads, self.read.dummy_cycles));
+        }
+        let read_seq = read_seq.instr(Instr::new(read, data_pads, 0x04)).build();
+
+        let commands = &self.commands;
+        Ok(LookupTable::new()
+            .command(Command::Read, read_seq)
+            .command(
+                Command::ReadStatus,
+                SequenceBuilder::new()
+                    .instr(Instr::new(sdr::CMD, Pads::One, commands.read_status))
+                    .instr(Instr::new(sdr::READ, Pads::One, 0x04))
+                    .build(),
+            )
+            .command(
+                Command::WriteEnable,
+                SequenceBuilder::new()
+                    .instr(Instr::new(sdr::CMD, Pads::One, commands.write_enable))
+                    .build(),
+            )
+            .command(
+                Command::EraseSector,
+                SequenceBuilder::new()
+                    .instr(Instr::new(sdr::CMD, Pads::One, commands.erase_sector))
+                    .instr(Instr::new(sdr::RADDR, Pads::One, self.address_bits))
+                    .build(),
+            )
+            .command(
+                Command::PageProgram,
+                SequenceBuilder::new()
+                    .instr(Instr::new(sdr::CMD, Pads::One, commands.page_program))
+                    .instr(Instr::new(sdr::RADDR, Pads::One, self.address_bits))
+                    .instr(Instr::new(sdr::WRITE, Pads::One, 0x04))
+                    .build(),
+            )
+            .command(
+                Command::ChipErase,
+                SequenceBuilder::new()
+                    .instr(Instr::new(sdr::CMD, Pads::One, commands.chip_erase))
+                    .build(),
+            ))
+    }
+
+    /// Returns the `FlashPadType` name for the data pads.
+    fn pad_type(&self) -> Result<(FlashPadType, &'static str), Error> {
+        Ok(match pads("pads", self.pads)? {
+            Pads::One => (FlashPadType::Single, "Single"),
+            Pads::Two => (FlashPadType::Dual, "Dual"),
+            Pads::Four => (FlashPadType::Quad, "Quad"),
+            Pads::Eight => (FlashPadType::Octal, "Octal"),
+        })
+    }
+
+    /// Check the fields that `recommended_fcb` can't handle.
+    fn validate(&self) -> Result<(), Error> {
+        let fields = [
+            ("flash_size", self.flash_size),
+            ("page_size", self.page_size),
+            ("sector_size", self.sector_size),
+        ];
+        if let Some((field, _)) = fields.iter().find(|(_, size)| *size == 0) {
+            return Err(Error::InvalidField { field });
+        }
+        if self.max_frequency_mhz < 30 {
+            return Err(Error::InvalidField {
+                field: "max_frequency_mhz",
+            });
+        }
+        Ok(())
+    }
+
+    /// Describe the part as a [`NorFlashDevice`].
+    pub fn device(&self) -> Result<NorFlashDevice, Error> {
+        self.validate()?;
+        Ok(NorFlashDevice::new(self.lookup_table()?)
+            .flash_size(self.flash_size)
+            .page_size(self.page_size)
+            .sector_size(self.sector_size)
+            .pad_type(self.pad_type()?.0)
+            .max_frequency_mhz(self.max_frequency_mhz)
+            .dqs(self.dqs)
+            .ddr(self.read.ddr))
+    }
+
+    /// Produce the recommended configuration block for the selected chip.
+    pub fn to_block(&self) -> Result<nor::ConfigurationBlock, Error> {
+        Ok(nor::recommended_fcb(&self.device()?))
+    }
+
+    /// Generate Rust source that produces the same configuration block with
+    /// this crate's `const` API.
+    ///
+    /// The source defines a public `SERIAL_NOR_CONFIGURATION_BLOCK` constant.
+    pub fn to_rust(&self) -> Result<String, Error> {
+        self.validate()?;
+        let (_, pad_type) = self.pad_type()?;
+        let lookup_table = self.lookup_table()?;
+
+        let mut uses = rust::lookup_table_uses(&lookup_table);
+        if pad_type != "Single" {
+            uses.push("FlashPadType");
+        }
+        let mut source = String::new();
+        writeln!(
+            source,
+            "//! Serial NOR configuration block for the {}\n",
+            self.name
+        )
+        .unwrap();
+        writeln!(source, "{}", rust::use_declaration("flexspi", uses)).unwrap();
+        writeln!(
+            source,
+            "use imxrt_boot_gen::serial_flash::nor::{{self, NorFlashDevice}};\n"
+        )
+        .unwrap();
+        writeln!(source, "{}", rust::lookup_table("LUT", &lookup_table)).unwrap();
+
+        write!(
+            source,
+            "const DEVICE: NorFlashDevice = NorFlashDevice::new(LUT)\n    \
+             .flash_size({})\n    .page_size({})\n    .sector_size({})",
+            self.flash_size, self.page_size, self.sector_size
+        )
+        .unwrap();
+        if pad_type != "Single" {
+            write!(source, "\n    .pad_type(FlashPadType::{pad_type})").unwrap();
+        }
+        if self.max_frequency_mhz != default_max_frequency_mhz() {
+            write!(
+                source,
+                "\n    .max_frequency_mhz({})",
+                self.max_frequency_mhz
+            )
+            .unwrap();
+        }
+        if self.dqs {
+            source.push_str("\n    .dqs(true)");
+        }
+        if self.read.ddr {
+            source.push_str("\n    .ddr(true)");
+        }
+        source.push_str(
+            ";\n\n\
+             pub const SERIAL_NOR_CONFIGURATION_BLOCK: nor::ConfigurationBlock =\n    \
+             nor::recommended_fcb(&DEVICE);\n",
+        );
+        Ok(source)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Commands, FlashPart, Read};
+    use crate::description::Error;
+    use crate::flexspi::Command;
+
+    fn w25q64jv() -> FlashPart {
+        FlashPart {
+            name: "W25Q64JV".into(),
+            flash_size: 8 * 1024 * 1024,
+            page_size: 256,
+            sector_size: 4096,
+            max_frequency_mhz: 133,
+            pads: 4,
+            address_bits: 24,
+            dqs: false,
+            read: Read {
+                opcode: 0xEB,
+                address_pads: None,
+                dummy_cycles: 6,
+                ddr: false,
+            },
+            commands: Commands::default(),
+        }
+    }
+
+    #[test]
+    fn read_sequence() {
+        let lut = w25q64jv().lookup_table().unwrap();
+        assert_eq!(
+            lut.get_sequence(Command::Read as usize).to_string(),
+            "CMD_SDR SINGLE 0xEB; RADDR_SDR QUAD 0x18; DUMMY_SDR QUAD 0x06; READ_SDR QUAD 0x04"
+        );
+        let part = FlashPart {
+            read: Read {
+                address_pads: Some(1),
+                dummy_cycles: 0,
+                ..w25q64jv().read
+            },
+            ..w25q64jv()
+        };
+        let lut = part.lookup_table().unwrap();
+        assert_eq!(
+            lut.get_sequence(Command::Read as usize).to_string(),
+            "CMD_SDR SINGLE 0xEB; RADDR_SDR SINGLE 0x18; READ_SDR QUAD 0x04"
+        );
+        assert_eq!(
+            lut.get_sequence(Command::PageProgram as usize).to_string(),
+            "CMD_SDR SINGLE 0x02; RADDR_SDR SINGLE 0x18; WRITE_SDR SINGLE 0x04"
+        );
+    }
+
+    #[test]
+    fn invalid() {
+        let part = FlashPart {
+            pads: 3,
+            ..w25q64jv()
+        };
+        assert_eq!(
+            part.to_block().unwrap_err(),
+            Error::InvalidField { field: "pads" }
+        );
+        let part = FlashPart {
+            sector_size: 0,
+            ..w25q64jv()
+        };
+        assert_eq!(
+            part.to_block().unwrap_err(),
+            Error::InvalidField {
+                field: "sector_size"
+            }
+        );
+        let part = FlashPart {
+            max_frequency_mhz: 20,
+            ..w25q64jv()
+        };
+        assert_eq!(
+            part.to_block().unwrap_err(),
+            Error::InvalidField {
+                field: "max_frequency_mhz"
+            }
+        );
+    }
+
+    #[test]
+    fn to_rust() {
+        let source = w25q64jv().to_rust().unwrap();
+        assert!(source.starts_with("//! Serial NOR configuration block for the W25Q64JV\n"));
+        assert!(source.contains("    .instr(Instr::new(sdr::DUMMY, Pads::Four, 0x06))\n"));
+        assert!(source.contains("    .pad_type(FlashPadType::Quad)\n    .max_frequency_mhz(133);"));
+        assert!(source.contains("nor::recommended_fcb(&DEVICE);"));
+    }
+}
@@ -0,0 +1,724 @@
+//! Generate Rust source that uses this crate's `const` API
+
+use super::{Error, FlexSpi, SerialNor, Slot, COMMANDS};
+use crate::flexspi::{LookupTable, Sequence, INSTRUCTIONS_PER_SEQUENCE, JUMP_ON_CS, STOP};
+use std::fmt::Write;
+
+/// How the generated source spells an instruction.
+enum Expr {
+    /// `STOP`
+    Stop,
+    /// `JUMP_ON_CS`
+    JumpOnCs,
+    /// `Instr::jump_on_cs(index)`
+    JumpWithin(u8),
+    /// `Instr::new(rate::NAME, pads, operand)`, with the name and rate of a
+    /// known opcode.
+    Named(&'static str, &'static str),
+    /// `Instr::new(Opcode::from_raw(opcode), pads, operand)`, for every
+    /// instruction that the other forms can't express.
+    Raw,
+}
+
+impl Expr {
+    fn new(instr: crate::flexspi::Instr) -> Self {
+        if instr == STOP {
+            return Expr::Stop;
+        }
+        if instr == JUMP_ON_CS {
+            return Expr::JumpOnCs;
+        }
+        if instr.opcode() == JUMP_ON_CS.opcode()
+            && instr.pad_bits() == 0
+            && usize::from(instr.operand()) < INSTRUCTIONS_PER_SEQUENCE
+        {
+            return Expr::JumpWithin(instr.operand());
+        }
+        match instr.pads().and(instr.opcode().name()) {
+            Some(name) => match name.rsplit_once('_') {
+                Some((name, rate @ ("SDR" | "DDR"))) => Expr::Named(name, rate),
+                _ => Expr::Raw,
+            },
+            // An unknown opcode, or a STOP or JUMP_ON_CS with an operand
+            // or pads that the constants don't have.
+            None => Expr::Raw,
+        }
+    }
+}
+
+const PADS: [&str; 4] = ["Pads::One", "Pads::Two", "Pads::Four", "Pads::Eight"];
+
+/// Returns the expression that creates the instruction, like
+/// `Instr::new(sdr::CMD, Pads::One, 0xEB)`.
+fn instr(instr: crate::flexspi::Instr) -> String {
+    let pads = PADS[usize::from(instr.pad_bits())];
+    match Expr::new(instr) {
+        Expr::Stop => "STOP".into(),
+        Expr::JumpOnCs => "JUMP_ON_CS".into(),
+        Expr::JumpWithin(index) => format!("Instr::jump_on_cs({index})"),
+        Expr::Named(name, rate) => format!(
+            "Instr::new({}::{name}, {pads}, {:#04X})",
+            rate.to_lowercase(),
+            instr.operand()
+        ),
+        Expr::Raw => format!(
+            "Instr::new(Opcode::from_raw({:#04X}), {pads}, {:#04X})",
+            instr.opcode().raw(),
+            instr.operand()
+        ),
+    }
+}
+
+/// Returns the constant's name for the lookup table slot, like `SEQ_READ_STATUS`
+/// or `SEQ_2`.
+fn sequence_name(slot: Slot) -> String {
+    match COMMANDS
+        .iter()
+        .find(|command| **command as usize == slot.index())
+    {
+        Some(command) => {
+            let mut name = String::from("SEQ");
+            for ch in format!("{command:?}").chars() {
+                if ch.is_uppercase() {
+                    name.push('_');
+                }
+                name.push(ch.to_ascii_uppercase());
+            }
+            name
+        }
+        None => format!("SEQ_{}", slot.index()),
+    }
+}
+
+/// Returns the `SequenceBuilder` chain that creates the sequence.
+fn sequence(sequence: &Sequence) -> String {
+    let count = sequence
+        .0
+        .iter()
+        .rposition(|instr| *instr != STOP)
+        .map_or(0, |last| last + 1);
+    let mut source = String::from("SequenceBuilder::new()\n");
+    for instr in &sequence.0[..count] {
+        writeln!(source, "    .instr({})", self::instr(*instr)).unwrap();
+    }
+    source.push_str("    .build()");
+    source
+}
+
+/// Returns the `flexspi` items that the generated lookup table uses.
+pub(crate) fn lookup_table_uses(lookup_table: &LookupTable) -> Vec<&'static str> {
+    let instrs: Vec<_> = (0..16)
+        .flat_map(|index| lookup_table.get_sequence(index).0)
+        .collect();
+    let exprs: Vec<_> = instrs.iter().map(|instr| Expr::new(*instr)).collect();
+    let any = |matches: fn(&Expr) -> bool| exprs.iter().any(matches);
+    let mut uses = Vec::new();
+    if any(|expr| matches!(expr, Expr::Named(_, "DDR"))) {
+        uses.push("opcodes::ddr");
+    }
+    if any(|expr| matches!(expr, Expr::Named(_, "SDR"))) {
+        uses.push("opcodes::sdr");
+    }
+    if COMMANDS
+        .iter()
+        .any(|command| lookup_table.get_sequence(*command as usize) != Sequence::stopped())
+    {
+        uses.push("Command");
+    }
+    if any(|expr| matches!(expr, Expr::JumpWithin(_) | Expr::Named(..) | Expr::Raw)) {
+        uses.push("Instr");
+    }
+    uses.push("LookupTable");
+    if any(|expr| matches!(expr, Expr::Raw)) {
+        uses.push("Opcode");
+    }
+    if any(|expr| matches!(expr, Expr::Named(..) | Expr::Raw)) {
+        uses.push("Pads");
+    }
+    if (0..16).any(|index| lookup_table.get_sequence(index) != Sequence::stopped()) {
+        uses.extend(["Sequence", "SequenceBuilder"]);
+    }
+    if any(|expr| matches!(expr, Expr::JumpOnCs)) {
+        uses.push("JUMP_ON_CS");
+    }
+    let stop_before_last = (0..16).any(|index| {
+        let sequence = lookup_table.get_sequence(index).0;
+        let last = sequence.iter().rposition(|instr| *instr != STOP);
+        last.is_some_and(|last| sequence[..last].contains(&STOP))
+    });
+    if stop_before_last {
+        uses.push("STOP");
+    }
+    uses
+}
+
+/// Returns a `use` declaration for `items` in the crate's `module`, sorted like
+/// `rustfmt` sorts them.
+pub(crate) fn use_declaration(module: &str, mut items: Vec<&str>) -> String {
+    items.sort_by_key(|item| {
+        let first = item.chars().next().unwrap_or_default();
+        let class = if *item == "self" {
+            -1
+        } else if first.is_lowercase() {
+            0
+        } else if item.chars().any(|ch| ch.is_lowercase()) {
+            1
+        } else {
+            2
+        };
+        (class, *item)
+    });
+    format!("use imxrt_boot_gen::{module}::{{{}}};", items.join(", "))
+}
+
+/// Returns a `Sequence` constant for every sequence that isn't empty, followed
+/// by a `LookupTable` constant named `name`.
+pub(crate) fn lookup_table(name: &str, lookup_table: &LookupTable) -> String {
+    let slots: Vec<_> = (0..16)
+        .filter_map(Slot::new)
+        .filter(|slot| lookup_table.get_sequence(slot.index()) != Sequence::stopped())
+        .collect();
+
+    let mut source = String::new();
+    for slot in &slots {
+        writeln!(
+            source,
+            "const {}: Sequence = {};\n",
+            sequence_name(*slot),
+            sequence(&lookup_table.get_sequence(slot.index()))
+        )
+        .unwrap();
+    }
+    write!(source, "const {name}: LookupTable = LookupTable::new()").unwrap();
+    for slot in &slots {
+        match COMMANDS
+            .iter()
+            .find(|command| **command as usize == slot.index())
+        {
+            Some(command) => write!(
+                source,
+                "\n    .command(Command::{command:?}, {})",
+                sequence_name(*slot)
+            ),
+            None => write!(
+                source,
+                "\n    .sequence({}, {})",
+                slot.index(),
+                sequence_name(*slot)
+            ),
+        }
+        .unwrap();
+    }
+    source.push_str(";\n");
+    source
+}
+
+/// Builder calls, and the `flexspi` items that they use.
+#[derive(Default)]
+struct Calls {
+    calls: Vec<String>,
+    uses: Vec<&'static str>,
+}
+
+impl Calls {
+    fn call(&mut self, uses: &[&'static str], call: String) {
+        for item in uses {
+            if !self.uses.contains(item) {
+                self.uses.push(item);
+            }
+        }
+        self.calls.push(call);
+    }
+    /// Returns the calls, one per line, with `indent`.
+    fn join(&self, indent: &str) -> String {
+        self.calls
+            .iter()
+            .map(|call| format!("\n{indent}.{call}"))
+            .collect()
+    }
+}
+
+/// Returns the calls that set every FlexSPI field that differs from
+/// a new configuration block.
+fn flexspi_calls(flexspi: &FlexSpi) -> Result<Calls, Error> {
+    let default = FlexSpi::default();
+    let mut calls = Calls::default();
+    if flexspi.version != default.version {
+        let version = flexspi.version;
+        calls.call(
+            &["Version"],
+            format!(
+                "version(Version::new({}, {}, {}))",
+                version.major(),
+                version.minor(),
+                version.bugfix()
+            ),
+        );
+    }
+    if flexspi.read_sample_clk_src != default.read_sample_clk_src {
+        calls.call(
+            &["ReadSampleClockSource"],
+            format!(
+                "read_sample_clk_src(ReadSampleClockSource::{:?})",
+                flexspi.read_sample_clk_src
+            ),
+        );
+    }
+    if flexspi.cs_hold_time != default.cs_hold_time {
+        calls.call(&[], format!("cs_hold_time({:#04X})", flexspi.cs_hold_time));
+    }
+    if flexspi.cs_setup_time != default.cs_setup_time {
+        calls.call(
+            &[],
+            format!("cs_setup_time({:#04X})", flexspi.cs_setup_time),
+        );
+    }
+    if flexspi.column_address_width != default.column_address_width {
+        calls.call(
+            &["ColumnAddressWidth"],
+            format!(
+                "column_address_width(ColumnAddressWidth::{:?})",
+                flexspi.column_address_width
+            ),
+        );
+    }
+    if let Some(device_mode) = flexspi.device_mode {
+        calls.call(
+            &["DeviceModeConfiguration", "DeviceModeSequence"],
+            format!(
+                "device_mode_configuration(DeviceModeConfiguration::Enabled {{ \
+                 device_mode_arg: {:#010X}, \
+                 device_mode_seq: DeviceModeSequence::new({}, {}) }})",
+                device_mode.arg, device_mode.number_of_luts, device_mode.starting_lut_index
+            ),
+        );
+    }
+    #[cfg(any(
+        feature = "imxrt1170",
+        feature = "imxrt1180",
+        feature = "imxrt500",
+        feature = "imxrt600"
+    ))]
+    if flexspi.device_mode_type != default.device_mode_type {
+        calls.call(
+            &["DeviceModeType"],
+            format!(
+                "device_mode_type(DeviceModeType::{:?})",
+                flexspi.device_mode_type
+            ),
+        );
+    }
+    if flexspi.wait_time_cfg_commands != default.wait_time_cfg_commands {
+        let wait_time_us =
+            flexspi
+                .wait_time_cfg_commands
+                .checked_mul(100)
+                .ok_or(Error::InvalidField {
+                    field: "wait_time_cfg_commands",
+                })?;
+        calls.call(
+            &["WaitTimeConfigurationCommands"],
+            format!("wait_time_cfg_commands(WaitTimeConfigurationCommands::new({wait_time_us}))"),
+        );
+    }
+    // Setting any entry sets the enable flag, so only the first entry
+    // needs a call if they're all empty.
+    let commands = &flexspi.config_commands;
+    for (index, command) in commands.iter().enumerate() {
+        let empty = (
+            command.number_of_luts,
+            command.starting_lut_index,
+            command.arg,
+        ) == (0, 0, 0);
+        if empty && (index != 0 || commands.iter().any(|other| other != command)) {
+            continue;
+        }
+        calls.call(
+            &["ConfigurationCommand"],
+            format!(
+                "config_command({index}, ConfigurationCommand::new({}, {}, {:#010X}))",
+                command.number_of_luts, command.starting_lut_index, command.arg
+            ),
+        );
+    }
+    if flexspi.controller_misc_options != default.controller_misc_options {
+        calls.call(
+            &[],
+            format!(
+                "controller_misc_options({:#010X})",
+                flexspi.controller_misc_options
+            ),
+        );
+    }
+    if flexspi.serial_flash_pad_type != default.serial_flash_pad_type {
+        calls.call(
+            &["FlashPadType"],
+            format!(
+                "serial_flash_pad_type(FlashPadType::{:?})",
+                flexspi.serial_flash_pad_type
+            ),
+        );
+    }
+    if flexspi.serial_clk_freq != default.serial_clk_freq {
+        calls.call(
+            &["SerialClockFrequency"],
+            format!(
+                "serial_clk_freq(SerialClockFrequency::{:?})",
+                flexspi.serial_clk_freq
+            ),
+        );
+    }
+    let sequences = &flexspi.lut_custom_seqs;
+    for (index, sequence) in sequences.iter().enumerate() {
+        let empty = (sequence.number_of_luts, sequence.starting_lut_index) == (0, 0);
+        if empty && (index != 0 || sequences.iter().any(|other| other != sequence)) {
+            continue;
+        }
+        calls.call(
+            &["LutCustomSequence"],
+            format!(
+                "lut_custom_seq({index}, LutCustomSequence::new({}, {}))",
+                sequence.number_of_luts, sequence.starting_lut_index
+            ),
+        );
+    }
+    let sizes = flexspi.flash_sizes;
+    for (region, size) in [
+        ("A1", sizes.a1),
+        ("A2", sizes.a2),
+        ("B1", sizes.b1),
+        ("B2", sizes.b2),
+    ] {
+        if size != 0 {
+            calls.call(
+                &["SerialFlashRegion"],
+                format!("flash_size_bytes(SerialFlashRegion::{region}, {size:#X})"),
+            );
+        }
+    }
+    let overrides = [
+        ("cs", flexspi.cs_pad_setting_override),
+        ("sclk", flexspi.sclk_pad_setting_override),
+        ("data", flexspi.data_pad_setting_override),
+        ("dqs", flexspi.dqs_pad_setting_override),
+    ];
+    for (pad, raw) in overrides {
+        if raw != 0 {
+            calls.call(
+                &["PadSettingOverride"],
+                format!("{pad}_pad_setting_override(PadSettingOverride::from_raw({raw:#010X}))"),
+            );
+        }
+    }
+    if flexspi.timeout_ms != default.timeout_ms {
+        calls.call(&[], format!("timeout_ms({})", flexspi.timeout_ms));
+    }
+    if flexspi.command_interval != default.command_interval {
+        calls.call(
+            &[],
+            format!("command_interval({})", flexspi.command_interval),
+        );
+    }
+    if flexspi.data_valid_time != default.data_valid_time {
+        let time = flexspi.data_valid_time;
+        let (a, b) = (time.port_a(), time.port_b());
+        let mut call = format!(
+            "data_valid_time(DataValidTime::new({}, {})",
+            a.time_100ps(),
+            b.time_100ps()
+        );
+        if (a.delay_cells(), b.delay_cells()) != (0, 0) {
+            call += &format!(".delay_cells({}, {})", a.delay_cells(), b.delay_cells());
+        }
+        call.push(')');
+        calls.call(&["DataValidTime"], call);
+    }
+    if (flexspi.busy_offset, flexspi.busy_bit_polarity)
+        != (default.busy_offset, default.busy_bit_polarity)
+    {
+        calls.call(
+            &["BusyBit", "BusyBitPolarity"],
+            format!(
+                "busy_bit(BusyBit::new({}, BusyBitPolarity::{:?}))",
+                flexspi.busy_offset, flexspi.busy_bit_polarity
+            ),
+        );
+    }
+    #[cfg(any(feature = "imxrt500", feature = "imxrt600"))]
+    {
+        if flexspi.dll0_cr_val != default.dll0_cr_val {
+            calls.call(&[], format!("dll0_cr_val({:#010X})", flexspi.dll0_cr_val));
+        }
+        if flexspi.dll1_cr_val != default.dll1_cr_val {
+            calls.call(&[], format!("dll1_cr_val({:#010X})", flexspi.dll1_cr_val));
+        }
+    }
+    Ok(calls)
+}
+
+/// Returns the calls that set every serial NOR field that differs from a new
+/// configuration block.
+fn serial_nor_calls(description: &SerialNor) -> Calls {
+    let default = SerialNor::default();
+    let mut calls = Calls::default();
+    if description.page_size != default.page_size {
+        calls.call(&[], format!("page_size({})", description.page_size));
+    }
+    if description.sector_size != default.sector_size {
+        calls.call(&[], format!("sector_size({})", description.sector_size));
+    }
+    if description.ip_cmd_serial_clk_freq != default.ip_cmd_serial_clk_freq {
+        calls.call(
+            &[],
+            format!(
+                "ip_cmd_serial_clk_freq(nor::SerialClockFrequency::{:?})",
+                description.ip_cmd_serial_clk_freq
+            ),
+        );
+    }
+    #[cfg(any(
+        feature = "imxrt1170",
+        feature = "imxrt1180",
+        feature = "imxrt500",
+        feature = "imxrt600"
+    ))]
+    {
+        if let Some(block_size) = description.block_size {
+            calls.call(&[], format!("block_size({block_size})"));
+        }
+        let flags = [
+            ("is_data_order_swapped", description.is_data_order_swapped),
+            ("need_exit_no_cmd_mode", description.need_exit_no_cmd_mode),
+            (
+                "half_clk_for_non_read_cmd",
+                description.half_clk_for_non_read_cmd,
+            ),
+            (
+                "need_restore_no_cmd_mode",
+                description.need_restore_no_cmd_mode,
+            ),
+        ];
+        for (name, flag) in flags {
+            if flag {
+                calls.call(&[], format!("{name}(true)"));
+            }
+        }
+        if description.serial_nor_type != default.serial_nor_type {
+            calls.call(
+                &[],
+                format!(
+                    "serial_nor_type(nor::SerialNorType::{:?})",
+                    description.serial_nor_type
+                ),
+            );
+        }
+        if description.flash_state_ctx != default.flash_state_ctx {
+            calls.call(
+                &[],
+                format!(
+                    "flash_state_ctx(nor::FlashStateContext::from_raw({:#010X}))",
+                    description.flash_state_ctx
+                ),
+            );
+        }
+    }
+    calls
+}
+
+/// Returns a Rust module that defines `SERIAL_NOR_CONFIGURATION_BLOCK`.
+pub(crate) fn serial_nor(description: &SerialNor) -> Result<String, Error> {
+    let block = description.to_block()?;
+    let lookup_table = block.mem_cfg().get_lookup_table();
+    let flexspi = flexspi_calls(&description.flexspi)?;
+    let nor = serial_nor_calls(description);
+
+    let mut uses = lookup_table_uses(lookup_table);
+    uses.push("self");
+    for item in &flexspi.uses {
+        if !uses.contains(item) {
+            uses.push(item);
+        }
+    }
+
+    let mut source = String::from("//! Serial NOR configuration block\n\n");
+    writeln!(source, "{}", use_declaration("flexspi", uses)).unwrap();
+    writeln!(source, "use imxrt_boot_gen::serial_flash::nor;\n").unwrap();
+    writeln!(source, "{}", self::lookup_table("LUT", lookup_table)).unwrap();
+    write!(
+        source,
+        "pub const SERIAL_NOR_CONFIGURATION_BLOCK: nor::ConfigurationBlock =\n    \
+         nor::ConfigurationBlock::new(\n        \
+         flexspi::ConfigurationBlock::new(LUT){},\n    ){};\n",
+        flexspi.join("            "),
+        nor.join("    ")
+    )
+    .unwrap();
+    Ok(source)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::description::SerialNor;
+    use crate::flexspi::{
+        self,
+        opcodes::{ddr, sdr},
+        Command, ConfigurationCommand, DataValidTime, Instr, LookupTable, Opcode, Pads,
+        SequenceBuilder, JUMP_ON_CS,
+    };
+    use crate::serial_flash::nor;
+
+    #[test]
+    fn lookup_table() {
+        let lut = LookupTable::new()
+            .command(
+                Command::ReadStatus,
+                SequenceBuilder::new()
+                    .instr(Instr::new(sdr::CMD, Pads::One, 0x05))
+                    .instr(Instr::new(ddr::READ, Pads::Eight, 0x04))
+                    .build(),
+            )
+            .sequence(
+                2,
+                SequenceBuilder::new()
+                    .instr(Instr::new(sdr::CMD, Pads::One, 0x06))
+                    .instr(JUMP_ON_CS)
+                    .build(),
+            )
+            .sequence(
+                3,
+                SequenceBuilder::new()
+                    .instr(Instr::new(sdr::CMD, Pads::One, 0x07))
+                    .instr(Instr::jump_on_cs(1))
+                    .build(),
+            );
+        assert_eq!(
+            super::lookup_table("LUT", &lut),
+            "\
+const SEQ_READ_STATUS: Sequence = SequenceBuilder::new()
+    .instr(Instr::new(sdr::CMD, Pads::One, 0x05))
+    .instr(Instr::new(ddr::READ, Pads::Eight, 0x04))
+    .build();
+
+const SEQ_2: Sequence = SequenceBuilder::new()
+    .instr(Instr::new(sdr::CMD, Pads::One, 0x06))
+    .instr(JUMP_ON_CS)
+    .build();
+
+const SEQ_WRITE_ENABLE: Sequence = SequenceBuilder::new()
+    .instr(Instr::new(sdr::CMD, Pads::One, 0x07))
+    .instr(Instr::jump_on_cs(1))
+    .build();
+
+const LUT: LookupTable = LookupTable::new()
+    .command(Command::ReadStatus, SEQ_READ_STATUS)
+    .sequence(2, SEQ_2)
+    .command(Command::WriteEnable, SEQ_WRITE_ENABLE);
+"
+        );
+        assert_eq!(
+            super::lookup_table_uses(&lut),
+            [
+                "opcodes::ddr",
+                "opcodes::sdr",
+                "Command",
+                "Instr",
+                "LookupTable",
+                "Pads",
+                "Sequence",
+                "SequenceBuilder",
+                "JUMP_ON_CS"
+            ]
+        );
+        assert_eq!(
+            super::use_declaration("flexspi", vec!["STOP", "self", "Pads", "opcodes::sdr"]),
+            "use imxrt_boot_gen::flexspi::{self, opcodes::sdr, Pads, STOP};"
+        );
+    }
+
+    #[test]
+    fn raw_instructions() {
+        let raw = Instr::new(Opcode::from_raw(0x3F), Pads::Four, 0x12);
+        let stop = Instr::new(Opcode::from_raw(0x00), Pads::One, 0x05);
+        let jump = Instr::new(Opcode::from_raw(0x1F), Pads::Two, 0x01);
+        let lut = LookupTable::new().sequence(
+            2,
+            SequenceBuilder::new()
+                .instr(Instr::new(sdr::CMD, Pads::One, 0x06))
+                .instr(raw)
+                .instr(stop)
+                .instr(jump)
+                .build(),
+        );
+        let block = nor::ConfigurationBlock::new(flexspi::ConfigurationBlock::new(lut))
+            .page_size(256)
+            .sector_size(4096);
+
+        // Parse the bytes, then describe them as JSON and as Rust.
+        let parsed = nor::ConfigurationBlock::from_bytes(&block.to_bytes()).unwrap();
+        let description = SerialNor::from_block(&parsed);
+        let json = serde_json::to_string(&description).unwrap();
+        assert!(json.contains(r#""UNKNOWN(0x3F) QUAD 0x12","STOP 0x05","JUMP_ON_CS DUAL 0x01""#));
+        let description: SerialNor = serde_json::from_str(&json).unwrap();
+        assert_eq!(
+            description.to_block().unwrap().mem_cfg().get_lookup_table(),
+            block.mem_cfg().get_lookup_table(),
+        );
+
+        let source = super::lookup_table("LUT", parsed.mem_cfg().get_lookup_table());
+        assert!(source.contains(
+            "\
+const SEQ_2: Sequence = SequenceBuilder::new()
+    .instr(Instr::new(sdr::CMD, Pads::One, 0x06))
+    .instr(Instr::new(Opcode::from_raw(0x3F), Pads::Four, 0x12))
+    .instr(Instr::new(Opcode::from_raw(0x00), Pads::One, 0x05))
+    .instr(Instr::new(Opcode::from_raw(0x1F), Pads::Two, 0x01))
+    .build();
+"
+        ));
+        assert_eq!(
+            super::lookup_table_uses(parsed.mem_cfg().get_lookup_table()),
+            [
+                "opcodes::sdr",
+                "Instr",
+                "LookupTable",
+                "Opcode",
+                "Pads",
+                "Sequence",
+                "SequenceBuilder"
+            ]
+        );
+    }
+
+    #[test]
+    fn serial_nor() {
+        let block = nor::ConfigurationBlock::new(
+            flexspi::ConfigurationBlock::new(LookupTable::new())
+                .cs_hold_time(0x01)
+                .config_command(1, ConfigurationCommand::new(1, 2, 0x40))
+                .data_valid_time(DataValidTime::new(16, 0).delay_cells(0, 12)),
+        )
+        .page_size(256)
+        .sector_size(4096);
+        assert_eq!(
+            super::serial_nor(&SerialNor::from_block(&block)).unwrap(),
+            "\
+//! Serial NOR configuration block
+
+use imxrt_boot_gen::flexspi::{self, ConfigurationCommand, DataValidTime, LookupTable};
+use imxrt_boot_gen::serial_flash::nor;
+
+const LUT: LookupTable = LookupTable::new();
+
+pub const SERIAL_NOR_CONFIGURATION_BLOCK: nor::ConfigurationBlock =
+    nor::ConfigurationBlock::new(
+        flexspi::ConfigurationBlock::new(LUT)
+            .cs_hold_time(0x01)
+            .config_command(1, ConfigurationCommand::new(1, 2, 0x00000040))
+            .data_valid_time(DataValidTime::new(16, 0).delay_cells(0, 12)),
+    )
+    .page_size(256)
+    .sector_size(4096);
+"
+        );
+    }
+}
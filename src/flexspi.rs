@@ -54,17 +54,46 @@
 //! Once you've created your sequences and lookup table, use the lookup table to create
 //! a [`ConfigurationBlock`]. See the `ConfigurationBlock` documentation
 //! for more information.
+//!
+//! # Chip-specific fields
+//!
+//! Some fields only exist on some chips. For instance, byte `0x011` is
+//! `deviceModeType` on the 1170, 1180, 500, and 600, and it's reserved on the
+//! others. The chip feature selects the layout, so `ConfigurationBlock` only has
+//! setters for the fields that exist on your chip. Setting a field that your chip
+//! reserves fails to compile.
 
 mod fields;
+mod getters;
+pub(crate) mod layout;
 mod lookup;
+mod pad_setting;
+mod parse;
+mod profile;
+mod regions;
 mod sequence;
 
 pub use fields::*;
+pub use layout::{Field, FIELDS};
+pub(crate) use lookup::NUMBER_OF_SEQUENCES;
 pub use lookup::{Command, LookupTable};
-pub use sequence::{opcodes, Instr, Pads, Sequence, SequenceBuilder, JUMP_ON_CS, STOP};
+pub use pad_setting::PadSettingOverride;
+#[cfg(not(any(
+    feature = "imxrt1170",
+    feature = "imxrt1180",
+    feature = "imxrt500",
+    feature = "imxrt600"
+)))]
+pub use pad_setting::{DriveStrength, Pull, PullKeep, SlewRate, Speed};
+pub use profile::{Performance, Profile};
+pub use regions::FlashRegions;
+#[cfg(feature = "serde")]
+pub(crate) use sequence::INSTRUCTIONS_PER_SEQUENCE;
+pub use sequence::{opcodes, Instr, Opcode, Pads, Sequence, SequenceBuilder, JUMP_ON_CS, STOP};
 
 /// A version identifier.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "zerocopy", derive(zerocopy::IntoBytes, zerocopy::Immutable))]
 #[repr(transparent)]
 pub struct Version(u32);
 
@@ -78,6 +107,51 @@ impl Version {
             ((b'V' as u32) << 24) | ((major as u32) << 16) | ((minor as u32) << 8) | bugfix as u32,
         )
     }
+
+    /// Parse a raw version identifier.
+    ///
+    /// Returns `None` if the most significant byte isn't ASCII 'V'.
+    ///
+    /// ```
+    /// use imxrt_boot_gen::flexspi::Version;
+    ///
+    /// assert_eq!(Version::from_raw(0x5601_0400), Some(Version::new(1, 4, 0)));
+    /// assert_eq!(Version::from_raw(0x0001_0400), None);
+    /// ```
+    pub const fn from_raw(raw: u32) -> Option<Version> {
+        if (raw >> 24) as u8 == b'V' {
+            Some(Version(raw))
+        } else {
+            None
+        }
+    }
+
+    /// Returns the raw version identifier.
+    pub const fn raw(self) -> u32 {
+        self.0
+    }
+
+    /// Returns the major version.
+    pub const fn major(self) -> u8 {
+        (self.0 >> 16) as u8
+    }
+
+    /// Returns the minor version.
+    pub const fn minor(self) -> u8 {
+        (self.0 >> 8) as u8
+    }
+
+    /// Returns the bugfix version.
+    pub const fn bugfix(self) -> u8 {
+        self.0 as u8
+    }
+}
+
+/// Formats the version like `V1.4.0`.
+impl core::fmt::Display for Version {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "V{}.{}.{}", self.major(), self.minor(), self.bugfix())
+    }
 }
 
 /// ASCII 'FCFB'
@@ -125,12 +199,18 @@ pub const RECOMMENDED_CS_SETUP_TIME: u8 = 0x03;
 ///         .column_address_width(ColumnAddressWidth::OtherDevices)
 ///         .device_mode_configuration(DeviceModeConfiguration::Disabled)
 ///         .wait_time_cfg_commands(WaitTimeConfigurationCommands::new(40_000))
-///         .flash_size(SerialFlashRegion::A1, 0x0020_0000)
+///         .flash_size(SerialFlashRegion::A1, Density::from_mebibytes(2))
 ///         .serial_clk_freq(SerialClockFrequency::MHz60)
 ///         .serial_flash_pad_type(FlashPadType::Quad);
+/// ```
+///
+/// # Layout
 ///
+/// The configuration block is [`SIZE`](ConfigurationBlock::SIZE) bytes, and it's
+/// aligned to [`ALIGNMENT`](ConfigurationBlock::ALIGNMENT) bytes.
 #[derive(Debug, Clone, Copy)]
-#[repr(C, packed)]
+#[cfg_attr(feature = "zerocopy", derive(zerocopy::IntoBytes, zerocopy::Immutable))]
+#[repr(C, align(4))]
 pub struct ConfigurationBlock {
     tag: u32,
     version: Version,
@@ -140,42 +220,53 @@ pub struct ConfigurationBlock {
     cs_setup_time: u8,
     column_address_width: ColumnAddressWidth,
     device_mode_configuration: u8,
-    /// TODO: this isn't reserved on 1170.
-    /// It's "device mode type", with a default value
-    /// of "generic."
-    _reserved1: [u8; 1], // 0x011
+    /// `deviceModeType` on 11xx chips; reserved on all others.
+    device_mode_type: u8,
     wait_time_cfg_commands: WaitTimeConfigurationCommands,
     device_mode_sequence: DeviceModeSequence,
     device_mode_arg: u32,
-    config_cmd_enable: u8,
+    pub(crate) config_cmd_enable: u8,
     _reserved2: [u8; 3], // 0x01D
-    config_cmd_seqs: [u8; 12],
+    pub(crate) config_cmd_seqs: [[u8; 4]; CONFIG_CMD_SLOTS],
     _reserved3: [u8; 4], // 0x02C
-    cfg_cmd_args: [u8; 12],
+    pub(crate) cfg_cmd_args: [u32; CONFIG_CMD_SLOTS],
     _reserved4: [u8; 4], // 0x03C
     controller_misc_options: u32,
     pub(crate) device_type: u8,
     serial_flash_pad_type: FlashPadType,
     serial_clk_freq: SerialClockFrequency,
-    lut_custom_seq_enable: u8,
+    pub(crate) lut_custom_seq_enable: u8,
     _reserved5: [u8; 8], // 0x048
     /// A1, A2, B1, B2
-    serial_flash_sizes: [u32; 4],
-    cs_pad_setting_override: u32,
-    sclk_pad_setting_override: u32,
-    data_pad_setting_override: u32,
-    dqs_pad_setting_override: u32,
-    timeout_ms: u32,
-    command_interval: u32,
-    data_valid_time: u32,
-    busy_offset: u16,
-    busy_bit_polarity: u16,
-    lookup_table: LookupTable,
-    lut_custom_seq: [u8; 48],
-    _reserved6: [u8; 16],
+    pub(crate) serial_flash_sizes: [u32; 4],
+    pub(crate) cs_pad_setting_override: PadSettingOverride,
+    pub(crate) sclk_pad_setting_override: PadSettingOverride,
+    pub(crate) data_pad_setting_override: PadSettingOverride,
+    pub(crate) dqs_pad_setting_override: PadSettingOverride,
+    pub(crate) timeout_ms: u32,
+    pub(crate) command_interval: u32,
+    pub(crate) data_valid_time: DataValidTime,
+    pub(crate) busy_offset: u16,
+    pub(crate) busy_bit_polarity: u16,
+    pub(crate) lookup_table: LookupTable,
+    pub(crate) lut_custom_seq: [LutCustomSequence; LUT_CUSTOM_SEQ_SLOTS],
+    /// `dll0CrVal` and `dll1CrVal` on 500 and 600 chips; reserved on all others.
+    #[cfg(any(feature = "imxrt500", feature = "imxrt600"))]
+    dll_cr_val: [u32; 2],
+    _reserved6: [u8; RESERVED6_SIZE],
 }
 
+#[cfg(any(feature = "imxrt500", feature = "imxrt600"))]
+const RESERVED6_SIZE: usize = 8;
+#[cfg(not(any(feature = "imxrt500", feature = "imxrt600")))]
+const RESERVED6_SIZE: usize = 16;
+
 impl ConfigurationBlock {
+    /// The size of a FlexSPI configuration block, in bytes.
+    pub const SIZE: usize = 448;
+    /// The alignment of a FlexSPI configuration block, in bytes.
+    pub const ALIGNMENT: usize = 4;
+
     /// Create a new configuration block that uses `lookup_table` as the
     /// FlexSPI LUT
     pub const fn new(lookup_table: LookupTable) -> Self {
@@ -191,36 +282,47 @@ impl ConfigurationBlock {
             device_mode_sequence: DeviceModeSequence::new(0, 0),
             device_mode_arg: 0,
             config_cmd_enable: 0,
-            config_cmd_seqs: [0; 12],
-            cfg_cmd_args: [0; 12],
+            config_cmd_seqs: [[0; 4]; CONFIG_CMD_SLOTS],
+            cfg_cmd_args: [0; CONFIG_CMD_SLOTS],
             controller_misc_options: 0,
             device_type: 0, // Invalid value; must be updated in NOR / NAND configuration block
             serial_flash_pad_type: FlashPadType::Single,
             serial_clk_freq: SerialClockFrequency::MHz30,
             lut_custom_seq_enable: 0,
             serial_flash_sizes: [0; 4],
-            cs_pad_setting_override: 0,
-            sclk_pad_setting_override: 0,
-            data_pad_setting_override: 0,
-            dqs_pad_setting_override: 0,
+            cs_pad_setting_override: PadSettingOverride::from_raw(0),
+            sclk_pad_setting_override: PadSettingOverride::from_raw(0),
+            data_pad_setting_override: PadSettingOverride::from_raw(0),
+            dqs_pad_setting_override: PadSettingOverride::from_raw(0),
             timeout_ms: 0,
             command_interval: 0,
-            data_valid_time: 0,
+            data_valid_time: DataValidTime::new(0, 0),
             busy_offset: 0,
             busy_bit_polarity: 0,
             lookup_table,
-            lut_custom_seq: [0; 48],
+            lut_custom_seq: [LutCustomSequence::new(0, 0); LUT_CUSTOM_SEQ_SLOTS],
 
             _reserved0: [0; 4],
-            _reserved1: [0; 1],
+            device_mode_type: 0,
             _reserved2: [0; 3],
             _reserved3: [0; 4],
             _reserved4: [0; 4],
             _reserved5: [0; 8],
-            _reserved6: [0; 16],
+            #[cfg(any(feature = "imxrt500", feature = "imxrt600"))]
+            dll_cr_val: [0; 2],
+            _reserved6: [0; RESERVED6_SIZE],
         }
     }
 
+    /// Replace the lookup table.
+    ///
+    /// Use this to share one configuration block across lookup tables. All
+    /// other fields are unchanged.
+    pub const fn with_lookup_table(mut self, lookup_table: LookupTable) -> Self {
+        self.lookup_table = lookup_table;
+        self
+    }
+
     /// Override the version.
     ///
     /// The default value is [`VERSION_DEFAULT`].
@@ -267,6 +369,31 @@ impl ConfigurationBlock {
     /// parameters in the FCB.
     ///
     /// If not set, this defaults to `DeviceModeConfiguration::Disabled`.
+    ///
+    /// The device mode sequence may span more than one LUT sequence. Use
+    /// [`config_commands`](Self::config_commands) to run additional command and
+    /// argument pairs after the device mode configuration.
+    ///
+    /// ```
+    /// use imxrt_boot_gen::flexspi::{
+    ///     ConfigurationBlock, ConfigurationCommand, DeviceModeConfiguration, DeviceModeSequence,
+    ///     LookupTable,
+    /// };
+    ///
+    /// // The device mode configuration spans the two sequences at LUT indices 6
+    /// // and 7. Then, two configuration commands run the sequences at indices 10
+    /// // and 12.
+    /// const FLEXSPI_CONFIGURATION_BLOCK: ConfigurationBlock =
+    ///     ConfigurationBlock::new(LookupTable::new())
+    ///         .device_mode_configuration(DeviceModeConfiguration::Enabled {
+    ///             device_mode_arg: 0x02,
+    ///             device_mode_seq: DeviceModeSequence::new(2, 6),
+    ///         })
+    ///         .config_commands(&[
+    ///             ConfigurationCommand::new(1, 10, 0x40),
+    ///             ConfigurationCommand::new(1, 12, 0x1F),
+    ///         ]);
+    /// ```
     pub const fn device_mode_configuration(
         mut self,
         device_mode_configuration: DeviceModeConfiguration,
@@ -287,6 +414,38 @@ impl ConfigurationBlock {
         self
     }
 
+    /// Sets `deviceModeType`, which describes the purpose of the device mode
+    /// configuration.
+    ///
+    /// If not set, this defaults to [`DeviceModeType::Generic`].
+    ///
+    /// ```
+    /// use imxrt_boot_gen::flexspi::{
+    ///     ConfigurationBlock, DeviceModeConfiguration, DeviceModeSequence, DeviceModeType,
+    ///     LookupTable,
+    /// };
+    ///
+    /// // Switch an octal flash from SPI to OPI mode with the sequence at LUT
+    /// // index 2.
+    /// const FLEXSPI_CONFIGURATION_BLOCK: ConfigurationBlock =
+    ///     ConfigurationBlock::new(LookupTable::new())
+    ///         .device_mode_configuration(DeviceModeConfiguration::Enabled {
+    ///             device_mode_arg: 0x02,
+    ///             device_mode_seq: DeviceModeSequence::new(1, 2),
+    ///         })
+    ///         .device_mode_type(DeviceModeType::Spi2Xpi);
+    /// ```
+    #[cfg(any(
+        feature = "imxrt1170",
+        feature = "imxrt1180",
+        feature = "imxrt500",
+        feature = "imxrt600"
+    ))]
+    pub const fn device_mode_type(mut self, device_mode_type: DeviceModeType) -> Self {
+        self.device_mode_type = device_mode_type as u8;
+        self
+    }
+
     /// Sets `waitTimeCfgCommands`
     ///
     /// If not set, this defaults to `WaitTimeConfigurationCommands::disable()`.
@@ -306,6 +465,16 @@ impl ConfigurationBlock {
         self
     }
 
+    /// Sets the serial flash pad type, `sFlashPad`, to the widest pad type used
+    /// in the lookup table.
+    ///
+    /// This prevents mismatches between the pad type and the sequences. See
+    /// [`LookupTable::widest_pad_type`] for more information.
+    pub const fn serial_flash_pad_type_from_lut(self) -> Self {
+        let serial_flash_pad_type = self.lookup_table.widest_pad_type();
+        self.serial_flash_pad_type(serial_flash_pad_type)
+    }
+
     /// Sets the serial clock frequencey, `serialClkFreq`
     ///
     /// If not set, this defaults to `SerialClockFrequency::MHz30`.
@@ -316,9 +485,196 @@ impl ConfigurationBlock {
 
     /// Set a flash size for the provided flash region
     ///
-    /// Any region that's not set will default to `0`.
-    pub const fn flash_size(mut self, flash_region: SerialFlashRegion, flash_size: u32) -> Self {
-        self.serial_flash_sizes[flash_region as usize] = flash_size;
+    /// Any region that's not set will default to `0`. To set all regions, and
+    /// check that they fit in the chip's FlexSPI window, use
+    /// [`flash_regions`](Self::flash_regions).
+    pub const fn flash_size(self, flash_region: SerialFlashRegion, density: Density) -> Self {
+        self.flash_size_bytes(flash_region, density.bytes())
+    }
+
+    /// Set a flash size, in bytes, for the provided flash region
+    ///
+    /// Prefer [`flash_size`](Self::flash_size), which makes the units explicit.
+    pub const fn flash_size_bytes(mut self, flash_region: SerialFlashRegion, bytes: u32) -> Self {
+        self.serial_flash_sizes[flash_region as usize] = bytes;
+        self
+    }
+
+    /// Combine two quad flashes, one on A1 and one on B1, into an eight pad
+    /// bus, each with the `density`
+    ///
+    /// This enables the [`PARALLEL`](ControllerMiscOptions::PARALLEL)
+    /// controller option, and sets the A1 and B1 flash sizes to `density`.
+    /// The two flashes must be the same part. In parallel mode, each byte
+    /// splits across both flashes, so the lookup table's sequences still
+    /// describe a single quad flash, and the controller runs each sequence on
+    /// both ports at once. The pages and sectors that the boot ROM sees are
+    /// twice the size of each flash's pages and sectors.
+    ///
+    /// Not every chip's boot ROM supports parallel mode; see your chip's
+    /// reference manual.
+    pub const fn parallel_flash_size(self, density: Density) -> Self {
+        let options = self
+            .get_controller_misc_options()
+            .union(ControllerMiscOptions::PARALLEL);
+        self.controller_misc_option_flags(options)
+            .flash_size(SerialFlashRegion::A1, density)
+            .flash_size(SerialFlashRegion::B1, density)
+    }
+
+    /// Sets `csPadSettingOverride`, the pad setting for the chip select pads.
+    ///
+    /// See [`PadSettingOverride`] for more information.
+    pub const fn cs_pad_setting_override(mut self, pad_setting: PadSettingOverride) -> Self {
+        self.cs_pad_setting_override = pad_setting;
+        self
+    }
+
+    /// Sets `sclkPadSettingOverride`, the pad setting for the serial clock pads.
+    ///
+    /// See [`PadSettingOverride`] for more information.
+    pub const fn sclk_pad_setting_override(mut self, pad_setting: PadSettingOverride) -> Self {
+        self.sclk_pad_setting_override = pad_setting;
+        self
+    }
+
+    /// Sets `dataPadSettingOverride`, the pad setting for the data pads.
+    ///
+    /// See [`PadSettingOverride`] for more information.
+    pub const fn data_pad_setting_override(mut self, pad_setting: PadSettingOverride) -> Self {
+        self.data_pad_setting_override = pad_setting;
+        self
+    }
+
+    /// Sets `dqsPadSettingOverride`, the pad setting for the DQS pad.
+    ///
+    /// See [`PadSettingOverride`] for more information.
+    pub const fn dqs_pad_setting_override(mut self, pad_setting: PadSettingOverride) -> Self {
+        self.dqs_pad_setting_override = pad_setting;
+        self
+    }
+
+    /// Sets `timeoutInMs`, the timeout for busy status checks, in milliseconds.
+    ///
+    /// Slow flash devices may need a longer timeout when the boot ROM waits for
+    /// the device to finish an operation. If not set, this defaults to `0`, which
+    /// lets the boot ROM use its default timeout.
+    pub const fn timeout_ms(mut self, timeout_ms: u32) -> Self {
+        self.timeout_ms = timeout_ms;
+        self
+    }
+
+    /// Sets `commandInterval`, the chip select deselect interval between two
+    /// commands.
+    ///
+    /// The boot ROM uses this value to configure the FlexSPI chip select interval.
+    /// Consult your chip's reference manual for the unit. If not set, this defaults
+    /// to `0`.
+    pub const fn command_interval(mut self, command_interval: u32) -> Self {
+        self.command_interval = command_interval;
+        self
+    }
+
+    /// Sets `dataValidTime`, the delay from a serial clock edge to valid data
+    /// for each port.
+    ///
+    /// If not set, this defaults to `0` for both ports.
+    pub const fn data_valid_time(mut self, data_valid_time: DataValidTime) -> Self {
+        self.data_valid_time = data_valid_time;
+        self
+    }
+
+    /// Sets `busyOffset` and `busyBitPolarity`, which describe the busy flag in
+    /// the flash's status register.
+    ///
+    /// If not set, the busy flag is bit 0, and it's 1 when the flash is busy.
+    pub const fn busy_bit(mut self, busy_bit: BusyBit) -> Self {
+        self.busy_offset = busy_bit.offset() as u16;
+        self.busy_bit_polarity = busy_bit.polarity() as u16;
+        self
+    }
+
+    /// Assign a configuration command to one of the three `configCmdSeqs` and
+    /// `cfgCmdArgs` slots.
+    ///
+    /// This also sets `configCmdEnable`. The boot ROM executes the configuration
+    /// commands in slot order. Consider using [`wait_time_cfg_commands`](Self::wait_time_cfg_commands)
+    /// if the flash device doesn't report when it's busy.
+    ///
+    /// ```
+    /// use imxrt_boot_gen::flexspi::{ConfigurationBlock, ConfigurationCommand, LookupTable};
+    ///
+    /// // Enable quad mode, then set the read parameters. The sequences are at
+    /// // LUT indices 2 and 4, and they write the argument to a status register.
+    /// const FLEXSPI_CONFIGURATION_BLOCK: ConfigurationBlock =
+    ///     ConfigurationBlock::new(LookupTable::new())
+    ///         .config_command(0, ConfigurationCommand::new(1, 2, 0x40))
+    ///         .config_command(1, ConfigurationCommand::new(1, 4, 0xF0));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is not less than 3. When evaluated in a `const` context,
+    /// the panic is a compile-time error.
+    pub const fn config_command(mut self, index: usize, command: ConfigurationCommand) -> Self {
+        assert!(
+            index < CONFIG_CMD_SLOTS,
+            "There are only three configuration commands"
+        );
+        self.config_cmd_seqs[index] = command.sequence;
+        self.cfg_cmd_args[index] = command.arg;
+        self.config_cmd_enable = 1;
+        self
+    }
+
+    /// Assign the configuration commands to the `configCmdSeqs` and `cfgCmdArgs`
+    /// slots, starting with the first slot.
+    ///
+    /// This is the same as calling [`config_command`](Self::config_command) for
+    /// each command. It doesn't change any slots after the last command.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are more than three commands. When evaluated in a `const`
+    /// context, the panic is a compile-time error.
+    pub const fn config_commands(mut self, commands: &[ConfigurationCommand]) -> Self {
+        assert!(
+            commands.len() <= CONFIG_CMD_SLOTS,
+            "There are only three configuration commands"
+        );
+        let mut index = 0;
+        while index < commands.len() {
+            self = self.config_command(index, commands[index]);
+            index += 1;
+        }
+        self
+    }
+
+    /// Assign a custom LUT sequence to one of the twelve `lutCustomSeq` slots.
+    ///
+    /// This also sets `lutCustomSeqEnable`, which tells the boot ROM to use the
+    /// custom sequences instead of the [`Command`] indices. See your chip's
+    /// reference manual for the command that's associated with each slot.
+    ///
+    /// ```
+    /// use imxrt_boot_gen::flexspi::{ConfigurationBlock, LookupTable, LutCustomSequence};
+    ///
+    /// const FLEXSPI_CONFIGURATION_BLOCK: ConfigurationBlock =
+    ///     ConfigurationBlock::new(LookupTable::new())
+    ///         .lut_custom_seq(5, LutCustomSequence::new(1, 8));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is not less than 12. When evaluated in a `const` context,
+    /// the panic is a compile-time error.
+    pub const fn lut_custom_seq(mut self, index: usize, sequence: LutCustomSequence) -> Self {
+        assert!(
+            index < LUT_CUSTOM_SEQ_SLOTS,
+            "There are only twelve custom LUT sequences"
+        );
+        self.lut_custom_seq[index] = sequence;
+        self.lut_custom_seq_enable = 1;
         self
     }
 
@@ -330,7 +686,320 @@ impl ConfigurationBlock {
         self.controller_misc_options = options;
         self
     }
+
+    /// Set miscellaneous controller options from named flags.
+    ///
+    /// This replaces all options, including those set with
+    /// [`controller_misc_options`](Self::controller_misc_options).
+    pub const fn controller_misc_option_flags(self, options: ControllerMiscOptions) -> Self {
+        self.controller_misc_options(options.bits())
+    }
+
+    /// Sets `dll0CrVal`, the FlexSPI `DLLCR[0]` value for port A.
+    ///
+    /// If not set, this defaults to zero.
+    #[cfg(any(feature = "imxrt500", feature = "imxrt600"))]
+    pub const fn dll0_cr_val(mut self, dll0_cr_val: u32) -> Self {
+        self.dll_cr_val[0] = dll0_cr_val;
+        self
+    }
+
+    /// Sets `dll1CrVal`, the FlexSPI `DLLCR[1]` value for port B.
+    ///
+    /// If not set, this defaults to zero.
+    #[cfg(any(feature = "imxrt500", feature = "imxrt600"))]
+    pub const fn dll1_cr_val(mut self, dll1_cr_val: u32) -> Self {
+        self.dll_cr_val[1] = dll1_cr_val;
+        self
+    }
+
+    /// Copy `bytes` into the configuration block, starting at the byte `offset`.
+    ///
+    /// Use this to set fields that this crate doesn't model. The offset is
+    /// relative to the start of the FlexSPI configuration block, as described in
+    /// your chip's reference manual. This method performs no checking on the
+    /// input, and it may overwrite values set by other methods.
+    ///
+    /// ```
+    /// use imxrt_boot_gen::flexspi::{ConfigurationBlock, LookupTable};
+    ///
+    /// // timeoutInMs = 100
+    /// const FLEXSPI_CONFIGURATION_BLOCK: ConfigurationBlock = unsafe {
+    ///     ConfigurationBlock::new(LookupTable::new()).raw_bytes(0x70, &100u32.to_le_bytes())
+    /// };
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the bytes extend beyond the end of the 448 byte configuration block.
+    ///
+    /// # Safety
+    ///
+    /// Some fields are represented by enums. If the bytes overlap one of these fields,
+    /// the bytes must be a valid value for the enum.
+    pub const unsafe fn raw_bytes(self, offset: usize, bytes: &[u8]) -> Self {
+        // Safety: caller upholds the requirements.
+        unsafe { crate::raw::write_bytes(self, offset, bytes) }
+    }
+
+    /// Returns `true` if the two configuration blocks have the same bytes.
+    ///
+    /// This is the `const` equivalent of `==`.
+    pub const fn eq_bytes(&self, other: &Self) -> bool {
+        crate::raw::eq_bytes(&self.to_bytes(), &other.to_bytes())
+    }
+
+    /// Serialize the configuration block into its in-memory layout.
+    ///
+    /// The bytes don't depend on the host's endianness. Use this to inspect or
+    /// store a configuration block without `transmute`.
+    ///
+    /// ```
+    /// use imxrt_boot_gen::flexspi::{ConfigurationBlock, LookupTable};
+    ///
+    /// const BYTES: [u8; ConfigurationBlock::SIZE] =
+    ///     ConfigurationBlock::new(LookupTable::new()).to_bytes();
+    /// assert_eq!(&BYTES[..4], b"FCFB");
+    /// ```
+    pub const fn to_bytes(&self) -> [u8; Self::SIZE] {
+        use crate::raw::put;
+        use core::mem::offset_of;
+
+        let mut bytes = [0; Self::SIZE];
+        put(&mut bytes, offset_of!(Self, tag), &self.tag.to_le_bytes());
+        put(
+            &mut bytes,
+            offset_of!(Self, version),
+            &self.version.0.to_le_bytes(),
+        );
+        put(&mut bytes, offset_of!(Self, _reserved0), &self._reserved0);
+        bytes[offset_of!(Self, read_sample_clk_src)] = self.read_sample_clk_src as u8;
+        bytes[offset_of!(Self, cs_hold_time)] = self.cs_hold_time;
+        bytes[offset_of!(Self, cs_setup_time)] = self.cs_setup_time;
+        bytes[offset_of!(Self, column_address_width)] = self.column_address_width as u8;
+        bytes[offset_of!(Self, device_mode_configuration)] = self.device_mode_configuration;
+        bytes[offset_of!(Self, device_mode_type)] = self.device_mode_type;
+        put(
+            &mut bytes,
+            offset_of!(Self, wait_time_cfg_commands),
+            &self.wait_time_cfg_commands.to_bytes(),
+        );
+        put(
+            &mut bytes,
+            offset_of!(Self, device_mode_sequence),
+            &self.device_mode_sequence.to_bytes(),
+        );
+        put(
+            &mut bytes,
+            offset_of!(Self, device_mode_arg),
+            &self.device_mode_arg.to_le_bytes(),
+        );
+        bytes[offset_of!(Self, config_cmd_enable)] = self.config_cmd_enable;
+        put(&mut bytes, offset_of!(Self, _reserved2), &self._reserved2);
+        let mut idx = 0;
+        while idx < CONFIG_CMD_SLOTS {
+            put(
+                &mut bytes,
+                offset_of!(Self, config_cmd_seqs) + idx * 4,
+                &self.config_cmd_seqs[idx],
+            );
+            put(
+                &mut bytes,
+                offset_of!(Self, cfg_cmd_args) + idx * 4,
+                &self.cfg_cmd_args[idx].to_le_bytes(),
+            );
+            idx += 1;
+        }
+        put(&mut bytes, offset_of!(Self, _reserved3), &self._reserved3);
+        put(&mut bytes, offset_of!(Self, _reserved4), &self._reserved4);
+        put(
+            &mut bytes,
+            offset_of!(Self, controller_misc_options),
+            &self.controller_misc_options.to_le_bytes(),
+        );
+        bytes[offset_of!(Self, device_type)] = self.device_type;
+        bytes[offset_of!(Self, serial_flash_pad_type)] = self.serial_flash_pad_type as u8;
+        bytes[offset_of!(Self, serial_clk_freq)] = self.serial_clk_freq as u8;
+        bytes[offset_of!(Self, lut_custom_seq_enable)] = self.lut_custom_seq_enable;
+        put(&mut bytes, offset_of!(Self, _reserved5), &self._reserved5);
+        let mut idx = 0;
+        while idx < self.serial_flash_sizes.len() {
+            put(
+                &mut bytes,
+                offset_of!(Self, serial_flash_sizes) + idx * 4,
+                &self.serial_flash_sizes[idx].to_le_bytes(),
+            );
+            idx += 1;
+        }
+        put(
+            &mut bytes,
+            offset_of!(Self, cs_pad_setting_override),
+            &self.cs_pad_setting_override.raw().to_le_bytes(),
+        );
+        put(
+            &mut bytes,
+            offset_of!(Self, sclk_pad_setting_override),
+            &self.sclk_pad_setting_override.raw().to_le_bytes(),
+        );
+        put(
+            &mut bytes,
+            offset_of!(Self, data_pad_setting_override),
+            &self.data_pad_setting_override.raw().to_le_bytes(),
+        );
+        put(
+            &mut bytes,
+            offset_of!(Self, dqs_pad_setting_override),
+            &self.dqs_pad_setting_override.raw().to_le_bytes(),
+        );
+        put(
+            &mut bytes,
+            offset_of!(Self, timeout_ms),
+            &self.timeout_ms.to_le_bytes(),
+        );
+        put(
+            &mut bytes,
+            offset_of!(Self, command_interval),
+            &self.command_interval.to_le_bytes(),
+        );
+        put(
+            &mut bytes,
+            offset_of!(Self, data_valid_time),
+            &self.data_valid_time.to_bytes(),
+        );
+        put(
+            &mut bytes,
+            offset_of!(Self, busy_offset),
+            &self.busy_offset.to_le_bytes(),
+        );
+        put(
+            &mut bytes,
+            offset_of!(Self, busy_bit_polarity),
+            &self.busy_bit_polarity.to_le_bytes(),
+        );
+        put(
+            &mut bytes,
+            offset_of!(Self, lookup_table),
+            &self.lookup_table.to_bytes(),
+        );
+        let mut idx = 0;
+        while idx < LUT_CUSTOM_SEQ_SLOTS {
+            put(
+                &mut bytes,
+                offset_of!(Self, lut_custom_seq) + idx * 4,
+                &self.lut_custom_seq[idx].to_bytes(),
+            );
+            idx += 1;
+        }
+        #[cfg(any(feature = "imxrt500", feature = "imxrt600"))]
+        {
+            put(
+                &mut bytes,
+                offset_of!(Self, dll_cr_val),
+                &self.dll_cr_val[0].to_le_bytes(),
+            );
+            put(
+                &mut bytes,
+                offset_of!(Self, dll_cr_val) + 4,
+                &self.dll_cr_val[1].to_le_bytes(),
+            );
+        }
+        put(&mut bytes, offset_of!(Self, _reserved6), &self._reserved6);
+        bytes
+    }
 }
 
-const _STATIC_ASSERT_SIZE: [u32; 1] =
-    [0; (core::mem::size_of::<ConfigurationBlock>() == 448) as usize];
+impl PartialEq for ConfigurationBlock {
+    fn eq(&self, other: &Self) -> bool {
+        self.eq_bytes(other)
+    }
+}
+
+impl Eq for ConfigurationBlock {}
+
+/// Number of `configCmdSeqs` and `cfgCmdArgs` slots.
+pub(crate) const CONFIG_CMD_SLOTS: usize = 3;
+/// Number of `lutCustomSeq` slots.
+pub(crate) const LUT_CUSTOM_SEQ_SLOTS: usize = 12;
+
+const _: () = assert!(core::mem::size_of::<ConfigurationBlock>() == ConfigurationBlock::SIZE);
+const _: () = assert!(core::mem::align_of::<ConfigurationBlock>() == ConfigurationBlock::ALIGNMENT);
+
+// Safety: the configuration block is `repr(C)`, and its reserved fields fill
+// every gap, so it has no padding. The `zerocopy` derive checks the same thing.
+// Not `Pod`, since the enumerated fields don't accept every bit pattern.
+#[cfg(feature = "bytemuck")]
+unsafe impl bytemuck::NoUninit for ConfigurationBlock {}
+
+//
+// Keep these two tests in sync
+//
+// The first one lets you know if the second one is failing to compile
+// in the way we expect.
+//
+
+/// ```
+/// use imxrt_boot_gen::flexspi::{ConfigurationBlock, ConfigurationCommand, LookupTable};
+/// const CMD: ConfigurationCommand = ConfigurationCommand::new(1, 2, 0);
+/// const CFG: ConfigurationBlock =
+///     ConfigurationBlock::new(LookupTable::new()).config_commands(&[CMD, CMD, CMD]);
+/// ```
+#[cfg(doctest)]
+struct ConfigCommandsWithinSlots;
+
+/// ```compile_fail
+/// use imxrt_boot_gen::flexspi::{ConfigurationBlock, ConfigurationCommand, LookupTable};
+/// const CMD: ConfigurationCommand = ConfigurationCommand::new(1, 2, 0);
+/// const CFG: ConfigurationBlock =
+///     ConfigurationBlock::new(LookupTable::new()).config_commands(&[CMD, CMD, CMD, CMD]);
+/// ```
+#[cfg(doctest)]
+struct ConfigCommandsBeyondSlots;
+
+//
+// Keep these two tests in sync
+//
+// The first one lets you know if the second one is failing to compile
+// in the way we expect.
+//
+
+/// ```
+/// use imxrt_boot_gen::flexspi::{ConfigurationBlock, LookupTable};
+/// fn copy_device_mode_type(from: &ConfigurationBlock) -> ConfigurationBlock {
+///     let cfg = ConfigurationBlock::new(LookupTable::new());
+///     match from.get_device_mode_type() {
+///         Some(device_mode_type) => cfg.device_mode_type(device_mode_type),
+///         None => cfg,
+///     }
+/// }
+/// ```
+#[cfg(all(
+    doctest,
+    any(
+        feature = "imxrt1170",
+        feature = "imxrt1180",
+        feature = "imxrt500",
+        feature = "imxrt600"
+    )
+))]
+struct DeviceModeTypeOnSupportedChip;
+
+/// ```compile_fail
+/// use imxrt_boot_gen::flexspi::{ConfigurationBlock, LookupTable};
+/// fn copy_device_mode_type(from: &ConfigurationBlock) -> ConfigurationBlock {
+///     let cfg = ConfigurationBlock::new(LookupTable::new());
+///     match from.get_device_mode_type() {
+///         Some(device_mode_type) => cfg.device_mode_type(device_mode_type),
+///         None => cfg,
+///     }
+/// }
+/// ```
+#[cfg(all(
+    doctest,
+    not(any(
+        feature = "imxrt1170",
+        feature = "imxrt1180",
+        feature = "imxrt500",
+        feature = "imxrt600"
+    ))
+))]
+struct DeviceModeTypeOnReservedChip;
@@ -2,6 +2,8 @@
 
 /// `readSampleClkSrc` of the general FCB   
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "zerocopy", derive(zerocopy::IntoBytes, zerocopy::Immutable))]
 #[repr(u8)]
 pub enum ReadSampleClockSource {
     InternalLoopback = 0x00,
@@ -9,21 +11,56 @@ pub enum ReadSampleClockSource {
     FlashProvidedDQS = 0x03,
 }
 
+impl ReadSampleClockSource {
+    pub(crate) const fn from_raw(raw: u8) -> Option<Self> {
+        Some(match raw {
+            0x00 => ReadSampleClockSource::InternalLoopback,
+            0x01 => ReadSampleClockSource::LoopbackFromDQSPad,
+            0x03 => ReadSampleClockSource::FlashProvidedDQS,
+            _ => return None,
+        })
+    }
+}
+
 /// `columnAdressWidth`
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "zerocopy", derive(zerocopy::IntoBytes, zerocopy::Immutable))]
 #[repr(u8)]
 pub enum ColumnAddressWidth {
     OtherDevices = 0,
     Hyperflash = 3,
-    // TODO serial NAND flash values 12 and 13 apply, at a minimum,
-    // to the following chips:
-    //
-    // - imxrt1020
-    // - imxrt1170
+    /// A serial NAND flash with 12 column address bits.
+    ///
+    /// Check your flash's datasheet for the column address width.
+    #[cfg(any(feature = "imxrt1020", feature = "imxrt1024", feature = "imxrt1170"))]
+    SerialNand12 = 12,
+    /// A serial NAND flash with 13 column address bits.
+    ///
+    /// Check your flash's datasheet for the column address width.
+    #[cfg(any(feature = "imxrt1020", feature = "imxrt1024", feature = "imxrt1170"))]
+    SerialNand13 = 13,
+    // TODO serial NAND flash values 12 and 13 may apply to other chips.
+    // Add them as we confirm them with each chip's reference manual.
+}
+
+impl ColumnAddressWidth {
+    pub(crate) const fn from_raw(raw: u8) -> Option<Self> {
+        Some(match raw {
+            0 => ColumnAddressWidth::OtherDevices,
+            3 => ColumnAddressWidth::Hyperflash,
+            #[cfg(any(feature = "imxrt1020", feature = "imxrt1024", feature = "imxrt1170"))]
+            12 => ColumnAddressWidth::SerialNand12,
+            #[cfg(any(feature = "imxrt1020", feature = "imxrt1024", feature = "imxrt1170"))]
+            13 => ColumnAddressWidth::SerialNand13,
+            _ => return None,
+        })
+    }
 }
 
 /// Sequence parameter for device mode configuration
 #[derive(Default, Clone, Copy, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "zerocopy", derive(zerocopy::IntoBytes, zerocopy::Immutable))]
 #[repr(transparent)]
 pub struct DeviceModeSequence([u8; 4]);
 impl DeviceModeSequence {
@@ -36,6 +73,107 @@ impl DeviceModeSequence {
             (((starting_lut_index as u32) << 8) | (number_of_luts as u32)).to_le_bytes(),
         )
     }
+    /// Returns the number of LUT sequences.
+    pub const fn number_of_luts(self) -> u8 {
+        self.0[0]
+    }
+    /// Returns the starting LUT index.
+    pub const fn starting_lut_index(self) -> u8 {
+        self.0[1]
+    }
+    pub(crate) const fn to_bytes(self) -> [u8; 4] {
+        self.0
+    }
+    pub(crate) const fn from_bytes(bytes: [u8; 4]) -> Self {
+        Self(bytes)
+    }
+}
+
+/// A custom LUT sequence, an element of `lutCustomSeq`
+///
+/// Use [`ConfigurationBlock::lut_custom_seq`](super::ConfigurationBlock::lut_custom_seq)
+/// to assign custom sequences.
+#[derive(Default, Clone, Copy, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "zerocopy", derive(zerocopy::IntoBytes, zerocopy::Immutable))]
+#[repr(transparent)]
+pub struct LutCustomSequence([u8; 4]);
+impl LutCustomSequence {
+    /// Create a new custom LUT sequence
+    ///
+    /// `number_of_luts`: number of LUT sequences for the command
+    /// `starting_lut_index`: starting LUT index of the command
+    ///
+    /// # Panics
+    ///
+    /// Panics if the sequences extend beyond the end of the lookup table. When
+    /// evaluated in a `const` context, the panic is a compile-time error.
+    pub const fn new(number_of_luts: u8, starting_lut_index: u8) -> Self {
+        assert!(
+            number_of_luts as usize + starting_lut_index as usize <= super::NUMBER_OF_SEQUENCES,
+            "The custom LUT sequence extends beyond the end of the lookup table"
+        );
+        LutCustomSequence([number_of_luts, starting_lut_index, 0, 0])
+    }
+    /// Returns the number of LUT sequences.
+    pub const fn number_of_luts(self) -> u8 {
+        self.0[0]
+    }
+    /// Returns the starting LUT index.
+    pub const fn starting_lut_index(self) -> u8 {
+        self.0[1]
+    }
+    pub(crate) const fn to_bytes(self) -> [u8; 4] {
+        self.0
+    }
+    pub(crate) const fn from_bytes(bytes: [u8; 4]) -> Self {
+        Self(bytes)
+    }
+}
+
+/// A device configuration command, an element of `configCmdSeqs` and `cfgCmdArgs`
+///
+/// The boot ROM executes configuration commands to configure the flash device,
+/// like setting the drive strength or the number of dummy cycles. Use
+/// [`ConfigurationBlock::config_command`](super::ConfigurationBlock::config_command)
+/// to assign configuration commands.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct ConfigurationCommand {
+    pub(crate) sequence: [u8; 4],
+    pub(crate) arg: u32,
+}
+impl ConfigurationCommand {
+    /// Create a new configuration command
+    ///
+    /// `number_of_luts`: number of LUT sequences for the configuration command
+    /// `starting_lut_index`: starting LUT index of the configuration command
+    /// `arg`: the argument for the configuration command
+    ///
+    /// # Panics
+    ///
+    /// Panics if the sequences extend beyond the end of the lookup table. When
+    /// evaluated in a `const` context, the panic is a compile-time error.
+    pub const fn new(number_of_luts: u8, starting_lut_index: u8, arg: u32) -> Self {
+        assert!(
+            number_of_luts as usize + starting_lut_index as usize <= super::NUMBER_OF_SEQUENCES,
+            "The configuration command extends beyond the end of the lookup table"
+        );
+        ConfigurationCommand {
+            sequence: [number_of_luts, starting_lut_index, 0, 0],
+            arg,
+        }
+    }
+    /// Returns the number of LUT sequences.
+    pub const fn number_of_luts(self) -> u8 {
+        self.sequence[0]
+    }
+    /// Returns the starting LUT index.
+    pub const fn starting_lut_index(self) -> u8 {
+        self.sequence[1]
+    }
+    /// Returns the argument.
+    pub const fn arg(self) -> u32 {
+        self.arg
+    }
 }
 
 /// Describes both the `deviceModeCfgEnable` field, and
@@ -57,6 +195,36 @@ pub enum DeviceModeConfiguration {
     },
 }
 
+/// `deviceModeType`, the purpose of the device mode configuration
+///
+/// The boot ROM uses the device mode type to decide how to apply the
+/// [`DeviceModeConfiguration`]. For instance, an octal flash may need to switch
+/// from SPI to OPI mode before the boot ROM can read the rest of the image.
+#[cfg(any(
+    feature = "imxrt1170",
+    feature = "imxrt1180",
+    feature = "imxrt500",
+    feature = "imxrt600"
+))]
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(u8)]
+pub enum DeviceModeType {
+    /// A generic configuration.
+    #[default]
+    Generic = 0,
+    /// Enables the flash's quad mode.
+    QuadEnable = 1,
+    /// Switches the flash from SPI mode to a DPI, QPI, or OPI mode.
+    Spi2Xpi = 2,
+    /// Switches the flash from a DPI, QPI, or OPI mode to SPI mode.
+    Xpi2Spi = 3,
+    /// Switches the flash from SPI mode to a mode that doesn't require a command.
+    Spi2NoCmd = 4,
+    /// Resets the flash.
+    Reset = 5,
+}
+
 /// Wait time for all configuration commands
 ///
 /// From the docs...
@@ -66,6 +234,7 @@ pub enum DeviceModeConfiguration {
 /// > for all device memory configuration commands instead of using read
 /// > status to wait until these commands complete.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "zerocopy", derive(zerocopy::IntoBytes, zerocopy::Immutable))]
 #[repr(transparent)]
 pub struct WaitTimeConfigurationCommands(u16);
 impl WaitTimeConfigurationCommands {
@@ -80,10 +249,269 @@ impl WaitTimeConfigurationCommands {
     pub const fn new(wait_time_us: u16) -> Self {
         WaitTimeConfigurationCommands(wait_time_us / 100)
     }
+
+    pub(crate) const fn to_bytes(self) -> [u8; 2] {
+        self.0.to_le_bytes()
+    }
+
+    pub(crate) const fn from_bytes(bytes: [u8; 2]) -> Self {
+        WaitTimeConfigurationCommands(u16::from_le_bytes(bytes))
+    }
+}
+
+/// One port's data valid time, `flexspi_dll_time_t`
+///
+/// The time is in units of 0.1ns. When the time is zero, the boot ROM uses
+/// the number of DLL delay cells instead.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "zerocopy", derive(zerocopy::IntoBytes, zerocopy::Immutable))]
+#[repr(C)]
+pub struct DllTime {
+    time_100ps: u8,
+    delay_cells: u8,
+}
+
+impl DllTime {
+    /// Create a DLL time from a time, in 0.1ns, and a number of delay cells
+    pub const fn new(time_100ps: u8, delay_cells: u8) -> Self {
+        DllTime {
+            time_100ps,
+            delay_cells,
+        }
+    }
+
+    /// Returns the time, in 0.1ns
+    pub const fn time_100ps(self) -> u8 {
+        self.time_100ps
+    }
+
+    /// Returns the number of DLL delay cells
+    pub const fn delay_cells(self) -> u8 {
+        self.delay_cells
+    }
+}
+
+/// `dataValidTime`, the delay from a serial clock edge to valid data
+///
+/// The FlexSPI configuration block has one [`DllTime`] for each port. Each
+/// time is expressed in units of 0.1ns, up to 25.5ns. A time of `0` lets the
+/// boot ROM use the port's delay cells. If both are `0`, the boot ROM chooses
+/// the delay.
+///
+/// ```
+/// use imxrt_boot_gen::flexspi::DataValidTime;
+///
+/// // 2.5ns for port A, and 3ns for port B.
+/// const DATA_VALID_TIME: DataValidTime = DataValidTime::new(25, 30);
+/// // Port A uses the time, and port B uses 12 delay cells.
+/// const DELAY_CELLS: DataValidTime = DataValidTime::new(25, 0).delay_cells(0, 12);
+/// ```
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "zerocopy", derive(zerocopy::IntoBytes, zerocopy::Immutable))]
+#[repr(C)]
+pub struct DataValidTime {
+    port_a: DllTime,
+    port_b: DllTime,
+}
+
+impl DataValidTime {
+    /// Create a data valid time for ports A and B, with no delay cells
+    ///
+    /// `port_a`: the data valid time for port A, in 0.1ns
+    /// `port_b`: the data valid time for port B, in 0.1ns
+    pub const fn new(port_a: u8, port_b: u8) -> Self {
+        DataValidTime {
+            port_a: DllTime::new(port_a, 0),
+            port_b: DllTime::new(port_b, 0),
+        }
+    }
+
+    /// Create a data valid time that's the same for both ports
+    ///
+    /// `time` is in units of 0.1ns.
+    pub const fn both(time: u8) -> Self {
+        Self::new(time, time)
+    }
+
+    /// Set the number of DLL delay cells for ports A and B
+    pub const fn delay_cells(mut self, port_a: u8, port_b: u8) -> Self {
+        self.port_a.delay_cells = port_a;
+        self.port_b.delay_cells = port_b;
+        self
+    }
+
+    /// Returns the port A data valid time
+    pub const fn port_a(self) -> DllTime {
+        self.port_a
+    }
+
+    /// Returns the port B data valid time
+    pub const fn port_b(self) -> DllTime {
+        self.port_b
+    }
+
+    pub(crate) const fn from_bytes([a_time, a_cells, b_time, b_cells]: [u8; 4]) -> Self {
+        DataValidTime {
+            port_a: DllTime::new(a_time, a_cells),
+            port_b: DllTime::new(b_time, b_cells),
+        }
+    }
+
+    pub(crate) const fn to_bytes(self) -> [u8; 4] {
+        [
+            self.port_a.time_100ps,
+            self.port_a.delay_cells,
+            self.port_b.time_100ps,
+            self.port_b.delay_cells,
+        ]
+    }
+}
+
+/// `busyBitPolarity`, the level of the busy flag while the flash is busy
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(u16)]
+pub enum BusyBitPolarity {
+    /// The busy flag is 1 when the flash is busy.
+    #[default]
+    ActiveHigh = 0,
+    /// The busy flag is 0 when the flash is busy.
+    ActiveLow = 1,
+}
+
+/// Describes the busy flag in the flash's status register
+///
+/// This sets both `busyOffset` and `busyBitPolarity`. The boot ROM reads the
+/// status register with the [`ReadStatus`](super::Command::ReadStatus) sequence,
+/// then checks the busy flag to learn when the flash finishes an operation.
+///
+/// ```
+/// use imxrt_boot_gen::flexspi::{BusyBit, BusyBitPolarity};
+///
+/// // The flash is ready when bit 7 is set.
+/// const BUSY_BIT: BusyBit = BusyBit::new(7, BusyBitPolarity::ActiveLow);
+/// ```
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BusyBit {
+    offset: u8,
+    polarity: BusyBitPolarity,
+}
+
+impl BusyBit {
+    /// Describe a busy flag at the bit `offset` with the `polarity`
+    ///
+    /// # Panics
+    ///
+    /// Panics if `offset` is greater than 31. When evaluated in a `const`
+    /// context, the panic is a compile-time error.
+    pub const fn new(offset: u8, polarity: BusyBitPolarity) -> Self {
+        assert!(offset <= 31, "The busy bit offset must be between 0 and 31");
+        BusyBit { offset, polarity }
+    }
+
+    /// Returns the bit offset of the busy flag
+    pub const fn offset(self) -> u8 {
+        self.offset
+    }
+
+    /// Returns the polarity of the busy flag
+    pub const fn polarity(self) -> BusyBitPolarity {
+        self.polarity
+    }
+}
+
+/// `controllerMiscOption`, a set of FlexSPI controller options
+///
+/// Combine options with [`union`](Self::union), then apply them with
+/// [`ConfigurationBlock::controller_misc_option_flags`](super::ConfigurationBlock::controller_misc_option_flags).
+/// Not every chip's boot ROM supports every option; see your chip's reference
+/// manual for more information.
+///
+/// The named options are the bits that every supported chip's boot ROM header
+/// defines at the same position. This type doesn't name a second pinmux
+/// option, and it doesn't check options against a chip: the position and
+/// support of those bits vary by chip, and they aren't described here. Use
+/// [`from_bits_retain`](Self::from_bits_retain) to set a bit that this type
+/// doesn't name.
+///
+/// ```
+/// use imxrt_boot_gen::flexspi::ControllerMiscOptions;
+///
+/// const OPTIONS: ControllerMiscOptions = ControllerMiscOptions::SAFE_CONFIG_FREQ
+///     .union(ControllerMiscOptions::DDR_MODE);
+/// assert_eq!(OPTIONS.bits(), 0x50);
+/// ```
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(transparent)]
+pub struct ControllerMiscOptions(u32);
+
+impl ControllerMiscOptions {
+    /// No options.
+    pub const EMPTY: Self = Self(0);
+    /// Enable the differential clock.
+    pub const DIFFERENTIAL_CLOCK: Self = Self(1 << 0);
+    /// Enable the CK2 clock.
+    pub const CK2: Self = Self(1 << 1);
+    /// Enable parallel mode, which uses both ports A and B.
+    pub const PARALLEL: Self = Self(1 << 2);
+    /// Enable word-addressable mode, typical for HyperFlash.
+    pub const WORD_ADDRESSABLE: Self = Self(1 << 3);
+    /// Configure the flash at a safe frequency before switching to the
+    /// configured serial clock frequency.
+    pub const SAFE_CONFIG_FREQ: Self = Self(1 << 4);
+    /// Apply the pad setting overrides.
+    pub const PAD_SETTING_OVERRIDE: Self = Self(1 << 5);
+    /// Enable DDR mode.
+    pub const DDR_MODE: Self = Self(1 << 6);
+
+    /// All options known to this crate.
+    const ALL: Self = Self(0x7F);
+
+    /// Returns the options for `bits`, or `None` if `bits` has an option that
+    /// this crate doesn't know.
+    ///
+    /// Use [`from_bits_retain`](Self::from_bits_retain) to keep unknown options.
+    pub const fn from_bits(bits: u32) -> Option<Self> {
+        if bits & !Self::ALL.0 != 0 {
+            None
+        } else {
+            Some(Self(bits))
+        }
+    }
+
+    /// Returns the options for `bits`, including options that this crate
+    /// doesn't know.
+    pub const fn from_bits_retain(bits: u32) -> Self {
+        Self(bits)
+    }
+
+    /// Returns the raw `controllerMiscOption` value.
+    pub const fn bits(self) -> u32 {
+        self.0
+    }
+
+    /// Returns the options in either `self` or `other`.
+    pub const fn union(self, other: Self) -> Self {
+        Self(self.0 | other.0)
+    }
+
+    /// Returns the options in `self` that aren't in `other`.
+    pub const fn difference(self, other: Self) -> Self {
+        Self(self.0 & !other.0)
+    }
+
+    /// Returns `true` if `self` has all of the options in `other`.
+    pub const fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
 }
 
 /// `sFlashPad` field
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "zerocopy", derive(zerocopy::IntoBytes, zerocopy::Immutable))]
 #[repr(u8)]
 pub enum FlashPadType {
     Single = 1,
@@ -92,14 +520,33 @@ pub enum FlashPadType {
     Octal = 8,
 }
 
+impl FlashPadType {
+    pub(crate) const fn from_raw(raw: u8) -> Option<Self> {
+        Some(match raw {
+            1 => FlashPadType::Single,
+            2 => FlashPadType::Dual,
+            4 => FlashPadType::Quad,
+            8 => FlashPadType::Octal,
+            _ => return None,
+        })
+    }
+}
+
 /// `serialClkFreq`
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "zerocopy", derive(zerocopy::IntoBytes, zerocopy::Immutable))]
 #[repr(u8)]
 pub enum SerialClockFrequency {
     MHz30 = 1,
     MHz50,
     MHz60,
-    #[cfg(not(any(feature = "imxrt1170", feature = "imxrt1180")))]
+    #[cfg(not(any(
+        feature = "imxrt1170",
+        feature = "imxrt1180",
+        feature = "imxrt500",
+        feature = "imxrt600"
+    )))]
     MHz75,
     MHz80,
     MHz100,
@@ -109,15 +556,225 @@ pub enum SerialClockFrequency {
         feature = "imxrt1060",
         feature = "imxrt1064",
         feature = "imxrt1170",
-        feature = "imxrt1180"
+        feature = "imxrt1180",
+        feature = "imxrt500",
+        feature = "imxrt600"
     ))]
     MHz120,
     MHz133,
     #[cfg(not(feature = "imxrt1010"))]
     MHz166,
+    #[cfg(any(
+        feature = "imxrt1170",
+        feature = "imxrt1180",
+        feature = "imxrt500",
+        feature = "imxrt600"
+    ))]
+    MHz200,
+}
+
+impl SerialClockFrequency {
+    /// Returns the frequency for exactly `mhz`, or `None` if the chip doesn't
+    /// support that frequency.
+    pub const fn from_mhz(mhz: u32) -> Option<Self> {
+        Some(match mhz {
+            30 => SerialClockFrequency::MHz30,
+            50 => SerialClockFrequency::MHz50,
+            60 => SerialClockFrequency::MHz60,
+            #[cfg(not(any(
+                feature = "imxrt1170",
+                feature = "imxrt1180",
+                feature = "imxrt500",
+                feature = "imxrt600"
+            )))]
+            75 => SerialClockFrequency::MHz75,
+            80 => SerialClockFrequency::MHz80,
+            100 => SerialClockFrequency::MHz100,
+            #[cfg(any(
+                feature = "imxrt1010",
+                feature = "imxrt1040",
+                feature = "imxrt1060",
+                feature = "imxrt1064",
+                feature = "imxrt1170",
+                feature = "imxrt1180",
+                feature = "imxrt500",
+                feature = "imxrt600"
+            ))]
+            120 => SerialClockFrequency::MHz120,
+            133 => SerialClockFrequency::MHz133,
+            #[cfg(not(feature = "imxrt1010"))]
+            166 => SerialClockFrequency::MHz166,
+            #[cfg(any(
+                feature = "imxrt1170",
+                feature = "imxrt1180",
+                feature = "imxrt500",
+                feature = "imxrt600"
+            ))]
+            200 => SerialClockFrequency::MHz200,
+            _ => return None,
+        })
+    }
+
+    /// Returns the supported frequency that's nearest to `mhz`.
+    ///
+    /// If `mhz` is equally far from two frequencies, this returns the slower
+    /// frequency.
+    pub const fn nearest(mhz: u32) -> Self {
+        // As fast as any supported frequency, so that the search terminates quickly.
+        const MAX_MHZ: u32 = 200;
+        let mhz = if mhz > MAX_MHZ { MAX_MHZ } else { mhz };
+        let mut delta = 0;
+        loop {
+            if let Some(frequency) = Self::from_mhz(mhz.saturating_sub(delta)) {
+                return frequency;
+            }
+            if let Some(frequency) = Self::from_mhz(mhz + delta) {
+                return frequency;
+            }
+            delta += 1;
+        }
+    }
+
+    /// Returns the frequency with the raw `serialClkFreq` value, or `None` if
+    /// the chip doesn't support that value.
+    pub(crate) fn from_raw(raw: u8) -> Option<Self> {
+        [
+            SerialClockFrequency::MHz30,
+            SerialClockFrequency::MHz50,
+            SerialClockFrequency::MHz60,
+            #[cfg(not(any(
+                feature = "imxrt1170",
+                feature = "imxrt1180",
+                feature = "imxrt500",
+                feature = "imxrt600"
+            )))]
+            SerialClockFrequency::MHz75,
+            SerialClockFrequency::MHz80,
+            SerialClockFrequency::MHz100,
+            #[cfg(any(
+                feature = "imxrt1010",
+                feature = "imxrt1040",
+                feature = "imxrt1060",
+                feature = "imxrt1064",
+                feature = "imxrt1170",
+                feature = "imxrt1180",
+                feature = "imxrt500",
+                feature = "imxrt600"
+            ))]
+            SerialClockFrequency::MHz120,
+            SerialClockFrequency::MHz133,
+            #[cfg(not(feature = "imxrt1010"))]
+            SerialClockFrequency::MHz166,
+            #[cfg(any(
+                feature = "imxrt1170",
+                feature = "imxrt1180",
+                feature = "imxrt500",
+                feature = "imxrt600"
+            ))]
+            SerialClockFrequency::MHz200,
+        ]
+        .into_iter()
+        .find(|frequency| *frequency as u8 == raw)
+    }
+
+    /// Returns the frequency in MHz.
+    pub const fn mhz(self) -> u32 {
+        match self {
+            SerialClockFrequency::MHz30 => 30,
+            SerialClockFrequency::MHz50 => 50,
+            SerialClockFrequency::MHz60 => 60,
+            #[cfg(not(any(
+                feature = "imxrt1170",
+                feature = "imxrt1180",
+                feature = "imxrt500",
+                feature = "imxrt600"
+            )))]
+            SerialClockFrequency::MHz75 => 75,
+            SerialClockFrequency::MHz80 => 80,
+            SerialClockFrequency::MHz100 => 100,
+            #[cfg(any(
+                feature = "imxrt1010",
+                feature = "imxrt1040",
+                feature = "imxrt1060",
+                feature = "imxrt1064",
+                feature = "imxrt1170",
+                feature = "imxrt1180",
+                feature = "imxrt500",
+                feature = "imxrt600"
+            ))]
+            SerialClockFrequency::MHz120 => 120,
+            SerialClockFrequency::MHz133 => 133,
+            #[cfg(not(feature = "imxrt1010"))]
+            SerialClockFrequency::MHz166 => 166,
+            #[cfg(any(
+                feature = "imxrt1170",
+                feature = "imxrt1180",
+                feature = "imxrt500",
+                feature = "imxrt600"
+            ))]
+            SerialClockFrequency::MHz200 => 200,
+        }
+    }
+}
+
+/// The density of a serial flash device
+///
+/// Flash datasheets usually describe density in megabits, while the
+/// configuration block expects bytes. Use the constructor that matches your
+/// datasheet to avoid confusing bits and bytes.
+///
+/// ```
+/// use imxrt_boot_gen::flexspi::Density;
+///
+/// // A 128Mbit flash is 16MiB.
+/// assert_eq!(Density::from_megabits(128), Density::from_mebibytes(16));
+/// assert_eq!(Density::from_mebibytes(16).bytes(), 16 * 1024 * 1024);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(transparent)]
+pub struct Density(u32);
+
+impl Density {
+    /// A density of `megabits`, where a megabit is 2^20 bits.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the density doesn't fit in a `u32` number of bytes. When
+    /// evaluated in a `const` context, the panic is a compile-time error.
+    pub const fn from_megabits(megabits: u32) -> Self {
+        match megabits.checked_mul(1024 * 1024 / 8) {
+            Some(bytes) => Density(bytes),
+            None => panic!("The flash density is too large"),
+        }
+    }
+
+    /// A density of `mebibytes`, where a mebibyte is 2^20 bytes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the density doesn't fit in a `u32` number of bytes. When
+    /// evaluated in a `const` context, the panic is a compile-time error.
+    pub const fn from_mebibytes(mebibytes: u32) -> Self {
+        match mebibytes.checked_mul(1024 * 1024) {
+            Some(bytes) => Density(bytes),
+            None => panic!("The flash density is too large"),
+        }
+    }
+
+    /// A density of `bytes`.
+    pub const fn from_bytes(bytes: u32) -> Self {
+        Density(bytes)
+    }
+
+    /// Returns the density in bytes.
+    pub const fn bytes(self) -> u32 {
+        self.0
+    }
 }
 
 /// A FlexSPI serial flash region
+///
+/// See [`FlashRegions`](super::FlashRegions) for each region's chip select.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[repr(usize)]
 pub enum SerialFlashRegion {
@@ -126,3 +783,130 @@ pub enum SerialFlashRegion {
     B1,
     B2,
 }
+
+#[cfg(test)]
+mod test {
+    use super::{ControllerMiscOptions, DataValidTime, SerialClockFrequency};
+
+    #[cfg(any(feature = "imxrt1020", feature = "imxrt1024", feature = "imxrt1170"))]
+    #[test]
+    fn column_address_width_serial_nand() {
+        use super::ColumnAddressWidth;
+        assert_eq!(ColumnAddressWidth::SerialNand12 as u8, 12);
+        assert_eq!(ColumnAddressWidth::SerialNand13 as u8, 13);
+    }
+
+    #[test]
+    fn controller_misc_options() {
+        let options = ControllerMiscOptions::DDR_MODE.union(ControllerMiscOptions::CK2);
+        assert_eq!(options.bits(), 0x42);
+        assert!(options.contains(ControllerMiscOptions::DDR_MODE));
+        assert!(!options.contains(ControllerMiscOptions::PARALLEL));
+        assert_eq!(
+            options.difference(ControllerMiscOptions::CK2),
+            ControllerMiscOptions::DDR_MODE
+        );
+        assert_eq!(ControllerMiscOptions::from_bits(0x42), Some(options));
+        assert_eq!(ControllerMiscOptions::from_bits(1 << 8), None);
+        assert_eq!(
+            ControllerMiscOptions::from_bits_retain(1 << 8).bits(),
+            1 << 8
+        );
+    }
+
+    #[test]
+    fn data_valid_time_layout() {
+        // flexspi_dll_time_t { uint8_t time_100ps; uint8_t delay_cells; } dataValidTime[2];
+        let time = DataValidTime::new(0x12, 0x34).delay_cells(0x56, 0x78);
+        let bytes: [u8; 4] = unsafe { core::mem::transmute(time) };
+        assert_eq!(bytes, [0x12, 0x56, 0x34, 0x78]);
+        assert_eq!(time.to_bytes(), bytes);
+        assert_eq!(DataValidTime::from_bytes(bytes), time);
+
+        // Brace elision: the SDK's `.dataValidTime = {16u, 16u}` initializes
+        // port A's time and delay cells, not both ports.
+        assert_eq!(
+            DataValidTime::new(16, 0).delay_cells(16, 0).to_bytes(),
+            [16, 16, 0, 0]
+        );
+    }
+
+    #[test]
+    fn serial_clock_frequency_max() {
+        assert_eq!(
+            SerialClockFrequency::nearest(u32::MAX).mhz(),
+            crate::Imxrt::SELECTED.max_serial_clock_mhz()
+        );
+    }
+
+    #[test]
+    fn serial_clock_frequency_from_mhz() {
+        assert_eq!(
+            SerialClockFrequency::from_mhz(100),
+            Some(SerialClockFrequency::MHz100)
+        );
+        assert_eq!(SerialClockFrequency::from_mhz(101), None);
+        assert_eq!(
+            SerialClockFrequency::nearest(0),
+            SerialClockFrequency::MHz30
+        );
+        assert_eq!(
+            SerialClockFrequency::nearest(55),
+            SerialClockFrequency::MHz50
+        );
+        assert_eq!(
+            SerialClockFrequency::nearest(58),
+            SerialClockFrequency::MHz60
+        );
+        assert_eq!(
+            SerialClockFrequency::nearest(90),
+            SerialClockFrequency::MHz80
+        );
+        assert_eq!(SerialClockFrequency::nearest(u32::MAX).mhz(), {
+            #[cfg(feature = "imxrt1010")]
+            {
+                133
+            }
+            #[cfg(any(
+                feature = "imxrt1170",
+                feature = "imxrt1180",
+                feature = "imxrt500",
+                feature = "imxrt600"
+            ))]
+            {
+                200
+            }
+            #[cfg(not(any(
+                feature = "imxrt1010",
+                feature = "imxrt1170",
+                feature = "imxrt1180",
+                feature = "imxrt500",
+                feature = "imxrt600"
+            )))]
+            {
+                166
+            }
+        });
+    }
+}
+
+//
+// Keep these two tests in sync
+//
+// The first one lets you know if the second one is failing to compile
+// in the way we expect.
+//
+
+/// ```
+/// use imxrt_boot_gen::flexspi::{BusyBit, BusyBitPolarity};
+/// const BUSY_BIT: BusyBit = BusyBit::new(31, BusyBitPolarity::ActiveHigh);
+/// ```
+#[cfg(doctest)]
+struct BusyBitOffsetInRange;
+
+/// ```compile_fail
+/// use imxrt_boot_gen::flexspi::{BusyBit, BusyBitPolarity};
+/// const BUSY_BIT: BusyBit = BusyBit::new(32, BusyBitPolarity::ActiveHigh);
+/// ```
+#[cfg(doctest)]
+struct BusyBitOffsetOutOfRange;
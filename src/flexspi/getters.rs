@@ -0,0 +1,259 @@
+//! Read-only accessors for the FlexSPI configuration block
+//!
+//! The setters share the field names, so the getters have a `get_` prefix.
+
+use super::{
+    BusyBit, BusyBitPolarity, ColumnAddressWidth, ConfigurationBlock, ConfigurationCommand,
+    ControllerMiscOptions, DataValidTime, Density, DeviceModeConfiguration, FlashPadType,
+    LookupTable, LutCustomSequence, PadSettingOverride, ReadSampleClockSource,
+    SerialClockFrequency, SerialFlashRegion, Version, WaitTimeConfigurationCommands,
+    CONFIG_CMD_SLOTS, LUT_CUSTOM_SEQ_SLOTS,
+};
+
+impl ConfigurationBlock {
+    /// Returns `version`.
+    pub const fn get_version(&self) -> Version {
+        self.version
+    }
+
+    /// Returns `readSampleClkSrc`.
+    pub const fn get_read_sample_clk_src(&self) -> ReadSampleClockSource {
+        self.read_sample_clk_src
+    }
+
+    /// Returns `csHoldTime`.
+    pub const fn get_cs_hold_time(&self) -> u8 {
+        self.cs_hold_time
+    }
+
+    /// Returns `csSetupTime`.
+    pub const fn get_cs_setup_time(&self) -> u8 {
+        self.cs_setup_time
+    }
+
+    /// Returns `columnAddressWidth`.
+    pub const fn get_column_address_width(&self) -> ColumnAddressWidth {
+        self.column_address_width
+    }
+
+    /// Returns `deviceModeCfgEnable`, `deviceModeSeq`, and `deviceModeArg`.
+    pub const fn get_device_mode_configuration(&self) -> DeviceModeConfiguration {
+        if self.device_mode_configuration == 0 {
+            DeviceModeConfiguration::Disabled
+        } else {
+            DeviceModeConfiguration::Enabled {
+                device_mode_arg: self.device_mode_arg,
+                device_mode_seq: self.device_mode_sequence,
+            }
+        }
+    }
+
+    /// Returns `deviceModeType`, or `None` if the value isn't known.
+    #[cfg(any(
+        feature = "imxrt1170",
+        feature = "imxrt1180",
+        feature = "imxrt500",
+        feature = "imxrt600"
+    ))]
+    pub const fn get_device_mode_type(&self) -> Option<super::DeviceModeType> {
+        use super::DeviceModeType;
+        Some(match self.device_mode_type {
+            0 => DeviceModeType::Generic,
+            1 => DeviceModeType::QuadEnable,
+            2 => DeviceModeType::Spi2Xpi,
+            3 => DeviceModeType::Xpi2Spi,
+            4 => DeviceModeType::Spi2NoCmd,
+            5 => DeviceModeType::Reset,
+            _ => return None,
+        })
+    }
+
+    /// Returns `waitTimeCfgCommands`.
+    pub const fn get_wait_time_cfg_commands(&self) -> WaitTimeConfigurationCommands {
+        self.wait_time_cfg_commands
+    }
+
+    /// Returns the configuration command in slot `index`, or `None` if
+    /// `configCmdEnable` is cleared.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is not less than 3.
+    pub const fn get_config_command(&self, index: usize) -> Option<ConfigurationCommand> {
+        assert!(
+            index < CONFIG_CMD_SLOTS,
+            "There are only three configuration commands"
+        );
+        if self.config_cmd_enable == 0 {
+            None
+        } else {
+            Some(ConfigurationCommand {
+                sequence: self.config_cmd_seqs[index],
+                arg: self.cfg_cmd_args[index],
+            })
+        }
+    }
+
+    /// Returns `controllerMiscOption`.
+    pub const fn get_controller_misc_options(&self) -> ControllerMiscOptions {
+        ControllerMiscOptions::from_bits_retain(self.controller_misc_options)
+    }
+
+    /// Returns `deviceType`.
+    ///
+    /// The serial NOR and NAND configuration blocks set the device type.
+    pub const fn get_device_type(&self) -> u8 {
+        self.device_type
+    }
+
+    /// Returns `sFlashPad`.
+    pub const fn get_serial_flash_pad_type(&self) -> FlashPadType {
+        self.serial_flash_pad_type
+    }
+
+    /// Returns `serialClkFreq`.
+    pub const fn get_serial_clk_freq(&self) -> SerialClockFrequency {
+        self.serial_clk_freq
+    }
+
+    /// Returns the custom LUT sequence in slot `index`, or `None` if
+    /// `lutCustomSeqEnable` is cleared.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is not less than 12.
+    pub const fn get_lut_custom_seq(&self, index: usize) -> Option<LutCustomSequence> {
+        assert!(
+            index < LUT_CUSTOM_SEQ_SLOTS,
+            "There are only twelve custom LUT sequences"
+        );
+        if self.lut_custom_seq_enable == 0 {
+            None
+        } else {
+            Some(self.lut_custom_seq[index])
+        }
+    }
+
+    /// Returns the flash size of the region.
+    pub const fn get_flash_size(&self, flash_region: SerialFlashRegion) -> Density {
+        Density::from_bytes(self.serial_flash_sizes[flash_region as usize])
+    }
+
+    /// Returns `csPadSettingOverride`.
+    pub const fn get_cs_pad_setting_override(&self) -> PadSettingOverride {
+        self.cs_pad_setting_override
+    }
+
+    /// Returns `sclkPadSettingOverride`.
+    pub const fn get_sclk_pad_setting_override(&self) -> PadSettingOverride {
+        self.sclk_pad_setting_override
+    }
+
+    /// Returns `dataPadSettingOverride`.
+    pub const fn get_data_pad_setting_override(&self) -> PadSettingOverride {
+        self.data_pad_setting_override
+    }
+
+    /// Returns `dqsPadSettingOverride`.
+    pub const fn get_dqs_pad_setting_override(&self) -> PadSettingOverride {
+        self.dqs_pad_setting_override
+    }
+
+    /// Returns `timeoutInMs`.
+    pub const fn get_timeout_ms(&self) -> u32 {
+        self.timeout_ms
+    }
+
+    /// Returns `commandInterval`.
+    pub const fn get_command_interval(&self) -> u32 {
+        self.command_interval
+    }
+
+    /// Returns `dataValidTime`.
+    pub const fn get_data_valid_time(&self) -> DataValidTime {
+        self.data_valid_time
+    }
+
+    /// Returns `busyOffset` and `busyBitPolarity`.
+    ///
+    /// Any non-zero `busyBitPolarity` is [`BusyBitPolarity::ActiveLow`].
+    pub const fn get_busy_bit(&self) -> BusyBit {
+        let polarity = if self.busy_bit_polarity == 0 {
+            BusyBitPolarity::ActiveHigh
+        } else {
+            BusyBitPolarity::ActiveLow
+        };
+        BusyBit::new((self.busy_offset & 0x1F) as u8, polarity)
+    }
+
+    /// Returns the lookup table.
+    pub const fn get_lookup_table(&self) -> &LookupTable {
+        &self.lookup_table
+    }
+
+    /// Returns `dll0CrVal`.
+    #[cfg(any(feature = "imxrt500", feature = "imxrt600"))]
+    pub const fn get_dll0_cr_val(&self) -> u32 {
+        self.dll_cr_val[0]
+    }
+
+    /// Returns `dll1CrVal`.
+    #[cfg(any(feature = "imxrt500", feature = "imxrt600"))]
+    pub const fn get_dll1_cr_val(&self) -> u32 {
+        self.dll_cr_val[1]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{
+        BusyBit, BusyBitPolarity, ConfigurationBlock, ConfigurationCommand, Density,
+        DeviceModeConfiguration, FlashPadType, LookupTable, SerialFlashRegion,
+    };
+    use crate::flexspi::{
+        opcodes::sdr::CMD, Command, DeviceModeSequence, Instr, Pads, SequenceBuilder,
+    };
+
+    #[test]
+    fn getters() {
+        let seq = SequenceBuilder::new()
+            .instr(Instr::new(CMD, Pads::One, 0x06))
+            .build();
+        let block = ConfigurationBlock::new(LookupTable::new().command(Command::WriteEnable, seq))
+            .cs_hold_time(1)
+            .device_mode_configuration(DeviceModeConfiguration::Enabled {
+                device_mode_arg: 0x42,
+                device_mode_seq: DeviceModeSequence::new(2, 6),
+            })
+            .config_command(1, ConfigurationCommand::new(1, 10, 0x40))
+            .serial_flash_pad_type(FlashPadType::Quad)
+            .flash_size(SerialFlashRegion::B1, Density::from_mebibytes(4))
+            .busy_bit(BusyBit::new(7, BusyBitPolarity::ActiveLow))
+            .timeout_ms(100);
+
+        assert_eq!(block.get_cs_hold_time(), 1);
+        assert!(
+            block.get_device_mode_configuration()
+                == DeviceModeConfiguration::Enabled {
+                    device_mode_arg: 0x42,
+                    device_mode_seq: DeviceModeSequence::new(2, 6),
+                }
+        );
+        assert_eq!(
+            block.get_config_command(1),
+            Some(ConfigurationCommand::new(1, 10, 0x40))
+        );
+        assert_eq!(block.get_serial_flash_pad_type(), FlashPadType::Quad);
+        assert_eq!(
+            block.get_flash_size(SerialFlashRegion::B1),
+            Density::from_mebibytes(4)
+        );
+        assert_eq!(block.get_flash_size(SerialFlashRegion::A1).bytes(), 0);
+        assert_eq!(
+            block.get_busy_bit(),
+            BusyBit::new(7, BusyBitPolarity::ActiveLow)
+        );
+        assert_eq!(block.get_timeout_ms(), 100);
+        assert_eq!(block.get_lut_custom_seq(0), None);
+    }
+}
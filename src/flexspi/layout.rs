@@ -0,0 +1,126 @@
+//! The FlexSPI configuration block layout
+
+use super::{ConfigurationBlock, DeviceModeSequence, LookupTable, Version};
+use core::mem::{offset_of, size_of};
+
+/// The location of a named field within a configuration block
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Field {
+    /// The field name, as it appears in NXP's headers.
+    pub name: &'static str,
+    /// The byte offset from the start of the configuration block.
+    pub offset: usize,
+    /// The size of the field, in bytes.
+    pub size: usize,
+}
+
+macro_rules! field {
+    ($name:literal, $field:ident, $ty:ty) => {
+        field!($name, $field, $ty, 0)
+    };
+    ($name:literal, $field:ident, $ty:ty, $extra:expr) => {
+        Field {
+            name: $name,
+            offset: offset_of!(ConfigurationBlock, $field) + $extra,
+            size: size_of::<$ty>(),
+        }
+    };
+}
+
+#[cfg(not(any(
+    feature = "imxrt1170",
+    feature = "imxrt1180",
+    feature = "imxrt500",
+    feature = "imxrt600"
+)))]
+const FIELD_COUNT: usize = 33;
+#[cfg(any(feature = "imxrt1170", feature = "imxrt1180"))]
+const FIELD_COUNT: usize = 34;
+#[cfg(any(feature = "imxrt500", feature = "imxrt600"))]
+const FIELD_COUNT: usize = 36;
+
+/// The named fields of the FlexSPI configuration block, in offset order
+///
+/// The table excludes reserved fields. Some fields depend on the selected chip.
+///
+/// ```
+/// use imxrt_boot_gen::flexspi::FIELDS;
+///
+/// let field = FIELDS.iter().find(|field| field.name == "timeoutInMs").unwrap();
+/// assert_eq!((field.offset, field.size), (0x70, 4));
+/// ```
+pub const FIELDS: [Field; FIELD_COUNT] = [
+    field!("tag", tag, u32),
+    field!("version", version, Version),
+    field!("readSampleClkSrc", read_sample_clk_src, u8),
+    field!("csHoldTime", cs_hold_time, u8),
+    field!("csSetupTime", cs_setup_time, u8),
+    field!("columnAddressWidth", column_address_width, u8),
+    field!("deviceModeCfgEnable", device_mode_configuration, u8),
+    #[cfg(any(
+        feature = "imxrt1170",
+        feature = "imxrt1180",
+        feature = "imxrt500",
+        feature = "imxrt600"
+    ))]
+    field!("deviceModeType", device_mode_type, u8),
+    field!("waitTimeCfgCommands", wait_time_cfg_commands, u16),
+    field!("deviceModeSeq", device_mode_sequence, DeviceModeSequence),
+    field!("deviceModeArg", device_mode_arg, u32),
+    field!("configCmdEnable", config_cmd_enable, u8),
+    field!("configCmdSeqs", config_cmd_seqs, [u8; 12]),
+    field!("cfgCmdArgs", cfg_cmd_args, [u8; 12]),
+    field!("controllerMiscOption", controller_misc_options, u32),
+    field!("deviceType", device_type, u8),
+    field!("sflashPadType", serial_flash_pad_type, u8),
+    field!("serialClkFreq", serial_clk_freq, u8),
+    field!("lutCustomSeqEnable", lut_custom_seq_enable, u8),
+    field!("sflashA1Size", serial_flash_sizes, u32, 0),
+    field!("sflashA2Size", serial_flash_sizes, u32, 4),
+    field!("sflashB1Size", serial_flash_sizes, u32, 8),
+    field!("sflashB2Size", serial_flash_sizes, u32, 12),
+    field!("csPadSettingOverride", cs_pad_setting_override, u32),
+    field!("sclkPadSettingOverride", sclk_pad_setting_override, u32),
+    field!("dataPadSettingOverride", data_pad_setting_override, u32),
+    field!("dqsPadSettingOverride", dqs_pad_setting_override, u32),
+    field!("timeoutInMs", timeout_ms, u32),
+    field!("commandInterval", command_interval, u32),
+    field!("dataValidTime", data_valid_time, u32),
+    field!("busyOffset", busy_offset, u16),
+    field!("busyBitPolarity", busy_bit_polarity, u16),
+    field!("lookupTable", lookup_table, LookupTable),
+    field!("lutCustomSeq", lut_custom_seq, [u8; 48]),
+    #[cfg(any(feature = "imxrt500", feature = "imxrt600"))]
+    field!("dll0CrVal", dll_cr_val, u32, 0),
+    #[cfg(any(feature = "imxrt500", feature = "imxrt600"))]
+    field!("dll1CrVal", dll_cr_val, u32, 4),
+];
+
+/// Asserts that the fields are in offset order, don't overlap, and fit in a
+/// block of `block_size` bytes.
+#[cfg(test)]
+pub(crate) fn check_fields(fields: &[Field], block_size: usize) {
+    for pair in fields.windows(2) {
+        assert!(
+            pair[0].offset + pair[0].size <= pair[1].offset,
+            "{:?} overlaps {:?}",
+            pair[0],
+            pair[1]
+        );
+    }
+    let last = fields.last().unwrap();
+    assert!(last.offset + last.size <= block_size);
+}
+
+#[cfg(test)]
+mod test {
+    use super::{check_fields, FIELDS};
+
+    #[test]
+    fn fields() {
+        check_fields(&FIELDS, 448);
+        assert_eq!(FIELDS[0].offset, 0);
+        let lut = FIELDS.iter().find(|f| f.name == "lookupTable").unwrap();
+        assert_eq!((lut.offset, lut.size), (0x080, 256));
+    }
+}
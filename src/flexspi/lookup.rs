@@ -1,6 +1,7 @@
 //! FlexSPI Lookup table
 
-use super::sequence::{Sequence, SEQUENCE_SIZE};
+use super::sequence::{Sequence, INSTRUCTIONS_PER_SEQUENCE, SEQUENCE_SIZE};
+use super::FlashPadType;
 
 /// The default sequence definition lookup indices
 ///
@@ -14,6 +15,17 @@ pub enum Command {
     ReadStatus = 1,
     WriteEnable = 3,
     EraseSector = 5,
+    /// Erase a block, which may be larger than a sector.
+    ///
+    /// The boot ROM's serial NOR driver looks for this sequence when it erases
+    /// a block.
+    #[cfg(any(
+        feature = "imxrt1170",
+        feature = "imxrt1180",
+        feature = "imxrt500",
+        feature = "imxrt600"
+    ))]
+    EraseBlock = 8,
     PageProgram = 9,
     ChipErase = 11,
     Dummy = 15,
@@ -21,7 +33,7 @@ pub enum Command {
 
 /// Size of the lookup table in bytes
 const LOOKUP_TABLE_SIZE_BYTES: usize = 256;
-const NUMBER_OF_SEQUENCES: usize = LOOKUP_TABLE_SIZE_BYTES / SEQUENCE_SIZE;
+pub(crate) const NUMBER_OF_SEQUENCES: usize = LOOKUP_TABLE_SIZE_BYTES / SEQUENCE_SIZE;
 
 /// A sequence lookup table, part of the general FlexSPI configuration block
 ///
@@ -46,10 +58,20 @@ const NUMBER_OF_SEQUENCES: usize = LOOKUP_TABLE_SIZE_BYTES / SEQUENCE_SIZE;
 ///         .instr(Instr::new(RADDR, Pads::Four, 0x02))
 ///         .build());
 /// ```
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "zerocopy",
+    derive(zerocopy::IntoBytes, zerocopy::Immutable, zerocopy::FromBytes)
+)]
 #[repr(transparent)]
 pub struct LookupTable([Sequence; NUMBER_OF_SEQUENCES]);
 
+// Safety: a lookup table is an array of sequences, which are `Pod`.
+#[cfg(feature = "bytemuck")]
+unsafe impl bytemuck::Zeroable for LookupTable {}
+#[cfg(feature = "bytemuck")]
+unsafe impl bytemuck::Pod for LookupTable {}
+
 impl Default for LookupTable {
     fn default() -> Self {
         Self::new()
@@ -66,6 +88,86 @@ impl LookupTable {
         self.0[cmd as usize] = sequence;
         self
     }
+    /// Returns the sequence that's assigned to the command
+    ///
+    /// An unassigned command is a sequence of STOPs.
+    pub const fn get_command(&self, cmd: Command) -> Sequence {
+        self.0[cmd as usize]
+    }
+    /// Assign the `sequence` to the lookup table `index`
+    ///
+    /// Use this for sequences that don't have a [`Command`], like the sequences
+    /// referenced by configuration commands.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is not less than 16. When evaluated in a `const` context,
+    /// the panic is a compile-time error.
+    pub const fn sequence(mut self, index: usize, sequence: Sequence) -> Self {
+        assert!(
+            index < NUMBER_OF_SEQUENCES,
+            "There are only sixteen sequences"
+        );
+        self.0[index] = sequence;
+        self
+    }
+    /// Returns the sequence at the lookup table `index`
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is not less than 16.
+    pub const fn get_sequence(&self, index: usize) -> Sequence {
+        assert!(
+            index < NUMBER_OF_SEQUENCES,
+            "There are only sixteen sequences"
+        );
+        self.0[index]
+    }
+    pub(crate) fn from_bytes(bytes: &[u8; LOOKUP_TABLE_SIZE_BYTES]) -> Self {
+        let mut lookup_table = LookupTable::new();
+        for (sequence, raw) in lookup_table
+            .0
+            .iter_mut()
+            .zip(bytes.chunks_exact(SEQUENCE_SIZE))
+        {
+            *sequence = Sequence::from_bytes(raw.try_into().unwrap());
+        }
+        lookup_table
+    }
+    pub(crate) const fn to_bytes(self) -> [u8; LOOKUP_TABLE_SIZE_BYTES] {
+        let mut bytes = [0; LOOKUP_TABLE_SIZE_BYTES];
+        let mut seq = 0;
+        while seq < NUMBER_OF_SEQUENCES {
+            crate::raw::put(&mut bytes, seq * SEQUENCE_SIZE, &self.0[seq].to_bytes());
+            seq += 1;
+        }
+        bytes
+    }
+    /// Returns the widest pad type used by any instruction in the lookup table
+    ///
+    /// If the lookup table is empty, this returns [`FlashPadType::Single`].
+    pub const fn widest_pad_type(&self) -> FlashPadType {
+        let mut widest = 0;
+        let mut seq = 0;
+        while seq < NUMBER_OF_SEQUENCES {
+            let mut instr = 0;
+            while instr < INSTRUCTIONS_PER_SEQUENCE {
+                if let Some(pads) = self.0[seq].0[instr].pads() {
+                    if pads > widest {
+                        widest = pads;
+                    }
+                }
+                instr += 1;
+            }
+            seq += 1;
+        }
+        match widest {
+            0 => FlashPadType::Single,
+            1 => FlashPadType::Dual,
+            2 => FlashPadType::Quad,
+            _ => FlashPadType::Octal,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -84,4 +186,40 @@ mod test {
             .command(Command::ChipErase, SequenceBuilder::new().build())
             .command(Command::Dummy, SequenceBuilder::new().build());
     }
+
+    #[cfg(any(
+        feature = "imxrt1170",
+        feature = "imxrt1180",
+        feature = "imxrt500",
+        feature = "imxrt600"
+    ))]
+    #[test]
+    fn erase_block() {
+        assert_eq!(Command::EraseBlock as usize, 8);
+    }
+
+    #[test]
+    fn widest_pad_type() {
+        use crate::flexspi::{opcodes::sdr::*, FlashPadType, Instr, Pads, JUMP_ON_CS};
+
+        assert_eq!(LookupTable::new().widest_pad_type(), FlashPadType::Single);
+
+        let lut = LookupTable::new()
+            .command(
+                Command::ReadStatus,
+                SequenceBuilder::new()
+                    .instr(Instr::new(CMD, Pads::One, 0x05))
+                    .instr(Instr::new(READ, Pads::One, 0x04))
+                    .build(),
+            )
+            .command(
+                Command::Read,
+                SequenceBuilder::new()
+                    .instr(Instr::new(CMD, Pads::One, 0xEB))
+                    .instr(Instr::new(RADDR, Pads::Four, 0x18))
+                    .instr(JUMP_ON_CS)
+                    .build(),
+            );
+        assert_eq!(lut.widest_pad_type(), FlashPadType::Quad);
+    }
 }
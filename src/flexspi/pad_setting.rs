@@ -0,0 +1,222 @@
+//! Pad setting overrides
+
+/// A pad setting override, for `csPadSettingOverride`, `sclkPadSettingOverride`,
+/// `dataPadSettingOverride`, and `dqsPadSettingOverride`
+///
+/// The value is written to the IOMUXC pad control registers for the FlexSPI pads.
+/// On the 10xx chips, use the typed methods to describe the pad settings. On all
+/// chips, use [`from_raw`](Self::from_raw) to provide the raw pad control
+/// register value.
+///
+/// The 1170, 1180, 500, and 600 don't have typed methods. Their pad control
+/// registers have a different layout for each pad group, and the ROM's choice
+/// of register for each override isn't described here. Take the raw value
+/// from your chip's reference manual.
+///
+/// The boot ROM only applies the overrides if you enable pad setting overrides
+/// in `controllerMiscOption`. See your chip's reference manual for more
+/// information.
+///
+/// ```
+/// # #[cfg(not(any(feature = "imxrt1170", feature = "imxrt1180", feature = "imxrt500", feature = "imxrt600")))] {
+/// use imxrt_boot_gen::flexspi::{DriveStrength, PadSettingOverride, SlewRate, Speed};
+///
+/// const DQS_PAD_SETTING: PadSettingOverride = PadSettingOverride::new()
+///     .drive_strength(DriveStrength::R0Div6)
+///     .speed(Speed::Max)
+///     .slew_rate(SlewRate::Fast);
+/// # }
+/// ```
+#[derive(Default, Clone, Copy, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "zerocopy", derive(zerocopy::IntoBytes, zerocopy::Immutable))]
+#[repr(transparent)]
+pub struct PadSettingOverride(u32);
+
+impl PadSettingOverride {
+    /// A pad setting override with a raw pad control register value
+    pub const fn from_raw(raw: u32) -> Self {
+        PadSettingOverride(raw)
+    }
+    /// Returns the raw pad control register value
+    pub const fn raw(self) -> u32 {
+        self.0
+    }
+}
+
+/// Pad slew rate
+#[cfg(not(any(
+    feature = "imxrt1170",
+    feature = "imxrt1180",
+    feature = "imxrt500",
+    feature = "imxrt600"
+)))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u32)]
+pub enum SlewRate {
+    Slow = 0,
+    Fast = 1,
+}
+
+/// Pad drive strength, expressed as a fraction of the R0 output impedance
+#[cfg(not(any(
+    feature = "imxrt1170",
+    feature = "imxrt1180",
+    feature = "imxrt500",
+    feature = "imxrt600"
+)))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u32)]
+pub enum DriveStrength {
+    /// Output driver disabled
+    Disabled = 0,
+    R0 = 1,
+    R0Div2 = 2,
+    R0Div3 = 3,
+    R0Div4 = 4,
+    R0Div5 = 5,
+    R0Div6 = 6,
+    R0Div7 = 7,
+}
+
+/// Pad speed
+///
+/// See your chip's reference manual for the frequency of each speed.
+#[cfg(not(any(
+    feature = "imxrt1170",
+    feature = "imxrt1180",
+    feature = "imxrt500",
+    feature = "imxrt600"
+)))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u32)]
+pub enum Speed {
+    Low = 0,
+    Medium = 1,
+    Fast = 2,
+    Max = 3,
+}
+
+/// Pad pull resistor
+#[cfg(not(any(
+    feature = "imxrt1170",
+    feature = "imxrt1180",
+    feature = "imxrt500",
+    feature = "imxrt600"
+)))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u32)]
+pub enum Pull {
+    Down100k = 0,
+    Up47k = 1,
+    Up100k = 2,
+    Up22k = 3,
+}
+
+/// Pad pull / keeper selection
+#[cfg(not(any(
+    feature = "imxrt1170",
+    feature = "imxrt1180",
+    feature = "imxrt500",
+    feature = "imxrt600"
+)))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PullKeep {
+    /// Neither the pull nor the keeper is enabled
+    Disabled,
+    /// The keeper is enabled
+    Keeper,
+    /// The pull resistor is enabled
+    Pull(Pull),
+}
+
+#[cfg(not(any(
+    feature = "imxrt1170",
+    feature = "imxrt1180",
+    feature = "imxrt500",
+    feature = "imxrt600"
+)))]
+impl PadSettingOverride {
+    const SRE_SHIFT: u32 = 0;
+    const DSE_SHIFT: u32 = 3;
+    const SPEED_SHIFT: u32 = 6;
+    const ODE_SHIFT: u32 = 11;
+    const PKE_SHIFT: u32 = 12;
+    const PUE_SHIFT: u32 = 13;
+    const PUS_SHIFT: u32 = 14;
+    const HYS_SHIFT: u32 = 16;
+
+    /// A pad setting override with all fields cleared
+    pub const fn new() -> Self {
+        PadSettingOverride(0)
+    }
+
+    const fn field(self, shift: u32, width: u32, value: u32) -> Self {
+        let mask = ((1 << width) - 1) << shift;
+        PadSettingOverride((self.0 & !mask) | ((value << shift) & mask))
+    }
+
+    /// Set the slew rate (`SRE`)
+    pub const fn slew_rate(self, slew_rate: SlewRate) -> Self {
+        self.field(Self::SRE_SHIFT, 1, slew_rate as u32)
+    }
+    /// Set the drive strength (`DSE`)
+    pub const fn drive_strength(self, drive_strength: DriveStrength) -> Self {
+        self.field(Self::DSE_SHIFT, 3, drive_strength as u32)
+    }
+    /// Set the speed (`SPEED`)
+    pub const fn speed(self, speed: Speed) -> Self {
+        self.field(Self::SPEED_SHIFT, 2, speed as u32)
+    }
+    /// Enable or disable the open drain output (`ODE`)
+    pub const fn open_drain(self, open_drain: bool) -> Self {
+        self.field(Self::ODE_SHIFT, 1, open_drain as u32)
+    }
+    /// Set the pull / keeper (`PKE`, `PUE`, and `PUS`)
+    pub const fn pull_keep(self, pull_keep: PullKeep) -> Self {
+        match pull_keep {
+            PullKeep::Disabled => self.field(Self::PKE_SHIFT, 1, 0),
+            PullKeep::Keeper => self
+                .field(Self::PKE_SHIFT, 1, 1)
+                .field(Self::PUE_SHIFT, 1, 0),
+            PullKeep::Pull(pull) => self
+                .field(Self::PKE_SHIFT, 1, 1)
+                .field(Self::PUE_SHIFT, 1, 1)
+                .field(Self::PUS_SHIFT, 2, pull as u32),
+        }
+    }
+    /// Enable or disable the hysteresis (`HYS`)
+    pub const fn hysteresis(self, hysteresis: bool) -> Self {
+        self.field(Self::HYS_SHIFT, 1, hysteresis as u32)
+    }
+}
+
+#[cfg(all(
+    test,
+    not(any(
+        feature = "imxrt1170",
+        feature = "imxrt1180",
+        feature = "imxrt500",
+        feature = "imxrt600"
+    ))
+))]
+mod test {
+    use super::{DriveStrength, PadSettingOverride, Pull, PullKeep, SlewRate, Speed};
+
+    #[test]
+    fn fields() {
+        // Commonly used in NXP's SDKs for the FlexSPI pads.
+        const SDK_PAD_SETTING: u32 = 0x10F1;
+        let pad = PadSettingOverride::new()
+            .slew_rate(SlewRate::Fast)
+            .drive_strength(DriveStrength::R0Div6)
+            .speed(Speed::Max)
+            .pull_keep(PullKeep::Keeper);
+        assert_eq!(pad.raw(), SDK_PAD_SETTING);
+
+        let pad = pad.pull_keep(PullKeep::Pull(Pull::Up22k)).hysteresis(true);
+        assert_eq!(
+            pad.raw(),
+            SDK_PAD_SETTING | (1 << 13) | (3 << 14) | (1 << 16)
+        );
+    }
+}
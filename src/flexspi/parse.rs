@@ -0,0 +1,167 @@
+//! Decode the FlexSPI configuration block from bytes
+
+use super::{
+    ColumnAddressWidth, ConfigurationBlock, DataValidTime, DeviceModeSequence, FlashPadType,
+    LookupTable, LutCustomSequence, PadSettingOverride, ReadSampleClockSource,
+    SerialClockFrequency, Version, WaitTimeConfigurationCommands, CONFIG_CMD_SLOTS,
+    LUT_CUSTOM_SEQ_SLOTS, TAG,
+};
+use crate::parse::{ParseError, Reader};
+use core::mem::offset_of;
+
+impl ConfigurationBlock {
+    /// Decode a configuration block from its in-memory layout.
+    ///
+    /// This is the inverse of [`to_bytes`](Self::to_bytes). It checks the tag,
+    /// the version, and every field that the selected chip constrains. It
+    /// doesn't check `deviceType`.
+    ///
+    /// ```
+    /// use imxrt_boot_gen::flexspi::{ConfigurationBlock, LookupTable};
+    ///
+    /// let block = ConfigurationBlock::new(LookupTable::new()).cs_hold_time(1);
+    /// assert_eq!(ConfigurationBlock::from_bytes(&block.to_bytes()), Ok(block));
+    /// ```
+    pub fn from_bytes(bytes: &[u8; Self::SIZE]) -> Result<Self, ParseError> {
+        let r = Reader(bytes);
+        macro_rules! at {
+            ($field:ident) => {
+                offset_of!(Self, $field)
+            };
+        }
+
+        let tag = r.u32(at!(tag));
+        if tag != TAG {
+            return Err(ParseError::Tag(tag));
+        }
+        let raw_version = r.u32(at!(version));
+        let version = Version::from_raw(raw_version).ok_or(ParseError::Version(raw_version))?;
+
+        let mut block =
+            ConfigurationBlock::new(LookupTable::from_bytes(&r.array(at!(lookup_table))));
+        block.version = version;
+        block._reserved0 = r.array(at!(_reserved0));
+        block.read_sample_clk_src = r.field(
+            "readSampleClkSrc",
+            at!(read_sample_clk_src),
+            ReadSampleClockSource::from_raw,
+        )?;
+        block.cs_hold_time = r.u8(at!(cs_hold_time));
+        block.cs_setup_time = r.u8(at!(cs_setup_time));
+        block.column_address_width = r.field(
+            "columnAddressWidth",
+            at!(column_address_width),
+            ColumnAddressWidth::from_raw,
+        )?;
+        block.device_mode_configuration = r.u8(at!(device_mode_configuration));
+        block.device_mode_type = r.u8(at!(device_mode_type));
+        block.wait_time_cfg_commands =
+            WaitTimeConfigurationCommands::from_bytes(r.array(at!(wait_time_cfg_commands)));
+        block.device_mode_sequence =
+            DeviceModeSequence::from_bytes(r.array(at!(device_mode_sequence)));
+        block.device_mode_arg = r.u32(at!(device_mode_arg));
+        block.config_cmd_enable = r.u8(at!(config_cmd_enable));
+        block._reserved2 = r.array(at!(_reserved2));
+        for idx in 0..CONFIG_CMD_SLOTS {
+            block.config_cmd_seqs[idx] = r.array(at!(config_cmd_seqs) + idx * 4);
+            block.cfg_cmd_args[idx] = r.u32(at!(cfg_cmd_args) + idx * 4);
+        }
+        block._reserved3 = r.array(at!(_reserved3));
+        block._reserved4 = r.array(at!(_reserved4));
+        block.controller_misc_options = r.u32(at!(controller_misc_options));
+        block.device_type = r.u8(at!(device_type));
+        block.serial_flash_pad_type = r.field(
+            "sflashPadType",
+            at!(serial_flash_pad_type),
+            FlashPadType::from_raw,
+        )?;
+        block.serial_clk_freq = r.field(
+            "serialClkFreq",
+            at!(serial_clk_freq),
+            SerialClockFrequency::from_raw,
+        )?;
+        block.lut_custom_seq_enable = r.u8(at!(lut_custom_seq_enable));
+        block._reserved5 = r.array(at!(_reserved5));
+        for (idx, size) in block.serial_flash_sizes.iter_mut().enumerate() {
+            *size = r.u32(at!(serial_flash_sizes) + idx * 4);
+        }
+        block.cs_pad_setting_override =
+            PadSettingOverride::from_raw(r.u32(at!(cs_pad_setting_override)));
+        block.sclk_pad_setting_override =
+            PadSettingOverride::from_raw(r.u32(at!(sclk_pad_setting_override)));
+        block.data_pad_setting_override =
+            PadSettingOverride::from_raw(r.u32(at!(data_pad_setting_override)));
+        block.dqs_pad_setting_override =
+            PadSettingOverride::from_raw(r.u32(at!(dqs_pad_setting_override)));
+        block.timeout_ms = r.u32(at!(timeout_ms));
+        block.command_interval = r.u32(at!(command_interval));
+        block.data_valid_time = DataValidTime::from_bytes(r.array(at!(data_valid_time)));
+        block.busy_offset = r.u16(at!(busy_offset));
+        block.busy_bit_polarity = r.u16(at!(busy_bit_polarity));
+        for idx in 0..LUT_CUSTOM_SEQ_SLOTS {
+            block.lut_custom_seq[idx] =
+                LutCustomSequence::from_bytes(r.array(at!(lut_custom_seq) + idx * 4));
+        }
+        #[cfg(any(feature = "imxrt500", feature = "imxrt600"))]
+        {
+            block.dll_cr_val = [r.u32(at!(dll_cr_val)), r.u32(at!(dll_cr_val) + 4)];
+        }
+        block._reserved6 = r.array(at!(_reserved6));
+        Ok(block)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{ConfigurationBlock, ParseError};
+    use crate::flexspi::{
+        opcodes::sdr::*, Command, ConfigurationCommand, FlashPadType, Instr, LookupTable, Pads,
+        SequenceBuilder,
+    };
+
+    #[test]
+    fn round_trip() {
+        let lut = LookupTable::new().command(
+            Command::Read,
+            SequenceBuilder::new()
+                .instr(Instr::new(CMD, Pads::One, 0xEB))
+                .instr(Instr::new(RADDR, Pads::Four, 0x18))
+                .build(),
+        );
+        let block = ConfigurationBlock::new(lut)
+            .serial_flash_pad_type(FlashPadType::Quad)
+            .config_command(1, ConfigurationCommand::new(1, 4, 0xDEAD_BEEF))
+            .timeout_ms(100);
+        assert_eq!(ConfigurationBlock::from_bytes(&block.to_bytes()), Ok(block));
+    }
+
+    #[test]
+    fn invalid() {
+        let bytes = ConfigurationBlock::new(LookupTable::new()).to_bytes();
+
+        let mut tag = bytes;
+        tag[0] = 0;
+        assert_eq!(
+            ConfigurationBlock::from_bytes(&tag),
+            Err(ParseError::Tag(0x4246_4300))
+        );
+
+        let mut version = bytes;
+        version[7] = 0;
+        assert_eq!(
+            ConfigurationBlock::from_bytes(&version),
+            Err(ParseError::Version(0x0001_0000))
+        );
+
+        let mut pad_type = bytes;
+        pad_type[0x45] = 3;
+        assert_eq!(
+            ConfigurationBlock::from_bytes(&pad_type),
+            Err(ParseError::Field {
+                name: "sflashPadType",
+                offset: 0x45,
+                value: 3
+            })
+        );
+    }
+}
@@ -0,0 +1,245 @@
+//! Per-chip FlexSPI configuration block baselines
+
+use super::{
+    ConfigurationBlock, ControllerMiscOptions, ReadSampleClockSource, SerialClockFrequency,
+    Version, RECOMMENDED_CS_HOLD_TIME, RECOMMENDED_CS_SETUP_TIME, VERSION_DEFAULT,
+};
+use crate::Imxrt;
+
+/// A baseline for the FlexSPI configuration block fields that typically depend
+/// on the chip, and not the flash device
+///
+/// The profiles reflect the configuration blocks that NXP ships for each chip's
+/// EVK. Apply a profile with [`ConfigurationBlock::profile`], then override any
+/// field as needed.
+///
+/// ```
+/// use imxrt_boot_gen::flexspi::{ConfigurationBlock, LookupTable, Profile};
+///
+/// const FLEXSPI_CONFIGURATION_BLOCK: ConfigurationBlock =
+///     ConfigurationBlock::new(LookupTable::new())
+///         .profile(Profile::IMXRT1170)
+///         .cs_hold_time(0x01);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Profile {
+    /// The configuration block version.
+    pub version: Version,
+    /// `readSampleClkSrc`.
+    pub read_sample_clk_src: ReadSampleClockSource,
+    /// `csHoldTime`.
+    pub cs_hold_time: u8,
+    /// `csSetupTime`.
+    pub cs_setup_time: u8,
+    /// `controllerMiscOption`.
+    pub controller_misc_options: u32,
+}
+
+/// `controllerMiscOption` with the safe configuration frequency enabled.
+const SAFE_CONFIG_FREQ_ENABLE: u32 = ControllerMiscOptions::SAFE_CONFIG_FREQ.bits();
+
+impl Profile {
+    /// The i.MX RT 1010 baseline.
+    pub const IMXRT1010: Profile = Profile {
+        version: VERSION_DEFAULT,
+        read_sample_clk_src: ReadSampleClockSource::LoopbackFromDQSPad,
+        cs_hold_time: RECOMMENDED_CS_HOLD_TIME,
+        cs_setup_time: RECOMMENDED_CS_SETUP_TIME,
+        controller_misc_options: 0,
+    };
+    /// The i.MX RT 1020 baseline.
+    pub const IMXRT1020: Profile = Profile {
+        version: Version::new(1, 4, 0),
+        read_sample_clk_src: ReadSampleClockSource::LoopbackFromDQSPad,
+        cs_hold_time: RECOMMENDED_CS_HOLD_TIME,
+        cs_setup_time: RECOMMENDED_CS_SETUP_TIME,
+        controller_misc_options: 0,
+    };
+    /// The i.MX RT 1015 baseline, which is the same as the 1020 baseline.
+    pub const IMXRT1015: Profile = Profile::IMXRT1020;
+    /// The i.MX RT 1024 baseline, which is the same as the 1020 baseline.
+    pub const IMXRT1024: Profile = Profile::IMXRT1020;
+    /// The i.MX RT 1040 baseline, which is the same as the 1060 baseline.
+    pub const IMXRT1040: Profile = Profile::IMXRT1060;
+    /// The i.MX RT 1050 baseline, which is the same as the 1060 baseline.
+    pub const IMXRT1050: Profile = Profile::IMXRT1060;
+    /// The i.MX RT 1060 baseline.
+    pub const IMXRT1060: Profile = Profile {
+        version: Version::new(1, 4, 0),
+        read_sample_clk_src: ReadSampleClockSource::LoopbackFromDQSPad,
+        cs_hold_time: RECOMMENDED_CS_HOLD_TIME,
+        cs_setup_time: RECOMMENDED_CS_SETUP_TIME,
+        controller_misc_options: SAFE_CONFIG_FREQ_ENABLE,
+    };
+    /// The i.MX RT 1064 baseline, which is the same as the 1060 baseline.
+    pub const IMXRT1064: Profile = Profile::IMXRT1060;
+    /// The i.MX RT 1170 baseline.
+    pub const IMXRT1170: Profile = Profile::IMXRT11XX;
+    /// The i.MX RT 1180 baseline.
+    pub const IMXRT1180: Profile = Profile::IMXRT11XX;
+    /// The i.MX RT 500 baseline, which is the same as the 1170 baseline.
+    pub const IMXRT500: Profile = Profile::IMXRT11XX;
+    /// The i.MX RT 600 baseline, which is the same as the 1170 baseline.
+    pub const IMXRT600: Profile = Profile::IMXRT11XX;
+
+    const IMXRT11XX: Profile = Profile {
+        version: Version::new(1, 4, 0),
+        read_sample_clk_src: ReadSampleClockSource::LoopbackFromDQSPad,
+        cs_hold_time: RECOMMENDED_CS_HOLD_TIME,
+        cs_setup_time: RECOMMENDED_CS_SETUP_TIME,
+        controller_misc_options: SAFE_CONFIG_FREQ_ENABLE,
+    };
+
+    /// Returns the baseline for `chip`.
+    pub const fn for_chip(chip: Imxrt) -> Profile {
+        match chip {
+            Imxrt::Imxrt1010 => Profile::IMXRT1010,
+            Imxrt::Imxrt1015 => Profile::IMXRT1015,
+            Imxrt::Imxrt1020 => Profile::IMXRT1020,
+            Imxrt::Imxrt1024 => Profile::IMXRT1024,
+            Imxrt::Imxrt1040 => Profile::IMXRT1040,
+            Imxrt::Imxrt1050 => Profile::IMXRT1050,
+            Imxrt::Imxrt1060 => Profile::IMXRT1060,
+            Imxrt::Imxrt1064 => Profile::IMXRT1064,
+            Imxrt::Imxrt1170 => Profile::IMXRT1170,
+            Imxrt::Imxrt1180 => Profile::IMXRT1180,
+            Imxrt::Imxrt500 => Profile::IMXRT500,
+            Imxrt::Imxrt600 => Profile::IMXRT600,
+        }
+    }
+}
+
+impl ConfigurationBlock {
+    /// Apply all fields from the profile.
+    ///
+    /// Call this before the setters that override the profile's fields.
+    pub const fn profile(self, profile: Profile) -> Self {
+        self.version(profile.version)
+            .read_sample_clk_src(profile.read_sample_clk_src)
+            .cs_hold_time(profile.cs_hold_time)
+            .cs_setup_time(profile.cs_setup_time)
+            .controller_misc_options(profile.controller_misc_options)
+    }
+}
+
+/// A trade-off between read performance and compatibility
+///
+/// Each performance profile sets the read sampling configuration and the related
+/// controller options together. Apply a performance profile with
+/// [`ConfigurationBlock::performance`].
+///
+/// These profiles are starting points derived from NXP's EVK configurations;
+/// they're not tuned for your board. They haven't been measured against each
+/// other on real boards.
+///
+/// The throughput profiles don't set any `controllerMiscOption` bits. The
+/// options that affect reads, like DDR mode and word addressing, depend on the
+/// flash device, so set them for your device. The configuration block doesn't
+/// have an AHB prefetch setting; the boot ROM and your application's FlexSPI
+/// driver configure prefetch.
+///
+/// Confirm that your board reliably boots and executes in place before shipping
+/// a profile other than `MaximumCompatibility`.
+///
+/// ```
+/// use imxrt_boot_gen::flexspi::{ConfigurationBlock, LookupTable, Performance, SerialClockFrequency};
+///
+/// const FLEXSPI_CONFIGURATION_BLOCK: ConfigurationBlock =
+///     ConfigurationBlock::new(LookupTable::new())
+///         .performance(Performance::MaximumThroughput)
+///         .serial_clk_freq(SerialClockFrequency::MHz133);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Performance {
+    /// Favor booting on any board, at the cost of read throughput.
+    ///
+    /// Samples reads with the internal loopback and runs the serial clock at
+    /// 30MHz. On chips whose [`Profile`] enables the safe configuration
+    /// frequency, this also enables the safe configuration frequency; it's left
+    /// alone on the 1010, 1015, 1020, and 1024. The internal loopback doesn't
+    /// compensate for board and pad delays, so it only works at low serial clock
+    /// frequencies.
+    MaximumCompatibility,
+    /// Favor execute-in-place throughput.
+    ///
+    /// Samples reads with the loopback from the DQS pad, which compensates for pad
+    /// delays and supports higher serial clock frequencies. Choose the serial clock
+    /// frequency separately; it should be the fastest frequency that your flash
+    /// device and board support. Leave the DQS pad unconnected when you use this
+    /// profile.
+    MaximumThroughput,
+    /// Favor execute-in-place throughput for devices that drive DQS.
+    ///
+    /// Samples reads with the DQS signal provided by the flash device. This is
+    /// typical for octal and HyperFlash devices. Choose the serial clock frequency
+    /// separately.
+    FlashProvidedDqs,
+}
+
+impl ConfigurationBlock {
+    /// Apply the performance profile.
+    ///
+    /// The profile sets `readSampleClkSrc`, and it may set `serialClkFreq` and
+    /// some `controllerMiscOption` bits. It doesn't clear any other
+    /// `controllerMiscOption` bits.
+    pub const fn performance(mut self, performance: Performance) -> Self {
+        match performance {
+            Performance::MaximumCompatibility => {
+                self.controller_misc_options |= Profile::for_chip(Imxrt::SELECTED)
+                    .controller_misc_options
+                    & SAFE_CONFIG_FREQ_ENABLE;
+                self.read_sample_clk_src(ReadSampleClockSource::InternalLoopback)
+                    .serial_clk_freq(SerialClockFrequency::MHz30)
+            }
+            Performance::MaximumThroughput => {
+                self.read_sample_clk_src(ReadSampleClockSource::LoopbackFromDQSPad)
+            }
+            Performance::FlashProvidedDqs => {
+                self.read_sample_clk_src(ReadSampleClockSource::FlashProvidedDQS)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{ConfigurationBlock, Performance, Profile, ReadSampleClockSource};
+    use crate::flexspi::LookupTable;
+    use crate::Imxrt;
+
+    #[test]
+    fn performance_keeps_misc_options() {
+        let block = ConfigurationBlock::new(LookupTable::new())
+            .controller_misc_options(1 << 6)
+            .performance(Performance::MaximumCompatibility);
+        let safe_config_freq = if matches!(
+            Imxrt::SELECTED,
+            Imxrt::Imxrt1010 | Imxrt::Imxrt1015 | Imxrt::Imxrt1020 | Imxrt::Imxrt1024
+        ) {
+            0
+        } else {
+            1 << 4
+        };
+        assert_eq!(block.controller_misc_options, (1 << 6) | safe_config_freq);
+        assert_eq!(
+            block.read_sample_clk_src,
+            ReadSampleClockSource::InternalLoopback
+        );
+    }
+
+    #[test]
+    fn throughput_keeps_misc_options() {
+        for performance in [
+            Performance::MaximumThroughput,
+            Performance::FlashProvidedDqs,
+        ] {
+            let block = ConfigurationBlock::new(LookupTable::new())
+                .profile(Profile::for_chip(Imxrt::SELECTED))
+                .performance(performance);
+            assert_eq!(
+                block.controller_misc_options,
+                Profile::for_chip(Imxrt::SELECTED).controller_misc_options
+            );
+        }
+    }
+}
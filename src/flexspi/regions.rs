@@ -0,0 +1,161 @@
+//! Serial flash regions, and their chip selects
+
+use super::{ConfigurationBlock, Density, SerialFlashRegion};
+use crate::Imxrt;
+
+/// The densities of all four serial flash regions
+///
+/// Each region is one flash device, selected by one FlexSPI chip select:
+///
+/// | Region | Port | Chip select       |
+/// | ------ | ---- | ----------------- |
+/// | A1     | A    | `FLEXSPI_A_SS0_B` |
+/// | A2     | A    | `FLEXSPI_A_SS1_B` |
+/// | B1     | B    | `FLEXSPI_B_SS0_B` |
+/// | B2     | B    | `FLEXSPI_B_SS1_B` |
+///
+/// The FlexSPI controller maps the regions into the chip's FlexSPI address
+/// window in that order, with no gaps. A region with a zero density takes no
+/// addresses, and the next region starts where it would have started. The
+/// boot ROM only boots from A1.
+///
+/// Every time you set a region's density, `FlashRegions` checks that the
+/// regions fit in the chip's [FlexSPI window](Imxrt::flexspi_window). If the
+/// chip's window size isn't known, there's no check. Apply the regions with
+/// [`ConfigurationBlock::flash_regions`].
+///
+/// ```
+/// use imxrt_boot_gen::flexspi::{Density, FlashRegions, SerialFlashRegion};
+/// use imxrt_boot_gen::Imxrt;
+///
+/// const REGIONS: FlashRegions = FlashRegions::new(Imxrt::SELECTED)
+///     .density(SerialFlashRegion::A1, Density::from_mebibytes(16))
+///     .density(SerialFlashRegion::B1, Density::from_mebibytes(8));
+///
+/// // B1 follows A1, since A2 is empty.
+/// assert_eq!(
+///     REGIONS.address(SerialFlashRegion::B1),
+///     Imxrt::SELECTED.flexspi_base() + 16 * 1024 * 1024
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FlashRegions {
+    chip: Imxrt,
+    densities: [Density; 4],
+}
+
+impl FlashRegions {
+    /// Describe the `chip`'s flash regions, all with a zero density.
+    pub const fn new(chip: Imxrt) -> Self {
+        FlashRegions {
+            chip,
+            densities: [Density::from_bytes(0); 4],
+        }
+    }
+    /// Set the `region`'s density.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the regions don't fit in the chip's FlexSPI window. When
+    /// evaluated in a `const` context, the panic is a compile-time error.
+    pub const fn density(mut self, region: SerialFlashRegion, density: Density) -> Self {
+        self.densities[region as usize] = density;
+        if let Some(window) = self.chip.flexspi_window() {
+            assert!(
+                self.total_bytes() <= window.bytes() as u64,
+                "The flash regions don't fit in the chip's FlexSPI window"
+            );
+        }
+        self
+    }
+    /// Returns the `region`'s density.
+    pub const fn get_density(&self, region: SerialFlashRegion) -> Density {
+        self.densities[region as usize]
+    }
+    /// Returns the chip.
+    pub const fn get_chip(&self) -> Imxrt {
+        self.chip
+    }
+    /// Returns the total size of all regions, in bytes.
+    pub const fn total_bytes(&self) -> u64 {
+        let mut total = 0;
+        let mut idx = 0;
+        while idx < self.densities.len() {
+            total += self.densities[idx].bytes() as u64;
+            idx += 1;
+        }
+        total
+    }
+    /// Returns the address of the `region`'s first byte.
+    ///
+    /// If the region's density is zero, this is the address of the next
+    /// populated region.
+    pub const fn address(&self, region: SerialFlashRegion) -> u32 {
+        let mut address = self.chip.flexspi_base();
+        let mut idx = 0;
+        while idx < region as usize {
+            address += self.densities[idx].bytes();
+            idx += 1;
+        }
+        address
+    }
+}
+
+impl ConfigurationBlock {
+    /// Set the flash sizes of all four regions.
+    pub const fn flash_regions(self, regions: FlashRegions) -> Self {
+        self.flash_size(SerialFlashRegion::A1, regions.densities[0])
+            .flash_size(SerialFlashRegion::A2, regions.densities[1])
+            .flash_size(SerialFlashRegion::B1, regions.densities[2])
+            .flash_size(SerialFlashRegion::B2, regions.densities[3])
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::FlashRegions;
+    use crate::flexspi::{ConfigurationBlock, Density, LookupTable, SerialFlashRegion};
+    use crate::Imxrt;
+
+    #[test]
+    fn addresses() {
+        let regions = FlashRegions::new(Imxrt::Imxrt1060)
+            .density(SerialFlashRegion::A1, Density::from_mebibytes(8))
+            .density(SerialFlashRegion::A2, Density::from_mebibytes(4))
+            .density(SerialFlashRegion::B2, Density::from_mebibytes(16));
+        assert_eq!(regions.address(SerialFlashRegion::A1), 0x6000_0000);
+        assert_eq!(regions.address(SerialFlashRegion::A2), 0x6080_0000);
+        assert_eq!(regions.address(SerialFlashRegion::B1), 0x60C0_0000);
+        assert_eq!(regions.address(SerialFlashRegion::B2), 0x60C0_0000);
+        assert_eq!(regions.total_bytes(), 28 * 1024 * 1024);
+
+        let block = ConfigurationBlock::new(LookupTable::new()).flash_regions(regions);
+        assert_eq!(
+            block.get_flash_size(SerialFlashRegion::A2),
+            Density::from_mebibytes(4)
+        );
+        assert_eq!(
+            block.get_flash_size(SerialFlashRegion::B1),
+            Density::from_bytes(0)
+        );
+        assert_eq!(
+            block.get_flash_size(SerialFlashRegion::B2),
+            Density::from_mebibytes(16)
+        );
+    }
+
+    #[test]
+    fn unknown_window() {
+        let regions = FlashRegions::new(Imxrt::Imxrt1010)
+            .density(SerialFlashRegion::A1, Density::from_mebibytes(512));
+        assert_eq!(regions.total_bytes(), 512 * 1024 * 1024);
+    }
+
+    #[test]
+    #[should_panic(expected = "FlexSPI window")]
+    fn too_large_for_window() {
+        let _ = FlashRegions::new(Imxrt::Imxrt1170)
+            .density(SerialFlashRegion::A1, Density::from_mebibytes(128))
+            .density(SerialFlashRegion::B1, Density::from_mebibytes(256));
+    }
+}
@@ -13,10 +13,20 @@ pub(crate) const INSTRUCTION_SIZE: usize = 2;
 /// Opcodes are available in the [`opcode` module](opcodes/index.html).
 ///
 /// `Instr`s are used to create FlexSPI lookup table command [`Sequence`s](struct.Sequence.html).
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "zerocopy",
+    derive(zerocopy::IntoBytes, zerocopy::Immutable, zerocopy::FromBytes)
+)]
 #[repr(transparent)]
 pub struct Instr([u8; INSTRUCTION_SIZE]);
 
+// Safety: an instruction is two bytes, and any two bytes are an instruction.
+#[cfg(feature = "bytemuck")]
+unsafe impl bytemuck::Zeroable for Instr {}
+#[cfg(feature = "bytemuck")]
+unsafe impl bytemuck::Pod for Instr {}
+
 impl Instr {
     /// Create a new FlexSPI LUT instruction
     ///
@@ -27,12 +37,55 @@ impl Instr {
         Instr([operand, (opcode.0 << 2) | (pads as u8)])
     }
 
+    /// Returns the raw pad count bits, or `None` for STOP and JUMP_ON_CS
+    /// instructions, which don't use pads.
+    pub(crate) const fn pads(self) -> Option<u8> {
+        let opcode = self.0[1] >> 2;
+        if opcode == opcodes::STOP.0 || opcode == opcodes::JUMP_ON_CS.0 {
+            None
+        } else {
+            Some(self.pad_bits())
+        }
+    }
+
+    /// Returns the raw pad count bits, even for STOP and JUMP_ON_CS
+    /// instructions.
+    pub(crate) const fn pad_bits(self) -> u8 {
+        self.0[1] & 0b11
+    }
+
+    /// Returns the opcode.
+    pub(crate) const fn opcode(self) -> Opcode {
+        Opcode(self.0[1] >> 2)
+    }
+
+    /// Returns the operand.
+    pub(crate) const fn operand(self) -> u8 {
+        self.0[0]
+    }
+
     const fn stop() -> Self {
         Instr::new(opcodes::STOP, Pads::One /* unused */, 0)
     }
 
-    const fn jump_on_cs() -> Self {
-        Instr::new(opcodes::JUMP_ON_CS, Pads::One /* unused */, 0)
+    /// Create a `JUMP_ON_CS` instruction that jumps to the `index`th
+    /// instruction of the sequence.
+    ///
+    /// When chip select deasserts, the next transfer that uses the sequence
+    /// starts at `index`, instead of the first instruction. A continuous read
+    /// uses this to skip the flash's read command. The
+    /// [`JUMP_ON_CS`](constant.JUMP_ON_CS.html) constant jumps to the first
+    /// instruction.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` isn't the index of an instruction in a sequence.
+    pub const fn jump_on_cs(index: u8) -> Self {
+        assert!(
+            (index as usize) < INSTRUCTIONS_PER_SEQUENCE,
+            "A sequence has eight instructions"
+        );
+        Instr::new(opcodes::JUMP_ON_CS, Pads::One /* unused */, index)
     }
 }
 
@@ -43,10 +96,31 @@ impl fmt::Debug for Instr {
     }
 }
 
+/// Formats the instruction like `CMD_SDR SINGLE 0xEB`.
+///
+/// `STOP` and `JUMP_ON_CS` instructions don't show their pads, unless the pad
+/// bits are set.
+impl fmt::Display for Instr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let opcode = self.opcode();
+        let pads = match self.pad_bits() {
+            0 => Pads::One,
+            1 => Pads::Two,
+            2 => Pads::Four,
+            _ => Pads::Eight,
+        };
+        if self.pads().is_none() && self.pad_bits() == 0 {
+            write!(f, "{} {:#04X}", opcode, self.operand())
+        } else {
+            write!(f, "{} {} {:#04X}", opcode, pads, self.operand())
+        }
+    }
+}
+
 /// STOP FlexSPI instruction
 pub const STOP: Instr = Instr::stop();
 /// JUMP_ON_CS FlexSPI instruction
-pub const JUMP_ON_CS: Instr = Instr::jump_on_cs();
+pub const JUMP_ON_CS: Instr = Instr::jump_on_cs(0);
 
 pub(crate) const INSTRUCTIONS_PER_SEQUENCE: usize = 8;
 
@@ -57,15 +131,71 @@ pub(crate) const INSTRUCTIONS_PER_SEQUENCE: usize = 8;
 /// you're interacting with.
 ///
 /// `Sequence`s are used to create a [`LookupTable`](crate::flexspi::LookupTable).
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "zerocopy",
+    derive(zerocopy::IntoBytes, zerocopy::Immutable, zerocopy::FromBytes)
+)]
 #[repr(transparent)]
 pub struct Sequence(pub(crate) [Instr; INSTRUCTIONS_PER_SEQUENCE]);
 pub(crate) const SEQUENCE_SIZE: usize = INSTRUCTIONS_PER_SEQUENCE * INSTRUCTION_SIZE;
 
+// Safety: a sequence is an array of instructions, which are `Pod`.
+#[cfg(feature = "bytemuck")]
+unsafe impl bytemuck::Zeroable for Sequence {}
+#[cfg(feature = "bytemuck")]
+unsafe impl bytemuck::Pod for Sequence {}
+
 impl Sequence {
     pub(crate) const fn stopped() -> Self {
         Sequence([STOP; INSTRUCTIONS_PER_SEQUENCE])
     }
+
+    pub(crate) fn from_bytes(bytes: &[u8; SEQUENCE_SIZE]) -> Self {
+        let mut sequence = Sequence::stopped();
+        for (instr, raw) in sequence
+            .0
+            .iter_mut()
+            .zip(bytes.chunks_exact(INSTRUCTION_SIZE))
+        {
+            *instr = Instr([raw[0], raw[1]]);
+        }
+        sequence
+    }
+
+    pub(crate) const fn to_bytes(self) -> [u8; SEQUENCE_SIZE] {
+        let mut bytes = [0; SEQUENCE_SIZE];
+        let mut idx = 0;
+        while idx < INSTRUCTIONS_PER_SEQUENCE {
+            bytes[idx * INSTRUCTION_SIZE] = self.0[idx].0[0];
+            bytes[idx * INSTRUCTION_SIZE + 1] = self.0[idx].0[1];
+            idx += 1;
+        }
+        bytes
+    }
+}
+
+/// Formats the instructions up to the first `STOP`, separated by `; `.
+///
+/// A sequence of only `STOP`s formats as `STOP`.
+impl fmt::Display for Sequence {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let count = self
+            .0
+            .iter()
+            .position(|instr| *instr == STOP)
+            .unwrap_or(INSTRUCTIONS_PER_SEQUENCE);
+        if count == 0 {
+            return write!(f, "STOP");
+        }
+        for (idx, instr) in self.0[..count].iter().enumerate() {
+            if idx > 0 {
+                write!(f, "; ")?;
+            }
+            write!(f, "{}", instr)?;
+        }
+        Ok(())
+    }
 }
 
 /// A [`Sequence`] builder
@@ -259,44 +389,136 @@ pub mod opcodes {
     }
 }
 
+/// Every opcode, and its name in NXP's headers.
+const OPCODE_NAMES: [(Opcode, &str); 28] = {
+    use opcodes::{ddr, sdr};
+    [
+        // SDR
+        (sdr::CMD, "CMD_SDR"),
+        (sdr::RADDR, "RADDR_SDR"),
+        (sdr::CADDR, "CADDR_SDR"),
+        (sdr::MODE1, "MODE1_SDR"),
+        (sdr::MODE2, "MODE2_SDR"),
+        (sdr::MODE4, "MODE4_SDR"),
+        (sdr::MODE8, "MODE8_SDR"),
+        (sdr::WRITE, "WRITE_SDR"),
+        (sdr::READ, "READ_SDR"),
+        (sdr::LEARN, "LEARN_SDR"),
+        (sdr::DATASZ, "DATASZ_SDR"),
+        (sdr::DUMMY, "DUMMY_SDR"),
+        (sdr::DUMMY_RWDS, "DUMMY_RWDS_SDR"),
+        // DDR
+        (ddr::CMD, "CMD_DDR"),
+        (ddr::RADDR, "RADDR_DDR"),
+        (ddr::CADDR, "CADDR_DDR"),
+        (ddr::MODE1, "MODE1_DDR"),
+        (ddr::MODE2, "MODE2_DDR"),
+        (ddr::MODE4, "MODE4_DDR"),
+        (ddr::MODE8, "MODE8_DDR"),
+        (ddr::WRITE, "WRITE_DDR"),
+        (ddr::READ, "READ_DDR"),
+        (ddr::LEARN, "LEARN_DDR"),
+        (ddr::DATASZ, "DATASZ_DDR"),
+        (ddr::DUMMY, "DUMMY_DDR"),
+        (ddr::DUMMY_RWDS, "DUMMY_RWDS_DDR"),
+        // Others
+        (opcodes::STOP, "STOP"),
+        (opcodes::JUMP_ON_CS, "JUMP_ON_CS"),
+    ]
+};
+
+#[cfg(feature = "serde")]
+impl Instr {
+    /// Parse an instruction that's formatted like its `Display` output.
+    ///
+    /// The operand may be decimal or hexadecimal, and `STOP` and `JUMP_ON_CS`
+    /// may omit it.
+    pub(crate) fn parse(text: &str) -> Option<Self> {
+        let mut words = text.split_whitespace();
+        let name = words.next()?;
+        let opcode = match OPCODE_NAMES.iter().find(|(_, other)| *other == name) {
+            Some((opcode, _)) => *opcode,
+            // Like the Display output for an opcode without a name.
+            None => {
+                let raw = name.strip_prefix("UNKNOWN(0x")?.strip_suffix(')')?;
+                let raw = u8::from_str_radix(raw, 16).ok()?;
+                if raw > 0x3F {
+                    return None;
+                }
+                Opcode(raw)
+            }
+        };
+        let no_pads = opcode == opcodes::STOP || opcode == opcodes::JUMP_ON_CS;
+        let mut words = words.peekable();
+        let pads = match words.peek().copied() {
+            Some("SINGLE") => Some(Pads::One),
+            Some("DUAL") => Some(Pads::Two),
+            Some("QUAD") => Some(Pads::Four),
+            Some("OCTAL") => Some(Pads::Eight),
+            _ => None,
+        };
+        if pads.is_some() {
+            words.next();
+        }
+        let pads = match pads {
+            Some(pads) => pads,
+            None if no_pads => Pads::One, /* unused */
+            None => return None,
+        };
+        let operand = match words.next() {
+            None if no_pads => 0,
+            None => return None,
+            Some(operand) => match operand.strip_prefix("0x").or(operand.strip_prefix("0X")) {
+                Some(hex) => u8::from_str_radix(hex, 16).ok()?,
+                None => operand.parse().ok()?,
+            },
+        };
+        if words.next().is_some() {
+            return None;
+        }
+        Some(Instr::new(opcode, pads, operand))
+    }
+}
+
+impl Opcode {
+    /// Create an opcode from its raw, six-bit value
+    ///
+    /// Prefer the named opcodes in the [`opcodes`] module. Use `from_raw` for
+    /// an opcode that the module doesn't name.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `raw` doesn't fit in six bits.
+    pub const fn from_raw(raw: u8) -> Self {
+        assert!(raw <= 0x3F, "An opcode has six bits");
+        Opcode(raw)
+    }
+
+    /// Returns the opcode's raw, six-bit value.
+    pub const fn raw(self) -> u8 {
+        self.0
+    }
+
+    /// Returns the opcode's name in NXP's headers, or `None` if the opcode
+    /// isn't known.
+    pub(crate) fn name(self) -> Option<&'static str> {
+        OPCODE_NAMES
+            .iter()
+            .find(|(opcode, _)| *opcode == self)
+            .map(|(_, name)| *name)
+    }
+
+    /// Returns `true` if the opcodes are equal, in a `const` context.
+    pub(crate) const fn const_eq(self, other: Opcode) -> bool {
+        self.0 == other.0
+    }
+}
+
 impl fmt::Display for Opcode {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        use opcodes::ddr;
-        use opcodes::sdr;
-        match *self {
-            // SDR
-            sdr::CMD => write!(f, "CMD_SDR"),
-            sdr::RADDR => write!(f, "RADDR_SDR"),
-            sdr::CADDR => write!(f, "CADDR_SDR"),
-            sdr::MODE1 => write!(f, "MODE1_SDR"),
-            sdr::MODE2 => write!(f, "MODE2_SDR"),
-            sdr::MODE4 => write!(f, "MODE4_SDR"),
-            sdr::MODE8 => write!(f, "MODE8_SDR"),
-            sdr::WRITE => write!(f, "WRITE_SDR"),
-            sdr::READ => write!(f, "READ_SDR"),
-            sdr::LEARN => write!(f, "LEARN_SDR"),
-            sdr::DATASZ => write!(f, "DATASZ_SDR"),
-            sdr::DUMMY => write!(f, "DUMMY_SDR"),
-            sdr::DUMMY_RWDS => write!(f, "DUMMY_RWDS_SDR"),
-            // DDR
-            ddr::CMD => write!(f, "CMD_DDR"),
-            ddr::RADDR => write!(f, "RADDR_DDR"),
-            ddr::CADDR => write!(f, "CADDR_DDR"),
-            ddr::MODE1 => write!(f, "MODE1_DDR"),
-            ddr::MODE2 => write!(f, "MODE2_DDR"),
-            ddr::MODE4 => write!(f, "MODE4_DDR"),
-            ddr::MODE8 => write!(f, "MODE8_DDR"),
-            ddr::WRITE => write!(f, "WRITE_DDR"),
-            ddr::READ => write!(f, "READ_DDR"),
-            ddr::LEARN => write!(f, "LEARN_DDR"),
-            ddr::DATASZ => write!(f, "DATASZ_DDR"),
-            ddr::DUMMY => write!(f, "DUMMY_DDR"),
-            ddr::DUMMY_RWDS => write!(f, "DUMMY_RWDS_DDR"),
-            // Others
-            opcodes::STOP => write!(f, "STOP"),
-            opcodes::JUMP_ON_CS => write!(f, "JUMP_ON_CS"),
-            // Should be unreachable
-            unknown => write!(f, "UNKNOWN({:#02X})", unknown.0),
+        match self.name() {
+            Some(name) => write!(f, "{}", name),
+            None => write!(f, "UNKNOWN({:#02X})", self.0),
         }
     }
 }
@@ -355,6 +577,28 @@ mod test {
         assert_eq!(&seq_to_bytes(SEQUENCE)[0..4], &EXPECTED);
     }
 
+    #[test]
+    fn display() {
+        const SEQUENCE: Sequence = SequenceBuilder::new()
+            .instr(Instr::new(CMD, Pads::One, 0xEB))
+            .instr(Instr::new(RADDR, Pads::Four, 0x18))
+            .instr(super::JUMP_ON_CS)
+            .build();
+        assert_eq!(
+            format!("{SEQUENCE}"),
+            "CMD_SDR SINGLE 0xEB; RADDR_SDR QUAD 0x18; JUMP_ON_CS 0x00"
+        );
+        assert_eq!(format!("{}", Sequence::stopped()), "STOP");
+    }
+
+    #[test]
+    fn jump_on_cs() {
+        assert_eq!(Instr::jump_on_cs(0), super::JUMP_ON_CS);
+        // Opcode 0x1F, jump to the second instruction.
+        assert_eq!(Instr::jump_on_cs(1).0, [0x01, 0x7C]);
+        assert_eq!(format!("{}", Instr::jump_on_cs(1)), "JUMP_ON_CS 0x01");
+    }
+
     #[test]
     fn teensy4_write_enable() {
         const EXPECTED: u128 = 0x0000_0406;
@@ -0,0 +1,242 @@
+//! Boot fuse and strapping advice
+//!
+//! The boot ROM reads a few settings from fuses, or from the `BOOT_CFG` pins,
+//! before it reads your configuration block. Those settings must agree with
+//! the block: the ROM must select FlexSPI serial NOR boot, and it must probe
+//! the flash with the right kind of read. [`BootFuses`] derives the settings
+//! that agree with a serial NOR configuration block.
+//!
+//! The settings are named, not encoded. Each chip places them at different
+//! fuse bits and `BOOT_CFG` pins, and this module doesn't carry those tables;
+//! see your chip's reference manual's fuse map to translate a setting into
+//! bits. Only [`BootMode`] has values, since its encoding is shared by every
+//! supported chip.
+//!
+//! The module doesn't advise on the flash hold time, the delay between reset
+//! and the ROM's first read. The configuration block doesn't describe it, so
+//! choose it from your flash's power-up timing.
+//!
+//! ```
+//! use imxrt_boot_gen::flexspi::{self, LookupTable};
+//! use imxrt_boot_gen::fuses::{BootDevice, BootFuses, FlashType};
+//! use imxrt_boot_gen::serial_flash::nor;
+//! use imxrt_boot_gen::Imxrt;
+//!
+//! # const FCB: nor::ConfigurationBlock = nor::ConfigurationBlock::new(flexspi::ConfigurationBlock::new(LookupTable::new()));
+//! const FUSES: BootFuses = BootFuses::from_nor(Imxrt::Imxrt1060, &FCB);
+//!
+//! assert_eq!(FUSES.get_boot_device(), BootDevice::SerialNor);
+//! assert_eq!(FUSES.get_flash_type(), FlashType::ThreeByteAddress);
+//! println!("{FUSES}");
+//! ```
+
+use core::fmt;
+
+use crate::flexspi::{
+    opcodes::{ddr, sdr},
+    ColumnAddressWidth, Command, FlashPadType, SerialClockFrequency,
+};
+use crate::serial_flash::nor;
+use crate::Imxrt;
+
+/// The device that the boot ROM boots from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BootDevice {
+    /// Serial NOR flash, on FlexSPI.
+    SerialNor,
+}
+
+/// The kind of serial NOR flash that the boot ROM probes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FlashType {
+    /// A flash that reads with three address bytes.
+    ThreeByteAddress,
+    /// A flash that reads with four address bytes.
+    FourByteAddress,
+    /// HyperFlash.
+    HyperFlash,
+    /// An octal flash.
+    ///
+    /// The fuses also select the flash vendor's octal protocol, which the
+    /// configuration block doesn't describe.
+    Octal,
+}
+
+/// The `BOOT_MODE` pin or fuse setting
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum BootMode {
+    /// Boot from the fuses.
+    BootFromFuses = 0b00,
+    /// Wait for a serial download.
+    SerialDownloader = 0b01,
+    /// Boot using the `BOOT_CFG` pins, or the fuses that override them.
+    InternalBoot = 0b10,
+}
+
+/// Boot settings that agree with a configuration block
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BootFuses {
+    chip: Imxrt,
+    boot_device: BootDevice,
+    flash_type: FlashType,
+    frequency: SerialClockFrequency,
+}
+
+impl BootFuses {
+    /// Derive the boot settings for a serial NOR configuration block.
+    ///
+    /// The flash type comes from the block's column address width, its pad
+    /// type, and the address width of its read sequence.
+    pub const fn from_nor(chip: Imxrt, fcb: &nor::ConfigurationBlock) -> Self {
+        let mem_cfg = fcb.mem_cfg();
+        let flash_type = match (
+            mem_cfg.get_column_address_width(),
+            mem_cfg.get_serial_flash_pad_type(),
+        ) {
+            (ColumnAddressWidth::Hyperflash, _) => FlashType::HyperFlash,
+            (_, FlashPadType::Octal) => FlashType::Octal,
+            _ if read_address_bits(mem_cfg.get_lookup_table()) == 32 => FlashType::FourByteAddress,
+            _ => FlashType::ThreeByteAddress,
+        };
+        BootFuses {
+            chip,
+            boot_device: BootDevice::SerialNor,
+            flash_type,
+            frequency: mem_cfg.get_serial_clk_freq(),
+        }
+    }
+    /// Returns the chip.
+    pub const fn get_chip(&self) -> Imxrt {
+        self.chip
+    }
+    /// Returns the boot device.
+    pub const fn get_boot_device(&self) -> BootDevice {
+        self.boot_device
+    }
+    /// Returns the kind of flash that the boot ROM probes.
+    pub const fn get_flash_type(&self) -> FlashType {
+        self.flash_type
+    }
+    /// Returns the serial clock frequency that the configuration block
+    /// selects.
+    pub const fn get_frequency(&self) -> SerialClockFrequency {
+        self.frequency
+    }
+    /// Returns the `BOOT_MODE` setting for booting from the configuration
+    /// block with the `BOOT_CFG` pins, or `None` if the chip's boot modes
+    /// differ.
+    ///
+    /// Once you program the boot fuses, use [`BootMode::BootFromFuses`].
+    pub const fn boot_mode(&self) -> Option<BootMode> {
+        match self.chip {
+            Imxrt::Imxrt1180 | Imxrt::Imxrt500 | Imxrt::Imxrt600 => None,
+            _ => Some(BootMode::InternalBoot),
+        }
+    }
+}
+
+/// Returns the operand of the first address instruction in the read
+/// sequence, or zero if there isn't one.
+const fn read_address_bits(lookup_table: &crate::flexspi::LookupTable) -> u8 {
+    let read = lookup_table.get_command(Command::Read);
+    let mut idx = 0;
+    while idx < read.0.len() {
+        let instr = read.0[idx];
+        let opcode = instr.opcode();
+        if opcode.const_eq(sdr::RADDR) || opcode.const_eq(ddr::RADDR) {
+            return instr.operand();
+        }
+        idx += 1;
+    }
+    0
+}
+
+/// Prints the settings as a table.
+impl fmt::Display for BootFuses {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Setting      | Value")?;
+        writeln!(f, "------------ | -----")?;
+        writeln!(f, "Chip         | {:?}", self.chip)?;
+        if let Some(boot_mode) = self.boot_mode() {
+            writeln!(
+                f,
+                "BOOT_MODE    | {:?} ({:#04b})",
+                boot_mode, boot_mode as u8
+            )?;
+        }
+        writeln!(f, "Boot device  | {:?}", self.boot_device)?;
+        writeln!(f, "Flash type   | {:?}", self.flash_type)?;
+        writeln!(f, "Frequency    | {} MHz", self.frequency.mhz())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{BootFuses, BootMode, FlashType};
+    use crate::flexspi::{
+        self, opcodes::sdr::*, ColumnAddressWidth, Command, FlashPadType, Instr, LookupTable, Pads,
+        SequenceBuilder, SerialClockFrequency,
+    };
+    use crate::serial_flash::nor;
+    use crate::Imxrt;
+
+    const fn fcb(address_bits: u8) -> nor::ConfigurationBlock {
+        let lookup_table = LookupTable::new().command(
+            Command::Read,
+            SequenceBuilder::new()
+                .instr(Instr::new(CMD, Pads::One, 0xEC))
+                .instr(Instr::new(RADDR, Pads::Four, address_bits))
+                .instr(Instr::new(READ, Pads::Four, 0x04))
+                .build(),
+        );
+        nor::ConfigurationBlock::new(
+            flexspi::ConfigurationBlock::new(lookup_table)
+                .serial_flash_pad_type(FlashPadType::Quad)
+                .serial_clk_freq(SerialClockFrequency::MHz100),
+        )
+    }
+
+    #[test]
+    fn address_width() {
+        let fuses = BootFuses::from_nor(Imxrt::Imxrt1060, &fcb(24));
+        assert_eq!(fuses.get_flash_type(), FlashType::ThreeByteAddress);
+        assert_eq!(fuses.get_frequency(), SerialClockFrequency::MHz100);
+        assert_eq!(fuses.boot_mode(), Some(BootMode::InternalBoot));
+
+        let fuses = BootFuses::from_nor(Imxrt::Imxrt1060, &fcb(32));
+        assert_eq!(fuses.get_flash_type(), FlashType::FourByteAddress);
+    }
+
+    #[test]
+    fn hyperflash_and_octal() {
+        let block = fcb(24);
+        let hyperflash = block.with_mem_cfg(
+            block
+                .mem_cfg()
+                .column_address_width(ColumnAddressWidth::Hyperflash),
+        );
+        assert_eq!(
+            BootFuses::from_nor(Imxrt::Imxrt1050, &hyperflash).get_flash_type(),
+            FlashType::HyperFlash
+        );
+
+        let octal = block.with_mem_cfg(block.mem_cfg().serial_flash_pad_type(FlashPadType::Octal));
+        assert_eq!(
+            BootFuses::from_nor(Imxrt::Imxrt1170, &octal).get_flash_type(),
+            FlashType::Octal
+        );
+    }
+
+    #[test]
+    fn table() {
+        let fuses = BootFuses::from_nor(Imxrt::Imxrt1060, &fcb(24));
+        let table = format!("{fuses}");
+        assert!(table.contains("BOOT_MODE    | InternalBoot (0b10)"));
+        assert!(table.contains("Flash type   | ThreeByteAddress"));
+        assert!(table.contains("Frequency    | 100 MHz"));
+
+        let fuses = BootFuses::from_nor(Imxrt::Imxrt1180, &fcb(24));
+        assert!(!format!("{fuses}").contains("BOOT_MODE"));
+    }
+}
@@ -0,0 +1,279 @@
+//! High assurance boot (HAB) signing support
+//!
+//! A signed image has a command sequence file (CSF) after the program. The IVT
+//! points to the CSF, and the boot data length covers it, so that the boot ROM
+//! loads and authenticates the image. A signing tool, like NXP's CST, writes
+//! the CSF into space that you reserve in the image.
+//!
+//! Reserve the CSF space with a [`CsfRegion`], and point the IVT to it.
+//!
+//! ```no_run
+//! use imxrt_boot_gen::hab::CsfRegion;
+//! use imxrt_boot_gen::ivt::{Ivt, Pointer};
+//!
+//! # unsafe extern "C" fn reset() -> ! { loop {} }
+//! #[link_section = ".csf"]
+//! static CSF: CsfRegion = CsfRegion::new();
+//!
+//! #[link_section = ".ivt"]
+//! static IVT: Ivt = Ivt::new(Pointer::from_reset(reset))
+//!     .csf(Pointer::from_static(&CSF))
+//!     .self_ptr(Pointer::from_static(&IVT));
+//! ```
+//!
+//! If you place the CSF at a fixed offset, describe the image with a
+//! [`SignedImage`]. It computes the CSF address for the IVT, the boot data
+//! length, and the region that the signing tool authenticates.
+//!
+//! For encrypted boot, the signing tool also writes a DEK blob, the wrapped
+//! data encryption key. Reserve space for it with a [`DekBlobRegion`], and
+//! describe its size with [`SignedImage::dek`].
+
+use crate::ivt::Pointer;
+
+/// The default CSF size, in bytes.
+pub const CSF_SIZE: usize = 0x2000;
+
+/// Zeroed space for the CSF
+///
+/// The signing tool replaces the zeros with the CSF. `N` is the size of the
+/// region, in bytes; the default is [`CSF_SIZE`].
+#[derive(Debug, Clone, Copy)]
+#[repr(C, align(4))]
+pub struct CsfRegion<const N: usize = CSF_SIZE>([u8; N]);
+
+impl<const N: usize> CsfRegion<N> {
+    /// Reserve a zeroed CSF region.
+    pub const fn new() -> Self {
+        CsfRegion([0; N])
+    }
+    /// The size of the region, in bytes.
+    pub const SIZE: usize = N;
+}
+
+impl<const N: usize> Default for CsfRegion<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The size of a DEK blob that wraps an AES-128 key, in bytes.
+pub const DEK_BLOB_SIZE: usize = DekSize::Aes128.blob_size() as usize;
+
+/// The size of a data encryption key (DEK)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u32)]
+pub enum DekSize {
+    /// A 128-bit key.
+    Aes128 = 16,
+    /// A 192-bit key.
+    Aes192 = 24,
+    /// A 256-bit key.
+    Aes256 = 32,
+}
+
+impl DekSize {
+    /// Returns the size of the DEK blob that wraps this key, in bytes.
+    ///
+    /// The blob has an 8 byte header, a 32 byte blob key, the wrapped key, and
+    /// a 16 byte MAC.
+    pub const fn blob_size(self) -> u32 {
+        8 + 32 + self as u32 + 16
+    }
+}
+
+/// Zeroed space for the DEK blob
+///
+/// The signing tool, or a provisioning step on the target, replaces the zeros
+/// with the DEK blob. `N` is the size of the region, in bytes; the default is
+/// [`DEK_BLOB_SIZE`].
+#[derive(Debug, Clone, Copy)]
+#[repr(C, align(4))]
+pub struct DekBlobRegion<const N: usize = DEK_BLOB_SIZE>([u8; N]);
+
+impl<const N: usize> DekBlobRegion<N> {
+    /// Reserve a zeroed DEK blob region.
+    pub const fn new() -> Self {
+        DekBlobRegion([0; N])
+    }
+    /// The size of the region, in bytes.
+    pub const SIZE: usize = N;
+}
+
+impl<const N: usize> Default for DekBlobRegion<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The layout of a signed image
+///
+/// Offsets are relative to the start of the image file, which the boot ROM
+/// reads from `image_start`. Unless otherwise specified, the CSF is
+/// [`CSF_SIZE`] bytes, and the image isn't encrypted.
+///
+/// An encrypted image has a DEK blob directly after the CSF region. The boot
+/// data length covers the DEK blob.
+///
+/// ```
+/// use imxrt_boot_gen::hab::SignedImage;
+///
+/// const SIGNED: SignedImage = SignedImage::new(0x6000_0000, 0x1000, 0x2_0000);
+///
+/// assert_eq!(SIGNED.csf_address(), 0x6002_0000);
+/// assert_eq!(SIGNED.boot_data_length(), 0x2_2000);
+/// assert_eq!(SIGNED.signed_region(), (0x6000_1000, 0x1000, 0x1_F000));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SignedImage {
+    image_start: u32,
+    ivt_offset: u32,
+    csf_offset: u32,
+    csf_size: u32,
+    dek: Option<DekSize>,
+}
+
+impl SignedImage {
+    /// Describe a signed image with an IVT at `ivt_offset`, and a CSF at
+    /// `csf_offset`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the CSF doesn't follow the IVT, or if the CSF offset isn't
+    /// word aligned.
+    pub const fn new(image_start: u32, ivt_offset: u32, csf_offset: u32) -> Self {
+        assert!(csf_offset > ivt_offset, "The CSF must follow the IVT");
+        assert!(csf_offset.is_multiple_of(4), "The CSF must be word aligned");
+        SignedImage {
+            image_start,
+            ivt_offset,
+            csf_offset,
+            csf_size: CSF_SIZE as u32,
+            dek: None,
+        }
+    }
+    /// Set the size of the CSF region, in bytes.
+    pub const fn csf_size(mut self, csf_size: u32) -> Self {
+        self.csf_size = csf_size;
+        self
+    }
+    /// Encrypt the image with a DEK of the given size, and reserve space for
+    /// its DEK blob.
+    pub const fn dek(mut self, dek: DekSize) -> Self {
+        self.dek = Some(dek);
+        self
+    }
+    /// Returns the address of the IVT.
+    pub const fn ivt_address(&self) -> u32 {
+        self.image_start + self.ivt_offset
+    }
+    /// Returns the address of the CSF.
+    pub const fn csf_address(&self) -> u32 {
+        self.image_start + self.csf_offset
+    }
+    /// Returns a pointer to the CSF, for [`Ivt::csf`](crate::ivt::Ivt::csf).
+    pub const fn csf_pointer(&self) -> Pointer {
+        Pointer::from_address(self.csf_address())
+    }
+    /// Returns the boot data length, which spans from the start of the image
+    /// through the end of the CSF, or through the end of the DEK blob.
+    pub const fn boot_data_length(&self) -> u32 {
+        self.dek_blob_offset() + self.dek_blob_length()
+    }
+    /// Returns the offset of the DEK blob.
+    ///
+    /// The DEK blob directly follows the CSF region.
+    pub const fn dek_blob_offset(&self) -> u32 {
+        self.csf_offset + self.csf_size
+    }
+    /// Returns the address of the DEK blob.
+    pub const fn dek_blob_address(&self) -> u32 {
+        self.image_start + self.dek_blob_offset()
+    }
+    /// Returns the size of the DEK blob, or zero if the image isn't encrypted.
+    pub const fn dek_blob_length(&self) -> u32 {
+        match self.dek {
+            Some(dek) => dek.blob_size(),
+            None => 0,
+        }
+    }
+    /// Returns the region that the signing tool authenticates, as a start
+    /// address, a file offset, and a length.
+    ///
+    /// The region starts at the IVT, and it ends at the CSF.
+    pub const fn signed_region(&self) -> (u32, u32, u32) {
+        (
+            self.ivt_address(),
+            self.ivt_offset,
+            self.csf_offset - self.ivt_offset,
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl SignedImage {
+    /// Returns the CST `Blocks` line that authenticates the signed region of
+    /// `file`.
+    ///
+    /// This requires the `"std"` feature.
+    ///
+    /// ```
+    /// use imxrt_boot_gen::hab::SignedImage;
+    ///
+    /// let signed = SignedImage::new(0x6000_0000, 0x1000, 0x2_0000);
+    /// assert_eq!(
+    ///     signed.cst_blocks("image.bin"),
+    ///     r#"Blocks = 0x60001000 0x00001000 0x0001F000 "image.bin""#
+    /// );
+    /// ```
+    pub fn cst_blocks(&self, file: &str) -> String {
+        let (address, offset, length) = self.signed_region();
+        format!("Blocks = {address:#010X} {offset:#010X} {length:#010X} \"{file}\"")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{CsfRegion, DekBlobRegion, DekSize, SignedImage, CSF_SIZE, DEK_BLOB_SIZE};
+
+    static CSF: CsfRegion = CsfRegion::new();
+
+    #[test]
+    fn csf_region() {
+        assert_eq!(core::mem::size_of_val(&CSF), CSF_SIZE);
+        assert_eq!(core::mem::align_of_val(&CSF), 4);
+        assert_eq!(CsfRegion::<0x1000>::SIZE, 0x1000);
+    }
+
+    #[test]
+    fn layout() {
+        let signed = SignedImage::new(0x3000_0000, 0x1000, 0x8000).csf_size(0x1000);
+        assert_eq!(signed.ivt_address(), 0x3000_1000);
+        assert_eq!(signed.csf_pointer().as_ptr() as usize, 0x3000_8000);
+        assert_eq!(signed.boot_data_length(), 0x9000);
+        assert_eq!(signed.signed_region(), (0x3000_1000, 0x1000, 0x7000));
+    }
+
+    #[test]
+    fn encrypted_layout() {
+        assert_eq!(DEK_BLOB_SIZE, 72);
+        assert_eq!(DekBlobRegion::<DEK_BLOB_SIZE>::SIZE, 72);
+        assert_eq!(DekSize::Aes256.blob_size(), 88);
+
+        let signed = SignedImage::new(0x6000_0000, 0x1000, 0x2_0000).dek(DekSize::Aes128);
+        assert_eq!(signed.dek_blob_offset(), 0x2_2000);
+        assert_eq!(signed.dek_blob_address(), 0x6002_2000);
+        assert_eq!(signed.dek_blob_length(), 72);
+        assert_eq!(signed.boot_data_length(), 0x2_2048);
+
+        let plain = SignedImage::new(0x6000_0000, 0x1000, 0x2_0000);
+        assert_eq!(plain.dek_blob_length(), 0);
+        assert_eq!(plain.boot_data_length(), 0x2_2000);
+    }
+
+    #[test]
+    #[should_panic(expected = "follow the IVT")]
+    fn csf_before_ivt() {
+        let _ = SignedImage::new(0x6000_0000, 0x1000, 0x800);
+    }
+}
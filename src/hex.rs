@@ -0,0 +1,182 @@
+//! Intel HEX and Motorola S-record output
+//!
+//! Production programmers often consume Intel HEX or S-record files instead
+//! of raw binaries. A [`Segment`] places bytes at an absolute address, like
+//! a configuration block at the address where the boot ROM expects it. The
+//! writers encode any number of segments into one file.
+//!
+//! ```
+//! use imxrt_boot_gen::hex::{self, Segment};
+//! use imxrt_boot_gen::flexspi::{self, LookupTable};
+//! use imxrt_boot_gen::serial_flash::nor;
+//!
+//! let block = nor::ConfigurationBlock::new(flexspi::ConfigurationBlock::new(LookupTable::new()));
+//! let segments = [Segment::serial_nor_fcb(&block)];
+//! let ihex = hex::to_intel_hex(&segments);
+//! assert!(ihex.ends_with(":00000001FF\n"));
+//! ```
+//!
+//! This module requires the `"std"` feature.
+
+use crate::serial_flash::nor;
+use crate::Imxrt;
+use std::fmt::Write;
+
+/// The number of data bytes in each record.
+const RECORD_SIZE: usize = 16;
+
+/// Bytes placed at an absolute address
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Segment {
+    address: u32,
+    data: Vec<u8>,
+}
+
+impl Segment {
+    /// Place `data` at `address`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the data extends beyond the 32-bit address space.
+    pub fn new(address: u32, data: impl Into<Vec<u8>>) -> Self {
+        let data = data.into();
+        assert!(
+            u64::from(address) + data.len() as u64 <= 1 << 32,
+            "The segment extends beyond the 32-bit address space"
+        );
+        Segment { address, data }
+    }
+
+    /// Place a serial NOR configuration block at the selected chip's
+    /// [`fcb_address`](Imxrt::fcb_address).
+    pub fn serial_nor_fcb(block: &nor::ConfigurationBlock) -> Self {
+        Self::new(Imxrt::SELECTED.fcb_address(), block.to_bytes())
+    }
+
+    /// Returns the segment's address.
+    pub fn address(&self) -> u32 {
+        self.address
+    }
+
+    /// Returns the segment's data.
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+
+    /// Returns the records' addresses and data. Records don't cross a 64KiB
+    /// boundary.
+    fn records(&self) -> impl Iterator<Item = (u32, &[u8])> {
+        let mut address = self.address;
+        let mut data = self.data.as_slice();
+        core::iter::from_fn(move || {
+            if data.is_empty() {
+                return None;
+            }
+            let to_boundary = 0x1_0000 - (address as usize & 0xFFFF);
+            let (record, rest) = data.split_at(data.len().min(RECORD_SIZE).min(to_boundary));
+            let record_address = address;
+            address = address.wrapping_add(record.len() as u32);
+            data = rest;
+            Some((record_address, record))
+        })
+    }
+}
+
+/// Append a record, and the checksum that `checksum` computes from the sum
+/// of the record's bytes.
+fn record(out: &mut String, start: &str, bytes: &[u8], checksum: impl Fn(u8) -> u8) {
+    out.push_str(start);
+    let mut sum = 0u8;
+    for byte in bytes {
+        sum = sum.wrapping_add(*byte);
+        write!(out, "{byte:02X}").unwrap();
+    }
+    writeln!(out, "{:02X}", checksum(sum)).unwrap();
+}
+
+/// Encode the segments as Intel HEX.
+///
+/// The output uses data records with 32-bit extended linear addresses, and
+/// ends with an end-of-file record.
+pub fn to_intel_hex(segments: &[Segment]) -> String {
+    let checksum = |sum: u8| sum.wrapping_neg();
+    let mut out = String::new();
+    let mut upper = None;
+    for segment in segments {
+        for (address, data) in segment.records() {
+            let [a3, a2, a1, a0] = address.to_be_bytes();
+            if upper != Some([a3, a2]) {
+                upper = Some([a3, a2]);
+                record(&mut out, ":", &[2, 0, 0, 4, a3, a2], checksum);
+            }
+            let mut bytes = vec![data.len() as u8, a1, a0, 0];
+            bytes.extend_from_slice(data);
+            record(&mut out, ":", &bytes, checksum);
+        }
+    }
+    record(&mut out, ":", &[0, 0, 0, 1], checksum);
+    out
+}
+
+/// Encode the segments as Motorola S-records.
+///
+/// The output has an empty `S0` header, `S3` data records with 32-bit
+/// addresses, an `S5` or `S6` record count, and an `S7` termination record.
+pub fn to_srec(segments: &[Segment]) -> String {
+    let checksum = |sum: u8| !sum;
+    let mut out = String::new();
+    record(&mut out, "S0", &[3, 0, 0], checksum);
+    let mut count = 0u32;
+    for segment in segments {
+        for (address, data) in segment.records() {
+            let mut bytes = vec![data.len() as u8 + 5];
+            bytes.extend_from_slice(&address.to_be_bytes());
+            bytes.extend_from_slice(data);
+            record(&mut out, "S3", &bytes, checksum);
+            count += 1;
+        }
+    }
+    let [_, c2, c1, c0] = count.to_be_bytes();
+    if count <= 0xFFFF {
+        record(&mut out, "S5", &[3, c1, c0], checksum);
+    } else {
+        record(&mut out, "S6", &[4, c2, c1, c0], checksum);
+    }
+    record(&mut out, "S7", &[5, 0, 0, 0, 0], checksum);
+    out
+}
+
+#[cfg(test)]
+mod test {
+    use super::{to_intel_hex, to_srec, Segment};
+
+    #[test]
+    fn intel_hex() {
+        let segments = [Segment::new(0x6000_0000, *b"FCFB")];
+        assert_eq!(
+            to_intel_hex(&segments),
+            ":0200000460009A\n:0400000046434642EB\n:00000001FF\n"
+        );
+    }
+
+    #[test]
+    fn srec() {
+        let segments = [Segment::new(0x6000_0000, *b"FCFB")];
+        assert_eq!(
+            to_srec(&segments),
+            "S0030000FC\nS309600000004643464285\nS5030001FB\nS70500000000FA\n"
+        );
+    }
+
+    #[test]
+    fn boundaries() {
+        let segments = [Segment::new(0x6000_FFF8, [0xAA; 24])];
+        let ihex = to_intel_hex(&segments);
+        let lines: Vec<_> = ihex.lines().collect();
+        assert_eq!(lines.len(), 5);
+        assert!(lines[0].starts_with(":020000046000"));
+        assert!(lines[1].starts_with(":08FFF800"));
+        assert!(lines[2].starts_with(":020000046001"));
+        assert!(lines[3].starts_with(":10000000"));
+    }
+}
@@ -0,0 +1,573 @@
+//! Bootable image layout
+//!
+//! A bootable FlexSPI image has a configuration block, an IVT, boot data, and
+//! an optional DCD, followed by your program. [`Layout`] computes where each
+//! part belongs for an [`Imxrt`] chip, and it produces the IVT and boot data
+//! with the resulting addresses. Unless otherwise specified, the program starts
+//! [`APP_OFFSET`] bytes into flash.
+//!
+//! ```
+//! use imxrt_boot_gen::image::Layout;
+//! use imxrt_boot_gen::Imxrt;
+//!
+//! const LAYOUT: Layout = Layout::new(Imxrt::Imxrt1060, 0x6000_2401, 0x1_0000).finish();
+//!
+//! assert_eq!(LAYOUT.ivt_offset(), 0x1000);
+//! assert_eq!(LAYOUT.boot_data_offset(), 0x1020);
+//! assert_eq!(LAYOUT.get_app_offset(), 0x2000);
+//! assert_eq!(LAYOUT.image_size(), 0x1_2000);
+//! ```
+//!
+//! By default, the program executes in place (XIP) from flash. For a non-XIP
+//! image, set a [`load_address`](Layout::load_address). The boot ROM copies the
+//! image, from the start of flash through the end of the program, to the load
+//! address, and the IVT, boot data, and DCD addresses refer to the copy.
+//!
+//! ```
+//! use imxrt_boot_gen::image::Layout;
+//! use imxrt_boot_gen::Imxrt;
+//!
+//! const LAYOUT: Layout = Layout::new(Imxrt::Imxrt1060, 0x2020_2401, 0x1_0000)
+//!     .load_address(0x2020_0000)
+//!     .finish();
+//!
+//! assert_eq!(LAYOUT.address(LAYOUT.ivt_offset()), 0x2020_1000);
+//! assert_eq!(&LAYOUT.boot_data()[..4], &0x2020_0000u32.to_le_bytes());
+//! ```
+//!
+//! With the `"std"` feature, [`Layout::to_image`] assembles the image bytes from
+//! the configuration block and your program.
+//!
+//! The 1180 boots from a container, not an IVT; use the `ahab`
+//! module to describe its image.
+
+use crate::dcd::Dcd;
+use crate::ivt;
+use crate::Imxrt;
+
+#[cfg(any(feature = "imxrt1170", feature = "imxrt1180"))]
+use crate::xmcd::Xmcd;
+
+/// The default offset of the program from the start of flash.
+pub const APP_OFFSET: u32 = 0x2000;
+
+/// The offset of the DCD from the IVT.
+///
+/// The DCD follows the boot data, aligned to 16 bytes.
+const DCD_FROM_IVT: u32 = 0x30;
+
+/// The size of a FlexSPI boot configuration block, in bytes.
+#[cfg(any(feature = "imxrt1170", feature = "imxrt1180"))]
+const FCB_SIZE: u32 = 512;
+
+/// Returns `true` if `a_start..a_end` overlaps `b_start..b_end`.
+#[cfg(any(feature = "imxrt1170", feature = "imxrt1180"))]
+const fn overlaps(a_start: u32, a_end: u32, b_start: u32, b_end: u32) -> bool {
+    a_start < b_end && b_start < a_end
+}
+
+/// The layout of a bootable FlexSPI image
+///
+/// `entry` is the address of the program's entry point, and `app_size` is the
+/// size of the program in bytes. Offsets are relative to the start of the
+/// image, which is the start of flash unless otherwise specified.
+#[derive(Debug, Clone, Copy)]
+pub struct Layout {
+    chip: Imxrt,
+    entry: u32,
+    app_offset: u32,
+    app_size: u32,
+    dcd: Option<Dcd>,
+    load_address: Option<u32>,
+    plugin: bool,
+    flash_offset: u32,
+    #[cfg(any(feature = "imxrt1170", feature = "imxrt1180"))]
+    xmcd: Option<(Xmcd, u32)>,
+}
+
+impl Layout {
+    /// Describe an image for `chip`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `chip` doesn't boot with an IVT.
+    pub const fn new(chip: Imxrt, entry: u32, app_size: u32) -> Self {
+        assert!(
+            chip.ivt_offset().is_some(),
+            "The chip doesn't boot with an IVT"
+        );
+        Layout {
+            chip,
+            entry,
+            app_offset: APP_OFFSET,
+            app_size,
+            dcd: None,
+            load_address: None,
+            plugin: false,
+            flash_offset: 0,
+            #[cfg(any(feature = "imxrt1170", feature = "imxrt1180"))]
+            xmcd: None,
+        }
+    }
+    /// Set the offset of the program from the start of flash.
+    pub const fn app_offset(mut self, app_offset: u32) -> Self {
+        self.app_offset = app_offset;
+        self
+    }
+    /// Include a DCD, which the IVT points to.
+    pub const fn dcd(mut self, dcd: Dcd) -> Self {
+        self.dcd = Some(dcd);
+        self
+    }
+    /// Copy the image to `load_address` before executing it.
+    ///
+    /// `load_address` is the address of the copy of the start of flash, so the
+    /// program is copied to `load_address` plus the program offset. The entry
+    /// point should be in the copy.
+    pub const fn load_address(mut self, load_address: u32) -> Self {
+        self.load_address = Some(load_address);
+        self
+    }
+    /// Mark the image as a plugin.
+    ///
+    /// The boot ROM executes a plugin, then continues booting with the IVT that
+    /// the plugin returns.
+    pub const fn plugin(mut self, plugin: bool) -> Self {
+        self.plugin = plugin;
+        self
+    }
+    /// Place the image `flash_offset` bytes into flash.
+    ///
+    /// Use this for a secondary image; see [`Redundant`].
+    pub const fn flash_offset(mut self, flash_offset: u32) -> Self {
+        self.flash_offset = flash_offset;
+        self
+    }
+    /// Include an XMCD at `offset`.
+    ///
+    /// See your chip's reference manual for where the boot ROM expects the
+    /// XMCD.
+    #[cfg(any(feature = "imxrt1170", feature = "imxrt1180"))]
+    pub const fn xmcd(mut self, xmcd: Xmcd, offset: u32) -> Self {
+        self.xmcd = Some((xmcd, offset));
+        self
+    }
+    /// Check the layout.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the boot headers overlap the program, if the program doesn't
+    /// fit in the IVT's address space, or if the XMCD overlaps another part
+    /// of the image.
+    pub const fn finish(self) -> Self {
+        assert!(
+            self.headers_end() <= self.app_offset,
+            "The boot headers overlap the program"
+        );
+        assert!(
+            self.app_offset.checked_add(self.app_size).is_some(),
+            "The image is too large"
+        );
+        if let Some(load_address) = self.load_address {
+            assert!(
+                load_address.checked_add(self.image_size()).is_some(),
+                "The image doesn't fit at the load address"
+            );
+        }
+        #[cfg(any(feature = "imxrt1170", feature = "imxrt1180"))]
+        if let Some((xmcd, offset)) = &self.xmcd {
+            let (start, end) = (*offset, *offset + xmcd.len() as u32);
+            let fcb = self.fcb_offset();
+            assert!(
+                !overlaps(start, end, fcb, fcb + FCB_SIZE)
+                    && !overlaps(start, end, self.ivt_offset(), self.headers_end())
+                    && !overlaps(start, end, self.app_offset, self.image_size()),
+                "The XMCD overlaps another part of the image"
+            );
+        }
+        self
+    }
+    /// Returns the chip.
+    pub const fn get_chip(&self) -> Imxrt {
+        self.chip
+    }
+    /// Returns the address of the program's entry point.
+    pub const fn get_entry(&self) -> u32 {
+        self.entry
+    }
+    /// Returns the offset of the program.
+    pub const fn get_app_offset(&self) -> u32 {
+        self.app_offset
+    }
+    /// Returns the size of the program, in bytes.
+    pub const fn get_app_size(&self) -> u32 {
+        self.app_size
+    }
+    /// Returns the load address, or `None` if the program executes in place.
+    pub const fn get_load_address(&self) -> Option<u32> {
+        self.load_address
+    }
+    /// Returns `true` if the program executes in place from flash.
+    pub const fn is_xip(&self) -> bool {
+        self.load_address.is_none()
+    }
+    /// Returns `true` if the image is a plugin.
+    pub const fn get_plugin(&self) -> bool {
+        self.plugin
+    }
+    /// Returns the offset of the image in flash.
+    pub const fn get_flash_offset(&self) -> u32 {
+        self.flash_offset
+    }
+    /// Returns the DCD, if any.
+    pub const fn get_dcd(&self) -> Option<&Dcd> {
+        self.dcd.as_ref()
+    }
+    /// Returns the offset of the configuration block.
+    pub const fn fcb_offset(&self) -> u32 {
+        self.chip.fcb_offset()
+    }
+    /// Returns the offset of the IVT.
+    pub const fn ivt_offset(&self) -> u32 {
+        match self.chip.ivt_offset() {
+            Some(offset) => offset,
+            None => unreachable!(),
+        }
+    }
+    /// Returns the offset of the boot data.
+    pub const fn boot_data_offset(&self) -> u32 {
+        self.ivt_offset() + ivt::Ivt::SIZE as u32
+    }
+    /// Returns the offset of the DCD, or `None` if there's no DCD.
+    pub const fn dcd_offset(&self) -> Option<u32> {
+        match self.dcd {
+            Some(_) => Some(self.ivt_offset() + DCD_FROM_IVT),
+            None => None,
+        }
+    }
+    /// Returns the size of the image, from the start of flash through the end
+    /// of the program, in bytes.
+    ///
+    /// This is the boot data length.
+    pub const fn image_size(&self) -> u32 {
+        self.app_offset + self.app_size
+    }
+    /// Returns the address of `offset` when the image boots.
+    ///
+    /// For an XIP image, this is the flash address. Otherwise, this is the
+    /// address in the copy.
+    pub const fn address(&self, offset: u32) -> u32 {
+        match self.load_address {
+            Some(load_address) => load_address + offset,
+            None => self.chip.flexspi_base() + self.flash_offset + offset,
+        }
+    }
+    /// Returns the IVT's bytes.
+    pub const fn ivt(&self) -> [u8; ivt::Ivt::SIZE] {
+        use crate::raw::put;
+
+        let dcd = match self.dcd_offset() {
+            Some(offset) => self.address(offset),
+            None => 0,
+        };
+        let mut bytes = [0; ivt::Ivt::SIZE];
+        put(&mut bytes, 0, &ivt::HEADER.to_le_bytes());
+        put(&mut bytes, 4, &self.entry.to_le_bytes());
+        put(&mut bytes, 12, &dcd.to_le_bytes());
+        put(
+            &mut bytes,
+            16,
+            &self.address(self.boot_data_offset()).to_le_bytes(),
+        );
+        put(
+            &mut bytes,
+            20,
+            &self.address(self.ivt_offset()).to_le_bytes(),
+        );
+        bytes
+    }
+    /// Returns the boot data's bytes.
+    pub const fn boot_data(&self) -> [u8; ivt::BootData::SIZE] {
+        use crate::raw::put;
+
+        let mut bytes = [0; ivt::BootData::SIZE];
+        put(&mut bytes, 0, &self.address(0).to_le_bytes());
+        put(&mut bytes, 4, &self.image_size().to_le_bytes());
+        put(&mut bytes, 8, &(self.plugin as u32).to_le_bytes());
+        bytes
+    }
+
+    /// The offset of the first byte after the IVT, boot data, and DCD.
+    const fn headers_end(&self) -> u32 {
+        match (&self.dcd, self.dcd_offset()) {
+            (Some(dcd), Some(offset)) => offset + dcd.len() as u32,
+            _ => self.boot_data_offset() + ivt::BootData::SIZE as u32,
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Layout {
+    /// Assemble the image from the configuration block and the program.
+    ///
+    /// The image starts at the start of flash, even if it's loaded elsewhere,
+    /// and gaps are `0xFF`, like erased flash. The image is
+    /// [`image_size`](Self::image_size) bytes.
+    ///
+    /// This requires the `"std"` feature.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the layout is invalid (see [`finish`](Self::finish)), if the
+    /// configuration block overlaps the IVT, or if the program is larger than
+    /// the layout's program size.
+    pub fn to_image(&self, fcb: &[u8], app: &[u8]) -> Vec<u8> {
+        let layout = self.finish();
+        let fcb_offset = layout.fcb_offset() as usize;
+        let ivt_offset = layout.ivt_offset() as usize;
+        let app_offset = layout.app_offset as usize;
+        assert!(
+            fcb_offset + fcb.len() <= ivt_offset,
+            "The configuration block overlaps the IVT"
+        );
+        assert!(
+            app.len() <= layout.app_size as usize,
+            "The program is larger than the layout's program size"
+        );
+
+        let mut image = vec![0xFF; layout.image_size() as usize];
+        image[fcb_offset..][..fcb.len()].copy_from_slice(fcb);
+        image[ivt_offset..][..ivt::Ivt::SIZE].copy_from_slice(&layout.ivt());
+        image[layout.boot_data_offset() as usize..][..ivt::BootData::SIZE]
+            .copy_from_slice(&layout.boot_data());
+        if let (Some(dcd), Some(offset)) = (&layout.dcd, layout.dcd_offset()) {
+            image[offset as usize..][..dcd.len()].copy_from_slice(dcd.as_bytes());
+        }
+        #[cfg(any(feature = "imxrt1170", feature = "imxrt1180"))]
+        if let Some((xmcd, offset)) = &layout.xmcd {
+            image[*offset as usize..][..xmcd.len()].copy_from_slice(xmcd.as_bytes());
+        }
+        image[app_offset..][..app.len()].copy_from_slice(app);
+        image
+    }
+}
+
+/// A primary image and a secondary image
+///
+/// Some boot ROMs fall back to a secondary image when the primary image doesn't
+/// boot, which lets you update one image while keeping the other. Each image
+/// is a complete [`Layout`], and the secondary image starts at its
+/// [`flash_offset`](Layout::flash_offset). How the boot ROM finds the secondary
+/// image, usually with a fuse, depends on the chip; see your chip's reference
+/// manual.
+///
+/// ```
+/// use imxrt_boot_gen::image::{Layout, Redundant};
+/// use imxrt_boot_gen::Imxrt;
+///
+/// const IMAGES: Redundant = Redundant::new(
+///     Layout::new(Imxrt::Imxrt1170, 0x3000_2401, 0x8_0000),
+///     Layout::new(Imxrt::Imxrt1170, 0x3010_2401, 0x8_0000).flash_offset(0x10_0000),
+/// );
+///
+/// assert_eq!(IMAGES.secondary().address(0x1000), 0x3010_1000);
+/// assert_eq!(IMAGES.flash_size(), 0x18_2000);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct Redundant {
+    primary: Layout,
+    secondary: Layout,
+}
+
+impl Redundant {
+    /// Describe a primary image and a secondary image.
+    ///
+    /// # Panics
+    ///
+    /// Panics if either layout is invalid (see [`Layout::finish`]), if the
+    /// images are for different chips, if the primary image isn't at the
+    /// start of flash, or if the images overlap.
+    pub const fn new(primary: Layout, secondary: Layout) -> Self {
+        let primary = primary.finish();
+        let secondary = secondary.finish();
+        assert!(
+            primary.chip as u8 == secondary.chip as u8,
+            "The images must be for the same chip"
+        );
+        assert!(
+            primary.flash_offset == 0,
+            "The primary image must start at the start of flash"
+        );
+        assert!(
+            primary.image_size() <= secondary.flash_offset,
+            "The primary image overlaps the secondary image"
+        );
+        Redundant { primary, secondary }
+    }
+    /// Returns the primary image.
+    pub const fn primary(&self) -> &Layout {
+        &self.primary
+    }
+    /// Returns the secondary image.
+    pub const fn secondary(&self) -> &Layout {
+        &self.secondary
+    }
+    /// Returns the size of flash that both images span, in bytes.
+    pub const fn flash_size(&self) -> u32 {
+        self.secondary.flash_offset + self.secondary.image_size()
+    }
+}
+
+#[cfg(feature = "std")]
+impl Redundant {
+    /// Assemble both images.
+    ///
+    /// Each image has its own copy of the configuration block. The gap between
+    /// the images is `0xFF`. This requires the `"std"` feature.
+    ///
+    /// # Panics
+    ///
+    /// Panics under the same conditions as [`Layout::to_image`].
+    pub fn to_image(&self, fcb: &[u8], primary_app: &[u8], secondary_app: &[u8]) -> Vec<u8> {
+        let mut image = self.primary.to_image(fcb, primary_app);
+        image.resize(self.secondary.flash_offset as usize, 0xFF);
+        image.extend(self.secondary.to_image(fcb, secondary_app));
+        image
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Layout, Redundant};
+    use crate::dcd::{Dcd, Width};
+    use crate::Imxrt;
+
+    #[test]
+    fn ivt() {
+        let layout = Layout::new(Imxrt::Imxrt1060, 0x6000_2401, 0x1000).finish();
+        let ivt = layout.ivt();
+        assert_eq!(&ivt[..4], &[0xD1, 0x00, 0x20, 0x41]);
+        assert_eq!(&ivt[4..8], &0x6000_2401u32.to_le_bytes());
+        assert_eq!(&ivt[12..16], &[0; 4]);
+        assert_eq!(&ivt[16..20], &0x6000_1020u32.to_le_bytes());
+        assert_eq!(&ivt[20..24], &0x6000_1000u32.to_le_bytes());
+
+        let boot_data = layout.boot_data();
+        assert_eq!(&boot_data[..4], &0x6000_0000u32.to_le_bytes());
+        assert_eq!(&boot_data[4..8], &0x3000u32.to_le_bytes());
+        assert_eq!(&boot_data[8..], &[0; 4]);
+    }
+
+    #[test]
+    fn dcd() {
+        let dcd = Dcd::new().write(Width::Word, 0x400F_C068, 0xFFFF_FFFF);
+        let layout = Layout::new(Imxrt::Imxrt1170, 0x3000_2401, 0x1000)
+            .dcd(dcd)
+            .finish();
+        assert_eq!(layout.fcb_offset(), 0x400);
+        assert_eq!(layout.dcd_offset(), Some(0x1030));
+        assert_eq!(&layout.ivt()[12..16], &0x3000_1030u32.to_le_bytes());
+    }
+
+    #[test]
+    fn non_xip() {
+        let layout = Layout::new(Imxrt::Imxrt1170, 0x2401, 0x1000)
+            .load_address(0)
+            .dcd(Dcd::new())
+            .plugin(true)
+            .finish();
+        assert!(!layout.is_xip());
+        assert_eq!(layout.fcb_offset(), 0x400);
+
+        let ivt = layout.ivt();
+        assert_eq!(&ivt[4..8], &0x2401u32.to_le_bytes());
+        assert_eq!(&ivt[12..16], &0x1030u32.to_le_bytes());
+        assert_eq!(&ivt[16..20], &0x1020u32.to_le_bytes());
+        assert_eq!(&ivt[20..24], &0x1000u32.to_le_bytes());
+
+        let boot_data = layout.boot_data();
+        assert_eq!(&boot_data[..4], &[0; 4]);
+        assert_eq!(&boot_data[4..8], &0x3000u32.to_le_bytes());
+        assert_eq!(&boot_data[8..], &1u32.to_le_bytes());
+    }
+
+    #[test]
+    fn redundant() {
+        let images = Redundant::new(
+            Layout::new(Imxrt::Imxrt1060, 0x6000_2401, 0x1000),
+            Layout::new(Imxrt::Imxrt1060, 0x6000_6401, 0x1000).flash_offset(0x4000),
+        );
+        let secondary = images.secondary().ivt();
+        assert_eq!(&secondary[16..20], &0x6000_5020u32.to_le_bytes());
+        assert_eq!(&secondary[20..24], &0x6000_5000u32.to_le_bytes());
+        assert_eq!(
+            &images.secondary().boot_data()[..4],
+            &0x6000_4000u32.to_le_bytes()
+        );
+        assert_eq!(images.flash_size(), 0x7000);
+    }
+
+    #[test]
+    #[should_panic(expected = "overlaps the secondary image")]
+    fn redundant_overlap() {
+        let _ = Redundant::new(
+            Layout::new(Imxrt::Imxrt1060, 0x6000_2401, 0x1000),
+            Layout::new(Imxrt::Imxrt1060, 0x6000_4401, 0x1000).flash_offset(0x2000),
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "overlap the program")]
+    fn headers_overlap_app() {
+        let _ = Layout::new(Imxrt::Imxrt1060, 0x6000_1041, 0x1000)
+            .app_offset(0x1020)
+            .finish();
+    }
+
+    #[test]
+    #[should_panic(expected = "doesn't boot with an IVT")]
+    fn container_chip() {
+        let _ = Layout::new(Imxrt::Imxrt1180, 0x2800_2401, 0x1000);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn to_image() {
+        let layout = Layout::new(Imxrt::Imxrt1010, 0x6000_2401, 0x100).finish();
+        let image = layout.to_image(&[0xAA; 512], &[0xBB; 0x80]);
+        assert_eq!(image.len(), 0x2100);
+        assert_eq!(&image[..512], &[0xAA; 512]);
+        assert_eq!(image[512], 0xFF);
+        assert_eq!(&image[0x1000..0x1020], &layout.ivt());
+        assert_eq!(&image[0x1020..0x102C], &layout.boot_data());
+        assert_eq!(&image[0x2000..0x2080], &[0xBB; 0x80]);
+        assert!(image[0x2080..].iter().all(|byte| *byte == 0xFF));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn redundant_image() {
+        let images = Redundant::new(
+            Layout::new(Imxrt::Imxrt1010, 0x6000_2401, 0x100),
+            Layout::new(Imxrt::Imxrt1010, 0x6001_2401, 0x100).flash_offset(0x1_0000),
+        );
+        let image = images.to_image(&[0xAA; 512], &[0xBB; 0x100], &[0xCC; 0x100]);
+        assert_eq!(image.len(), 0x1_2100);
+        assert_eq!(image[0x2100], 0xFF);
+        assert_eq!(&image[0x1_0000..0x1_0200], &[0xAA; 512]);
+        assert_eq!(&image[0x1_1000..0x1_1020], &images.secondary().ivt());
+        assert_eq!(&image[0x1_2000..], &[0xCC; 0x100]);
+    }
+
+    #[test]
+    #[cfg(any(feature = "imxrt1170", feature = "imxrt1180"))]
+    #[should_panic(expected = "XMCD overlaps")]
+    fn xmcd_overlaps_ivt() {
+        use crate::xmcd::{MemoryInterface, Xmcd};
+
+        let xmcd = Xmcd::options(MemoryInterface::FlexSpi, 2, &[0xC000_0000]);
+        let _ = Layout::new(Imxrt::Imxrt1170, 0x3000_2401, 0x1000)
+            .xmcd(xmcd, 0xFFC)
+            .finish();
+    }
+}
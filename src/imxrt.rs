@@ -0,0 +1,383 @@
+//! i.MX RT processor families
+
+use crate::flexspi::Density;
+use core::{fmt, str::FromStr};
+
+/// A FlexSPI instance
+///
+/// Most chips boot from one FlexSPI instance. The 1180 can boot from either
+/// instance; see [`Imxrt::instance_base`].
+///
+/// The instance only selects an address. This crate doesn't have a clock table
+/// for each instance: [`SerialClockFrequency`](crate::flexspi::SerialClockFrequency)
+/// describes the chip's boot instance, and it isn't checked against the 1180's
+/// FlexSPI2 clocks. See your chip's reference manual for the FlexSPI2 serial
+/// clock frequencies.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FlexSpiInstance {
+    /// FlexSPI1.
+    FlexSpi1,
+    /// FlexSPI2.
+    FlexSpi2,
+}
+
+/// An i.MX RT processor family
+///
+/// Each variant corresponds to one of the crate's features. Use
+/// [`Imxrt::SELECTED`] to learn which family the crate was built for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Imxrt {
+    Imxrt1010,
+    Imxrt1015,
+    Imxrt1020,
+    Imxrt1024,
+    Imxrt1040,
+    Imxrt1050,
+    Imxrt1060,
+    Imxrt1064,
+    Imxrt1170,
+    Imxrt1180,
+    Imxrt500,
+    Imxrt600,
+}
+
+impl Imxrt {
+    /// All processor families, in the order of their variants.
+    pub const ALL: [Imxrt; 12] = [
+        Imxrt::Imxrt1010,
+        Imxrt::Imxrt1015,
+        Imxrt::Imxrt1020,
+        Imxrt::Imxrt1024,
+        Imxrt::Imxrt1040,
+        Imxrt::Imxrt1050,
+        Imxrt::Imxrt1060,
+        Imxrt::Imxrt1064,
+        Imxrt::Imxrt1170,
+        Imxrt::Imxrt1180,
+        Imxrt::Imxrt500,
+        Imxrt::Imxrt600,
+    ];
+
+    /// Returns the name of the crate's feature for this processor family.
+    ///
+    /// ```
+    /// use imxrt_boot_gen::Imxrt;
+    ///
+    /// assert_eq!(Imxrt::Imxrt1060.feature(), "imxrt1060");
+    /// ```
+    pub const fn feature(self) -> &'static str {
+        match self {
+            Imxrt::Imxrt1010 => "imxrt1010",
+            Imxrt::Imxrt1015 => "imxrt1015",
+            Imxrt::Imxrt1020 => "imxrt1020",
+            Imxrt::Imxrt1024 => "imxrt1024",
+            Imxrt::Imxrt1040 => "imxrt1040",
+            Imxrt::Imxrt1050 => "imxrt1050",
+            Imxrt::Imxrt1060 => "imxrt1060",
+            Imxrt::Imxrt1064 => "imxrt1064",
+            Imxrt::Imxrt1170 => "imxrt1170",
+            Imxrt::Imxrt1180 => "imxrt1180",
+            Imxrt::Imxrt500 => "imxrt500",
+            Imxrt::Imxrt600 => "imxrt600",
+        }
+    }
+
+    /// The processor family selected by the crate's feature.
+    pub const SELECTED: Imxrt = {
+        #[cfg(feature = "imxrt1010")]
+        let chip = Imxrt::Imxrt1010;
+        #[cfg(feature = "imxrt1015")]
+        let chip = Imxrt::Imxrt1015;
+        #[cfg(feature = "imxrt1020")]
+        let chip = Imxrt::Imxrt1020;
+        #[cfg(feature = "imxrt1024")]
+        let chip = Imxrt::Imxrt1024;
+        #[cfg(feature = "imxrt1040")]
+        let chip = Imxrt::Imxrt1040;
+        #[cfg(feature = "imxrt1050")]
+        let chip = Imxrt::Imxrt1050;
+        #[cfg(feature = "imxrt1060")]
+        let chip = Imxrt::Imxrt1060;
+        #[cfg(feature = "imxrt1064")]
+        let chip = Imxrt::Imxrt1064;
+        #[cfg(feature = "imxrt1170")]
+        let chip = Imxrt::Imxrt1170;
+        #[cfg(feature = "imxrt1180")]
+        let chip = Imxrt::Imxrt1180;
+        #[cfg(feature = "imxrt500")]
+        let chip = Imxrt::Imxrt500;
+        #[cfg(feature = "imxrt600")]
+        let chip = Imxrt::Imxrt600;
+        chip
+    };
+
+    /// Returns `true` if this is the processor family selected by the crate's feature.
+    pub const fn is_selected(self) -> bool {
+        self as u8 == Imxrt::SELECTED as u8
+    }
+
+    /// Returns the address of the FlexSPI flash that the chip boots from.
+    ///
+    /// The 1064 boots from its internal flash, which is on FlexSPI2. The 1024's
+    /// internal flash is on the same FlexSPI as external flash.
+    pub const fn flexspi_base(self) -> u32 {
+        match self {
+            Imxrt::Imxrt1010
+            | Imxrt::Imxrt1015
+            | Imxrt::Imxrt1020
+            | Imxrt::Imxrt1024
+            | Imxrt::Imxrt1040
+            | Imxrt::Imxrt1050
+            | Imxrt::Imxrt1060 => 0x6000_0000,
+            Imxrt::Imxrt1064 => 0x7000_0000,
+            Imxrt::Imxrt1170 => 0x3000_0000,
+            Imxrt::Imxrt1180 => 0x2800_0000,
+            Imxrt::Imxrt500 | Imxrt::Imxrt600 => 0x0800_0000,
+        }
+    }
+
+    /// Returns the size of the address window that starts at
+    /// [`flexspi_base`](Imxrt::flexspi_base), or `None` if the window size
+    /// isn't known.
+    ///
+    /// All of the FlexSPI's flash regions must fit in the window. See
+    /// [`FlashRegions`](crate::flexspi::FlashRegions). The 1010, 1015, 1020,
+    /// and 1024 return `None`; see your chip's reference manual's memory map.
+    ///
+    /// ```
+    /// use imxrt_boot_gen::flexspi::Density;
+    /// use imxrt_boot_gen::Imxrt;
+    ///
+    /// assert_eq!(Imxrt::Imxrt1060.flexspi_window(), Some(Density::from_mebibytes(504)));
+    /// assert_eq!(Imxrt::Imxrt1170.flexspi_window(), Some(Density::from_mebibytes(256)));
+    /// assert_eq!(Imxrt::Imxrt1010.flexspi_window(), None);
+    /// ```
+    pub const fn flexspi_window(self) -> Option<Density> {
+        Some(match self {
+            Imxrt::Imxrt1010 | Imxrt::Imxrt1015 | Imxrt::Imxrt1020 | Imxrt::Imxrt1024 => {
+                return None
+            }
+            Imxrt::Imxrt1040 | Imxrt::Imxrt1050 | Imxrt::Imxrt1060 => Density::from_mebibytes(504),
+            Imxrt::Imxrt1064 => Density::from_mebibytes(240),
+            Imxrt::Imxrt1170 => Density::from_mebibytes(256),
+            Imxrt::Imxrt1180 | Imxrt::Imxrt500 | Imxrt::Imxrt600 => Density::from_mebibytes(128),
+        })
+    }
+
+    /// Returns the FlexSPI instance that [`flexspi_base`](Imxrt::flexspi_base)
+    /// describes.
+    pub const fn boot_instance(self) -> FlexSpiInstance {
+        match self {
+            Imxrt::Imxrt1064 => FlexSpiInstance::FlexSpi2,
+            _ => FlexSpiInstance::FlexSpi1,
+        }
+    }
+
+    /// Returns the address of the FlexSPI `instance` flash, or `None` if the
+    /// chip doesn't boot from that instance.
+    ///
+    /// ```
+    /// use imxrt_boot_gen::{FlexSpiInstance, Imxrt};
+    ///
+    /// assert_eq!(Imxrt::Imxrt1180.instance_base(FlexSpiInstance::FlexSpi1), Some(0x2800_0000));
+    /// assert_eq!(Imxrt::Imxrt1180.instance_base(FlexSpiInstance::FlexSpi2), Some(0x0400_0000));
+    /// assert_eq!(Imxrt::Imxrt1060.instance_base(FlexSpiInstance::FlexSpi2), None);
+    /// ```
+    pub const fn instance_base(self, instance: FlexSpiInstance) -> Option<u32> {
+        match (self, instance) {
+            (Imxrt::Imxrt1180, FlexSpiInstance::FlexSpi2) => Some(0x0400_0000),
+            _ if instance as u8 == self.boot_instance() as u8 => Some(self.flexspi_base()),
+            _ => None,
+        }
+    }
+
+    /// Returns the address of the serial NOR configuration block in the FlexSPI
+    /// `instance` flash, or `None` if the chip doesn't boot from that instance.
+    pub const fn instance_fcb_address(self, instance: FlexSpiInstance) -> Option<u32> {
+        match self.instance_base(instance) {
+            Some(base) => Some(base + self.fcb_offset()),
+            None => None,
+        }
+    }
+
+    /// Returns the offset of the serial NOR configuration block from the
+    /// start of flash.
+    pub const fn fcb_offset(self) -> u32 {
+        match self {
+            Imxrt::Imxrt1170 | Imxrt::Imxrt1180 | Imxrt::Imxrt500 | Imxrt::Imxrt600 => 0x400,
+            _ => 0,
+        }
+    }
+
+    /// Returns the offset of the image vector table from the start of flash, or
+    /// `None` if the chip doesn't boot with an IVT.
+    ///
+    /// The 1180 boots from a container, not an IVT. The 500 and 600 boot from
+    /// an image header that's part of the image's vector table.
+    pub const fn ivt_offset(self) -> Option<u32> {
+        match self {
+            Imxrt::Imxrt1180 | Imxrt::Imxrt500 | Imxrt::Imxrt600 => None,
+            _ => Some(0x1000),
+        }
+    }
+
+    /// Returns the UF2 family ID for the chip, or `None` if the chip doesn't
+    /// have one.
+    ///
+    /// All 10xx chips share the `MIMXRT10XX` family ID.
+    pub const fn uf2_family_id(self) -> Option<u32> {
+        match self {
+            Imxrt::Imxrt1170 | Imxrt::Imxrt1180 | Imxrt::Imxrt500 | Imxrt::Imxrt600 => None,
+            _ => Some(0x4FB2_D5BD),
+        }
+    }
+
+    /// Returns the address of the serial NOR configuration block.
+    ///
+    /// ```
+    /// use imxrt_boot_gen::Imxrt;
+    ///
+    /// assert_eq!(Imxrt::Imxrt1060.fcb_address(), 0x6000_0000);
+    /// assert_eq!(Imxrt::Imxrt1170.fcb_address(), 0x3000_0400);
+    /// ```
+    pub const fn fcb_address(self) -> u32 {
+        self.flexspi_base() + self.fcb_offset()
+    }
+
+    /// Returns the density of the chip's in-package serial NOR flash, or
+    /// `None` if the chip doesn't have internal flash.
+    ///
+    /// ```
+    /// use imxrt_boot_gen::flexspi::Density;
+    /// use imxrt_boot_gen::Imxrt;
+    ///
+    /// assert_eq!(Imxrt::Imxrt1064.internal_flash(), Some(Density::from_mebibytes(4)));
+    /// assert_eq!(Imxrt::Imxrt1060.internal_flash(), None);
+    /// ```
+    pub const fn internal_flash(self) -> Option<Density> {
+        match self {
+            Imxrt::Imxrt1024 | Imxrt::Imxrt1064 => Some(Density::from_mebibytes(4)),
+            _ => None,
+        }
+    }
+
+    /// Returns the fastest `serialClkFreq` that the chip's boot ROM accepts,
+    /// in MHz.
+    ///
+    /// When the chip is [`SELECTED`](Imxrt::SELECTED), this is the fastest
+    /// [`SerialClockFrequency`](crate::flexspi::SerialClockFrequency).
+    ///
+    /// ```
+    /// use imxrt_boot_gen::Imxrt;
+    ///
+    /// assert_eq!(Imxrt::Imxrt1010.max_serial_clock_mhz(), 133);
+    /// assert_eq!(Imxrt::Imxrt1060.max_serial_clock_mhz(), 166);
+    /// ```
+    pub const fn max_serial_clock_mhz(self) -> u32 {
+        match self {
+            Imxrt::Imxrt1010 => 133,
+            Imxrt::Imxrt1170 | Imxrt::Imxrt1180 | Imxrt::Imxrt500 | Imxrt::Imxrt600 => 200,
+            _ => 166,
+        }
+    }
+
+    /// Returns `true` if the serial NOR configuration block has a `blockSize`
+    /// for the chip.
+    ///
+    /// When the chip is [`SELECTED`](Imxrt::SELECTED), this is also when the
+    /// crate provides the serial NOR extras.
+    pub const fn supports_block_size(self) -> bool {
+        matches!(
+            self,
+            Imxrt::Imxrt1170 | Imxrt::Imxrt1180 | Imxrt::Imxrt500 | Imxrt::Imxrt600
+        )
+    }
+
+    /// Returns `true` if the chip's boot ROM can boot from an octal flash.
+    ///
+    /// ```
+    /// use imxrt_boot_gen::Imxrt;
+    ///
+    /// assert!(Imxrt::Imxrt1170.supports_octal());
+    /// assert!(!Imxrt::Imxrt1010.supports_octal());
+    /// ```
+    pub const fn supports_octal(self) -> bool {
+        !matches!(
+            self,
+            Imxrt::Imxrt1010 | Imxrt::Imxrt1015 | Imxrt::Imxrt1020 | Imxrt::Imxrt1024
+        )
+    }
+}
+
+/// Prints the name of the crate's feature, like `imxrt1060`.
+impl fmt::Display for Imxrt {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.feature())
+    }
+}
+
+/// The string doesn't name a processor family.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParseImxrtError;
+
+impl fmt::Display for ParseImxrtError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("unknown i.MX RT processor family; expected one of ")?;
+        for (idx, chip) in Imxrt::ALL.iter().enumerate() {
+            if idx > 0 {
+                f.write_str(", ")?;
+            }
+            write!(f, "{chip}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseImxrtError {}
+
+/// Parses a feature name, like `imxrt1060`.
+///
+/// The match ignores ASCII case, and the `imxrt` prefix is optional. Use this to
+/// select a chip from an environment variable or a command-line flag.
+///
+/// ```
+/// use imxrt_boot_gen::Imxrt;
+///
+/// assert_eq!("imxrt1170".parse(), Ok(Imxrt::Imxrt1170));
+/// assert_eq!("IMXRT1064".parse(), Ok(Imxrt::Imxrt1064));
+/// assert_eq!("1010".parse(), Ok(Imxrt::Imxrt1010));
+/// assert!("imxrt1030".parse::<Imxrt>().is_err());
+/// ```
+impl FromStr for Imxrt {
+    type Err = ParseImxrtError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Imxrt::ALL
+            .into_iter()
+            .find(|chip| {
+                let feature = chip.feature();
+                feature.eq_ignore_ascii_case(s) || feature["imxrt".len()..].eq_ignore_ascii_case(s)
+            })
+            .ok_or(ParseImxrtError)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Imxrt;
+
+    #[test]
+    fn display_round_trip() {
+        for chip in Imxrt::ALL {
+            assert_eq!(chip.to_string().parse(), Ok(chip));
+        }
+    }
+
+    #[test]
+    fn all_in_variant_order() {
+        for (idx, chip) in Imxrt::ALL.into_iter().enumerate() {
+            assert_eq!(chip as usize, idx);
+        }
+        assert!(Imxrt::ALL.contains(&Imxrt::SELECTED));
+    }
+}
@@ -0,0 +1,284 @@
+//! Image vector table (IVT) support
+//!
+//! The IVT tells the boot ROM where to find your program's entry point, and
+//! the other boot data structures. Each of these locations is a [`Pointer`].
+//! Create a pointer from a typed entry point, or from a reference to a static,
+//! so that you don't need to cast addresses to integers.
+//!
+//! ```
+//! use imxrt_boot_gen::ivt::Pointer;
+//!
+//! unsafe extern "C" fn reset() -> ! {
+//!     loop {}
+//! }
+//!
+//! static VECTOR_TABLE: [u32; 2] = [0; 2];
+//!
+//! const ENTRY: Pointer = Pointer::from_reset(reset);
+//! const VECTORS: Pointer = Pointer::from_static(&VECTOR_TABLE);
+//! ```
+//!
+//! # IVT
+//!
+//! Build an [`Ivt`] and its [`BootData`] in `static`s. Like the configuration
+//! blocks, you're responsible for placing the IVT where the boot ROM expects
+//! it; see [`Imxrt::ivt_offset`](crate::Imxrt::ivt_offset). The IVT points to
+//! itself, so use the static's address for [`self_ptr`](Ivt::self_ptr).
+//!
+//! ```no_run
+//! use imxrt_boot_gen::ivt::{BootData, Ivt, Pointer};
+//!
+//! extern "C" {
+//!     static __image_start: u8;
+//! }
+//!
+//! unsafe extern "C" fn reset() -> ! {
+//!     loop {}
+//! }
+//!
+//! #[link_section = ".boot_data"]
+//! static BOOT_DATA: BootData =
+//!     BootData::new(Pointer::from_static(unsafe { &__image_start }), 0x20_0000);
+//!
+//! #[no_mangle]
+//! #[link_section = ".ivt"]
+//! static IVT: Ivt = Ivt::new(Pointer::from_reset(reset))
+//!     .boot_data(Pointer::from_static(&BOOT_DATA))
+//!     .self_ptr(Pointer::from_static(&IVT));
+//! ```
+//!
+//! For a non-XIP image, the boot ROM copies the image to RAM before it boots.
+//! Link the IVT and boot data at their addresses in the copy, and start the
+//! boot data at the copy of the start of flash. [`image::Layout`](crate::image::Layout)
+//! computes these addresses.
+
+/// A pointer in a boot data structure
+///
+/// The pointer is the same size as a pointer on the target, and it's
+/// resolved by the linker. Use [`from_address`](Pointer::from_address)
+/// only when the address isn't known to the linker.
+#[derive(Debug, Clone, Copy)]
+#[repr(transparent)]
+pub struct Pointer(*const ());
+
+// Safety: the pointer is never dereferenced. The boot ROM reads the
+// pointer's value.
+unsafe impl Sync for Pointer {}
+
+impl Pointer {
+    /// A null pointer, which indicates that the boot data structure is absent.
+    pub const fn null() -> Self {
+        Pointer(core::ptr::null())
+    }
+
+    /// Point to an entry function.
+    pub const fn from_fn(entry: unsafe extern "C" fn()) -> Self {
+        Pointer(entry as *const ())
+    }
+
+    /// Point to an entry function that never returns, like `cortex-m-rt`'s
+    /// `Reset` handler.
+    pub const fn from_reset(entry: unsafe extern "C" fn() -> !) -> Self {
+        Pointer(entry as *const ())
+    }
+
+    /// Point to a static, like a vector table or another boot data structure.
+    pub const fn from_static<T>(object: &'static T) -> Self {
+        Pointer(object as *const T as *const ())
+    }
+
+    /// Point to a fixed address.
+    pub const fn from_address(address: u32) -> Self {
+        Pointer(address as usize as *const ())
+    }
+
+    /// Returns the raw pointer.
+    pub const fn as_ptr(self) -> *const () {
+        self.0
+    }
+}
+
+/// The IVT header
+///
+/// The header has the tag `0xD1`, the big-endian length `0x0020`, and the
+/// version `0x41`.
+pub const HEADER: u32 = 0x4120_00D1;
+
+/// An image vector table
+///
+/// All pointers other than the entry are null until you set them.
+#[derive(Debug, Clone, Copy)]
+#[repr(C)]
+pub struct Ivt {
+    header: u32,
+    entry: Pointer,
+    _reserved1: u32,
+    dcd: Pointer,
+    boot_data: Pointer,
+    self_ptr: Pointer,
+    csf: Pointer,
+    _reserved2: u32,
+}
+
+#[cfg(target_pointer_width = "32")]
+const _: () = assert!(core::mem::size_of::<Ivt>() == Ivt::SIZE);
+
+impl Ivt {
+    /// The size of an IVT on the target, in bytes.
+    pub const SIZE: usize = 32;
+
+    /// Create an IVT that jumps to `entry`.
+    pub const fn new(entry: Pointer) -> Self {
+        Ivt {
+            header: HEADER,
+            entry,
+            _reserved1: 0,
+            dcd: Pointer::null(),
+            boot_data: Pointer::null(),
+            self_ptr: Pointer::null(),
+            csf: Pointer::null(),
+            _reserved2: 0,
+        }
+    }
+    /// Point to the device configuration data (DCD).
+    pub const fn dcd(mut self, dcd: Pointer) -> Self {
+        self.dcd = dcd;
+        self
+    }
+    /// Point to the [`BootData`].
+    pub const fn boot_data(mut self, boot_data: Pointer) -> Self {
+        self.boot_data = boot_data;
+        self
+    }
+    /// Point to the IVT itself.
+    pub const fn self_ptr(mut self, self_ptr: Pointer) -> Self {
+        self.self_ptr = self_ptr;
+        self
+    }
+    /// Point to the command sequence file (CSF) for secure boot.
+    pub const fn csf(mut self, csf: Pointer) -> Self {
+        self.csf = csf;
+        self
+    }
+    /// Returns the IVT header.
+    pub const fn get_header(&self) -> u32 {
+        self.header
+    }
+    /// Returns the entry pointer.
+    pub const fn get_entry(&self) -> Pointer {
+        self.entry
+    }
+    /// Returns the DCD pointer.
+    pub const fn get_dcd(&self) -> Pointer {
+        self.dcd
+    }
+    /// Returns the boot data pointer.
+    pub const fn get_boot_data(&self) -> Pointer {
+        self.boot_data
+    }
+    /// Returns the pointer to the IVT itself.
+    pub const fn get_self_ptr(&self) -> Pointer {
+        self.self_ptr
+    }
+    /// Returns the CSF pointer.
+    pub const fn get_csf(&self) -> Pointer {
+        self.csf
+    }
+}
+
+/// The boot data that an [`Ivt`] points to
+///
+/// The boot data describes the image that the boot ROM loads: its start
+/// address, and its length in bytes.
+#[derive(Debug, Clone, Copy)]
+#[repr(C)]
+pub struct BootData {
+    start: Pointer,
+    length: u32,
+    plugin: u32,
+}
+
+#[cfg(target_pointer_width = "32")]
+const _: () = assert!(core::mem::size_of::<BootData>() == BootData::SIZE);
+
+impl BootData {
+    /// The size of the boot data on the target, in bytes.
+    pub const SIZE: usize = 12;
+
+    /// Create boot data for an image that starts at `start` and spans
+    /// `length` bytes.
+    pub const fn new(start: Pointer, length: u32) -> Self {
+        BootData {
+            start,
+            length,
+            plugin: 0,
+        }
+    }
+    /// Mark the image as a plugin.
+    pub const fn plugin(mut self, plugin: bool) -> Self {
+        self.plugin = plugin as u32;
+        self
+    }
+    /// Returns the image's start.
+    pub const fn get_start(&self) -> Pointer {
+        self.start
+    }
+    /// Returns the image's length, in bytes.
+    pub const fn get_length(&self) -> u32 {
+        self.length
+    }
+    /// Returns `true` if the image is a plugin.
+    pub const fn get_plugin(&self) -> bool {
+        self.plugin != 0
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{BootData, Ivt, Pointer, HEADER};
+
+    static OBJECT: u32 = 0;
+
+    unsafe extern "C" fn entry() {}
+
+    #[test]
+    fn pointers() {
+        assert!(Pointer::null().as_ptr().is_null());
+        assert_eq!(
+            Pointer::from_address(0x6000_2000).as_ptr() as usize,
+            0x6000_2000
+        );
+        assert_eq!(
+            Pointer::from_static(&OBJECT).as_ptr(),
+            core::ptr::addr_of!(OBJECT).cast()
+        );
+        assert_eq!(
+            Pointer::from_fn(entry).as_ptr(),
+            entry as unsafe extern "C" fn() as *const ()
+        );
+    }
+
+    #[test]
+    fn header() {
+        assert_eq!(HEADER.to_le_bytes(), [0xD1, 0x00, 0x20, 0x41]);
+    }
+
+    static IVT: Ivt = Ivt::new(Pointer::from_fn(entry))
+        .boot_data(Pointer::from_static(&BOOT_DATA))
+        .self_ptr(Pointer::from_static(&IVT));
+    static BOOT_DATA: BootData = BootData::new(Pointer::from_address(0x6000_0000), 0x1000);
+
+    #[test]
+    fn ivt() {
+        assert_eq!(IVT.get_header(), HEADER);
+        assert_eq!(IVT.get_self_ptr().as_ptr(), core::ptr::addr_of!(IVT).cast());
+        assert_eq!(
+            IVT.get_boot_data().as_ptr(),
+            core::ptr::addr_of!(BOOT_DATA).cast()
+        );
+        assert!(IVT.get_dcd().as_ptr().is_null());
+        assert!(IVT.get_csf().as_ptr().is_null());
+        assert_eq!(BOOT_DATA.get_length(), 0x1000);
+        assert!(!BOOT_DATA.plugin(true).plugin(false).get_plugin());
+    }
+}
@@ -12,8 +12,10 @@
 //! As of this writing, the API supports
 //!
 //! - serial NOR flash via FlexSPI
+//! - serial NAND flash via FlexSPI
+//! - eMMC boot configuration for uSDHC
 //!
-//! Other configurations, like NAND flash and parallel SEMC, may be added in the future.
+//! Other configurations, like parallel SEMC, may be added in the future.
 //!
 //! `imxrt-boot-gen` does not perscribe a way to properly place these data structures in a
 //! firmware image. Consider using [`imxrt-rt`](https://docs.rs/imxrt-rt) if you need
@@ -39,12 +41,35 @@
 //! The supported features are listed below.
 //!
 //! - `"imxrt1010"`
+//! - `"imxrt1015"`
 //! - `"imxrt1020"`
+//! - `"imxrt1024"`
 //! - `"imxrt1040"`
 //! - `"imxrt1060"`
 //! - `"imxrt1064"`
 //! - `"imxrt1170"`
 //! - `"imxrt1180"`
+//! - `"imxrt500"`
+//! - `"imxrt600"`
+//!
+//! The optional `"std"` feature adds host-side conveniences. With `"std"`, the
+//! FlexSPI, serial NOR, and serial NAND configuration blocks implement `Display`, printing a
+//! report of every field and the disassembled lookup table. Their `to_c_array`
+//! methods export the blocks as commented C arrays, and the [`hex`] module
+//! writes Intel HEX and S-record files. The [`uf2`] module writes UF2 files,
+//! and the [`build_rs`] module writes the block from a build script.
+//!
+//! The optional `"bytemuck"` and `"zerocopy"` features let you view data
+//! structures as bytes without `unsafe`. With `"bytemuck"`, the FlexSPI, serial
+//! NOR, and serial NAND configuration blocks implement `NoUninit`, and `Instr`,
+//! `Sequence`, and `LookupTable` implement `Pod`. With `"zerocopy"`, the FlexSPI
+//! configuration block, `Instr`, `Sequence`, and `LookupTable` implement
+//! `IntoBytes`.
+//!
+//! The optional `"serde"` feature adds the [`description`] module, which
+//! describes configuration blocks with `serde` data structures. `"serde"`
+//! enables `"std"`. The optional `"toml"` feature parses flash part
+//! descriptions from TOML, and it enables `"serde"`.
 //!
 //! ## License
 //!
@@ -59,7 +84,84 @@
 //! for inclusion in the work by you, as defined in the Apache-2.0 license, shall be
 //! dual licensed as above, without any additional terms or conditions.
 
-#![cfg_attr(not(test), no_std)]
+#![cfg_attr(not(any(test, feature = "std")), no_std)]
 
+#[cfg(feature = "imxrt1180")]
+pub mod ahab;
+#[cfg(any(feature = "imxrt1050", feature = "imxrt1060", feature = "imxrt1064"))]
+pub mod bee;
+#[cfg(feature = "std")]
+pub mod build_rs;
+#[cfg(feature = "std")]
+mod c_array;
+#[cfg(feature = "imxrt1170")]
+pub mod cm4;
+pub mod dcd;
+#[cfg(feature = "serde")]
+pub mod description;
 pub mod flexspi;
+pub mod fuses;
+#[cfg(not(any(feature = "imxrt1180", feature = "imxrt500", feature = "imxrt600")))]
+pub mod hab;
+#[cfg(feature = "std")]
+pub mod hex;
+pub mod image;
+mod imxrt;
+pub mod ivt;
+#[cfg(any(feature = "imxrt1010", feature = "imxrt1170", feature = "imxrt1180"))]
+pub mod otfad;
+mod parse;
+pub mod prelude;
+mod raw;
+#[cfg(any(feature = "imxrt1060", feature = "imxrt1064"))]
+pub mod remap;
+#[cfg(feature = "std")]
+mod report;
 pub mod serial_flash;
+#[cfg(feature = "std")]
+pub mod uf2;
+pub mod usdhc;
+#[cfg(any(feature = "imxrt1170", feature = "imxrt1180"))]
+pub mod xmcd;
+
+pub use imxrt::{FlexSpiInstance, Imxrt, ParseImxrtError};
+pub use parse::ParseError;
+
+/// Assert that a configuration block is placed at the expected address.
+///
+/// The boot ROM looks for the configuration block at a fixed address, which
+/// depends on your chip and boot device. Use this macro early in your
+/// program to check that your linker script placed the block at `address`.
+/// The macro also checks that the block's address is a multiple of the block's
+/// alignment.
+///
+/// ```no_run
+/// use imxrt_boot_gen::serial_flash::nor;
+/// # use imxrt_boot_gen::flexspi::{self, LookupTable};
+///
+/// # const SERIAL_NOR_CONFIGURATION_BLOCK: nor::ConfigurationBlock =
+/// #   nor::ConfigurationBlock::new(flexspi::ConfigurationBlock::new(LookupTable::new()));
+/// #[link_section = ".fcb"]
+/// static FCB: nor::ConfigurationBlock = SERIAL_NOR_CONFIGURATION_BLOCK;
+///
+/// imxrt_boot_gen::assert_placement!(FCB, 0x6000_0000);
+/// ```
+///
+/// # Panics
+///
+/// Panics if the block isn't placed at `address`.
+#[macro_export]
+macro_rules! assert_placement {
+    ($block:path, $address:expr) => {{
+        let address = ::core::ptr::addr_of!($block) as usize;
+        ::core::assert!(
+            address % ::core::mem::align_of_val(&$block) == 0,
+            "The configuration block is not aligned"
+        );
+        ::core::assert_eq!(
+            address,
+            $address,
+            "The configuration block is not placed at the expected address"
+        );
+    }};
+}
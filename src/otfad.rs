@@ -0,0 +1,233 @@
+//! On-the-fly AES decryption (OTFAD) key blobs
+//!
+//! OTFAD decrypts encrypted XIP images as the core reads them. The OTFAD has
+//! four contexts. Each context has an AES-128 key, a 64-bit counter, and a
+//! flash region that the context decrypts. The boot ROM loads the contexts from
+//! key blobs in flash.
+//!
+//! Use [`Context`] to describe a context, and [`KeyBlobs`] to lay out the four
+//! key blobs. A key blob holds a context's register image, followed by zeros.
+//! You must wrap each key blob with your key encryption key before you program
+//! it into flash; this crate doesn't implement key wrapping. See your chip's
+//! reference manual for where the boot ROM expects the key blobs.
+//!
+//! This module doesn't support key scrambling, and it doesn't know each
+//! chip's key blob offset. Both need the chip's OTFAD and fuse documentation,
+//! which this crate doesn't describe. If you enable key scrambling, build the
+//! key blobs with NXP's tools instead.
+//!
+//! ```
+//! use imxrt_boot_gen::otfad::{Context, KeyBlobs};
+//!
+//! const CONTEXT: Context = Context::new([0x11; 16], [0x22; 8], 0x6000_1000, 0x6010_0000);
+//! const KEY_BLOBS: KeyBlobs = KeyBlobs::new().context(0, CONTEXT);
+//!
+//! let plain = KEY_BLOBS.to_bytes();
+//! assert_eq!(&plain[..16], &[0x11; 16]);
+//! ```
+
+/// The number of OTFAD contexts.
+pub const CONTEXTS: usize = 4;
+/// The size of one key blob, in bytes.
+pub const KEY_BLOB_SIZE: usize = 64;
+/// The size of all key blobs, in bytes.
+pub const KEY_BLOBS_SIZE: usize = CONTEXTS * KEY_BLOB_SIZE;
+/// The size of a context's register image, in bytes.
+pub const CONTEXT_SIZE: usize = 32;
+
+/// The region granularity, in bytes.
+const REGION_ALIGNMENT: u32 = 1024;
+
+const VLD: u32 = 1 << 0;
+const RO: u32 = 1 << 1;
+const ADE: u32 = 1 << 2;
+
+/// An OTFAD context
+///
+/// The context decrypts reads from `start` up to, but excluding, `end`. Unless
+/// otherwise specified, the context is valid, it decrypts, and its registers
+/// aren't read-only.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Context {
+    key: [u8; 16],
+    counter: [u8; 8],
+    start: u32,
+    end: u32,
+    read_only: bool,
+    decrypt: bool,
+}
+
+impl Context {
+    /// Describe a context that decrypts `start..end` with `key` and `counter`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `start` or `end` isn't 1KiB aligned, or if the region is
+    /// empty.
+    pub const fn new(key: [u8; 16], counter: [u8; 8], start: u32, end: u32) -> Self {
+        assert!(
+            start.is_multiple_of(REGION_ALIGNMENT) && end.is_multiple_of(REGION_ALIGNMENT),
+            "The OTFAD region must be 1KiB aligned"
+        );
+        assert!(end > start, "The OTFAD region must not be empty");
+        Context {
+            key,
+            counter,
+            start,
+            end,
+            read_only: false,
+            decrypt: true,
+        }
+    }
+    /// Lock the context's registers after the boot ROM loads them.
+    pub const fn read_only(mut self, read_only: bool) -> Self {
+        self.read_only = read_only;
+        self
+    }
+    /// Enable or disable decryption for the region.
+    ///
+    /// Disabling decryption passes reads through without decrypting them.
+    pub const fn decrypt(mut self, decrypt: bool) -> Self {
+        self.decrypt = decrypt;
+        self
+    }
+    /// Returns the region's start address.
+    pub const fn get_start(&self) -> u32 {
+        self.start
+    }
+    /// Returns the region's end address, excluding the end.
+    pub const fn get_end(&self) -> u32 {
+        self.end
+    }
+    /// Returns the region descriptor's first word, `RGD_W0`.
+    pub const fn rgd_w0(&self) -> u32 {
+        self.start
+    }
+    /// Returns the region descriptor's second word, `RGD_W1`.
+    ///
+    /// The word has the region's last 1KiB block, and the context's flags.
+    pub const fn rgd_w1(&self) -> u32 {
+        let mut word = (self.end - REGION_ALIGNMENT) | VLD;
+        if self.read_only {
+            word |= RO;
+        }
+        if self.decrypt {
+            word |= ADE;
+        }
+        word
+    }
+    /// Returns the context's register image: the key, counter, `RGD_W0`, and
+    /// `RGD_W1`.
+    pub const fn to_bytes(&self) -> [u8; CONTEXT_SIZE] {
+        use crate::raw::put;
+
+        let mut bytes = [0; CONTEXT_SIZE];
+        put(&mut bytes, 0, &self.key);
+        put(&mut bytes, 16, &self.counter);
+        put(&mut bytes, 24, &self.rgd_w0().to_le_bytes());
+        put(&mut bytes, 28, &self.rgd_w1().to_le_bytes());
+        bytes
+    }
+}
+
+/// The four OTFAD key blobs, before wrapping
+///
+/// Unused contexts are zero, which the boot ROM treats as invalid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeyBlobs {
+    contexts: [Option<Context>; CONTEXTS],
+}
+
+impl Default for KeyBlobs {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl KeyBlobs {
+    /// Create key blobs without any valid contexts.
+    pub const fn new() -> Self {
+        KeyBlobs {
+            contexts: [None; CONTEXTS],
+        }
+    }
+    /// Set the context at `index`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is four or more, or if the context's region overlaps
+    /// another context's region.
+    pub const fn context(mut self, index: usize, context: Context) -> Self {
+        assert!(index < CONTEXTS, "OTFAD has four contexts");
+        let mut idx = 0;
+        while idx < CONTEXTS {
+            if let Some(other) = self.contexts[idx] {
+                assert!(
+                    idx == index || context.end <= other.start || other.end <= context.start,
+                    "OTFAD regions must not overlap"
+                );
+            }
+            idx += 1;
+        }
+        self.contexts[index] = Some(context);
+        self
+    }
+    /// Returns the context at `index`, or `None` if it isn't set.
+    pub const fn get_context(&self, index: usize) -> Option<Context> {
+        self.contexts[index]
+    }
+    /// Returns the plain key blobs, one per context.
+    pub const fn to_bytes(&self) -> [u8; KEY_BLOBS_SIZE] {
+        let mut bytes = [0; KEY_BLOBS_SIZE];
+        let mut idx = 0;
+        while idx < CONTEXTS {
+            if let Some(context) = self.contexts[idx] {
+                crate::raw::put(&mut bytes, idx * KEY_BLOB_SIZE, &context.to_bytes());
+            }
+            idx += 1;
+        }
+        bytes
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Context, KeyBlobs, KEY_BLOB_SIZE};
+
+    const CONTEXT: Context = Context::new([0xAA; 16], [0xBB; 8], 0x6000_1000, 0x6000_2000);
+
+    #[test]
+    fn region_descriptor() {
+        assert_eq!(CONTEXT.rgd_w0(), 0x6000_1000);
+        assert_eq!(CONTEXT.rgd_w1(), 0x6000_1C05);
+        assert_eq!(CONTEXT.read_only(true).rgd_w1(), 0x6000_1C07);
+        assert_eq!(CONTEXT.decrypt(false).rgd_w1(), 0x6000_1C01);
+    }
+
+    #[test]
+    fn key_blobs() {
+        let bytes = KeyBlobs::new().context(2, CONTEXT).to_bytes();
+        let blob = &bytes[2 * KEY_BLOB_SIZE..3 * KEY_BLOB_SIZE];
+        assert_eq!(&blob[..16], &[0xAA; 16]);
+        assert_eq!(&blob[16..24], &[0xBB; 8]);
+        assert_eq!(
+            &blob[24..32],
+            &[0x00, 0x10, 0x00, 0x60, 0x05, 0x1C, 0x00, 0x60]
+        );
+        assert!(blob[32..].iter().all(|byte| *byte == 0));
+        assert!(bytes[..2 * KEY_BLOB_SIZE].iter().all(|byte| *byte == 0));
+    }
+
+    #[test]
+    #[should_panic(expected = "must not overlap")]
+    fn overlap() {
+        let other = Context::new([0; 16], [0; 8], 0x6000_1800, 0x6000_3000);
+        let _ = KeyBlobs::new().context(0, CONTEXT).context(1, other);
+    }
+
+    #[test]
+    #[should_panic(expected = "1KiB aligned")]
+    fn unaligned() {
+        let _ = Context::new([0; 16], [0; 8], 0x6000_1001, 0x6000_2000);
+    }
+}
@@ -0,0 +1,81 @@
+//! Decode configuration blocks from bytes
+
+use core::fmt;
+
+/// An error produced when decoding a configuration block from bytes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ParseError {
+    /// The tag isn't ASCII 'FCFB'.
+    Tag(u32),
+    /// The version's most significant byte isn't ASCII 'V'.
+    Version(u32),
+    /// The device type doesn't match the kind of configuration block.
+    DeviceType(u8),
+    /// The field's value isn't valid for the selected chip.
+    Field {
+        /// The field name, as it appears in NXP's headers.
+        name: &'static str,
+        /// The byte offset from the start of the configuration block.
+        offset: usize,
+        /// The invalid value.
+        value: u32,
+    },
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ParseError::Tag(tag) => write!(f, "tag {tag:#010X} is not 'FCFB'"),
+            ParseError::Version(version) => {
+                write!(f, "version {version:#010X} does not start with 'V'")
+            }
+            ParseError::DeviceType(device_type) => {
+                write!(f, "deviceType {device_type} is not expected")
+            }
+            ParseError::Field {
+                name,
+                offset,
+                value,
+            } => write!(
+                f,
+                "{name} at offset {offset:#05X} has invalid value {value:#X}"
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseError {}
+
+/// Reads little-endian values from a configuration block.
+pub(crate) struct Reader<'a>(pub(crate) &'a [u8]);
+
+impl Reader<'_> {
+    pub(crate) fn array<const N: usize>(&self, offset: usize) -> [u8; N] {
+        self.0[offset..offset + N].try_into().unwrap()
+    }
+    pub(crate) fn u8(&self, offset: usize) -> u8 {
+        self.0[offset]
+    }
+    pub(crate) fn u16(&self, offset: usize) -> u16 {
+        u16::from_le_bytes(self.array(offset))
+    }
+    pub(crate) fn u32(&self, offset: usize) -> u32 {
+        u32::from_le_bytes(self.array(offset))
+    }
+    /// Decode the byte at `offset`, or describe why it's invalid.
+    pub(crate) fn field<T>(
+        &self,
+        name: &'static str,
+        offset: usize,
+        decode: impl FnOnce(u8) -> Option<T>,
+    ) -> Result<T, ParseError> {
+        let value = self.u8(offset);
+        decode(value).ok_or(ParseError::Field {
+            name,
+            offset,
+            value: value.into(),
+        })
+    }
+}
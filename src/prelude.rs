@@ -0,0 +1,43 @@
+//! Common imports for configuration block authors
+//!
+//! ```
+//! use imxrt_boot_gen::prelude::*;
+//!
+//! const SEQ_READ: Sequence = SequenceBuilder::new()
+//!     .instr(Instr::new(CMD, Pads::One, 0xEB))
+//!     .instr(Instr::new(RADDR, Pads::Four, 0x18))
+//!     .build();
+//!
+//! const SERIAL_NOR_CONFIGURATION_BLOCK: nor::ConfigurationBlock = nor::ConfigurationBlock::new(
+//!     flexspi::ConfigurationBlock::new(LookupTable::new().command(Command::Read, SEQ_READ))
+//!         .serial_flash_pad_type(FlashPadType::Quad)
+//!         .flash_size(SerialFlashRegion::A1, Density::from_mebibytes(8)),
+//! )
+//! .page_size(256)
+//! .sector_size(4096)
+//! .ip_cmd_serial_clk_freq(nor::SerialClockFrequency::MHz30)
+//! .finish();
+//! ```
+//!
+//! The prelude includes the SDR opcodes. Use the qualified [`ddr`] opcodes
+//! for DDR instructions. Since the FlexSPI and serial NOR configuration blocks
+//! share names, refer to those through the [`flexspi`] and [`nor`] modules.
+
+#[cfg(any(
+    feature = "imxrt1170",
+    feature = "imxrt1180",
+    feature = "imxrt500",
+    feature = "imxrt600"
+))]
+pub use crate::flexspi::DeviceModeType;
+pub use crate::flexspi::{
+    self,
+    opcodes::{ddr, sdr::*},
+    BusyBit, BusyBitPolarity, ColumnAddressWidth, Command, ConfigurationCommand,
+    ControllerMiscOptions, DataValidTime, Density, DeviceModeConfiguration, DeviceModeSequence,
+    FlashPadType, Instr, LookupTable, LutCustomSequence, PadSettingOverride, Pads, Performance,
+    Profile, ReadSampleClockSource, Sequence, SequenceBuilder, SerialClockFrequency,
+    SerialFlashRegion, Version, WaitTimeConfigurationCommands, JUMP_ON_CS, STOP,
+};
+pub use crate::serial_flash::nor;
+pub use crate::Imxrt;
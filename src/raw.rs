@@ -0,0 +1,54 @@
+//! Unchecked access to the bytes of a configuration block
+
+/// Copy `bytes` into `value`, starting at the byte `offset`.
+///
+/// # Panics
+///
+/// Panics if the bytes extend beyond the end of `value`.
+///
+/// # Safety
+///
+/// The bytes must form a valid value for every field that they overlap.
+pub(crate) const unsafe fn write_bytes<T: Copy>(mut value: T, offset: usize, bytes: &[u8]) -> T {
+    assert!(
+        offset <= core::mem::size_of::<T>() && bytes.len() <= core::mem::size_of::<T>() - offset,
+        "Raw bytes extend beyond the end of the configuration block"
+    );
+    let dst = (&mut value as *mut T).cast::<u8>();
+    let mut idx = 0;
+    while idx < bytes.len() {
+        // Safety: bounds checked above. Caller ensures that the bytes are valid
+        // for each field.
+        unsafe { dst.add(offset + idx).write(bytes[idx]) };
+        idx += 1;
+    }
+    value
+}
+
+/// Copy `src` into `bytes`, starting at the byte `offset`.
+///
+/// # Panics
+///
+/// Panics if `src` extends beyond the end of `bytes`.
+pub(crate) const fn put(bytes: &mut [u8], offset: usize, src: &[u8]) {
+    let mut idx = 0;
+    while idx < src.len() {
+        bytes[offset + idx] = src[idx];
+        idx += 1;
+    }
+}
+
+/// Returns `true` if the two byte slices are equal.
+pub(crate) const fn eq_bytes(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    let mut idx = 0;
+    while idx < a.len() {
+        if a[idx] != b[idx] {
+            return false;
+        }
+        idx += 1;
+    }
+    true
+}
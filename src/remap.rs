@@ -0,0 +1,133 @@
+//! FlexSPI address remapping
+//!
+//! The 1060 and 1064 can remap a range of FlexSPI addresses to another part of
+//! flash. A bootloader uses the remap to run an image from a secondary slot at
+//! the addresses that the image is linked for, so the same image runs from
+//! either slot. The `IOMUXC_GPR` registers `GPR30`, `GPR31`, and `GPR32` hold
+//! the start, end, and offset of the remapped range.
+//!
+//! [`Remap`] computes the register values. Write them from your bootloader
+//! before jumping to the image, or add them to a [`Dcd`].
+//!
+//! The module doesn't describe the 1170's remap. The 1170 remaps in its
+//! FlexSPI controllers, not in `IOMUXC_GPR`, and those registers aren't
+//! described here.
+//!
+//! ```
+//! use imxrt_boot_gen::remap::Remap;
+//!
+//! // Run the image stored 1MiB into flash at the start of flash.
+//! const REMAP: Remap = Remap::new(0x6000_0000, 0x6010_0000, 0x10_0000);
+//!
+//! for (address, value) in REMAP.registers() {
+//!     // Write value to address...
+//! #   let _ = (address, value);
+//! }
+//! ```
+
+use crate::dcd::Dcd;
+
+/// `IOMUXC_GPR_GPR30`, the start of the remapped range.
+pub const GPR30: u32 = 0x400A_C078;
+/// `IOMUXC_GPR_GPR31`, the end of the remapped range.
+pub const GPR31: u32 = 0x400A_C07C;
+/// `IOMUXC_GPR_GPR32`, the offset of the remapped range.
+pub const GPR32: u32 = 0x400A_C080;
+
+/// The remap granularity, in bytes.
+const ALIGNMENT: u32 = 4096;
+
+/// A remapped FlexSPI address range
+///
+/// Reads from `start` up to, but excluding, `end` read from the address plus
+/// `offset`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Remap {
+    start: u32,
+    end: u32,
+    offset: u32,
+}
+
+impl Remap {
+    /// Remap `start..end` by `offset` bytes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `start`, `end`, or `offset` isn't 4KiB aligned, or if the
+    /// range is empty.
+    pub const fn new(start: u32, end: u32, offset: u32) -> Self {
+        assert!(
+            start.is_multiple_of(ALIGNMENT)
+                && end.is_multiple_of(ALIGNMENT)
+                && offset.is_multiple_of(ALIGNMENT),
+            "The remap addresses must be 4KiB aligned"
+        );
+        assert!(end > start, "The remapped range must not be empty");
+        Remap { start, end, offset }
+    }
+    /// Returns the start of the remapped range.
+    pub const fn get_start(&self) -> u32 {
+        self.start
+    }
+    /// Returns the end of the remapped range, excluding the end.
+    pub const fn get_end(&self) -> u32 {
+        self.end
+    }
+    /// Returns the remap offset.
+    pub const fn get_offset(&self) -> u32 {
+        self.offset
+    }
+    /// Returns the address that `address` reads from.
+    pub const fn translate(&self, address: u32) -> u32 {
+        if self.start <= address && address < self.end {
+            address + self.offset
+        } else {
+            address
+        }
+    }
+    /// Returns the register addresses and values.
+    ///
+    /// The offset and end come before the start, so that the range is
+    /// complete when the start takes effect.
+    pub const fn registers(&self) -> [(u32, u32); 3] {
+        [(GPR32, self.offset), (GPR31, self.end), (GPR30, self.start)]
+    }
+    /// Append the register writes to `dcd`.
+    pub const fn dcd(&self, dcd: Dcd) -> Dcd {
+        dcd.write_words(&self.registers())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Remap, GPR30, GPR31, GPR32};
+    use crate::dcd::Dcd;
+
+    const REMAP: Remap = Remap::new(0x6000_0000, 0x6008_0000, 0x8_0000);
+
+    #[test]
+    fn translate() {
+        assert_eq!(REMAP.translate(0x6000_2000), 0x6008_2000);
+        assert_eq!(REMAP.translate(0x6008_0000), 0x6008_0000);
+    }
+
+    #[test]
+    fn registers() {
+        assert_eq!(
+            REMAP.registers(),
+            [
+                (GPR32, 0x8_0000),
+                (GPR31, 0x6008_0000),
+                (GPR30, 0x6000_0000)
+            ]
+        );
+        // Header, one write command header, and three address-value pairs.
+        assert_eq!(REMAP.dcd(Dcd::new()).len(), 4 + 4 + 3 * 8);
+    }
+
+    #[test]
+    #[should_panic(expected = "4KiB aligned")]
+    fn unaligned() {
+        let _ = Remap::new(0x6000_0000, 0x6008_0000, 0x800);
+    }
+}
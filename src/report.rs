@@ -0,0 +1,285 @@
+//! Human-readable configuration block reports
+//!
+//! The `Display` implementations print one field per line, in offset order.
+//! Each line has the field's byte offset, its name from NXP's headers, and
+//! its decoded value. The report disassembles every lookup table sequence
+//! that isn't empty.
+
+use crate::flexspi::{
+    self, Command, DeviceModeConfiguration, DllTime, Field, Sequence, SerialFlashRegion,
+};
+use crate::serial_flash::{nand, nor};
+use std::fmt;
+
+/// The lookup table commands that this crate names.
+pub(crate) const COMMANDS: &[Command] = &[
+    Command::Read,
+    Command::ReadStatus,
+    Command::WriteEnable,
+    Command::EraseSector,
+    #[cfg(any(
+        feature = "imxrt1170",
+        feature = "imxrt1180",
+        feature = "imxrt500",
+        feature = "imxrt600"
+    ))]
+    Command::EraseBlock,
+    Command::PageProgram,
+    Command::ChipErase,
+    Command::Dummy,
+];
+
+/// Describe a FlexSPI field, or `None` to show its raw value.
+fn describe_flexspi(block: &flexspi::ConfigurationBlock, name: &str) -> Option<String> {
+    Some(match name {
+        "tag" => "'FCFB'".into(),
+        "version" => block.get_version().to_string(),
+        "readSampleClkSrc" => format!("{:?}", block.get_read_sample_clk_src()),
+        "columnAddressWidth" => format!("{:?}", block.get_column_address_width()),
+        "deviceModeCfgEnable" => match block.get_device_mode_configuration() {
+            DeviceModeConfiguration::Disabled => "disabled".into(),
+            DeviceModeConfiguration::Enabled { .. } => "enabled".into(),
+        },
+        #[cfg(any(
+            feature = "imxrt1170",
+            feature = "imxrt1180",
+            feature = "imxrt500",
+            feature = "imxrt600"
+        ))]
+        "deviceModeType" => format!("{:?}", block.get_device_mode_type()?),
+        "deviceModeSeq" => match block.get_device_mode_configuration() {
+            DeviceModeConfiguration::Disabled => return None,
+            DeviceModeConfiguration::Enabled {
+                device_mode_seq, ..
+            } => format!(
+                "{} LUT(s) starting at index {}",
+                device_mode_seq.number_of_luts(),
+                device_mode_seq.starting_lut_index()
+            ),
+        },
+        "controllerMiscOption" => {
+            let options = block.get_controller_misc_options();
+            let names = [
+                (
+                    flexspi::ControllerMiscOptions::DIFFERENTIAL_CLOCK,
+                    "DIFFERENTIAL_CLOCK",
+                ),
+                (flexspi::ControllerMiscOptions::CK2, "CK2"),
+                (flexspi::ControllerMiscOptions::PARALLEL, "PARALLEL"),
+                (
+                    flexspi::ControllerMiscOptions::WORD_ADDRESSABLE,
+                    "WORD_ADDRESSABLE",
+                ),
+                (
+                    flexspi::ControllerMiscOptions::SAFE_CONFIG_FREQ,
+                    "SAFE_CONFIG_FREQ",
+                ),
+                (
+                    flexspi::ControllerMiscOptions::PAD_SETTING_OVERRIDE,
+                    "PAD_SETTING_OVERRIDE",
+                ),
+                (flexspi::ControllerMiscOptions::DDR_MODE, "DDR_MODE"),
+            ];
+            let names: Vec<_> = names
+                .into_iter()
+                .filter(|(option, _)| options.contains(*option))
+                .map(|(_, name)| name)
+                .collect();
+            if names.is_empty() {
+                return None;
+            }
+            format!("{:#010X} ({})", options.bits(), names.join(" | "))
+        }
+        "sflashPadType" => format!("{:?}", block.get_serial_flash_pad_type()),
+        "serialClkFreq" => format!("{:?}", block.get_serial_clk_freq()),
+        "sflashA1Size" => size(block, SerialFlashRegion::A1),
+        "sflashA2Size" => size(block, SerialFlashRegion::A2),
+        "sflashB1Size" => size(block, SerialFlashRegion::B1),
+        "sflashB2Size" => size(block, SerialFlashRegion::B2),
+        "timeoutInMs" => format!("{} ms", block.get_timeout_ms()),
+        "commandInterval" => format!("{} ns", block.get_command_interval()),
+        "dataValidTime" => {
+            let time = block.get_data_valid_time();
+            let port = |port: DllTime| {
+                format!(
+                    "{} x 0.1ns, {} delay cells",
+                    port.time_100ps(),
+                    port.delay_cells()
+                )
+            };
+            format!(
+                "port A {}, port B {}",
+                port(time.port_a()),
+                port(time.port_b())
+            )
+        }
+        "busyBitPolarity" => format!("{:?}", block.get_busy_bit().polarity()),
+        "lookupTable" => "see below".into(),
+        _ => return None,
+    })
+}
+
+fn size(block: &flexspi::ConfigurationBlock, region: SerialFlashRegion) -> String {
+    format!("{} bytes", block.get_flash_size(region).bytes())
+}
+
+/// Describe a serial NOR field, or `None` to show its raw value.
+fn describe_nor(block: &nor::ConfigurationBlock, name: &str) -> Option<String> {
+    Some(match name {
+        "pageSize" => format!("{} bytes", block.get_page_size()),
+        "sectorSize" => format!("{} bytes", block.get_sector_size()),
+        "ipcmdSerialClkFreq" => format!("{:?}", block.get_ip_cmd_serial_clk_freq()),
+        #[cfg(any(
+            feature = "imxrt1170",
+            feature = "imxrt1180",
+            feature = "imxrt500",
+            feature = "imxrt600"
+        ))]
+        "serialNorType" => format!("{:?}", block.get_extras().get_serial_nor_type()?),
+        #[cfg(any(
+            feature = "imxrt1170",
+            feature = "imxrt1180",
+            feature = "imxrt500",
+            feature = "imxrt600"
+        ))]
+        "blockSize" => format!("{} bytes", block.get_extras().get_block_size()?),
+        _ => return describe_flexspi(block.mem_cfg(), name),
+    })
+}
+
+/// Describe a serial NAND field, or `None` to show its raw value.
+fn describe_nand(block: &nand::ConfigurationBlock, name: &str) -> Option<String> {
+    Some(match name {
+        "pageDataSize" => format!("{} bytes", block.get_page_data_size()),
+        "pageTotalSize" => format!("{} bytes", block.get_page_total_size()),
+        "ipCmdSerialClkFreq" => format!("{:?}", block.get_ip_cmd_serial_clk_freq()),
+        "readPageTimeUs" => format!("{} us", block.get_read_page_time_us()),
+        _ => return describe_flexspi(block.mem_cfg(), name),
+    })
+}
+
+/// Format the raw, little-endian value of `field`.
+fn raw(field: &Field, bytes: &[u8]) -> String {
+    let bytes = &bytes[field.offset..field.offset + field.size];
+    match *bytes {
+        [b0] => format!("{b0:#04X}"),
+        [b0, b1] => format!("{:#06X}", u16::from_le_bytes([b0, b1])),
+        [b0, b1, b2, b3] => format!("{:#010X}", u32::from_le_bytes([b0, b1, b2, b3])),
+        _ => bytes
+            .iter()
+            .map(|byte| format!("{byte:02X}"))
+            .collect::<Vec<_>>()
+            .join(" "),
+    }
+}
+
+fn write_report(
+    f: &mut fmt::Formatter,
+    fields: &[Field],
+    bytes: &[u8],
+    lookup_table: &flexspi::LookupTable,
+    describe: impl Fn(&str) -> Option<String>,
+) -> fmt::Result {
+    for field in fields {
+        let value = describe(field.name).unwrap_or_else(|| raw(field, bytes));
+        writeln!(f, "{:#05X}  {:<24}{}", field.offset, field.name, value)?;
+        if field.name == "lookupTable" {
+            write_lookup_table(f, lookup_table)?;
+        }
+    }
+    Ok(())
+}
+
+/// Disassemble every sequence that isn't all `STOP`s.
+fn write_lookup_table(f: &mut fmt::Formatter, lookup_table: &flexspi::LookupTable) -> fmt::Result {
+    let bytes = lookup_table.to_bytes();
+    for (idx, chunk) in bytes.chunks_exact(16).enumerate() {
+        let sequence = Sequence::from_bytes(chunk.try_into().unwrap());
+        if sequence == Sequence::stopped() {
+            continue;
+        }
+        let name = COMMANDS
+            .iter()
+            .find(|command| **command as usize == idx)
+            .map(|command| format!("{command:?}"))
+            .unwrap_or_default();
+        writeln!(f, "         [{idx:>2}] {name:<12} {sequence}")?;
+    }
+    Ok(())
+}
+
+impl fmt::Display for flexspi::ConfigurationBlock {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write_report(
+            f,
+            &flexspi::FIELDS,
+            &self.to_bytes(),
+            self.get_lookup_table(),
+            |name| describe_flexspi(self, name),
+        )
+    }
+}
+
+impl fmt::Display for nor::ConfigurationBlock {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write_report(
+            f,
+            &nor::FIELDS,
+            &self.to_bytes(),
+            self.mem_cfg().get_lookup_table(),
+            |name| describe_nor(self, name),
+        )
+    }
+}
+
+impl fmt::Display for nand::ConfigurationBlock {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write_report(
+            f,
+            &nand::FIELDS,
+            &self.to_bytes(),
+            self.mem_cfg().get_lookup_table(),
+            |name| describe_nand(self, name),
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::flexspi::{
+        self, opcodes::sdr::*, Command, Density, Instr, LookupTable, Pads, SequenceBuilder,
+        SerialFlashRegion,
+    };
+    use crate::serial_flash::nor;
+
+    #[test]
+    fn report() {
+        let lut = LookupTable::new().command(
+            Command::Read,
+            SequenceBuilder::new()
+                .instr(Instr::new(CMD, Pads::One, 0xEB))
+                .instr(Instr::new(RADDR, Pads::Four, 0x18))
+                .build(),
+        );
+        let block = nor::ConfigurationBlock::new(
+            flexspi::ConfigurationBlock::new(lut)
+                .flash_size(SerialFlashRegion::A1, Density::from_mebibytes(8)),
+        )
+        .page_size(256)
+        .sector_size(4096);
+        let report = block.to_string();
+        let lines: Vec<_> = report.lines().collect();
+
+        assert_eq!(lines[0], "0x000  tag                     'FCFB'");
+        assert!(lines.contains(&"0x050  sflashA1Size            8388608 bytes"));
+        assert!(lines.contains(&"0x1C0  pageSize                256 bytes"));
+        assert!(
+            lines.contains(&"         [ 0] Read         CMD_SDR SINGLE 0xEB; RADDR_SDR QUAD 0x18")
+        );
+        assert_eq!(
+            lines.iter().filter(|line| line.contains("] ")).count(),
+            1,
+            "Only the Read sequence is disassembled"
+        );
+    }
+}
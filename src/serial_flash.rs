@@ -1,15 +1,145 @@
-//! Serial NOR flash boot
+//! Serial NOR and NAND flash boot
 //!
 //! `serial_flash` provides the types necessary to boot an i.MX RT processor
-//! from serial NOR flash. *Note: NAND Flash boot not yet implemented.*
+//! from serial NOR or serial NAND flash.
 //!
 //! # Serial NOR Configuration Block
 //!
 //! To create a serial NOR configuration block, first create a FlexSPI
-//! configuration block. See the [`flexspi`](crate::flexspi) module for more details.
+//! configuration block. See the [`flexspi`] module for more details.
 //!
 //! Use the FlexSPI configuration block to create a Serial NOR configuration
 //! block. You are responsible for placing the serial NOR configuration block at the correct
 //! location in memory. See [`nor::ConfigurationBlock`] for an example.
+//!
+//! # Serial NAND Configuration Block
+//!
+//! A serial NAND configuration block starts with the same FlexSPI configuration
+//! block, followed by the NAND geometry. See [`nand::ConfigurationBlock`] for an
+//! example.
 
+use crate::flexspi;
+
+pub mod nand;
 pub mod nor;
+
+mod private {
+    pub trait Sealed {}
+}
+
+/// A device-specific tail that follows the FlexSPI configuration block
+///
+/// This trait is sealed; it's implemented by the device-specific tails in
+/// this module, like [`nor::Tail`].
+pub trait DeviceTail: private::Sealed + Copy {
+    /// The `deviceType` value written into the FlexSPI configuration block.
+    const DEVICE_TYPE: u8;
+}
+
+/// A serial flash configuration block
+///
+/// The configuration block starts with the shared FlexSPI configuration block, and
+/// it's followed by a device-specific tail. The complete block is always 512 bytes.
+///
+/// You should prefer the device-specific aliases, like [`nor::ConfigurationBlock`],
+/// over naming this type directly.
+///
+/// # Layout
+///
+/// The configuration block is [`SIZE`](ConfigurationBlock::SIZE) bytes, and it's
+/// aligned to [`ALIGNMENT`](ConfigurationBlock::ALIGNMENT) bytes. Use
+/// [`assert_placement!`](crate::assert_placement) to check that the block is
+/// placed where your chip's boot ROM expects it.
+#[derive(Debug, Clone, Copy)]
+#[repr(C, align(4))]
+pub struct ConfigurationBlock<T> {
+    pub(crate) mem_cfg: flexspi::ConfigurationBlock,
+    pub(crate) tail: T,
+}
+
+impl<T: DeviceTail> ConfigurationBlock<T> {
+    /// The size of a serial flash configuration block, in bytes.
+    pub const SIZE: usize = 512;
+    /// The alignment of a serial flash configuration block, in bytes.
+    pub const ALIGNMENT: usize = flexspi::ConfigurationBlock::ALIGNMENT;
+
+    const LAYOUT: () = {
+        assert!(
+            core::mem::size_of::<Self>() == Self::SIZE,
+            "A serial flash configuration block must be 512 bytes"
+        );
+        assert!(core::mem::align_of::<Self>() == Self::ALIGNMENT);
+    };
+
+    /// Combine the FlexSPI configuration block with the device-specific tail.
+    pub(crate) const fn with_tail(mut mem_cfg: flexspi::ConfigurationBlock, tail: T) -> Self {
+        #[allow(clippy::let_unit_value)]
+        let () = Self::LAYOUT;
+        mem_cfg.device_type = T::DEVICE_TYPE;
+        ConfigurationBlock { mem_cfg, tail }
+    }
+
+    /// Returns the FlexSPI configuration block.
+    pub const fn mem_cfg(&self) -> &flexspi::ConfigurationBlock {
+        &self.mem_cfg
+    }
+
+    /// Returns the FlexSPI configuration block for modification.
+    ///
+    /// Use this to adjust the FlexSPI configuration block after you've created this
+    /// configuration block, like changing the flash size or lookup table. To replace
+    /// the entire FlexSPI configuration block, use [`with_mem_cfg`](Self::with_mem_cfg);
+    /// otherwise, you'll lose this block's `deviceType`.
+    ///
+    /// ```
+    /// use imxrt_boot_gen::flexspi::{self, Density, LookupTable, SerialFlashRegion};
+    /// use imxrt_boot_gen::serial_flash::nor;
+    ///
+    /// # const PRESET: nor::ConfigurationBlock =
+    /// #   nor::ConfigurationBlock::new(flexspi::ConfigurationBlock::new(LookupTable::new()));
+    /// const SERIAL_NOR_CONFIGURATION_BLOCK: nor::ConfigurationBlock = {
+    ///     let mut fcb = PRESET;
+    ///     let mem_cfg = fcb.mem_cfg_mut();
+    ///     *mem_cfg = mem_cfg.flash_size(SerialFlashRegion::A1, Density::from_mebibytes(16));
+    ///     fcb
+    /// };
+    /// ```
+    pub const fn mem_cfg_mut(&mut self) -> &mut flexspi::ConfigurationBlock {
+        &mut self.mem_cfg
+    }
+
+    /// Replace the FlexSPI configuration block.
+    ///
+    /// This method keeps the `deviceType` of this configuration block.
+    pub const fn with_mem_cfg(self, mem_cfg: flexspi::ConfigurationBlock) -> Self {
+        Self::with_tail(mem_cfg, self.tail)
+    }
+
+    /// Replace the FlexSPI lookup table.
+    ///
+    /// See [`flexspi::ConfigurationBlock::with_lookup_table`] for more information.
+    pub const fn with_lookup_table(mut self, lookup_table: flexspi::LookupTable) -> Self {
+        self.mem_cfg = self.mem_cfg.with_lookup_table(lookup_table);
+        self
+    }
+
+    /// Copy `bytes` into the configuration block, starting at the byte `offset`.
+    ///
+    /// Use this to set fields that this crate doesn't model. The offset is
+    /// relative to the start of the 512 byte configuration block, as described in
+    /// your chip's reference manual. This method performs no checking on the
+    /// input, and it may overwrite values set by other methods.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the bytes extend beyond the end of the configuration block.
+    ///
+    /// # Safety
+    ///
+    /// Some fields are represented by enums. If the bytes overlap one of these fields,
+    /// the bytes must be a valid value for the enum.
+    pub const unsafe fn raw_bytes(self, offset: usize, bytes: &[u8]) -> Self {
+        // Safety: caller upholds the requirements.
+        unsafe { crate::raw::write_bytes(self, offset, bytes) }
+    }
+}
@@ -0,0 +1,500 @@
+//! Serial NAND configuration blocks and fields
+//!
+//! The serial NAND configuration block shares the FlexSPI configuration block
+//! with the serial NOR configuration block. Its tail describes the NAND
+//! geometry, and how the boot ROM checks for ECC failures.
+
+use crate::{
+    flexspi::{self, Field},
+    parse::{ParseError, Reader},
+    serial_flash::{self, nor::SerialClockFrequency},
+};
+use core::mem::{offset_of, size_of};
+
+pub mod presets;
+
+/// A serial NAND configuration block
+///
+/// Unless otherwise specified, all unset fields are set to a bitpattern of zero.
+///
+/// ```
+/// use imxrt_boot_gen::serial_flash::nand;
+/// # use imxrt_boot_gen::flexspi::{self, LookupTable};
+///
+/// # const FLEXSPI_CONFIGURATION_BLOCK: flexspi::ConfigurationBlock = flexspi::ConfigurationBlock::new(LookupTable::new());
+/// const SERIAL_NAND_CONFIGURATION_BLOCK: nand::ConfigurationBlock =
+///     nand::ConfigurationBlock::new(FLEXSPI_CONFIGURATION_BLOCK)
+///         .page_data_size(2048)
+///         .page_total_size(2048 + 64)
+///         .pages_per_block(64)
+///         .blocks_per_device(1024)
+///         .finish();
+/// ```
+///
+/// ## Geometry
+///
+/// The boot ROM can't find a boot image without the page data size and the
+/// pages per block. End your builder chain with
+/// [`finish`](ConfigurationBlock::finish) to turn a missing `page_data_size` or
+/// `pages_per_block` into a compile-time error.
+pub type ConfigurationBlock = serial_flash::ConfigurationBlock<Tail>;
+
+/// The serial NAND-specific tail of a [`ConfigurationBlock`]
+///
+/// Use the [`ConfigurationBlock`] methods to set these fields.
+#[derive(Debug, Clone, Copy)]
+#[repr(C)]
+pub struct Tail {
+    page_data_size: u32,
+    page_total_size: u32,
+    pages_per_block: u32,
+    bypass_read_status: u8,
+    bypass_ecc_read: u8,
+    has_multi_planes: u8,
+    skip_odd_blocks: u8,
+    ecc_check_custom_enable: u8,
+    ip_cmd_serial_clk_freq: SerialClockFrequency,
+    read_page_time_us: u16,
+    ecc_status_mask: u32,
+    ecc_failure_mask: u32,
+    blocks_per_device: u32,
+    _reserved: [u8; 32],
+}
+
+const _: () = assert!(64 == size_of::<Tail>());
+
+impl Tail {
+    const fn to_bytes(self) -> [u8; 64] {
+        use crate::raw::put;
+
+        let mut bytes = [0; 64];
+        put(
+            &mut bytes,
+            offset_of!(Self, page_data_size),
+            &self.page_data_size.to_le_bytes(),
+        );
+        put(
+            &mut bytes,
+            offset_of!(Self, page_total_size),
+            &self.page_total_size.to_le_bytes(),
+        );
+        put(
+            &mut bytes,
+            offset_of!(Self, pages_per_block),
+            &self.pages_per_block.to_le_bytes(),
+        );
+        bytes[offset_of!(Self, bypass_read_status)] = self.bypass_read_status;
+        bytes[offset_of!(Self, bypass_ecc_read)] = self.bypass_ecc_read;
+        bytes[offset_of!(Self, has_multi_planes)] = self.has_multi_planes;
+        bytes[offset_of!(Self, skip_odd_blocks)] = self.skip_odd_blocks;
+        bytes[offset_of!(Self, ecc_check_custom_enable)] = self.ecc_check_custom_enable;
+        bytes[offset_of!(Self, ip_cmd_serial_clk_freq)] = self.ip_cmd_serial_clk_freq as u8;
+        put(
+            &mut bytes,
+            offset_of!(Self, read_page_time_us),
+            &self.read_page_time_us.to_le_bytes(),
+        );
+        put(
+            &mut bytes,
+            offset_of!(Self, ecc_status_mask),
+            &self.ecc_status_mask.to_le_bytes(),
+        );
+        put(
+            &mut bytes,
+            offset_of!(Self, ecc_failure_mask),
+            &self.ecc_failure_mask.to_le_bytes(),
+        );
+        put(
+            &mut bytes,
+            offset_of!(Self, blocks_per_device),
+            &self.blocks_per_device.to_le_bytes(),
+        );
+        put(&mut bytes, offset_of!(Self, _reserved), &self._reserved);
+        bytes
+    }
+    /// Decode the tail from a complete serial NAND configuration block.
+    fn from_bytes(bytes: &[u8; ConfigurationBlock::SIZE]) -> Result<Self, ParseError> {
+        let r = Reader(bytes);
+        let base = flexspi::ConfigurationBlock::SIZE;
+        Ok(Tail {
+            page_data_size: r.u32(base + offset_of!(Self, page_data_size)),
+            page_total_size: r.u32(base + offset_of!(Self, page_total_size)),
+            pages_per_block: r.u32(base + offset_of!(Self, pages_per_block)),
+            bypass_read_status: r.u8(base + offset_of!(Self, bypass_read_status)),
+            bypass_ecc_read: r.u8(base + offset_of!(Self, bypass_ecc_read)),
+            has_multi_planes: r.u8(base + offset_of!(Self, has_multi_planes)),
+            skip_odd_blocks: r.u8(base + offset_of!(Self, skip_odd_blocks)),
+            ecc_check_custom_enable: r.u8(base + offset_of!(Self, ecc_check_custom_enable)),
+            ip_cmd_serial_clk_freq: r.field(
+                "ipCmdSerialClkFreq",
+                base + offset_of!(Self, ip_cmd_serial_clk_freq),
+                SerialClockFrequency::from_raw,
+            )?,
+            read_page_time_us: r.u16(base + offset_of!(Self, read_page_time_us)),
+            ecc_status_mask: r.u32(base + offset_of!(Self, ecc_status_mask)),
+            ecc_failure_mask: r.u32(base + offset_of!(Self, ecc_failure_mask)),
+            blocks_per_device: r.u32(base + offset_of!(Self, blocks_per_device)),
+            _reserved: r.array(base + offset_of!(Self, _reserved)),
+        })
+    }
+}
+
+impl PartialEq for ConfigurationBlock {
+    fn eq(&self, other: &Self) -> bool {
+        self.eq_bytes(other)
+    }
+}
+
+impl Eq for ConfigurationBlock {}
+
+impl serial_flash::private::Sealed for Tail {}
+impl serial_flash::DeviceTail for Tail {
+    const DEVICE_TYPE: u8 = 2;
+}
+
+impl ConfigurationBlock {
+    /// Create a new serial NAND configuration block based on the FlexSPI
+    /// configuration block
+    pub const fn new(mem_cfg: flexspi::ConfigurationBlock) -> Self {
+        Self::with_tail(
+            mem_cfg,
+            Tail {
+                page_data_size: 0,
+                page_total_size: 0,
+                pages_per_block: 0,
+                bypass_read_status: 0,
+                bypass_ecc_read: 0,
+                has_multi_planes: 0,
+                skip_odd_blocks: 0,
+                ecc_check_custom_enable: 0,
+                ip_cmd_serial_clk_freq: SerialClockFrequency::NoChange,
+                read_page_time_us: 0,
+                ecc_status_mask: 0,
+                ecc_failure_mask: 0,
+                blocks_per_device: 0,
+                _reserved: [0; 32],
+            },
+        )
+    }
+    /// Set the size of a page's data area, in bytes.
+    pub const fn page_data_size(mut self, page_data_size: u32) -> Self {
+        self.tail.page_data_size = page_data_size;
+        self
+    }
+    /// Set the size of a page, including its spare area, in bytes.
+    pub const fn page_total_size(mut self, page_total_size: u32) -> Self {
+        self.tail.page_total_size = page_total_size;
+        self
+    }
+    /// Set the number of pages in each block.
+    pub const fn pages_per_block(mut self, pages_per_block: u32) -> Self {
+        self.tail.pages_per_block = pages_per_block;
+        self
+    }
+    /// Set the number of blocks in the device.
+    pub const fn blocks_per_device(mut self, blocks_per_device: u32) -> Self {
+        self.tail.blocks_per_device = blocks_per_device;
+        self
+    }
+    /// Set `bypassReadStatus`.
+    ///
+    /// When set, the boot ROM doesn't poll the status register after it
+    /// loads a page into the cache.
+    pub const fn bypass_read_status(mut self, bypass_read_status: bool) -> Self {
+        self.tail.bypass_read_status = bypass_read_status as u8;
+        self
+    }
+    /// Set `bypassEccRead`.
+    ///
+    /// When set, the boot ROM doesn't check the ECC status after it reads
+    /// a page.
+    pub const fn bypass_ecc_read(mut self, bypass_ecc_read: bool) -> Self {
+        self.tail.bypass_ecc_read = bypass_ecc_read as u8;
+        self
+    }
+    /// Set `hasMultiPlanes` for a device with more than one plane.
+    pub const fn has_multi_planes(mut self, has_multi_planes: bool) -> Self {
+        self.tail.has_multi_planes = has_multi_planes as u8;
+        self
+    }
+    /// Set `skippOddBlocks` to skip odd blocks when searching for the image.
+    pub const fn skip_odd_blocks(mut self, skip_odd_blocks: bool) -> Self {
+        self.tail.skip_odd_blocks = skip_odd_blocks as u8;
+        self
+    }
+    /// Check the ECC status with custom masks.
+    ///
+    /// The boot ROM masks the status with `status_mask`, then compares the
+    /// result with `failure_mask` to detect an ECC failure. This sets
+    /// `eccCheckCustomEnable`.
+    pub const fn ecc_check_custom(mut self, status_mask: u32, failure_mask: u32) -> Self {
+        self.tail.ecc_check_custom_enable = 1;
+        self.tail.ecc_status_mask = status_mask;
+        self.tail.ecc_failure_mask = failure_mask;
+        self
+    }
+    /// Set the serial clock frequency
+    pub const fn ip_cmd_serial_clk_freq(
+        mut self,
+        serial_clock_frequency: SerialClockFrequency,
+    ) -> Self {
+        self.tail.ip_cmd_serial_clk_freq = serial_clock_frequency;
+        self
+    }
+    /// Set the time to load a page into the cache, in microseconds.
+    pub const fn read_page_time_us(mut self, read_page_time_us: u16) -> Self {
+        self.tail.read_page_time_us = read_page_time_us;
+        self
+    }
+    /// Returns the size of a page's data area.
+    pub const fn get_page_data_size(&self) -> u32 {
+        self.tail.page_data_size
+    }
+    /// Returns the size of a page, including its spare area.
+    pub const fn get_page_total_size(&self) -> u32 {
+        self.tail.page_total_size
+    }
+    /// Returns the number of pages in each block.
+    pub const fn get_pages_per_block(&self) -> u32 {
+        self.tail.pages_per_block
+    }
+    /// Returns the number of blocks in the device.
+    pub const fn get_blocks_per_device(&self) -> u32 {
+        self.tail.blocks_per_device
+    }
+    /// Returns `true` if the boot ROM doesn't poll the status register.
+    pub const fn get_bypass_read_status(&self) -> bool {
+        self.tail.bypass_read_status != 0
+    }
+    /// Returns `true` if the boot ROM doesn't check the ECC status.
+    pub const fn get_bypass_ecc_read(&self) -> bool {
+        self.tail.bypass_ecc_read != 0
+    }
+    /// Returns `true` if the device has more than one plane.
+    pub const fn get_has_multi_planes(&self) -> bool {
+        self.tail.has_multi_planes != 0
+    }
+    /// Returns `true` if the boot ROM skips odd blocks.
+    pub const fn get_skip_odd_blocks(&self) -> bool {
+        self.tail.skip_odd_blocks != 0
+    }
+    /// Returns the ECC status and failure masks, or `None` if the custom
+    /// ECC check isn't enabled.
+    pub const fn get_ecc_check_custom(&self) -> Option<(u32, u32)> {
+        if self.tail.ecc_check_custom_enable != 0 {
+            Some((self.tail.ecc_status_mask, self.tail.ecc_failure_mask))
+        } else {
+            None
+        }
+    }
+    /// Returns the IP command serial clock frequency.
+    pub const fn get_ip_cmd_serial_clk_freq(&self) -> SerialClockFrequency {
+        self.tail.ip_cmd_serial_clk_freq
+    }
+    /// Returns the time to load a page into the cache, in microseconds.
+    pub const fn get_read_page_time_us(&self) -> u16 {
+        self.tail.read_page_time_us
+    }
+    /// Returns `true` if the two configuration blocks have the same bytes.
+    ///
+    /// This is the `const` equivalent of `==`.
+    pub const fn eq_bytes(&self, other: &Self) -> bool {
+        crate::raw::eq_bytes(&self.to_bytes(), &other.to_bytes())
+    }
+    /// Serialize the configuration block into its in-memory layout.
+    ///
+    /// The bytes don't depend on the host's endianness.
+    pub const fn to_bytes(&self) -> [u8; Self::SIZE] {
+        let mut bytes = [0; Self::SIZE];
+        crate::raw::put(&mut bytes, 0, &self.mem_cfg.to_bytes());
+        crate::raw::put(
+            &mut bytes,
+            flexspi::ConfigurationBlock::SIZE,
+            &self.tail.to_bytes(),
+        );
+        bytes
+    }
+    /// Decode a serial NAND configuration block from its in-memory layout.
+    ///
+    /// This is the inverse of [`to_bytes`](Self::to_bytes). It checks that
+    /// `deviceType` indicates serial NAND.
+    ///
+    /// ```
+    /// use imxrt_boot_gen::serial_flash::nand;
+    /// use imxrt_boot_gen::flexspi::{self, LookupTable};
+    ///
+    /// let block = nand::ConfigurationBlock::new(flexspi::ConfigurationBlock::new(LookupTable::new()))
+    ///     .page_data_size(2048)
+    ///     .pages_per_block(64);
+    /// let bytes = block.to_bytes();
+    /// assert_eq!(nand::ConfigurationBlock::from_bytes(&bytes), Ok(block));
+    /// ```
+    pub fn from_bytes(bytes: &[u8; Self::SIZE]) -> Result<Self, ParseError> {
+        let mem_cfg = flexspi::ConfigurationBlock::from_bytes(
+            bytes[..flexspi::ConfigurationBlock::SIZE]
+                .try_into()
+                .unwrap(),
+        )?;
+        let device_type = mem_cfg.get_device_type();
+        if device_type != <Tail as serial_flash::DeviceTail>::DEVICE_TYPE {
+            return Err(ParseError::DeviceType(device_type));
+        }
+        let tail = Tail::from_bytes(bytes)?;
+        Ok(Self::with_tail(mem_cfg, tail))
+    }
+    /// Check that the page data size and pages per block are set.
+    ///
+    /// Call `finish` at the end of your builder chain.
+    ///
+    /// # Panics
+    ///
+    /// Panics if either the page data size or the pages per block is zero. When
+    /// evaluated in a `const` or `static` initializer, the panic is a compile-time
+    /// error.
+    pub const fn finish(self) -> Self {
+        assert!(
+            self.tail.page_data_size != 0,
+            "Serial NAND page data size is not set"
+        );
+        assert!(
+            self.tail.pages_per_block != 0,
+            "Serial NAND pages per block is not set"
+        );
+        self
+    }
+}
+
+macro_rules! field {
+    ($name:literal, $field:ident, $ty:ty) => {
+        Field {
+            name: $name,
+            offset: offset_of!(ConfigurationBlock, tail) + offset_of!(Tail, $field),
+            size: size_of::<$ty>(),
+        }
+    };
+}
+
+const TAIL_FIELDS: [Field; 13] = [
+    field!("pageDataSize", page_data_size, u32),
+    field!("pageTotalSize", page_total_size, u32),
+    field!("pagesPerBlock", pages_per_block, u32),
+    field!("bypassReadStatus", bypass_read_status, u8),
+    field!("bypassEccRead", bypass_ecc_read, u8),
+    field!("hasMultiPlanes", has_multi_planes, u8),
+    field!("skippOddBlocks", skip_odd_blocks, u8),
+    field!("eccCheckCustomEnable", ecc_check_custom_enable, u8),
+    field!("ipCmdSerialClkFreq", ip_cmd_serial_clk_freq, u8),
+    field!("readPageTimeUs", read_page_time_us, u16),
+    field!("eccStatusMask", ecc_status_mask, u32),
+    field!("eccFailureMask", ecc_failure_mask, u32),
+    field!("blocksPerDevice", blocks_per_device, u32),
+];
+
+/// The named fields of the serial NAND configuration block, in offset order
+///
+/// The table starts with the FlexSPI configuration block's [`FIELDS`](flexspi::FIELDS),
+/// and it excludes reserved fields.
+pub const FIELDS: [Field; flexspi::FIELDS.len() + TAIL_FIELDS.len()] = {
+    let mut fields = [flexspi::FIELDS[0]; flexspi::FIELDS.len() + TAIL_FIELDS.len()];
+    let mut idx = 0;
+    while idx < flexspi::FIELDS.len() {
+        fields[idx] = flexspi::FIELDS[idx];
+        idx += 1;
+    }
+    while idx < fields.len() {
+        fields[idx] = TAIL_FIELDS[idx - flexspi::FIELDS.len()];
+        idx += 1;
+    }
+    fields
+};
+
+// Safety: the FlexSPI configuration block has no padding, and it's followed
+// by the 64-byte tail. The tail's `repr(C)` fields are naturally aligned, so
+// it has no padding either.
+#[cfg(feature = "bytemuck")]
+unsafe impl bytemuck::NoUninit for ConfigurationBlock {}
+
+#[cfg(test)]
+mod test {
+    use super::{flexspi, ConfigurationBlock, SerialClockFrequency, FIELDS};
+    use crate::flexspi::{layout::check_fields, LookupTable};
+
+    fn block() -> ConfigurationBlock {
+        ConfigurationBlock::new(flexspi::ConfigurationBlock::new(LookupTable::new()))
+            .page_data_size(2048)
+            .page_total_size(2112)
+            .pages_per_block(64)
+            .blocks_per_device(1024)
+            .has_multi_planes(true)
+            .ecc_check_custom(0x30, 0x20)
+            .ip_cmd_serial_clk_freq(SerialClockFrequency::MHz30)
+            .read_page_time_us(0x0102)
+            .finish()
+    }
+
+    #[test]
+    fn to_bytes() {
+        let bytes = block().to_bytes();
+        assert_eq!(bytes[0x044], 2, "deviceType is serial NAND");
+        assert_eq!(&bytes[0x1C0..0x1C4], &2048u32.to_le_bytes());
+        assert_eq!(&bytes[0x1C4..0x1C8], &2112u32.to_le_bytes());
+        assert_eq!(&bytes[0x1C8..0x1CC], &64u32.to_le_bytes());
+        assert_eq!(&bytes[0x1CC..0x1D2], &[0, 0, 1, 0, 1, 1]);
+        assert_eq!(&bytes[0x1D2..0x1D4], &[0x02, 0x01]);
+        assert_eq!(&bytes[0x1D4..0x1D8], &0x30u32.to_le_bytes());
+        assert_eq!(&bytes[0x1D8..0x1DC], &0x20u32.to_le_bytes());
+        assert_eq!(&bytes[0x1DC..0x1E0], &1024u32.to_le_bytes());
+        assert!(bytes[0x1E0..].iter().all(|byte| *byte == 0));
+    }
+
+    #[cfg(target_endian = "little")]
+    #[test]
+    fn to_bytes_matches_memory() {
+        let block = block();
+        let memory: [u8; 512] = unsafe { core::mem::transmute(block) };
+        assert_eq!(block.to_bytes(), memory);
+    }
+
+    #[test]
+    fn from_bytes() {
+        use crate::ParseError;
+
+        let bytes = block().to_bytes();
+        assert_eq!(ConfigurationBlock::from_bytes(&bytes), Ok(block()));
+
+        let mut device_type = bytes;
+        device_type[0x44] = 1;
+        assert_eq!(
+            ConfigurationBlock::from_bytes(&device_type),
+            Err(ParseError::DeviceType(1))
+        );
+
+        let mut clock = bytes;
+        clock[0x1D1] = 0xFF;
+        assert_eq!(
+            ConfigurationBlock::from_bytes(&clock),
+            Err(ParseError::Field {
+                name: "ipCmdSerialClkFreq",
+                offset: 0x1D1,
+                value: 0xFF
+            })
+        );
+    }
+
+    #[test]
+    fn getters() {
+        let block = block();
+        assert_eq!(block.get_page_data_size(), 2048);
+        assert_eq!(block.get_pages_per_block(), 64);
+        assert!(block.get_has_multi_planes());
+        assert!(!block.get_bypass_ecc_read());
+        assert_eq!(block.get_ecc_check_custom(), Some((0x30, 0x20)));
+    }
+
+    #[test]
+    fn fields() {
+        check_fields(&FIELDS, 512);
+        let blocks = FIELDS.iter().find(|f| f.name == "blocksPerDevice").unwrap();
+        assert_eq!((blocks.offset, blocks.size), (0x1DC, 4));
+    }
+}
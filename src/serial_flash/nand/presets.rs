@@ -0,0 +1,11 @@
+//! Sequences and geometry for common serial NAND flash parts
+//!
+//! Each module describes one vendor's parts with lookup table sequences, and
+//! a function that applies the part's geometry to a configuration block.
+//!
+//! The presets don't assign the sequences to lookup table indices. The boot
+//! ROM expects each serial NAND command at a fixed index, and the indices
+//! are listed with the serial NAND configuration block in your chip's
+//! reference manual.
+
+pub mod winbond;
@@ -0,0 +1,164 @@
+//! Winbond serial NAND flash
+//!
+//! The W25N01GV is a 1Gb serial NAND part with 2048 byte pages, a 64 byte
+//! spare area per page, 64 pages per block, and 1024 blocks. The boot ROM
+//! loads a page into the part's cache with [`PAGE_DATA_READ`], polls
+//! [`READ_STATUS`] until the part isn't busy, then reads the cache with
+//! [`READ_FROM_CACHE`].
+//!
+//! The sequences assume the part is in buffer read mode, the default for the
+//! W25N01GVxxIG parts. The W25N01GVxxIT parts start in continuous read mode,
+//! which the boot ROM doesn't support.
+//!
+//! The part's ECC is enabled by default. After a page read, the ECC bits of
+//! the status register report whether the part corrected the data. The
+//! configuration block treats an uncorrectable ECC error as a failure.
+//!
+//! The part ships with its bad blocks marked in the spare area. This preset
+//! doesn't model bad block handling, and it doesn't place its sequences in a
+//! lookup table: the boot ROM's serial NAND lookup table indices, and its bad
+//! block search, aren't described by this crate. Program your boot image into
+//! known-good blocks.
+
+use crate::flexspi::{opcodes::sdr::*, Instr, Pads, Sequence, SequenceBuilder};
+#[cfg(any(feature = "imxrt1020", feature = "imxrt1024", feature = "imxrt1170"))]
+use crate::{
+    flexspi::{self, ColumnAddressWidth, FlashPadType, LookupTable, Profile, SerialClockFrequency},
+    serial_flash::{nand, nor::SerialClockFrequency as IpSerialClockFrequency},
+    Imxrt,
+};
+
+/// The size of a page's data area, in bytes.
+pub const PAGE_DATA_SIZE: u32 = 2048;
+/// The size of a page, including its spare area, in bytes.
+pub const PAGE_TOTAL_SIZE: u32 = 2048 + 64;
+/// The number of pages in each block.
+pub const PAGES_PER_BLOCK: u32 = 64;
+/// The number of blocks in the W25N01GV.
+pub const W25N01GV_BLOCKS: u32 = 1024;
+
+/// The ECC bits of status register 3.
+pub const ECC_STATUS_MASK: u32 = 0x30;
+/// The ECC bits for an uncorrectable error.
+pub const ECC_FAILURE_MASK: u32 = 0x20;
+
+/// Load the page at the row address into the cache.
+///
+/// The part expects eight dummy bits before the 16-bit page address. The
+/// sequence sends them as the upper byte of a 24-bit address.
+pub const PAGE_DATA_READ: Sequence = SequenceBuilder::new()
+    .instr(Instr::new(CMD, Pads::One, 0x13))
+    .instr(Instr::new(RADDR, Pads::One, 24))
+    .build();
+
+/// Read the cache from the column address, with the fast read quad output
+/// command.
+pub const READ_FROM_CACHE: Sequence = SequenceBuilder::new()
+    .instr(Instr::new(CMD, Pads::One, 0x6B))
+    .instr(Instr::new(CADDR, Pads::One, 16))
+    .instr(Instr::new(DUMMY, Pads::One, 8))
+    .instr(Instr::new(READ, Pads::Four, 0x80))
+    .build();
+
+/// Read status register 3, which has the busy and ECC bits.
+pub const READ_STATUS: Sequence = SequenceBuilder::new()
+    .instr(Instr::new(CMD, Pads::One, 0x0F))
+    .instr(Instr::new(CMD, Pads::One, 0xC0))
+    .instr(Instr::new(READ, Pads::One, 0x01))
+    .build();
+
+/// Set the write enable latch.
+pub const WRITE_ENABLE: Sequence = SequenceBuilder::new()
+    .instr(Instr::new(CMD, Pads::One, 0x06))
+    .build();
+
+/// Erase the block that contains the row address.
+pub const BLOCK_ERASE: Sequence = SequenceBuilder::new()
+    .instr(Instr::new(CMD, Pads::One, 0xD8))
+    .instr(Instr::new(RADDR, Pads::One, 24))
+    .build();
+
+/// Load program data into the cache at the column address, with the quad
+/// program data load command.
+pub const PROGRAM_LOAD: Sequence = SequenceBuilder::new()
+    .instr(Instr::new(CMD, Pads::One, 0x32))
+    .instr(Instr::new(CADDR, Pads::One, 16))
+    .instr(Instr::new(WRITE, Pads::Four, 0x40))
+    .build();
+
+/// Program the cache into the page at the row address.
+pub const PROGRAM_EXECUTE: Sequence = SequenceBuilder::new()
+    .instr(Instr::new(CMD, Pads::One, 0x10))
+    .instr(Instr::new(RADDR, Pads::One, 24))
+    .build();
+
+/// Generate a W25N01GV configuration block using the `lookup_table`
+///
+/// Place this module's sequences in `lookup_table` at the indices that your
+/// chip's boot ROM expects. The configuration block
+///
+/// - starts from the [`Profile`] for the chip selected by the crate's feature.
+/// - reads on four pads at 100MHz, and runs IP commands at 30MHz.
+/// - uses 12 column address bits, which address the page and its spare area.
+/// - sets the part's geometry and ECC masks.
+/// - waits up to 60us for a page read with ECC.
+///
+/// ```
+/// use imxrt_boot_gen::flexspi::LookupTable;
+/// use imxrt_boot_gen::serial_flash::nand::{self, presets::winbond};
+///
+/// # const LUT: LookupTable = LookupTable::new();
+/// const FCB: nand::ConfigurationBlock = winbond::w25n01gv_fcb(LUT);
+/// ```
+#[cfg(any(feature = "imxrt1020", feature = "imxrt1024", feature = "imxrt1170"))]
+pub const fn w25n01gv_fcb(lookup_table: LookupTable) -> nand::ConfigurationBlock {
+    let mem_cfg = flexspi::ConfigurationBlock::new(lookup_table)
+        .profile(Profile::for_chip(Imxrt::SELECTED))
+        .column_address_width(ColumnAddressWidth::SerialNand12)
+        .serial_flash_pad_type(FlashPadType::Quad)
+        .serial_clk_freq(SerialClockFrequency::MHz100)
+        .flash_size_bytes(
+            flexspi::SerialFlashRegion::A1,
+            W25N01GV_BLOCKS * PAGES_PER_BLOCK * PAGE_DATA_SIZE,
+        );
+    nand::ConfigurationBlock::new(mem_cfg)
+        .page_data_size(PAGE_DATA_SIZE)
+        .page_total_size(PAGE_TOTAL_SIZE)
+        .pages_per_block(PAGES_PER_BLOCK)
+        .blocks_per_device(W25N01GV_BLOCKS)
+        .ecc_check_custom(ECC_STATUS_MASK, ECC_FAILURE_MASK)
+        .ip_cmd_serial_clk_freq(IpSerialClockFrequency::MHz30)
+        .read_page_time_us(60)
+        .finish()
+}
+
+#[cfg(test)]
+mod test {
+    use super::{PAGE_DATA_READ, READ_FROM_CACHE};
+
+    #[test]
+    fn sequences() {
+        assert_eq!(PAGE_DATA_READ.0[0].operand(), 0x13);
+        assert_eq!(PAGE_DATA_READ.0[1].operand(), 24);
+        // Quad output, after eight dummy cycles.
+        assert_eq!(READ_FROM_CACHE.0[2].operand(), 8);
+        assert_eq!(READ_FROM_CACHE.0[3].pads(), Some(2));
+    }
+
+    #[cfg(any(feature = "imxrt1020", feature = "imxrt1024", feature = "imxrt1170"))]
+    #[test]
+    fn fcb() {
+        use super::w25n01gv_fcb;
+        use crate::flexspi::{ColumnAddressWidth, LookupTable};
+
+        let fcb = w25n01gv_fcb(LookupTable::new());
+        assert_eq!(fcb.get_page_total_size(), 2112);
+        assert_eq!(fcb.get_blocks_per_device(), 1024);
+        assert_eq!(fcb.get_ecc_check_custom(), Some((0x30, 0x20)));
+        assert_eq!(
+            fcb.mem_cfg().get_column_address_width(),
+            ColumnAddressWidth::SerialNand12
+        );
+        assert_eq!(fcb.to_bytes()[0x44], 2, "deviceType is serial NAND");
+    }
+}
@@ -1,19 +1,46 @@
 //! Serial NOR configuration blocks and fields
 
-use crate::flexspi;
+use crate::{
+    flexspi,
+    parse::{ParseError, Reader},
+    serial_flash,
+};
+
+mod builder;
+mod command_set;
+mod device;
+mod layout;
+pub mod option;
+pub mod presets;
+pub mod sfdp;
+
+pub use builder::{FcbBuilder, ValidationError};
+pub use command_set::{ContinuousRead, DeviceMode, NorCommand, NorCommandSet, QuadEnable};
+pub use device::{recommended_fcb, to_safe_boot, NorFlashDevice};
+pub use layout::FIELDS;
 
 /// `ipCmdSerialClkFreq` field for serial NOR-specific FCB
 ///
-/// Chip specific value, not used by ROM.
-#[derive(Debug, Clone, Copy)]
+/// Chip specific value, not used by ROM. Use [`NoChange`](SerialClockFrequency::NoChange)
+/// to keep the serial clock that's used for reads; its raw value is zero.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(u8)]
 pub enum SerialClockFrequency {
     /// No change, keep current serial clock unchanged
+    ///
+    /// This is the default if not set with
+    /// [`ip_cmd_serial_clk_freq`](ConfigurationBlock::ip_cmd_serial_clk_freq).
     NoChange = 0,
     MHz30,
     MHz50,
     MHz60,
-    #[cfg(not(any(feature = "imxrt1170", feature = "imxrt1180")))]
+    #[cfg(not(any(
+        feature = "imxrt1170",
+        feature = "imxrt1180",
+        feature = "imxrt500",
+        feature = "imxrt600"
+    )))]
     MHz75,
     MHz80,
     MHz100,
@@ -22,7 +49,9 @@ pub enum SerialClockFrequency {
         feature = "imxrt1060",
         feature = "imxrt1064",
         feature = "imxrt1170",
-        feature = "imxrt1180"
+        feature = "imxrt1180",
+        feature = "imxrt500",
+        feature = "imxrt600"
     ))]
     MHz120,
     MHz133,
@@ -35,6 +64,149 @@ pub enum SerialClockFrequency {
     MHz166,
 }
 
+/// `serialNorType`, the kind of serial NOR flash
+#[cfg(any(
+    feature = "imxrt1170",
+    feature = "imxrt1180",
+    feature = "imxrt500",
+    feature = "imxrt600"
+))]
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(u8)]
+pub enum SerialNorType {
+    /// A standard SPI flash.
+    #[default]
+    StandardSpi = 0,
+    /// A HyperBus flash, like HyperFlash.
+    HyperBus = 1,
+    /// An xSPI flash, like an octal flash.
+    Xspi = 2,
+    /// A flash that reads without a command.
+    NoCmd = 3,
+}
+
+/// `flashStateCtx`, the flash state context
+///
+/// The boot ROM uses the flash state context to track the flash's mode. For
+/// instance, an octal DTR flash may boot in a non-default mode, and the boot ROM
+/// may need to send a sequence to exit or restore that mode. The context
+/// packs four bytes, which mirror the fields of NXP's `flash_run_context_t`:
+///
+/// | Byte | Field                  |
+/// | ---- | ---------------------- |
+/// | 0    | `por_mode`             |
+/// | 1    | `current_mode`         |
+/// | 2    | `exit_no_cmd_sequence` |
+/// | 3    | `restore_sequence`     |
+///
+/// See your chip's reference manual for the values of each field. If not set,
+/// the context is zero.
+///
+/// ```
+/// use imxrt_boot_gen::serial_flash::nor::FlashStateContext;
+///
+/// const CONTEXT: FlashStateContext = FlashStateContext::new()
+///     .por_mode(0x00)
+///     .current_mode(0x82);
+/// assert_eq!(CONTEXT.raw(), 0x0000_8200);
+/// ```
+#[cfg(any(
+    feature = "imxrt1170",
+    feature = "imxrt1180",
+    feature = "imxrt500",
+    feature = "imxrt600"
+))]
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(transparent)]
+pub struct FlashStateContext([u8; 4]);
+
+#[cfg(any(
+    feature = "imxrt1170",
+    feature = "imxrt1180",
+    feature = "imxrt500",
+    feature = "imxrt600"
+))]
+impl FlashStateContext {
+    /// Create a flash state context with all fields set to zero.
+    pub const fn new() -> Self {
+        FlashStateContext([0; 4])
+    }
+    /// Create a flash state context from the raw `flashStateCtx` value.
+    pub const fn from_raw(raw: u32) -> Self {
+        FlashStateContext(raw.to_le_bytes())
+    }
+    /// Returns the raw `flashStateCtx` value.
+    pub const fn raw(self) -> u32 {
+        u32::from_le_bytes(self.0)
+    }
+    /// Set the flash's mode after power-on reset.
+    pub const fn por_mode(mut self, por_mode: u8) -> Self {
+        self.0[0] = por_mode;
+        self
+    }
+    /// Set the flash's current mode.
+    pub const fn current_mode(mut self, current_mode: u8) -> Self {
+        self.0[1] = current_mode;
+        self
+    }
+    /// Set the sequence that exits the no-command mode.
+    pub const fn exit_no_cmd_sequence(mut self, exit_no_cmd_sequence: u8) -> Self {
+        self.0[2] = exit_no_cmd_sequence;
+        self
+    }
+    /// Set the sequence that restores the flash's mode.
+    pub const fn restore_sequence(mut self, restore_sequence: u8) -> Self {
+        self.0[3] = restore_sequence;
+        self
+    }
+}
+
+impl SerialClockFrequency {
+    /// Returns the frequency with the raw `ipcmdSerialClkFreq` value, or `None`
+    /// if the chip doesn't support that value.
+    pub(crate) fn from_raw(raw: u8) -> Option<Self> {
+        [
+            SerialClockFrequency::NoChange,
+            SerialClockFrequency::MHz30,
+            SerialClockFrequency::MHz50,
+            SerialClockFrequency::MHz60,
+            #[cfg(not(any(
+                feature = "imxrt1170",
+                feature = "imxrt1180",
+                feature = "imxrt500",
+                feature = "imxrt600"
+            )))]
+            SerialClockFrequency::MHz75,
+            SerialClockFrequency::MHz80,
+            SerialClockFrequency::MHz100,
+            #[cfg(any(
+                feature = "imxrt1040",
+                feature = "imxrt1060",
+                feature = "imxrt1064",
+                feature = "imxrt1170",
+                feature = "imxrt1180",
+                feature = "imxrt500",
+                feature = "imxrt600"
+            ))]
+            SerialClockFrequency::MHz120,
+            SerialClockFrequency::MHz133,
+            #[cfg(any(
+                feature = "imxrt1040",
+                feature = "imxrt1050",
+                feature = "imxrt1060",
+                feature = "imxrt1064"
+            ))]
+            SerialClockFrequency::MHz166,
+        ]
+        .into_iter()
+        .find(|frequency| *frequency as u8 == raw)
+    }
+}
+
+const _: () = assert!(SerialClockFrequency::NoChange as u8 == 0);
+const _: () = assert!(core::mem::size_of::<SerialClockFrequency>() == 1);
+
 /// A serial NOR configuration block
 ///
 /// This is the memory that you'll need to properly place in memory in order to
@@ -61,77 +233,368 @@ pub enum SerialClockFrequency {
 ///     nor::ConfigurationBlock::new(FLEXSPI_CONFIGURATION_BLOCK)
 ///         .page_size(256)
 ///         .sector_size(4096)
-///         .ip_cmd_serial_clk_freq(nor::SerialClockFrequency::MHz30);
+///         .ip_cmd_serial_clk_freq(nor::SerialClockFrequency::MHz30)
+///         .finish();
 /// ```
+///
+/// ## Geometry
+///
+/// The boot ROM can execute from a configuration block that doesn't describe
+/// the page and sector sizes, but its program and erase routines will fail.
+/// End your builder chain with [`finish`](ConfigurationBlock::finish) to turn
+/// a missing `page_size` or `sector_size` into a compile-time error.
+pub type ConfigurationBlock = serial_flash::ConfigurationBlock<Tail>;
+
+/// The serial NOR-specific tail of a [`ConfigurationBlock`]
+///
+/// Use the [`ConfigurationBlock`] methods to set these fields.
 #[derive(Debug, Clone, Copy)]
-#[repr(C, packed)]
-pub struct ConfigurationBlock {
-    mem_cfg: flexspi::ConfigurationBlock,
+#[repr(C)]
+pub struct Tail {
     page_size: u32,
     sector_size: u32,
     ip_cmd_serial_clk_freq: SerialClockFrequency,
     extras: Extras,
 }
 
+impl Tail {
+    const fn to_bytes(self) -> [u8; 64] {
+        use crate::raw::put;
+        use core::mem::offset_of;
+
+        let mut bytes = [0; 64];
+        put(
+            &mut bytes,
+            offset_of!(Self, page_size),
+            &self.page_size.to_le_bytes(),
+        );
+        put(
+            &mut bytes,
+            offset_of!(Self, sector_size),
+            &self.sector_size.to_le_bytes(),
+        );
+        bytes[offset_of!(Self, ip_cmd_serial_clk_freq)] = self.ip_cmd_serial_clk_freq as u8;
+        put(
+            &mut bytes,
+            offset_of!(Self, extras),
+            &self.extras.to_bytes(),
+        );
+        bytes
+    }
+    /// Decode the tail from a complete serial NOR configuration block.
+    fn from_bytes(bytes: &[u8; ConfigurationBlock::SIZE]) -> Result<Self, ParseError> {
+        use core::mem::offset_of;
+
+        let r = Reader(bytes);
+        let base = flexspi::ConfigurationBlock::SIZE;
+        Ok(Tail {
+            page_size: r.u32(base + offset_of!(Self, page_size)),
+            sector_size: r.u32(base + offset_of!(Self, sector_size)),
+            ip_cmd_serial_clk_freq: r.field(
+                "ipcmdSerialClkFreq",
+                base + offset_of!(Self, ip_cmd_serial_clk_freq),
+                SerialClockFrequency::from_raw,
+            )?,
+            extras: Extras::from_bytes(r.array(base + offset_of!(Self, extras))),
+        })
+    }
+}
+
+impl PartialEq for ConfigurationBlock {
+    fn eq(&self, other: &Self) -> bool {
+        self.eq_bytes(other)
+    }
+}
+
+impl Eq for ConfigurationBlock {}
+
+impl serial_flash::private::Sealed for Tail {}
+impl serial_flash::DeviceTail for Tail {
+    const DEVICE_TYPE: u8 = 1;
+}
+
+/// Serial NOR fields that only apply to the 1170, 1180, 500, and 600
+///
+/// Build the extras in a `const` expression, then assign them to a
+/// configuration block with [`ConfigurationBlock::extras`]. The serial NOR
+/// configuration block also has setters for each field.
+///
+/// ```
+/// use imxrt_boot_gen::serial_flash::nor;
+/// # use imxrt_boot_gen::flexspi::{self, LookupTable};
+///
+/// const EXTRAS: nor::Extras = nor::Extras::new()
+///     .block_size(64 * 1024)
+///     .serial_nor_type(nor::SerialNorType::StandardSpi);
+///
+/// # const FLEXSPI_CONFIGURATION_BLOCK: flexspi::ConfigurationBlock = flexspi::ConfigurationBlock::new(LookupTable::new());
+/// const SERIAL_NOR_CONFIGURATION_BLOCK: nor::ConfigurationBlock =
+///     nor::ConfigurationBlock::new(FLEXSPI_CONFIGURATION_BLOCK)
+///         .page_size(256)
+///         .sector_size(4096)
+///         .extras(EXTRAS)
+///         .finish();
+/// ```
+#[cfg(any(
+    feature = "imxrt1170",
+    feature = "imxrt1180",
+    feature = "imxrt500",
+    feature = "imxrt600"
+))]
 #[derive(Debug, Clone, Copy)]
 #[repr(C, packed)]
-struct Imxrt11xxExtras {
+pub struct Extras {
     is_uniform_block_size: u8,
     is_data_order_swapped: u8,
-    _reserved0: [u8; 5],
+    _reserved0: [u8; 1],
+    serial_nor_type: u8,
+    need_exit_no_cmd_mode: u8,
+    half_clk_for_non_read_cmd: u8,
+    need_restore_no_cmd_mode: u8,
     block_size: u32,
     flash_state_ctx: u32,
     _reserved1: [u8; 40],
 }
 
-const _: () = assert!(55 == core::mem::size_of::<Imxrt11xxExtras>());
+/// Reserved on chips other than the 1170, 1180, 500, and 600.
+#[cfg(not(any(
+    feature = "imxrt1170",
+    feature = "imxrt1180",
+    feature = "imxrt500",
+    feature = "imxrt600"
+)))]
+#[derive(Debug, Clone, Copy)]
+#[repr(transparent)]
+struct Extras([u8; 55]);
+
+const _: () = assert!(55 == core::mem::size_of::<Extras>());
 
-#[cfg(any(feature = "imxrt1170", feature = "imxrt1180"))]
-type Extras = Imxrt11xxExtras;
+#[cfg(not(any(
+    feature = "imxrt1170",
+    feature = "imxrt1180",
+    feature = "imxrt500",
+    feature = "imxrt600"
+)))]
+impl Extras {
+    const fn new() -> Self {
+        Extras([0u8; 55])
+    }
+    const fn to_bytes(self) -> [u8; 55] {
+        self.0
+    }
+    const fn from_bytes(bytes: [u8; 55]) -> Self {
+        Extras(bytes)
+    }
+}
 
-#[cfg(not(any(feature = "imxrt1170", feature = "imxrt1180")))]
-type Extras = [u8; core::mem::size_of::<Imxrt11xxExtras>()];
+#[cfg(any(
+    feature = "imxrt1170",
+    feature = "imxrt1180",
+    feature = "imxrt500",
+    feature = "imxrt600"
+))]
+impl Default for Extras {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 
-const fn extras() -> Extras {
-    #[cfg(any(feature = "imxrt1170", feature = "imxrt1180"))]
-    {
+#[cfg(any(
+    feature = "imxrt1170",
+    feature = "imxrt1180",
+    feature = "imxrt500",
+    feature = "imxrt600"
+))]
+impl Extras {
+    /// Create the extras with their default values.
+    ///
+    /// By default, the block size equals the sector size. All other fields
+    /// are zero.
+    pub const fn new() -> Self {
         Extras {
             // By default, signal that block size equals sector size.
             is_uniform_block_size: 1u8,
             is_data_order_swapped: 0u8,
-            _reserved0: [0u8; 5],
+            _reserved0: [0u8; 1],
+            serial_nor_type: SerialNorType::StandardSpi as u8,
+            need_exit_no_cmd_mode: 0u8,
+            half_clk_for_non_read_cmd: 0u8,
+            need_restore_no_cmd_mode: 0u8,
             block_size: 0u32,
             flash_state_ctx: 0u32,
             _reserved1: [0u8; 40],
         }
     }
-    #[cfg(not(any(feature = "imxrt1170", feature = "imxrt1180")))]
-    {
-        [0u8; core::mem::size_of::<Imxrt11xxExtras>()]
+    /// Set the serial NOR block size if it differs from the sector size.
+    ///
+    /// By default, the configuration block signals to the hardware that the
+    /// sector size is the same as the block size. Calling this will override
+    /// that setting, allowing you to configure a different block size.
+    ///
+    /// The behavior is unspecified if you call this with a block size that's
+    /// equal to the sector size.
+    pub const fn block_size(mut self, block_size: u32) -> Self {
+        self.is_uniform_block_size = 0u8;
+        self.block_size = block_size;
+        self
+    }
+    /// Set `isDataOrderSwapped`.
+    ///
+    /// Some octal flashes return data in swapped byte order when they operate
+    /// in DDR mode; the data order is (D1, D0, D3, D2) instead of (D0, D1, D2, D3).
+    /// Set this to `true` for those flashes. If not set, this defaults to
+    /// `false`.
+    pub const fn is_data_order_swapped(mut self, is_data_order_swapped: bool) -> Self {
+        self.is_data_order_swapped = is_data_order_swapped as u8;
+        self
+    }
+    /// Set `serialNorType`.
+    ///
+    /// If not set, this defaults to [`SerialNorType::StandardSpi`].
+    pub const fn serial_nor_type(mut self, serial_nor_type: SerialNorType) -> Self {
+        self.serial_nor_type = serial_nor_type as u8;
+        self
+    }
+    /// Set `needExitNoCmdMode`.
+    ///
+    /// When `true`, the boot ROM exits the flash's no-command (continuous read)
+    /// mode before it executes other IP commands. If not set, this defaults to
+    /// `false`.
+    pub const fn need_exit_no_cmd_mode(mut self, need_exit_no_cmd_mode: bool) -> Self {
+        self.need_exit_no_cmd_mode = need_exit_no_cmd_mode as u8;
+        self
+    }
+    /// Set `halfClkForNonReadCmd`.
+    ///
+    /// When `true`, the boot ROM halves the serial clock frequency for commands
+    /// that aren't reads. If not set, this defaults to `false`.
+    pub const fn half_clk_for_non_read_cmd(mut self, half_clk_for_non_read_cmd: bool) -> Self {
+        self.half_clk_for_non_read_cmd = half_clk_for_non_read_cmd as u8;
+        self
+    }
+    /// Set `needRestoreNoCmdMode`.
+    ///
+    /// When `true`, the boot ROM restores the flash's no-command (continuous
+    /// read) mode after it executes an IP command. If not set, this defaults
+    /// to `false`.
+    pub const fn need_restore_no_cmd_mode(mut self, need_restore_no_cmd_mode: bool) -> Self {
+        self.need_restore_no_cmd_mode = need_restore_no_cmd_mode as u8;
+        self
+    }
+    /// Set `flashStateCtx`.
+    ///
+    /// See [`FlashStateContext`] for more information.
+    pub const fn flash_state_ctx(mut self, flash_state_ctx: FlashStateContext) -> Self {
+        self.flash_state_ctx = flash_state_ctx.raw();
+        self
+    }
+    /// Returns the block size, or `None` if the block size equals the sector
+    /// size.
+    pub const fn get_block_size(&self) -> Option<u32> {
+        if self.is_uniform_block_size != 0 {
+            None
+        } else {
+            Some(self.block_size)
+        }
+    }
+    /// Returns `isDataOrderSwapped`.
+    pub const fn get_is_data_order_swapped(&self) -> bool {
+        self.is_data_order_swapped != 0
+    }
+    /// Returns `serialNorType`, or `None` if the value isn't known.
+    pub const fn get_serial_nor_type(&self) -> Option<SerialNorType> {
+        Some(match self.serial_nor_type {
+            0 => SerialNorType::StandardSpi,
+            1 => SerialNorType::HyperBus,
+            2 => SerialNorType::Xspi,
+            3 => SerialNorType::NoCmd,
+            _ => return None,
+        })
+    }
+    /// Returns `needExitNoCmdMode`.
+    pub const fn get_need_exit_no_cmd_mode(&self) -> bool {
+        self.need_exit_no_cmd_mode != 0
+    }
+    /// Returns `halfClkForNonReadCmd`.
+    pub const fn get_half_clk_for_non_read_cmd(&self) -> bool {
+        self.half_clk_for_non_read_cmd != 0
+    }
+    /// Returns `needRestoreNoCmdMode`.
+    pub const fn get_need_restore_no_cmd_mode(&self) -> bool {
+        self.need_restore_no_cmd_mode != 0
+    }
+    /// Returns `flashStateCtx`.
+    pub const fn get_flash_state_ctx(&self) -> FlashStateContext {
+        FlashStateContext::from_raw(self.flash_state_ctx)
+    }
+    const fn to_bytes(self) -> [u8; 55] {
+        use crate::raw::put;
+        use core::mem::offset_of;
+
+        let mut bytes = [0; 55];
+        bytes[offset_of!(Self, is_uniform_block_size)] = self.is_uniform_block_size;
+        bytes[offset_of!(Self, is_data_order_swapped)] = self.is_data_order_swapped;
+        put(&mut bytes, offset_of!(Self, _reserved0), &self._reserved0);
+        bytes[offset_of!(Self, serial_nor_type)] = self.serial_nor_type;
+        bytes[offset_of!(Self, need_exit_no_cmd_mode)] = self.need_exit_no_cmd_mode;
+        bytes[offset_of!(Self, half_clk_for_non_read_cmd)] = self.half_clk_for_non_read_cmd;
+        bytes[offset_of!(Self, need_restore_no_cmd_mode)] = self.need_restore_no_cmd_mode;
+        let block_size = self.block_size;
+        put(
+            &mut bytes,
+            offset_of!(Self, block_size),
+            &block_size.to_le_bytes(),
+        );
+        let flash_state_ctx = self.flash_state_ctx;
+        put(
+            &mut bytes,
+            offset_of!(Self, flash_state_ctx),
+            &flash_state_ctx.to_le_bytes(),
+        );
+        put(&mut bytes, offset_of!(Self, _reserved1), &self._reserved1);
+        bytes
+    }
+    fn from_bytes(bytes: [u8; 55]) -> Self {
+        use core::mem::offset_of;
+
+        let r = Reader(&bytes);
+        Extras {
+            is_uniform_block_size: r.u8(offset_of!(Self, is_uniform_block_size)),
+            is_data_order_swapped: r.u8(offset_of!(Self, is_data_order_swapped)),
+            _reserved0: r.array(offset_of!(Self, _reserved0)),
+            serial_nor_type: r.u8(offset_of!(Self, serial_nor_type)),
+            need_exit_no_cmd_mode: r.u8(offset_of!(Self, need_exit_no_cmd_mode)),
+            half_clk_for_non_read_cmd: r.u8(offset_of!(Self, half_clk_for_non_read_cmd)),
+            need_restore_no_cmd_mode: r.u8(offset_of!(Self, need_restore_no_cmd_mode)),
+            block_size: r.u32(offset_of!(Self, block_size)),
+            flash_state_ctx: r.u32(offset_of!(Self, flash_state_ctx)),
+            _reserved1: r.array(offset_of!(Self, _reserved1)),
+        }
     }
 }
 
 impl ConfigurationBlock {
     /// Create a new serial NOR configuration block based on the FlexSPI configuration
     /// block
-    pub const fn new(mut mem_cfg: flexspi::ConfigurationBlock) -> Self {
-        mem_cfg.device_type = 1;
-        ConfigurationBlock {
+    pub const fn new(mem_cfg: flexspi::ConfigurationBlock) -> Self {
+        Self::with_tail(
             mem_cfg,
-            page_size: 0,
-            sector_size: 0,
-            ip_cmd_serial_clk_freq: SerialClockFrequency::NoChange,
-            extras: extras(),
-        }
+            Tail {
+                page_size: 0,
+                sector_size: 0,
+                ip_cmd_serial_clk_freq: SerialClockFrequency::NoChange,
+                extras: Extras::new(),
+            },
+        )
     }
     /// Set the serial NOR page size
     pub const fn page_size(mut self, page_size: u32) -> Self {
-        self.page_size = page_size;
+        self.tail.page_size = page_size;
         self
     }
     /// Set the serial NOR sector size
     pub const fn sector_size(mut self, sector_size: u32) -> Self {
-        self.sector_size = sector_size;
+        self.tail.sector_size = sector_size;
         self
     }
     /// Set the serial clock frequency
@@ -139,24 +602,166 @@ impl ConfigurationBlock {
         mut self,
         serial_clock_frequency: SerialClockFrequency,
     ) -> Self {
-        self.ip_cmd_serial_clk_freq = serial_clock_frequency;
+        self.tail.ip_cmd_serial_clk_freq = serial_clock_frequency;
+        self
+    }
+    /// Returns the serial NOR page size.
+    pub const fn get_page_size(&self) -> u32 {
+        self.tail.page_size
+    }
+    /// Returns the serial NOR sector size.
+    pub const fn get_sector_size(&self) -> u32 {
+        self.tail.sector_size
+    }
+    /// Returns the IP command serial clock frequency.
+    pub const fn get_ip_cmd_serial_clk_freq(&self) -> SerialClockFrequency {
+        self.tail.ip_cmd_serial_clk_freq
+    }
+    /// Returns `true` if the two configuration blocks have the same bytes.
+    ///
+    /// This is the `const` equivalent of `==`.
+    pub const fn eq_bytes(&self, other: &Self) -> bool {
+        crate::raw::eq_bytes(&self.to_bytes(), &other.to_bytes())
+    }
+    /// Serialize the configuration block into its in-memory layout.
+    ///
+    /// The bytes don't depend on the host's endianness. Use this to inspect or
+    /// store a configuration block without `transmute`.
+    pub const fn to_bytes(&self) -> [u8; Self::SIZE] {
+        let mut bytes = [0; Self::SIZE];
+        crate::raw::put(&mut bytes, 0, &self.mem_cfg.to_bytes());
+        crate::raw::put(
+            &mut bytes,
+            flexspi::ConfigurationBlock::SIZE,
+            &self.tail.to_bytes(),
+        );
+        bytes
+    }
+    /// Decode a serial NOR configuration block from its in-memory layout.
+    ///
+    /// This is the inverse of [`to_bytes`](Self::to_bytes). It decodes the FlexSPI
+    /// configuration block with [`flexspi::ConfigurationBlock::from_bytes`], then
+    /// checks that `deviceType` indicates serial NOR. It doesn't check the page
+    /// or sector size.
+    ///
+    /// ```
+    /// use imxrt_boot_gen::serial_flash::nor;
+    /// use imxrt_boot_gen::flexspi::{self, LookupTable};
+    ///
+    /// let block = nor::ConfigurationBlock::new(flexspi::ConfigurationBlock::new(LookupTable::new()))
+    ///     .page_size(256)
+    ///     .sector_size(4096);
+    /// let bytes = block.to_bytes();
+    /// assert_eq!(nor::ConfigurationBlock::from_bytes(&bytes), Ok(block));
+    /// ```
+    pub fn from_bytes(bytes: &[u8; Self::SIZE]) -> Result<Self, ParseError> {
+        let mem_cfg = flexspi::ConfigurationBlock::from_bytes(
+            bytes[..flexspi::ConfigurationBlock::SIZE]
+                .try_into()
+                .unwrap(),
+        )?;
+        let device_type = mem_cfg.get_device_type();
+        if device_type != <Tail as serial_flash::DeviceTail>::DEVICE_TYPE {
+            return Err(ParseError::DeviceType(device_type));
+        }
+        let tail = Tail::from_bytes(bytes)?;
+        Ok(Self::with_tail(mem_cfg, tail))
+    }
+    /// Check that the page size and sector size are set.
+    ///
+    /// Call `finish` at the end of your builder chain.
+    ///
+    /// # Panics
+    ///
+    /// Panics if either the page size or sector size is zero. When evaluated
+    /// in a `const` or `static` initializer, the panic is a compile-time error.
+    pub const fn finish(self) -> Self {
+        assert!(self.tail.page_size != 0, "Serial NOR page size is not set");
+        assert!(
+            self.tail.sector_size != 0,
+            "Serial NOR sector size is not set"
+        );
         self
     }
 }
 
-#[cfg(any(feature = "imxrt1170", feature = "imxrt1180"))]
+#[cfg(any(
+    feature = "imxrt1170",
+    feature = "imxrt1180",
+    feature = "imxrt500",
+    feature = "imxrt600"
+))]
 impl ConfigurationBlock {
-    /// Set the serial NOR block size if it differs from the sector size.
+    /// Replace all of the serial NOR extras.
     ///
-    /// By default, the configuration block signals to the hardware that the
-    /// sector size is the same as the block size. Calling this will override
-    /// that setting, allowing you to configure a different block size.
+    /// See [`Extras`] for more information.
+    pub const fn extras(mut self, extras: Extras) -> Self {
+        self.tail.extras = extras;
+        self
+    }
+    /// Returns the serial NOR extras.
+    pub const fn get_extras(&self) -> Extras {
+        self.tail.extras
+    }
+    /// Set the serial NOR block size if it differs from the sector size.
     ///
-    /// The behavior is unspecified if you call this with a block size that's
-    /// equal to the sector size.
+    /// See [`Extras::block_size`] for more information.
     pub const fn block_size(mut self, block_size: u32) -> Self {
-        self.extras.is_uniform_block_size = 0u8;
-        self.extras.block_size = block_size;
+        self.tail.extras = self.tail.extras.block_size(block_size);
+        self
+    }
+    /// Set `isDataOrderSwapped`.
+    ///
+    /// See [`Extras::is_data_order_swapped`] for more information.
+    pub const fn is_data_order_swapped(mut self, is_data_order_swapped: bool) -> Self {
+        self.tail.extras = self
+            .tail
+            .extras
+            .is_data_order_swapped(is_data_order_swapped);
+        self
+    }
+    /// Set `serialNorType`.
+    ///
+    /// See [`Extras::serial_nor_type`] for more information.
+    pub const fn serial_nor_type(mut self, serial_nor_type: SerialNorType) -> Self {
+        self.tail.extras = self.tail.extras.serial_nor_type(serial_nor_type);
+        self
+    }
+    /// Set `needExitNoCmdMode`.
+    ///
+    /// See [`Extras::need_exit_no_cmd_mode`] for more information.
+    pub const fn need_exit_no_cmd_mode(mut self, need_exit_no_cmd_mode: bool) -> Self {
+        self.tail.extras = self
+            .tail
+            .extras
+            .need_exit_no_cmd_mode(need_exit_no_cmd_mode);
+        self
+    }
+    /// Set `halfClkForNonReadCmd`.
+    ///
+    /// See [`Extras::half_clk_for_non_read_cmd`] for more information.
+    pub const fn half_clk_for_non_read_cmd(mut self, half_clk_for_non_read_cmd: bool) -> Self {
+        self.tail.extras = self
+            .tail
+            .extras
+            .half_clk_for_non_read_cmd(half_clk_for_non_read_cmd);
+        self
+    }
+    /// Set `needRestoreNoCmdMode`.
+    ///
+    /// See [`Extras::need_restore_no_cmd_mode`] for more information.
+    pub const fn need_restore_no_cmd_mode(mut self, need_restore_no_cmd_mode: bool) -> Self {
+        self.tail.extras = self
+            .tail
+            .extras
+            .need_restore_no_cmd_mode(need_restore_no_cmd_mode);
+        self
+    }
+    /// Set `flashStateCtx`.
+    ///
+    /// See [`FlashStateContext`] for more information.
+    pub const fn flash_state_ctx(mut self, flash_state_ctx: FlashStateContext) -> Self {
+        self.tail.extras = self.tail.extras.flash_state_ctx(flash_state_ctx);
         self
     }
 }
@@ -164,6 +769,12 @@ impl ConfigurationBlock {
 const _STATIC_ASSERT_SIZE: [u32; 1] =
     [0; (core::mem::size_of::<ConfigurationBlock>() == 512) as usize];
 
+// Safety: the FlexSPI configuration block has no padding, and it's followed
+// by the 64-byte tail. The tail's fields are 4 + 4 + 1 + 55 bytes in a
+// `repr(C)` struct, so it has no padding either.
+#[cfg(feature = "bytemuck")]
+unsafe impl bytemuck::NoUninit for ConfigurationBlock {}
+
 #[cfg(test)]
 mod test {
     use super::{flexspi, ConfigurationBlock, SerialClockFrequency};
@@ -175,6 +786,385 @@ mod test {
             ConfigurationBlock::new(flexspi::ConfigurationBlock::new(LookupTable::new()))
                 .page_size(256)
                 .sector_size(4095)
-                .ip_cmd_serial_clk_freq(SerialClockFrequency::MHz30);
+                .ip_cmd_serial_clk_freq(SerialClockFrequency::MHz30)
+                .finish();
+    }
+
+    fn to_bytes(block: ConfigurationBlock) -> [u8; 512] {
+        block.to_bytes()
+    }
+
+    #[cfg(target_endian = "little")]
+    #[test]
+    fn to_bytes_matches_memory() {
+        use crate::flexspi::{BusyBit, BusyBitPolarity, ConfigurationCommand, DataValidTime};
+
+        let block = ConfigurationBlock::new(
+            flexspi::ConfigurationBlock::new(LookupTable::new())
+                .config_command(2, ConfigurationCommand::new(1, 3, 0x1234_5678))
+                .data_valid_time(DataValidTime::new(0x01, 0x02).delay_cells(0x03, 0x04))
+                .busy_bit(BusyBit::new(5, BusyBitPolarity::ActiveLow))
+                .timeout_ms(0xAABB_CCDD),
+        )
+        .page_size(256)
+        .sector_size(4096)
+        .ip_cmd_serial_clk_freq(SerialClockFrequency::MHz30);
+        let memory: [u8; 512] = unsafe { core::mem::transmute(block) };
+        assert_eq!(block.to_bytes(), memory);
+    }
+
+    #[test]
+    fn from_bytes() {
+        use crate::ParseError;
+
+        let block = ConfigurationBlock::new(flexspi::ConfigurationBlock::new(LookupTable::new()))
+            .page_size(256)
+            .sector_size(4096)
+            .ip_cmd_serial_clk_freq(SerialClockFrequency::MHz30);
+        let bytes = block.to_bytes();
+        assert_eq!(ConfigurationBlock::from_bytes(&bytes), Ok(block));
+
+        let mut device_type = bytes;
+        device_type[0x44] = 2;
+        assert_eq!(
+            ConfigurationBlock::from_bytes(&device_type),
+            Err(ParseError::DeviceType(2))
+        );
+
+        let mut clock = bytes;
+        clock[0x1C8] = 0xFF;
+        assert_eq!(
+            ConfigurationBlock::from_bytes(&clock),
+            Err(ParseError::Field {
+                name: "ipcmdSerialClkFreq",
+                offset: 0x1C8,
+                value: 0xFF
+            })
+        );
+    }
+
+    #[cfg(all(feature = "bytemuck", target_endian = "little"))]
+    #[test]
+    fn bytemuck_matches_to_bytes() {
+        let block = ConfigurationBlock::new(flexspi::ConfigurationBlock::new(LookupTable::new()))
+            .page_size(256)
+            .sector_size(4096);
+        assert_eq!(bytemuck::bytes_of(&block), &block.to_bytes()[..]);
+        assert_eq!(
+            bytemuck::bytes_of(block.mem_cfg()),
+            &block.mem_cfg().to_bytes()[..]
+        );
+    }
+
+    #[cfg(all(feature = "zerocopy", target_endian = "little"))]
+    #[test]
+    fn zerocopy_matches_to_bytes() {
+        use zerocopy::IntoBytes;
+
+        let block = ConfigurationBlock::new(flexspi::ConfigurationBlock::new(LookupTable::new()))
+            .page_size(256)
+            .sector_size(4096);
+        assert_eq!(block.mem_cfg().as_bytes(), &block.mem_cfg().to_bytes()[..]);
+    }
+
+    #[test]
+    fn raw_bytes() {
+        let base = ConfigurationBlock::new(flexspi::ConfigurationBlock::new(LookupTable::new()));
+        let typed = base.page_size(256).sector_size(4096);
+        let raw = unsafe {
+            base.raw_bytes(0x1C0, &256u32.to_le_bytes())
+                .raw_bytes(0x1C4, &4096u32.to_le_bytes())
+        };
+        assert_eq!(to_bytes(typed), to_bytes(raw));
+    }
+
+    #[test]
+    fn with_mem_cfg_keeps_device_type() {
+        let block = ConfigurationBlock::new(flexspi::ConfigurationBlock::new(LookupTable::new()))
+            .with_mem_cfg(flexspi::ConfigurationBlock::new(LookupTable::new()));
+        assert_eq!(block.mem_cfg().device_type, 1);
+    }
+
+    #[test]
+    fn with_lookup_table() {
+        use crate::flexspi::{opcodes::sdr::CMD, Command, Instr, Pads, SequenceBuilder};
+
+        let lut = LookupTable::new().command(
+            Command::WriteEnable,
+            SequenceBuilder::new()
+                .instr(Instr::new(CMD, Pads::One, 0x06))
+                .build(),
+        );
+        let expected =
+            ConfigurationBlock::new(flexspi::ConfigurationBlock::new(lut).cs_hold_time(1))
+                .page_size(256);
+        let actual = ConfigurationBlock::new(
+            flexspi::ConfigurationBlock::new(LookupTable::new()).cs_hold_time(1),
+        )
+        .page_size(256)
+        .with_lookup_table(lut);
+
+        assert_eq!(to_bytes(actual), to_bytes(expected));
+    }
+
+    #[test]
+    fn lut_custom_seq() {
+        use crate::flexspi::LutCustomSequence;
+
+        let block = ConfigurationBlock::new(
+            flexspi::ConfigurationBlock::new(LookupTable::new())
+                .lut_custom_seq(2, LutCustomSequence::new(1, 8)),
+        );
+        let bytes = to_bytes(block);
+        assert_eq!(bytes[0x047], 1);
+        assert_eq!(&bytes[0x188..0x18C], &[1, 8, 0, 0]);
+    }
+
+    #[test]
+    fn timeout_and_command_interval() {
+        let block = ConfigurationBlock::new(
+            flexspi::ConfigurationBlock::new(LookupTable::new())
+                .timeout_ms(500)
+                .command_interval(100),
+        );
+        let bytes = to_bytes(block);
+        assert_eq!(&bytes[0x070..0x074], &500u32.to_le_bytes());
+        assert_eq!(&bytes[0x074..0x078], &100u32.to_le_bytes());
+    }
+
+    #[test]
+    fn busy_bit() {
+        use crate::flexspi::{BusyBit, BusyBitPolarity};
+
+        let block = ConfigurationBlock::new(
+            flexspi::ConfigurationBlock::new(LookupTable::new())
+                .busy_bit(BusyBit::new(7, BusyBitPolarity::ActiveLow)),
+        );
+        let bytes = to_bytes(block);
+        assert_eq!(&bytes[0x07C..0x080], &[7, 0, 1, 0]);
+    }
+
+    #[cfg(any(
+        feature = "imxrt1170",
+        feature = "imxrt1180",
+        feature = "imxrt500",
+        feature = "imxrt600"
+    ))]
+    #[test]
+    fn device_mode_type() {
+        use crate::flexspi::DeviceModeType;
+
+        let block = ConfigurationBlock::new(
+            flexspi::ConfigurationBlock::new(LookupTable::new())
+                .device_mode_type(DeviceModeType::Spi2Xpi),
+        );
+        assert_eq!(to_bytes(block)[0x011], 2);
+    }
+
+    #[cfg(any(
+        feature = "imxrt1170",
+        feature = "imxrt1180",
+        feature = "imxrt500",
+        feature = "imxrt600"
+    ))]
+    #[test]
+    fn is_data_order_swapped() {
+        let block = ConfigurationBlock::new(flexspi::ConfigurationBlock::new(LookupTable::new()))
+            .is_data_order_swapped(true);
+        assert_eq!(to_bytes(block)[0x1CA], 1);
+    }
+
+    #[cfg(any(
+        feature = "imxrt1170",
+        feature = "imxrt1180",
+        feature = "imxrt500",
+        feature = "imxrt600"
+    ))]
+    #[test]
+    fn flash_state_ctx() {
+        use super::FlashStateContext;
+
+        let context = FlashStateContext::new()
+            .por_mode(1)
+            .current_mode(2)
+            .exit_no_cmd_sequence(3)
+            .restore_sequence(4);
+        assert_eq!(FlashStateContext::from_raw(context.raw()), context);
+        let block = ConfigurationBlock::new(flexspi::ConfigurationBlock::new(LookupTable::new()))
+            .flash_state_ctx(context);
+        assert_eq!(&to_bytes(block)[0x1D4..0x1D8], &[1, 2, 3, 4]);
+    }
+
+    #[cfg(any(
+        feature = "imxrt1170",
+        feature = "imxrt1180",
+        feature = "imxrt500",
+        feature = "imxrt600"
+    ))]
+    #[test]
+    fn serial_nor_type() {
+        use super::SerialNorType;
+
+        let block = ConfigurationBlock::new(flexspi::ConfigurationBlock::new(LookupTable::new()))
+            .serial_nor_type(SerialNorType::HyperBus);
+        assert_eq!(to_bytes(block)[0x1CC], 1);
+    }
+
+    #[cfg(any(
+        feature = "imxrt1170",
+        feature = "imxrt1180",
+        feature = "imxrt500",
+        feature = "imxrt600"
+    ))]
+    #[test]
+    fn no_cmd_mode() {
+        use super::SerialNorType;
+
+        let block = ConfigurationBlock::new(flexspi::ConfigurationBlock::new(LookupTable::new()))
+            .serial_nor_type(SerialNorType::NoCmd)
+            .need_exit_no_cmd_mode(true)
+            .half_clk_for_non_read_cmd(true)
+            .need_restore_no_cmd_mode(true);
+        assert_eq!(&to_bytes(block)[0x1CC..0x1D0], &[3, 1, 1, 1]);
+    }
+
+    #[cfg(any(
+        feature = "imxrt1170",
+        feature = "imxrt1180",
+        feature = "imxrt500",
+        feature = "imxrt600"
+    ))]
+    #[test]
+    fn extras() {
+        use super::{Extras, SerialNorType};
+
+        let base = ConfigurationBlock::new(flexspi::ConfigurationBlock::new(LookupTable::new()));
+        let expected = base
+            .block_size(64 * 1024)
+            .serial_nor_type(SerialNorType::Xspi)
+            .is_data_order_swapped(true);
+        let actual = base.extras(
+            Extras::new()
+                .block_size(64 * 1024)
+                .serial_nor_type(SerialNorType::Xspi)
+                .is_data_order_swapped(true),
+        );
+        assert_eq!(to_bytes(actual), to_bytes(expected));
+    }
+
+    #[test]
+    fn config_commands() {
+        use crate::flexspi::ConfigurationCommand;
+
+        let first = ConfigurationCommand::new(1, 10, 0x40);
+        let second = ConfigurationCommand::new(2, 12, 0x1F);
+        let expected = ConfigurationBlock::new(
+            flexspi::ConfigurationBlock::new(LookupTable::new())
+                .config_command(0, first)
+                .config_command(1, second),
+        );
+        let actual = ConfigurationBlock::new(
+            flexspi::ConfigurationBlock::new(LookupTable::new()).config_commands(&[first, second]),
+        );
+        assert_eq!(to_bytes(actual), to_bytes(expected));
+    }
+
+    #[test]
+    fn version() {
+        use crate::flexspi::Version;
+
+        let version = Version::new(1, 4, 2);
+        assert_eq!(
+            (version.major(), version.minor(), version.bugfix()),
+            (1, 4, 2)
+        );
+        assert_eq!(Version::from_raw(version.raw()), Some(version));
+        assert_eq!(format!("{version}"), "V1.4.2");
+    }
+
+    #[test]
+    fn getters() {
+        let block = ConfigurationBlock::new(flexspi::ConfigurationBlock::new(LookupTable::new()))
+            .page_size(256)
+            .sector_size(4096)
+            .ip_cmd_serial_clk_freq(SerialClockFrequency::MHz30);
+        assert_eq!(block.get_page_size(), 256);
+        assert_eq!(block.get_sector_size(), 4096);
+        assert_eq!(
+            block.get_ip_cmd_serial_clk_freq(),
+            SerialClockFrequency::MHz30
+        );
+        assert_eq!(block.mem_cfg().get_device_type(), 1);
+    }
+
+    #[cfg(any(
+        feature = "imxrt1170",
+        feature = "imxrt1180",
+        feature = "imxrt500",
+        feature = "imxrt600"
+    ))]
+    #[test]
+    fn extras_getters() {
+        use super::SerialNorType;
+
+        let block = ConfigurationBlock::new(flexspi::ConfigurationBlock::new(LookupTable::new()));
+        assert_eq!(block.get_extras().get_block_size(), None);
+        let extras = block
+            .block_size(64 * 1024)
+            .serial_nor_type(SerialNorType::HyperBus)
+            .need_restore_no_cmd_mode(true)
+            .get_extras();
+        assert_eq!(extras.get_block_size(), Some(64 * 1024));
+        assert_eq!(extras.get_serial_nor_type(), Some(SerialNorType::HyperBus));
+        assert!(extras.get_need_restore_no_cmd_mode());
+        assert!(!extras.get_need_exit_no_cmd_mode());
+    }
+
+    #[test]
+    fn eq() {
+        let base = ConfigurationBlock::new(flexspi::ConfigurationBlock::new(LookupTable::new()));
+        assert_eq!(base.page_size(256), base.page_size(256));
+        assert_ne!(base.page_size(256), base.page_size(512));
+        assert_ne!(
+            base,
+            base.with_mem_cfg(flexspi::ConfigurationBlock::new(LookupTable::new()).cs_hold_time(1))
+        );
+        assert!(base.mem_cfg().eq_bytes(base.mem_cfg()));
+    }
+
+    #[test]
+    #[should_panic]
+    fn raw_bytes_out_of_bounds() {
+        let base = ConfigurationBlock::new(flexspi::ConfigurationBlock::new(LookupTable::new()));
+        let _ = unsafe { base.raw_bytes(510, &[0; 3]) };
     }
 }
+
+//
+// Keep these two tests in sync
+//
+// The first one lets you know if the second one is failing to compile
+// in the way we expect.
+//
+
+/// ```
+/// use imxrt_boot_gen::{flexspi::{self, LookupTable}, serial_flash::nor};
+/// const CFG: nor::ConfigurationBlock =
+///     nor::ConfigurationBlock::new(flexspi::ConfigurationBlock::new(LookupTable::new()))
+///         .page_size(256)
+///         .sector_size(4096)
+///         .finish();
+/// ```
+#[cfg(doctest)]
+struct ConfigurationBlockFinishWithGeometry;
+
+/// ```compile_fail
+/// use imxrt_boot_gen::{flexspi::{self, LookupTable}, serial_flash::nor};
+/// const CFG: nor::ConfigurationBlock =
+///     nor::ConfigurationBlock::new(flexspi::ConfigurationBlock::new(LookupTable::new()))
+///         .page_size(256)
+///         // .sector_size(4096) <------- MISSING
+///         .finish();
+/// ```
+#[cfg(doctest)]
+struct ConfigurationBlockFinishWithoutGeometry;
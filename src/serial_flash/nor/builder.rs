@@ -0,0 +1,668 @@
+//! A runtime builder for serial NOR configuration blocks
+
+use core::fmt;
+
+use super::{ConfigurationBlock, SerialClockFrequency as IpSerialClockFrequency};
+use crate::flexspi::{
+    self, ColumnAddressWidth, ConfigurationCommand, ControllerMiscOptions, DataValidTime, Density,
+    DeviceModeConfiguration, FlashPadType, LookupTable, LutCustomSequence, PadSettingOverride,
+    ReadSampleClockSource, SerialClockFrequency, SerialFlashRegion, Version,
+    WaitTimeConfigurationCommands, CONFIG_CMD_SLOTS, LUT_CUSTOM_SEQ_SLOTS, NUMBER_OF_SEQUENCES,
+};
+
+/// `csHoldTime` and `csSetupTime` are five-bit fields in the FlexSPI controller.
+const MAX_CS_TIME: u8 = 0x1F;
+
+/// An error produced by an [`FcbBuilder`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ValidationError {
+    /// The chip select hold time is larger than 31.
+    CsHoldTime(u8),
+    /// The chip select setup time is larger than 31.
+    CsSetupTime(u8),
+    /// The configuration command wait time, in microseconds, isn't a multiple
+    /// of 100us.
+    WaitTimeCfgCommands(u16),
+    /// The LUT sequences extend beyond the end of the lookup table.
+    LutSequence {
+        /// The number of LUT sequences.
+        number_of_luts: u8,
+        /// The starting LUT index.
+        starting_lut_index: u8,
+    },
+    /// The chip doesn't support the serial clock frequency, in MHz.
+    SerialClkFreq(u32),
+    /// The configuration command index is not 0, 1, or 2.
+    ConfigCommandIndex(usize),
+    /// The custom LUT sequence index is larger than 11.
+    LutCustomSequenceIndex(usize),
+    /// The busy bit offset is larger than 31.
+    BusyOffset(u16),
+    /// The busy bit polarity is neither 0 nor 1.
+    BusyBitPolarity(u16),
+    /// The page size is zero, or it's not a power of two.
+    PageSize(u32),
+    /// The sector size is zero, or it's not a multiple of the page size.
+    SectorSize(u32),
+    /// The block size is zero, or it's not a multiple of the sector size.
+    BlockSize(u32),
+    /// None of the serial flash regions have a size.
+    NoFlashSize,
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ValidationError::CsHoldTime(time) => {
+                write!(f, "csHoldTime {time} is larger than {MAX_CS_TIME}")
+            }
+            ValidationError::CsSetupTime(time) => {
+                write!(f, "csSetupTime {time} is larger than {MAX_CS_TIME}")
+            }
+            ValidationError::WaitTimeCfgCommands(us) => {
+                write!(f, "waitTimeCfgCommands {us}us is not a multiple of 100us")
+            }
+            ValidationError::LutSequence {
+                number_of_luts,
+                starting_lut_index,
+            } => write!(
+                f,
+                "{number_of_luts} LUT sequence(s) starting at index {starting_lut_index} \
+                exceed the {NUMBER_OF_SEQUENCES} sequence lookup table"
+            ),
+            ValidationError::SerialClkFreq(mhz) => {
+                write!(f, "serialClkFreq {mhz}MHz is not supported by the chip")
+            }
+            ValidationError::ConfigCommandIndex(index) => write!(
+                f,
+                "configuration command index {index} is not less than {CONFIG_CMD_SLOTS}"
+            ),
+            ValidationError::LutCustomSequenceIndex(index) => write!(
+                f,
+                "custom LUT sequence index {index} is not less than {LUT_CUSTOM_SEQ_SLOTS}"
+            ),
+            ValidationError::BusyOffset(offset) => {
+                write!(f, "busyOffset {offset} is larger than 31")
+            }
+            ValidationError::BusyBitPolarity(polarity) => {
+                write!(f, "busyBitPolarity {polarity} is neither 0 nor 1")
+            }
+            ValidationError::PageSize(size) => {
+                write!(f, "page size {size} is not a non-zero power of two")
+            }
+            ValidationError::SectorSize(size) => write!(
+                f,
+                "sector size {size} is not a non-zero multiple of the page size"
+            ),
+            ValidationError::BlockSize(size) => write!(
+                f,
+                "block size {size} is not a non-zero multiple of the sector size"
+            ),
+            ValidationError::NoFlashSize => write!(f, "no serial flash region has a size"),
+        }
+    }
+}
+
+/// A runtime serial NOR configuration block builder
+///
+/// Use `FcbBuilder` when you're constructing a configuration block from user input,
+/// like a configuration file or a command-line tool. Unlike the `const` API, each
+/// setter validates its input, and [`build`](FcbBuilder::build) validates the
+/// relationships between fields. `FcbBuilder` can set every field that's modeled
+/// by the `const` API, plus the raw FlexSPI configuration block fields that don't
+/// yet have a typed `const` setter.
+///
+/// Unless otherwise specified, fields default to the values used by
+/// [`flexspi::ConfigurationBlock::new`] and [`ConfigurationBlock::new`].
+///
+/// ```
+/// use imxrt_boot_gen::flexspi::{LookupTable, SerialFlashRegion};
+/// use imxrt_boot_gen::serial_flash::nor::{FcbBuilder, ValidationError};
+///
+/// # fn main() -> Result<(), ValidationError> {
+/// let fcb = FcbBuilder::new(LookupTable::new())
+///     .cs_hold_time(0x03)?
+///     .flash_size(SerialFlashRegion::A1, 16 * 1024 * 1024)
+///     .page_size(256)?
+///     .sector_size(4096)?
+///     .build()?;
+/// # Ok(()) }
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct FcbBuilder {
+    block: ConfigurationBlock,
+}
+
+impl FcbBuilder {
+    /// Create a new builder that uses `lookup_table` as the FlexSPI LUT
+    pub const fn new(lookup_table: LookupTable) -> Self {
+        FcbBuilder {
+            block: ConfigurationBlock::new(flexspi::ConfigurationBlock::new(lookup_table)),
+        }
+    }
+
+    fn map_mem_cfg(
+        &mut self,
+        f: impl FnOnce(flexspi::ConfigurationBlock) -> flexspi::ConfigurationBlock,
+    ) -> &mut Self {
+        self.block.mem_cfg = f(self.block.mem_cfg);
+        self
+    }
+
+    fn validate_lut_sequence(
+        number_of_luts: u8,
+        starting_lut_index: u8,
+    ) -> Result<(), ValidationError> {
+        if usize::from(number_of_luts) + usize::from(starting_lut_index) > NUMBER_OF_SEQUENCES {
+            Err(ValidationError::LutSequence {
+                number_of_luts,
+                starting_lut_index,
+            })
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Set the lookup table.
+    pub fn lookup_table(&mut self, lookup_table: LookupTable) -> &mut Self {
+        self.block.mem_cfg.lookup_table = lookup_table;
+        self
+    }
+
+    /// Set the version.
+    pub fn version(&mut self, version: Version) -> &mut Self {
+        self.map_mem_cfg(|cfg| cfg.version(version))
+    }
+
+    /// Set `readSampleClkSrc`.
+    pub fn read_sample_clk_src(&mut self, read_sample_clk_src: ReadSampleClockSource) -> &mut Self {
+        self.map_mem_cfg(|cfg| cfg.read_sample_clk_src(read_sample_clk_src))
+    }
+
+    /// Set `csHoldTime`.
+    pub fn cs_hold_time(&mut self, cs_hold_time: u8) -> Result<&mut Self, ValidationError> {
+        if cs_hold_time > MAX_CS_TIME {
+            return Err(ValidationError::CsHoldTime(cs_hold_time));
+        }
+        Ok(self.map_mem_cfg(|cfg| cfg.cs_hold_time(cs_hold_time)))
+    }
+
+    /// Set `csSetupTime`.
+    pub fn cs_setup_time(&mut self, cs_setup_time: u8) -> Result<&mut Self, ValidationError> {
+        if cs_setup_time > MAX_CS_TIME {
+            return Err(ValidationError::CsSetupTime(cs_setup_time));
+        }
+        Ok(self.map_mem_cfg(|cfg| cfg.cs_setup_time(cs_setup_time)))
+    }
+
+    /// Set `columnAddressWidth`.
+    pub fn column_address_width(&mut self, column_address_width: ColumnAddressWidth) -> &mut Self {
+        self.map_mem_cfg(|cfg| cfg.column_address_width(column_address_width))
+    }
+
+    /// Set `deviceModeCfgEnable`, `deviceModeSeq`, and `deviceModeArg`.
+    pub fn device_mode_configuration(
+        &mut self,
+        device_mode_configuration: DeviceModeConfiguration,
+    ) -> Result<&mut Self, ValidationError> {
+        if let DeviceModeConfiguration::Enabled {
+            device_mode_seq, ..
+        } = device_mode_configuration
+        {
+            Self::validate_lut_sequence(
+                device_mode_seq.number_of_luts(),
+                device_mode_seq.starting_lut_index(),
+            )?;
+        }
+        Ok(self.map_mem_cfg(|cfg| cfg.device_mode_configuration(device_mode_configuration)))
+    }
+
+    /// Set `waitTimeCfgCommands` from a wait time in microseconds.
+    ///
+    /// The wait time must be a multiple of 100us.
+    pub fn wait_time_cfg_commands(
+        &mut self,
+        wait_time_us: u16,
+    ) -> Result<&mut Self, ValidationError> {
+        if !wait_time_us.is_multiple_of(100) {
+            return Err(ValidationError::WaitTimeCfgCommands(wait_time_us));
+        }
+        let wait_time = WaitTimeConfigurationCommands::new(wait_time_us);
+        Ok(self.map_mem_cfg(|cfg| cfg.wait_time_cfg_commands(wait_time)))
+    }
+
+    /// Set one of the three configuration commands.
+    ///
+    /// This also sets `configCmdEnable`. The configuration command
+    /// executes `number_of_luts` LUT sequences, starting at `starting_lut_index`,
+    /// with the argument `arg`.
+    pub fn config_command(
+        &mut self,
+        index: usize,
+        number_of_luts: u8,
+        starting_lut_index: u8,
+        arg: u32,
+    ) -> Result<&mut Self, ValidationError> {
+        if index >= CONFIG_CMD_SLOTS {
+            return Err(ValidationError::ConfigCommandIndex(index));
+        }
+        Self::validate_lut_sequence(number_of_luts, starting_lut_index)?;
+        let command = ConfigurationCommand::new(number_of_luts, starting_lut_index, arg);
+        Ok(self.map_mem_cfg(|cfg| cfg.config_command(index, command)))
+    }
+
+    /// Set `controllerMiscOption`.
+    pub fn controller_misc_options(&mut self, options: u32) -> &mut Self {
+        self.map_mem_cfg(|cfg| cfg.controller_misc_options(options))
+    }
+
+    /// Set `controllerMiscOption` from named flags.
+    pub fn controller_misc_option_flags(&mut self, options: ControllerMiscOptions) -> &mut Self {
+        self.map_mem_cfg(|cfg| cfg.controller_misc_option_flags(options))
+    }
+
+    /// Set `sFlashPad`.
+    pub fn serial_flash_pad_type(&mut self, serial_flash_pad_type: FlashPadType) -> &mut Self {
+        self.map_mem_cfg(|cfg| cfg.serial_flash_pad_type(serial_flash_pad_type))
+    }
+
+    /// Set `sFlashPad` to the widest pad type used in the lookup table.
+    pub fn serial_flash_pad_type_from_lut(&mut self) -> &mut Self {
+        self.map_mem_cfg(|cfg| cfg.serial_flash_pad_type_from_lut())
+    }
+
+    /// Set `serialClkFreq`.
+    pub fn serial_clk_freq(&mut self, serial_clk_freq: SerialClockFrequency) -> &mut Self {
+        self.map_mem_cfg(|cfg| cfg.serial_clk_freq(serial_clk_freq))
+    }
+
+    /// Set `serialClkFreq` from a frequency in MHz.
+    ///
+    /// The frequency must exactly match a frequency supported by the chip.
+    pub fn serial_clk_freq_mhz(&mut self, mhz: u32) -> Result<&mut Self, ValidationError> {
+        let serial_clk_freq =
+            SerialClockFrequency::from_mhz(mhz).ok_or(ValidationError::SerialClkFreq(mhz))?;
+        Ok(self.serial_clk_freq(serial_clk_freq))
+    }
+
+    /// Set one of the twelve custom LUT sequences.
+    ///
+    /// This also sets `lutCustomSeqEnable`.
+    pub fn lut_custom_sequence(
+        &mut self,
+        index: usize,
+        number_of_luts: u8,
+        starting_lut_index: u8,
+    ) -> Result<&mut Self, ValidationError> {
+        if index >= LUT_CUSTOM_SEQ_SLOTS {
+            return Err(ValidationError::LutCustomSequenceIndex(index));
+        }
+        Self::validate_lut_sequence(number_of_luts, starting_lut_index)?;
+        let sequence = LutCustomSequence::new(number_of_luts, starting_lut_index);
+        Ok(self.map_mem_cfg(|cfg| cfg.lut_custom_seq(index, sequence)))
+    }
+
+    /// Set the flash size, in bytes, for the flash region.
+    pub fn flash_size(&mut self, flash_region: SerialFlashRegion, flash_size: u32) -> &mut Self {
+        self.map_mem_cfg(|cfg| cfg.flash_size_bytes(flash_region, flash_size))
+    }
+
+    /// Set the A1 and B1 flash sizes for two quad flashes in parallel mode.
+    ///
+    /// This also enables the parallel controller option. See
+    /// [`flexspi::ConfigurationBlock::parallel_flash_size`].
+    pub fn parallel_flash_size(&mut self, density: Density) -> &mut Self {
+        self.map_mem_cfg(|cfg| cfg.parallel_flash_size(density))
+    }
+
+    /// Set the raw `csPadSettingOverride`.
+    pub fn cs_pad_setting_override(&mut self, pad_setting: u32) -> &mut Self {
+        self.map_mem_cfg(|cfg| {
+            cfg.cs_pad_setting_override(PadSettingOverride::from_raw(pad_setting))
+        })
+    }
+
+    /// Set the raw `sclkPadSettingOverride`.
+    pub fn sclk_pad_setting_override(&mut self, pad_setting: u32) -> &mut Self {
+        self.map_mem_cfg(|cfg| {
+            cfg.sclk_pad_setting_override(PadSettingOverride::from_raw(pad_setting))
+        })
+    }
+
+    /// Set the raw `dataPadSettingOverride`.
+    pub fn data_pad_setting_override(&mut self, pad_setting: u32) -> &mut Self {
+        self.map_mem_cfg(|cfg| {
+            cfg.data_pad_setting_override(PadSettingOverride::from_raw(pad_setting))
+        })
+    }
+
+    /// Set the raw `dqsPadSettingOverride`.
+    pub fn dqs_pad_setting_override(&mut self, pad_setting: u32) -> &mut Self {
+        self.map_mem_cfg(|cfg| {
+            cfg.dqs_pad_setting_override(PadSettingOverride::from_raw(pad_setting))
+        })
+    }
+
+    /// Set `timeoutInMs`.
+    pub fn timeout_ms(&mut self, timeout_ms: u32) -> &mut Self {
+        self.map_mem_cfg(|cfg| cfg.timeout_ms(timeout_ms))
+    }
+
+    /// Set `commandInterval`.
+    pub fn command_interval(&mut self, command_interval: u32) -> &mut Self {
+        self.map_mem_cfg(|cfg| cfg.command_interval(command_interval))
+    }
+
+    /// Set `dataValidTime`.
+    pub fn data_valid_time(&mut self, data_valid_time: DataValidTime) -> &mut Self {
+        self.map_mem_cfg(|cfg| cfg.data_valid_time(data_valid_time))
+    }
+
+    /// Set `busyOffset`, the bit offset of the busy flag in the status register.
+    pub fn busy_offset(&mut self, busy_offset: u16) -> Result<&mut Self, ValidationError> {
+        if busy_offset > 31 {
+            return Err(ValidationError::BusyOffset(busy_offset));
+        }
+        self.block.mem_cfg.busy_offset = busy_offset;
+        Ok(self)
+    }
+
+    /// Set `busyBitPolarity`.
+    ///
+    /// 0 indicates that the flash is busy when the busy flag is 1. 1 indicates
+    /// that the flash is busy when the busy flag is 0.
+    pub fn busy_bit_polarity(
+        &mut self,
+        busy_bit_polarity: u16,
+    ) -> Result<&mut Self, ValidationError> {
+        if busy_bit_polarity > 1 {
+            return Err(ValidationError::BusyBitPolarity(busy_bit_polarity));
+        }
+        self.block.mem_cfg.busy_bit_polarity = busy_bit_polarity;
+        Ok(self)
+    }
+
+    /// Set the serial NOR page size.
+    ///
+    /// The page size must be a non-zero power of two.
+    pub fn page_size(&mut self, page_size: u32) -> Result<&mut Self, ValidationError> {
+        if !page_size.is_power_of_two() {
+            return Err(ValidationError::PageSize(page_size));
+        }
+        self.block = self.block.page_size(page_size);
+        Ok(self)
+    }
+
+    /// Set the serial NOR sector size.
+    ///
+    /// The sector size must be non-zero. When you [`build`](FcbBuilder::build),
+    /// the sector size must also be a multiple of the page size.
+    pub fn sector_size(&mut self, sector_size: u32) -> Result<&mut Self, ValidationError> {
+        if sector_size == 0 {
+            return Err(ValidationError::SectorSize(sector_size));
+        }
+        self.block = self.block.sector_size(sector_size);
+        Ok(self)
+    }
+
+    /// Set `ipCmdSerialClkFreq`.
+    pub fn ip_cmd_serial_clk_freq(
+        &mut self,
+        serial_clock_frequency: IpSerialClockFrequency,
+    ) -> &mut Self {
+        self.block = self.block.ip_cmd_serial_clk_freq(serial_clock_frequency);
+        self
+    }
+
+    /// Validate all fields, and produce the serial NOR configuration block.
+    pub fn build(&self) -> Result<ConfigurationBlock, ValidationError> {
+        let block = self.block;
+        let page_size = block.tail.page_size;
+        if !page_size.is_power_of_two() {
+            return Err(ValidationError::PageSize(page_size));
+        }
+        let sector_size = block.tail.sector_size;
+        if sector_size == 0 || !sector_size.is_multiple_of(page_size) {
+            return Err(ValidationError::SectorSize(sector_size));
+        }
+        #[cfg(any(
+            feature = "imxrt1170",
+            feature = "imxrt1180",
+            feature = "imxrt500",
+            feature = "imxrt600"
+        ))]
+        {
+            let extras = block.tail.extras;
+            let block_size = extras.block_size;
+            if extras.is_uniform_block_size == 0 && !block_size.is_multiple_of(sector_size) {
+                return Err(ValidationError::BlockSize(block_size));
+            }
+        }
+        let sizes = block.mem_cfg.serial_flash_sizes;
+        if sizes.iter().all(|&size| size == 0) {
+            return Err(ValidationError::NoFlashSize);
+        }
+        Ok(block)
+    }
+}
+
+#[cfg(any(
+    feature = "imxrt1170",
+    feature = "imxrt1180",
+    feature = "imxrt500",
+    feature = "imxrt600"
+))]
+impl FcbBuilder {
+    /// Set `deviceModeType`.
+    pub fn device_mode_type(
+        &mut self,
+        device_mode_type: crate::flexspi::DeviceModeType,
+    ) -> &mut Self {
+        self.map_mem_cfg(|cfg| cfg.device_mode_type(device_mode_type))
+    }
+
+    /// Set the serial NOR block size if it differs from the sector size.
+    ///
+    /// The block size must be non-zero. When you [`build`](FcbBuilder::build),
+    /// the block size must also be a multiple of the sector size.
+    pub fn block_size(&mut self, block_size: u32) -> Result<&mut Self, ValidationError> {
+        if block_size == 0 {
+            return Err(ValidationError::BlockSize(block_size));
+        }
+        self.block = self.block.block_size(block_size);
+        Ok(self)
+    }
+
+    /// Set `isDataOrderSwapped`.
+    pub fn is_data_order_swapped(&mut self, is_data_order_swapped: bool) -> &mut Self {
+        self.block = self.block.is_data_order_swapped(is_data_order_swapped);
+        self
+    }
+
+    /// Set `serialNorType`.
+    pub fn serial_nor_type(&mut self, serial_nor_type: super::SerialNorType) -> &mut Self {
+        self.block = self.block.serial_nor_type(serial_nor_type);
+        self
+    }
+
+    /// Set `needExitNoCmdMode`.
+    pub fn need_exit_no_cmd_mode(&mut self, need_exit_no_cmd_mode: bool) -> &mut Self {
+        self.block = self.block.need_exit_no_cmd_mode(need_exit_no_cmd_mode);
+        self
+    }
+
+    /// Set `halfClkForNonReadCmd`.
+    pub fn half_clk_for_non_read_cmd(&mut self, half_clk_for_non_read_cmd: bool) -> &mut Self {
+        self.block = self
+            .block
+            .half_clk_for_non_read_cmd(half_clk_for_non_read_cmd);
+        self
+    }
+
+    /// Set `needRestoreNoCmdMode`.
+    pub fn need_restore_no_cmd_mode(&mut self, need_restore_no_cmd_mode: bool) -> &mut Self {
+        self.block = self
+            .block
+            .need_restore_no_cmd_mode(need_restore_no_cmd_mode);
+        self
+    }
+
+    /// Set `flashStateCtx`.
+    pub fn flash_state_ctx(&mut self, flash_state_ctx: super::FlashStateContext) -> &mut Self {
+        self.block = self.block.flash_state_ctx(flash_state_ctx);
+        self
+    }
+}
+
+#[cfg(any(feature = "imxrt500", feature = "imxrt600"))]
+impl FcbBuilder {
+    /// Set `dll0CrVal`, the FlexSPI `DLLCR[0]` value for port A.
+    pub fn dll0_cr_val(&mut self, dll0_cr_val: u32) -> &mut Self {
+        self.map_mem_cfg(|cfg| cfg.dll0_cr_val(dll0_cr_val))
+    }
+
+    /// Set `dll1CrVal`, the FlexSPI `DLLCR[1]` value for port B.
+    pub fn dll1_cr_val(&mut self, dll1_cr_val: u32) -> &mut Self {
+        self.map_mem_cfg(|cfg| cfg.dll1_cr_val(dll1_cr_val))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{FcbBuilder, ValidationError};
+    use crate::flexspi::{self, LookupTable, SerialFlashRegion};
+    use crate::serial_flash::nor::ConfigurationBlock;
+
+    #[test]
+    fn matches_const_api() {
+        const EXPECTED: ConfigurationBlock = ConfigurationBlock::new(
+            flexspi::ConfigurationBlock::new(LookupTable::new())
+                .cs_hold_time(0x01)
+                .flash_size_bytes(SerialFlashRegion::A1, 0x0020_0000),
+        )
+        .page_size(256)
+        .sector_size(4096);
+
+        let actual = FcbBuilder::new(LookupTable::new())
+            .cs_hold_time(0x01)
+            .unwrap()
+            .flash_size(SerialFlashRegion::A1, 0x0020_0000)
+            .page_size(256)
+            .unwrap()
+            .sector_size(4096)
+            .unwrap()
+            .build()
+            .unwrap();
+
+        assert_eq!(actual.to_bytes(), EXPECTED.to_bytes());
+    }
+
+    #[test]
+    fn validates_as_it_goes() {
+        let mut builder = FcbBuilder::new(LookupTable::new());
+        assert_eq!(
+            builder.cs_setup_time(32).unwrap_err(),
+            ValidationError::CsSetupTime(32)
+        );
+        assert_eq!(
+            builder.page_size(255).unwrap_err(),
+            ValidationError::PageSize(255)
+        );
+        assert_eq!(
+            builder.wait_time_cfg_commands(150).unwrap_err(),
+            ValidationError::WaitTimeCfgCommands(150)
+        );
+        assert_eq!(
+            builder.config_command(3, 1, 0, 0).unwrap_err(),
+            ValidationError::ConfigCommandIndex(3)
+        );
+        assert_eq!(
+            builder.lut_custom_sequence(0, 2, 15).unwrap_err(),
+            ValidationError::LutSequence {
+                number_of_luts: 2,
+                starting_lut_index: 15
+            }
+        );
+    }
+
+    #[test]
+    fn validates_relationships() {
+        let mut builder = FcbBuilder::new(LookupTable::new());
+        assert_eq!(builder.build().unwrap_err(), ValidationError::PageSize(0));
+
+        builder.page_size(256).unwrap().sector_size(1000).unwrap();
+        assert_eq!(
+            builder.build().unwrap_err(),
+            ValidationError::SectorSize(1000)
+        );
+
+        builder.sector_size(4096).unwrap();
+        assert_eq!(builder.build().unwrap_err(), ValidationError::NoFlashSize);
+
+        builder.flash_size(SerialFlashRegion::B1, 1024 * 1024);
+        assert!(builder.build().is_ok());
+    }
+
+    #[test]
+    fn raw_fields() {
+        let block = FcbBuilder::new(LookupTable::new())
+            .config_command(1, 2, 12, 0xDEAD_BEEF)
+            .unwrap()
+            .timeout_ms(0x1234_5678)
+            .busy_offset(7)
+            .unwrap()
+            .flash_size(SerialFlashRegion::A1, 1024)
+            .page_size(256)
+            .unwrap()
+            .sector_size(4096)
+            .unwrap()
+            .build()
+            .unwrap();
+        let bytes = block.to_bytes();
+        assert_eq!(bytes[0x01C], 1);
+        assert_eq!(&bytes[0x024..0x028], &[2, 12, 0, 0]);
+        assert_eq!(&bytes[0x034..0x038], &0xDEAD_BEEFu32.to_le_bytes());
+        assert_eq!(&bytes[0x070..0x074], &0x1234_5678u32.to_le_bytes());
+        assert_eq!(&bytes[0x07C..0x07E], &7u16.to_le_bytes());
+    }
+
+    #[test]
+    fn parallel_flash_size() {
+        let block = FcbBuilder::new(LookupTable::new())
+            .parallel_flash_size(flexspi::Density::from_mebibytes(16))
+            .page_size(256)
+            .unwrap()
+            .sector_size(4096)
+            .unwrap()
+            .build()
+            .unwrap();
+        let mem_cfg = block.mem_cfg();
+        assert!(mem_cfg
+            .get_controller_misc_options()
+            .contains(flexspi::ControllerMiscOptions::PARALLEL));
+        for region in [SerialFlashRegion::A1, SerialFlashRegion::B1] {
+            assert_eq!(
+                mem_cfg.get_flash_size(region),
+                flexspi::Density::from_mebibytes(16)
+            );
+        }
+    }
+
+    #[cfg(any(feature = "imxrt500", feature = "imxrt600"))]
+    #[test]
+    fn dll_cr_vals() {
+        let block = FcbBuilder::new(LookupTable::new())
+            .dll0_cr_val(0x0000_0100)
+            .dll1_cr_val(0x0000_0200)
+            .flash_size(SerialFlashRegion::A1, 1024)
+            .page_size(256)
+            .unwrap()
+            .sector_size(4096)
+            .unwrap()
+            .build()
+            .unwrap();
+        assert_eq!(block.mem_cfg().get_dll0_cr_val(), 0x0000_0100);
+        assert_eq!(block.mem_cfg().get_dll1_cr_val(), 0x0000_0200);
+    }
+}
@@ -0,0 +1,1354 @@
+//! Flash command sets
+//!
+//! Most serial NOR flash parts differ only in a handful of opcodes, pads, and
+//! dummy cycles. A [`NorCommandSet`] captures those parameters, then derives the
+//! [`LookupTable`] and the [`NorFlashDevice`] for the part. Unless otherwise
+//! specified, a command set uses the common single-pad read, status, write
+//! enable, page program, sector erase, and chip erase commands, with 3-byte
+//! addresses.
+//!
+//! ```
+//! use imxrt_boot_gen::flexspi::FlashPadType;
+//! use imxrt_boot_gen::serial_flash::nor::{self, NorCommand, NorCommandSet};
+//!
+//! // A quad SPI part that reads with the 1-4-4 0xEB command.
+//! const COMMANDS: NorCommandSet = NorCommandSet::new()
+//!     .read(
+//!         NorCommand::new(0xEB)
+//!             .address_pads(FlashPadType::Quad)
+//!             .data_pads(FlashPadType::Quad)
+//!             .dummy_cycles(6),
+//!     )
+//!     .max_frequency_mhz(133);
+//!
+//! const FCB: nor::ConfigurationBlock = COMMANDS.fcb(8 * 1024 * 1024);
+//! ```
+
+use super::{ConfigurationBlock, NorFlashDevice};
+#[cfg(any(
+    feature = "imxrt1170",
+    feature = "imxrt1180",
+    feature = "imxrt500",
+    feature = "imxrt600"
+))]
+use super::{FlashStateContext, SerialNorType};
+#[cfg(any(
+    feature = "imxrt1170",
+    feature = "imxrt1180",
+    feature = "imxrt500",
+    feature = "imxrt600"
+))]
+use crate::flexspi::DeviceModeType;
+use crate::flexspi::{
+    opcodes::{ddr, sdr},
+    Command, ConfigurationCommand, Density, DeviceModeConfiguration, DeviceModeSequence,
+    FlashPadType, Instr, LookupTable, Pads, Sequence, SequenceBuilder, STOP,
+};
+
+const fn pads(pad_type: FlashPadType) -> Pads {
+    match pad_type {
+        FlashPadType::Single => Pads::One,
+        FlashPadType::Dual => Pads::Two,
+        FlashPadType::Quad => Pads::Four,
+        FlashPadType::Octal => Pads::Eight,
+    }
+}
+
+/// A flash command's opcode, pads, and dummy cycles
+///
+/// Unless otherwise specified, the command, address, and data use one pad, the
+/// command doesn't have dummy cycles, and the command is SDR. Commands that
+/// take an address use the command set's address width.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NorCommand {
+    opcode: u8,
+    extension: Option<u8>,
+    command_pads: FlashPadType,
+    address_pads: FlashPadType,
+    data_pads: FlashPadType,
+    address_bits: Option<u8>,
+    mode_bits: Option<u8>,
+    dummy_cycles: u8,
+    ddr: bool,
+}
+
+impl NorCommand {
+    /// Describe a command that starts with `opcode`.
+    pub const fn new(opcode: u8) -> Self {
+        NorCommand {
+            opcode,
+            extension: None,
+            command_pads: FlashPadType::Single,
+            address_pads: FlashPadType::Single,
+            data_pads: FlashPadType::Single,
+            address_bits: None,
+            mode_bits: None,
+            dummy_cycles: 0,
+            ddr: false,
+        }
+    }
+    /// Send `extension` after the opcode.
+    ///
+    /// Some octal parts expect a two byte command, like the opcode followed by
+    /// the inverted opcode.
+    pub const fn extension(mut self, extension: u8) -> Self {
+        self.extension = Some(extension);
+        self
+    }
+    /// Set the pads for the opcode.
+    pub const fn command_pads(mut self, command_pads: FlashPadType) -> Self {
+        self.command_pads = command_pads;
+        self
+    }
+    /// Set the pads for the address.
+    pub const fn address_pads(mut self, address_pads: FlashPadType) -> Self {
+        self.address_pads = address_pads;
+        self
+    }
+    /// Set the pads for the dummy cycles and data.
+    pub const fn data_pads(mut self, data_pads: FlashPadType) -> Self {
+        self.data_pads = data_pads;
+        self
+    }
+    /// Send `address_bits` of address, instead of the command set's address
+    /// width.
+    ///
+    /// Use this for commands that need an address, but that aren't reads,
+    /// programs, or erases. For instance, an octal part's read status command
+    /// may need an address.
+    pub const fn address_bits(mut self, address_bits: u8) -> Self {
+        self.address_bits = Some(address_bits);
+        self
+    }
+    /// Send `mode_bits` on the address pads, after the address.
+    ///
+    /// The mode bits take the first dummy cycles: two cycles on four pads, or
+    /// four cycles on two pads. Flash parts use the mode bits to enter or exit
+    /// their [continuous read mode](NorCommandSet::continuous_read).
+    pub const fn mode_bits(mut self, mode_bits: u8) -> Self {
+        self.mode_bits = Some(mode_bits);
+        self
+    }
+    /// Set the dummy cycles, including any mode bit cycles.
+    pub const fn dummy_cycles(mut self, dummy_cycles: u8) -> Self {
+        self.dummy_cycles = dummy_cycles;
+        self
+    }
+    /// Indicate that the command transfers on both clock edges.
+    pub const fn ddr(mut self, ddr: bool) -> Self {
+        self.ddr = ddr;
+        self
+    }
+    /// Returns the opcode.
+    pub const fn get_opcode(&self) -> u8 {
+        self.opcode
+    }
+    /// Returns the command extension, if set.
+    pub const fn get_extension(&self) -> Option<u8> {
+        self.extension
+    }
+    /// Returns the pads for the opcode.
+    pub const fn get_command_pads(&self) -> FlashPadType {
+        self.command_pads
+    }
+    /// Returns the pads for the address.
+    pub const fn get_address_pads(&self) -> FlashPadType {
+        self.address_pads
+    }
+    /// Returns the pads for the dummy cycles and data.
+    pub const fn get_data_pads(&self) -> FlashPadType {
+        self.data_pads
+    }
+    /// Returns the address width, if it overrides the command set's address
+    /// width.
+    pub const fn get_address_bits(&self) -> Option<u8> {
+        self.address_bits
+    }
+    /// Returns the mode bits, if set.
+    pub const fn get_mode_bits(&self) -> Option<u8> {
+        self.mode_bits
+    }
+    /// Returns the dummy cycles.
+    pub const fn get_dummy_cycles(&self) -> u8 {
+        self.dummy_cycles
+    }
+    /// Returns `true` if the command transfers on both clock edges.
+    pub const fn get_ddr(&self) -> bool {
+        self.ddr
+    }
+
+    /// Returns the command's sequence.
+    ///
+    /// The sequence sends the opcode and its extension, then the address if
+    /// the address width is non-zero, then the mode bits, then the remaining
+    /// dummy cycles, then transfers `data`. Unless the command overrides it,
+    /// the address width is `address_bits`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the mode bits take more cycles than the dummy cycles.
+    const fn sequence(&self, address_bits: u8, data: Data) -> Sequence {
+        let address_bits = match self.address_bits {
+            Some(address_bits) => address_bits,
+            None => address_bits,
+        };
+        let (cmd, raddr, mode8, dummy, read, write) = if self.ddr {
+            (
+                ddr::CMD,
+                ddr::RADDR,
+                ddr::MODE8,
+                ddr::DUMMY,
+                ddr::READ,
+                ddr::WRITE,
+            )
+        } else {
+            (
+                sdr::CMD,
+                sdr::RADDR,
+                sdr::MODE8,
+                sdr::DUMMY,
+                sdr::READ,
+                sdr::WRITE,
+            )
+        };
+        let mut seq =
+            SequenceBuilder::new().instr(Instr::new(cmd, pads(self.command_pads), self.opcode));
+        if let Some(extension) = self.extension {
+            seq = seq.instr(Instr::new(cmd, pads(self.command_pads), extension));
+        }
+        if address_bits != 0 {
+            seq = seq.instr(Instr::new(raddr, pads(self.address_pads), address_bits));
+        }
+        let mut dummy_cycles = self.dummy_cycles;
+        if let Some(mode_bits) = self.mode_bits {
+            let mode_cycles = self.mode_cycles();
+            assert!(
+                mode_cycles <= dummy_cycles,
+                "The dummy cycles must include the mode bit cycles"
+            );
+            seq = seq.instr(Instr::new(mode8, pads(self.address_pads), mode_bits));
+            dummy_cycles -= mode_cycles;
+        }
+        if dummy_cycles != 0 {
+            seq = seq.instr(Instr::new(dummy, pads(self.data_pads), dummy_cycles));
+        }
+        match data {
+            Data::None => {}
+            Data::Read(size) => seq = seq.instr(Instr::new(read, pads(self.data_pads), size)),
+            Data::Write(size) => seq = seq.instr(Instr::new(write, pads(self.data_pads), size)),
+        }
+        seq.build()
+    }
+
+    /// Returns the clock cycles that the eight mode bits take.
+    const fn mode_cycles(&self) -> u8 {
+        let bits_per_cycle = self.address_pads as u8 * if self.ddr { 2 } else { 1 };
+        if bits_per_cycle >= 8 {
+            1
+        } else {
+            8 / bits_per_cycle
+        }
+    }
+
+    /// Returns the number of instructions before the address.
+    const fn command_instrs(&self) -> u8 {
+        if self.extension.is_some() {
+            2
+        } else {
+            1
+        }
+    }
+}
+
+/// Replace the first `STOP` in `sequence` with a `JUMP_ON_CS` to `index`.
+const fn jump_on_cs(mut sequence: Sequence, index: u8) -> Sequence {
+    let mut idx = 0;
+    while idx < sequence.0.len() {
+        if sequence.0[idx].opcode().const_eq(STOP.opcode()) {
+            sequence.0[idx] = Instr::jump_on_cs(index);
+            return sequence;
+        }
+        idx += 1;
+    }
+    panic!("The sequence has no room for a JUMP_ON_CS");
+}
+
+/// Remove the first `count` instructions from `sequence`.
+const fn skip(sequence: Sequence, count: u8) -> Sequence {
+    let mut skipped = Sequence::stopped();
+    let mut idx = count as usize;
+    while idx < sequence.0.len() {
+        skipped.0[idx - count as usize] = sequence.0[idx];
+        idx += 1;
+    }
+    skipped
+}
+
+/// The data that a command transfers, and the instruction's operand.
+#[derive(Clone, Copy)]
+enum Data {
+    None,
+    Read(u8),
+    Write(u8),
+}
+
+/// The LUT index of the read status sequence for xSPI modes.
+const READ_STATUS_XPI_SEQ_INDEX: usize = 2;
+/// The LUT index of the write enable sequence for xSPI modes.
+const WRITE_ENABLE_XPI_SEQ_INDEX: usize = 4;
+/// The LUT index of the device mode sequence.
+///
+/// The boot ROMs don't assign this index to any other command.
+const DEVICE_MODE_SEQ_INDEX: usize = 6;
+
+/// The LUT index of the sequence that enters 4-byte address mode.
+///
+/// The boot ROMs don't assign this index to any other command.
+const FOUR_BYTE_ADDRESSES_SEQ_INDEX: usize = 7;
+
+/// The LUT index of the read that re-enters continuous read mode.
+///
+/// The 1170, 1180, 500, and 600 boot ROMs use this index to restore the
+/// no-command mode.
+const RESTORE_CONTINUOUS_READ_SEQ_INDEX: usize = 14;
+/// The LUT index of the read that exits continuous read mode.
+///
+/// This is the [`Dummy`](Command::Dummy) sequence. The 1170, 1180, 500, and
+/// 600 boot ROMs use this index to exit the no-command mode.
+const EXIT_CONTINUOUS_READ_SEQ_INDEX: usize = Command::Dummy as usize;
+
+/// The largest flash that 3-byte addresses can reach, in bytes.
+const THREE_BYTE_ADDRESS_LIMIT: u32 = 16 * 1024 * 1024;
+
+/// A device mode configuration command
+///
+/// The boot ROM sends a write enable, then sends the command with the
+/// `deviceModeArg` as its data, before it reads the image. Use a device mode
+/// command to set the flash's quad enable bit, or to switch the flash into a
+/// faster mode.
+///
+/// Unless otherwise specified, the command sends one byte of data. If the
+/// command has an [address width](NorCommand::address_bits), the boot ROM
+/// sends address zero.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DeviceMode {
+    command: NorCommand,
+    arg: u32,
+    data_bytes: u8,
+    #[cfg(any(
+        feature = "imxrt1170",
+        feature = "imxrt1180",
+        feature = "imxrt500",
+        feature = "imxrt600"
+    ))]
+    device_mode_type: DeviceModeType,
+}
+
+impl DeviceMode {
+    /// Send `command` with `arg` as its data.
+    pub const fn new(command: NorCommand, arg: u32) -> Self {
+        DeviceMode {
+            command,
+            arg,
+            data_bytes: 1,
+            #[cfg(any(
+                feature = "imxrt1170",
+                feature = "imxrt1180",
+                feature = "imxrt500",
+                feature = "imxrt600"
+            ))]
+            device_mode_type: DeviceModeType::Generic,
+        }
+    }
+    /// Set the flash's quad enable bit by writing `arg` with `command`.
+    ///
+    /// On chips with a `deviceModeType` field, the type is
+    /// [`QuadEnable`](DeviceModeType::QuadEnable).
+    pub const fn quad_enable(command: NorCommand, arg: u32) -> Self {
+        let device_mode = Self::new(command, arg);
+        #[cfg(any(
+            feature = "imxrt1170",
+            feature = "imxrt1180",
+            feature = "imxrt500",
+            feature = "imxrt600"
+        ))]
+        let device_mode = device_mode.device_mode_type(DeviceModeType::QuadEnable);
+        device_mode
+    }
+    /// Set the number of data bytes, from 1 through 4.
+    ///
+    /// The boot ROM sends the argument's least significant byte first.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `data_bytes` is zero, or larger than four.
+    pub const fn data_bytes(mut self, data_bytes: u8) -> Self {
+        assert!(
+            0 < data_bytes && data_bytes <= 4,
+            "The device mode argument has one to four bytes"
+        );
+        self.data_bytes = data_bytes;
+        self
+    }
+    /// Set the purpose of the device mode configuration.
+    #[cfg(any(
+        feature = "imxrt1170",
+        feature = "imxrt1180",
+        feature = "imxrt500",
+        feature = "imxrt600"
+    ))]
+    pub const fn device_mode_type(mut self, device_mode_type: DeviceModeType) -> Self {
+        self.device_mode_type = device_mode_type;
+        self
+    }
+    /// Returns the command.
+    pub const fn get_command(&self) -> NorCommand {
+        self.command
+    }
+    /// Returns the argument.
+    pub const fn get_arg(&self) -> u32 {
+        self.arg
+    }
+    /// Returns the number of data bytes.
+    pub const fn get_data_bytes(&self) -> u8 {
+        self.data_bytes
+    }
+    /// Returns the purpose of the device mode configuration.
+    #[cfg(any(
+        feature = "imxrt1170",
+        feature = "imxrt1180",
+        feature = "imxrt500",
+        feature = "imxrt600"
+    ))]
+    pub const fn get_device_mode_type(&self) -> DeviceModeType {
+        self.device_mode_type
+    }
+
+    const fn sequence(&self) -> Sequence {
+        self.command.sequence(0, Data::Write(self.data_bytes))
+    }
+}
+
+/// Where a quad SPI flash keeps its quad enable bit, and how to set it
+///
+/// Many quad SPI parts don't respond to quad commands until their quad enable
+/// bit is set. Parts differ in the status register bit, and in the command
+/// that writes it. A scheme generates the [`DeviceMode`] that sets the bit,
+/// including the command sequence and the argument.
+///
+/// ```
+/// use imxrt_boot_gen::serial_flash::nor::{NorCommandSet, QuadEnable};
+///
+/// const COMMANDS: NorCommandSet = NorCommandSet::new()
+///     .quad_enable(QuadEnable::StatusRegister2Bit1);
+///
+/// let device_mode = COMMANDS.get_device_mode().unwrap();
+/// assert_eq!(device_mode.get_command().get_opcode(), 0x31);
+/// assert_eq!(device_mode.get_arg(), 0x02);
+/// ```
+///
+/// The boot ROM sends the common write enable command before the device mode
+/// command, so every scheme writes the non-volatile status register. Writing
+/// a status register also clears its other bits, like the block protection
+/// bits, which are zero by default.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QuadEnable {
+    /// Bit 6 of status register 1, written with `0x01`.
+    ///
+    /// ISSI and Macronix parts use this scheme.
+    StatusRegister1Bit6,
+    /// Bit 1 of status register 2, written with `0x31`.
+    ///
+    /// Winbond and Adesto parts use this scheme.
+    StatusRegister2Bit1,
+    /// Bit 1 of status register 2, written with `0x01` together with status
+    /// register 1.
+    ///
+    /// Status register 1 is written with zero. Use this scheme for parts that
+    /// can't write status register 2 on its own, like some GigaDevice parts.
+    StatusRegisters1And2Bit1,
+    /// Bit 7 of status register 2, written with `0x3E`.
+    StatusRegister2Bit7,
+}
+
+impl QuadEnable {
+    /// Returns the device mode configuration that sets the quad enable bit.
+    ///
+    /// On chips with a `deviceModeType` field, the type is
+    /// [`QuadEnable`](DeviceModeType::QuadEnable).
+    pub const fn device_mode(self) -> DeviceMode {
+        match self {
+            QuadEnable::StatusRegister1Bit6 => DeviceMode::quad_enable(NorCommand::new(0x01), 0x40),
+            QuadEnable::StatusRegister2Bit1 => DeviceMode::quad_enable(NorCommand::new(0x31), 0x02),
+            QuadEnable::StatusRegisters1And2Bit1 => {
+                // Status register 1, then status register 2.
+                DeviceMode::quad_enable(NorCommand::new(0x01), 0x0200).data_bytes(2)
+            }
+            QuadEnable::StatusRegister2Bit7 => DeviceMode::quad_enable(NorCommand::new(0x3E), 0x80),
+        }
+    }
+}
+
+/// Mode bits that enter and exit a flash's continuous read mode
+///
+/// In continuous read mode, also called XIP enhance or performance enhance
+/// mode, the flash expects each read to start with the address, not the read
+/// opcode. The read sends mode bits after the address. If they match the
+/// flash's continuous read pattern, the flash stays in continuous read mode
+/// after the read. Otherwise, it leaves continuous read mode. Check your flash's
+/// data sheet for the pattern. For instance, Winbond parts stay in continuous
+/// read mode when bits 5 and 4 are `0b10`, and Macronix parts stay in
+/// performance enhance mode when the upper and lower halves of the mode bits
+/// differ in every bit. `0xA5` enters either mode.
+///
+/// Unless otherwise specified, the exit mode bits are `0xFF`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ContinuousRead {
+    mode_bits: u8,
+    exit_mode_bits: u8,
+}
+
+impl ContinuousRead {
+    /// Enter continuous read mode with `mode_bits`.
+    pub const fn new(mode_bits: u8) -> Self {
+        ContinuousRead {
+            mode_bits,
+            exit_mode_bits: 0xFF,
+        }
+    }
+    /// Exit continuous read mode with `exit_mode_bits`.
+    pub const fn exit_mode_bits(mut self, exit_mode_bits: u8) -> Self {
+        self.exit_mode_bits = exit_mode_bits;
+        self
+    }
+    /// Returns the mode bits that enter continuous read mode.
+    pub const fn get_mode_bits(&self) -> u8 {
+        self.mode_bits
+    }
+    /// Returns the mode bits that exit continuous read mode.
+    pub const fn get_exit_mode_bits(&self) -> u8 {
+        self.exit_mode_bits
+    }
+}
+
+/// A flash part's commands and geometry
+///
+/// See the [module documentation](self) for the defaults.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NorCommandSet {
+    read: NorCommand,
+    read_status: NorCommand,
+    read_status_xpi: Option<NorCommand>,
+    write_enable: NorCommand,
+    write_enable_xpi: Option<NorCommand>,
+    page_program: NorCommand,
+    erase_sector: NorCommand,
+    erase_block: Option<NorCommand>,
+    chip_erase: NorCommand,
+    device_mode: Option<DeviceMode>,
+    enter_four_byte_addresses: Option<NorCommand>,
+    continuous_read: Option<ContinuousRead>,
+    address_bits: u8,
+    page_size: u32,
+    sector_size: u32,
+    block_size: u32,
+    max_frequency_mhz: u32,
+    dqs: bool,
+}
+
+impl Default for NorCommandSet {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl NorCommandSet {
+    /// Describe a part that uses the common single-pad commands.
+    ///
+    /// The part reads with `0x03`, has 256 byte pages, 4KiB sectors, and
+    /// 64KiB blocks, and runs at 30MHz.
+    pub const fn new() -> Self {
+        NorCommandSet {
+            read: NorCommand::new(0x03),
+            read_status: NorCommand::new(0x05),
+            read_status_xpi: None,
+            write_enable: NorCommand::new(0x06),
+            write_enable_xpi: None,
+            page_program: NorCommand::new(0x02),
+            erase_sector: NorCommand::new(0x20),
+            erase_block: None,
+            chip_erase: NorCommand::new(0x60),
+            device_mode: None,
+            enter_four_byte_addresses: None,
+            continuous_read: None,
+            address_bits: 24,
+            page_size: 256,
+            sector_size: 4096,
+            block_size: 64 * 1024,
+            max_frequency_mhz: 30,
+            dqs: false,
+        }
+    }
+    /// Set the read command.
+    pub const fn read(mut self, read: NorCommand) -> Self {
+        self.read = read;
+        self
+    }
+    /// Set the read status command.
+    pub const fn read_status(mut self, read_status: NorCommand) -> Self {
+        self.read_status = read_status;
+        self
+    }
+    /// Set the read status command for after the device mode configuration
+    /// switches the part into a DPI, QPI, or OPI mode.
+    ///
+    /// The lookup table has the command's sequence at index 2.
+    pub const fn read_status_xpi(mut self, read_status_xpi: NorCommand) -> Self {
+        self.read_status_xpi = Some(read_status_xpi);
+        self
+    }
+    /// Set the write enable command.
+    pub const fn write_enable(mut self, write_enable: NorCommand) -> Self {
+        self.write_enable = write_enable;
+        self
+    }
+    /// Set the write enable command for after the device mode configuration
+    /// switches the part into a DPI, QPI, or OPI mode.
+    ///
+    /// The lookup table has the command's sequence at index 4.
+    pub const fn write_enable_xpi(mut self, write_enable_xpi: NorCommand) -> Self {
+        self.write_enable_xpi = Some(write_enable_xpi);
+        self
+    }
+    /// Set the page program command.
+    pub const fn page_program(mut self, page_program: NorCommand) -> Self {
+        self.page_program = page_program;
+        self
+    }
+    /// Set the sector erase command.
+    pub const fn erase_sector(mut self, erase_sector: NorCommand) -> Self {
+        self.erase_sector = erase_sector;
+        self
+    }
+    /// Set the block erase command.
+    ///
+    /// Only the 1170, 1180, 500, and 600 boot ROMs use the block erase
+    /// sequence. On other chips, the lookup table doesn't include it.
+    pub const fn erase_block(mut self, erase_block: NorCommand) -> Self {
+        self.erase_block = Some(erase_block);
+        self
+    }
+    /// Set the chip erase command.
+    pub const fn chip_erase(mut self, chip_erase: NorCommand) -> Self {
+        self.chip_erase = chip_erase;
+        self
+    }
+    /// Set the command that the boot ROM sends to configure the flash.
+    ///
+    /// The lookup table has the command's sequence at index 6.
+    pub const fn device_mode(mut self, device_mode: DeviceMode) -> Self {
+        self.device_mode = Some(device_mode);
+        self
+    }
+    /// Set the flash's quad enable bit with the `scheme`.
+    ///
+    /// This is shorthand for a [`device_mode`](Self::device_mode) from
+    /// [`QuadEnable::device_mode`].
+    pub const fn quad_enable(self, scheme: QuadEnable) -> Self {
+        self.device_mode(scheme.device_mode())
+    }
+    /// Switch the flash into 4-byte address mode with `command`, then use
+    /// 32-bit addresses.
+    ///
+    /// Use this for a flash that's larger than 16MiB, and that doesn't have
+    /// 4-byte read, program, and erase commands. The read, page program,
+    /// sector erase, and block erase commands keep their opcodes, and they
+    /// send 32-bit addresses. Most parts enter 4-byte address mode with
+    /// `0xB7`.
+    ///
+    /// The lookup table has the command's sequence at index 7. The
+    /// configuration block runs it as the first configuration command, after
+    /// any device mode configuration.
+    ///
+    /// ```
+    /// use imxrt_boot_gen::flexspi::FlashPadType;
+    /// use imxrt_boot_gen::serial_flash::nor::{self, NorCommand, NorCommandSet, QuadEnable};
+    ///
+    /// const COMMANDS: NorCommandSet = NorCommandSet::new()
+    ///     .read(
+    ///         NorCommand::new(0xEB)
+    ///             .address_pads(FlashPadType::Quad)
+    ///             .data_pads(FlashPadType::Quad)
+    ///             .dummy_cycles(6),
+    ///     )
+    ///     .quad_enable(QuadEnable::StatusRegister1Bit6)
+    ///     .enter_four_byte_addresses(NorCommand::new(0xB7));
+    ///
+    /// const FCB: nor::ConfigurationBlock = COMMANDS.fcb(32 * 1024 * 1024);
+    /// ```
+    pub const fn enter_four_byte_addresses(mut self, command: NorCommand) -> Self {
+        self.enter_four_byte_addresses = Some(command);
+        self.address_bits(32)
+    }
+    /// Read in the flash's continuous read mode.
+    ///
+    /// The read command sends the `continuous_read` mode bits after the
+    /// address, in its first dummy cycles. The read sequence ends with a
+    /// `JUMP_ON_CS` to the address instruction, so every read after the first
+    /// skips the opcode. The lookup table also has
+    ///
+    /// - at index 14, the read command with the mode bits, which re-enters
+    ///   continuous read mode.
+    /// - at index 15, the read without its opcode, with the exit mode bits,
+    ///   which leaves continuous read mode.
+    ///
+    /// On chips with `serialNorType`, `needExitNoCmdMode`, and
+    /// `needRestoreNoCmdMode` fields, the configuration block describes a
+    /// no-command part, and the boot ROM exits and restores continuous read
+    /// mode around its other commands.
+    ///
+    /// In continuous read mode, the flash doesn't respond to any other
+    /// command. Before your firmware programs or erases the flash, it must
+    /// run the exit sequence. If the chip resets while the flash is in
+    /// continuous read mode, and the flash isn't also reset, a boot ROM that
+    /// sends the read opcode can't read the flash, and the chip won't boot.
+    /// Only use continuous read mode if your board resets the flash with the
+    /// chip.
+    ///
+    /// ```
+    /// use imxrt_boot_gen::flexspi::FlashPadType;
+    /// use imxrt_boot_gen::serial_flash::nor::{
+    ///     self, ContinuousRead, NorCommand, NorCommandSet, QuadEnable,
+    /// };
+    ///
+    /// const COMMANDS: NorCommandSet = NorCommandSet::new()
+    ///     .read(
+    ///         NorCommand::new(0xEB)
+    ///             .address_pads(FlashPadType::Quad)
+    ///             .data_pads(FlashPadType::Quad)
+    ///             .dummy_cycles(6),
+    ///     )
+    ///     .quad_enable(QuadEnable::StatusRegister2Bit1)
+    ///     .continuous_read(ContinuousRead::new(0x20));
+    ///
+    /// const FCB: nor::ConfigurationBlock = COMMANDS.fcb(16 * 1024 * 1024);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// The lookup table panics if the read command's dummy cycles are fewer
+    /// than the mode bit cycles.
+    pub const fn continuous_read(mut self, continuous_read: ContinuousRead) -> Self {
+        self.continuous_read = Some(continuous_read);
+        self
+    }
+    /// Set the number of address bits, 24 or 32.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `address_bits` isn't 24 or 32.
+    pub const fn address_bits(mut self, address_bits: u8) -> Self {
+        assert!(
+            address_bits == 24 || address_bits == 32,
+            "The address must be 24 or 32 bits"
+        );
+        self.address_bits = address_bits;
+        self
+    }
+    /// Set the page size, in bytes.
+    pub const fn page_size(mut self, page_size: u32) -> Self {
+        self.page_size = page_size;
+        self
+    }
+    /// Set the size erased by the sector erase command, in bytes.
+    pub const fn sector_size(mut self, sector_size: u32) -> Self {
+        self.sector_size = sector_size;
+        self
+    }
+    /// Set the size erased by the block erase command, in bytes.
+    pub const fn block_size(mut self, block_size: u32) -> Self {
+        self.block_size = block_size;
+        self
+    }
+    /// Set the part's maximum serial clock frequency, in MHz.
+    pub const fn max_frequency_mhz(mut self, max_frequency_mhz: u32) -> Self {
+        self.max_frequency_mhz = max_frequency_mhz;
+        self
+    }
+    /// Indicate that the part drives a DQS (data strobe) signal.
+    pub const fn dqs(mut self, dqs: bool) -> Self {
+        self.dqs = dqs;
+        self
+    }
+    /// Returns the read command.
+    pub const fn get_read(&self) -> NorCommand {
+        self.read
+    }
+    /// Returns the read status command.
+    pub const fn get_read_status(&self) -> NorCommand {
+        self.read_status
+    }
+    /// Returns the xSPI read status command, if set.
+    pub const fn get_read_status_xpi(&self) -> Option<NorCommand> {
+        self.read_status_xpi
+    }
+    /// Returns the write enable command.
+    pub const fn get_write_enable(&self) -> NorCommand {
+        self.write_enable
+    }
+    /// Returns the xSPI write enable command, if set.
+    pub const fn get_write_enable_xpi(&self) -> Option<NorCommand> {
+        self.write_enable_xpi
+    }
+    /// Returns the page program command.
+    pub const fn get_page_program(&self) -> NorCommand {
+        self.page_program
+    }
+    /// Returns the sector erase command.
+    pub const fn get_erase_sector(&self) -> NorCommand {
+        self.erase_sector
+    }
+    /// Returns the block erase command, if set.
+    pub const fn get_erase_block(&self) -> Option<NorCommand> {
+        self.erase_block
+    }
+    /// Returns the chip erase command.
+    pub const fn get_chip_erase(&self) -> NorCommand {
+        self.chip_erase
+    }
+    /// Returns the device mode command, if set.
+    pub const fn get_device_mode(&self) -> Option<DeviceMode> {
+        self.device_mode
+    }
+    /// Returns the command that enters 4-byte address mode.
+    pub const fn get_enter_four_byte_addresses(&self) -> Option<NorCommand> {
+        self.enter_four_byte_addresses
+    }
+    /// Returns the continuous read mode bits, if set.
+    pub const fn get_continuous_read(&self) -> Option<ContinuousRead> {
+        self.continuous_read
+    }
+    /// Returns the number of address bits.
+    pub const fn get_address_bits(&self) -> u8 {
+        self.address_bits
+    }
+    /// Returns the page size, in bytes.
+    pub const fn get_page_size(&self) -> u32 {
+        self.page_size
+    }
+    /// Returns the sector size, in bytes.
+    pub const fn get_sector_size(&self) -> u32 {
+        self.sector_size
+    }
+    /// Returns the block size, in bytes.
+    pub const fn get_block_size(&self) -> u32 {
+        self.block_size
+    }
+    /// Returns the part's maximum serial clock frequency, in MHz.
+    pub const fn get_max_frequency_mhz(&self) -> u32 {
+        self.max_frequency_mhz
+    }
+    /// Returns `true` if the part drives DQS.
+    pub const fn get_dqs(&self) -> bool {
+        self.dqs
+    }
+
+    /// Returns a lookup table with the read, read status, write enable,
+    /// sector erase, page program, and chip erase sequences.
+    ///
+    /// The lookup table also has the xSPI read status and write enable
+    /// sequences and the device mode sequence, if set, and the block erase
+    /// sequence, if the command set has a block erase command and
+    /// the chip's boot ROM uses it. With continuous read mode, it has the
+    /// sequences that restore and exit the mode.
+    pub const fn lookup_table(&self) -> LookupTable {
+        let address_bits = self.address_bits;
+
+        let read = match self.continuous_read {
+            Some(continuous_read) => {
+                let read = self.read.mode_bits(continuous_read.mode_bits);
+                let restore = read.sequence(address_bits, Data::Read(0x04));
+                let exit = self
+                    .read
+                    .mode_bits(continuous_read.exit_mode_bits)
+                    .sequence(address_bits, Data::Read(0x04));
+                let start = read.command_instrs();
+                LookupTable::new()
+                    .command(Command::Read, jump_on_cs(restore, start))
+                    .sequence(RESTORE_CONTINUOUS_READ_SEQ_INDEX, restore)
+                    .sequence(EXIT_CONTINUOUS_READ_SEQ_INDEX, skip(exit, start))
+            }
+            None => LookupTable::new().command(
+                Command::Read,
+                self.read.sequence(address_bits, Data::Read(0x04)),
+            ),
+        };
+        let mut lookup_table = read
+            .command(
+                Command::ReadStatus,
+                self.read_status.sequence(0, Data::Read(0x04)),
+            )
+            .command(
+                Command::WriteEnable,
+                self.write_enable.sequence(0, Data::None),
+            )
+            .command(
+                Command::EraseSector,
+                self.erase_sector.sequence(address_bits, Data::None),
+            )
+            .command(
+                Command::PageProgram,
+                self.page_program.sequence(address_bits, Data::Write(0x04)),
+            )
+            .command(Command::ChipErase, self.chip_erase.sequence(0, Data::None));
+
+        if let Some(read_status_xpi) = self.read_status_xpi {
+            lookup_table = lookup_table.sequence(
+                READ_STATUS_XPI_SEQ_INDEX,
+                read_status_xpi.sequence(0, Data::Read(0x04)),
+            );
+        }
+        if let Some(write_enable_xpi) = self.write_enable_xpi {
+            lookup_table = lookup_table.sequence(
+                WRITE_ENABLE_XPI_SEQ_INDEX,
+                write_enable_xpi.sequence(0, Data::None),
+            );
+        }
+        if let Some(device_mode) = self.device_mode {
+            lookup_table = lookup_table.sequence(DEVICE_MODE_SEQ_INDEX, device_mode.sequence());
+        }
+        if let Some(enter_four_byte_addresses) = self.enter_four_byte_addresses {
+            lookup_table = lookup_table.sequence(
+                FOUR_BYTE_ADDRESSES_SEQ_INDEX,
+                enter_four_byte_addresses.sequence(0, Data::None),
+            );
+        }
+
+        #[cfg(any(
+            feature = "imxrt1170",
+            feature = "imxrt1180",
+            feature = "imxrt500",
+            feature = "imxrt600"
+        ))]
+        if let Some(erase_block) = self.erase_block {
+            lookup_table = lookup_table.command(
+                Command::EraseBlock,
+                erase_block.sequence(address_bits, Data::None),
+            );
+        }
+
+        lookup_table
+    }
+
+    /// Describe a part with `flash_size` bytes as a [`NorFlashDevice`].
+    ///
+    /// The device's pad type is the read command's data pads, and the device
+    /// is DDR if the read command is DDR.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the part is larger than 16MiB, and the command set uses
+    /// 3-byte addresses. Those addresses can't reach the end of the flash.
+    pub const fn device(&self, flash_size: u32) -> NorFlashDevice {
+        assert!(
+            self.address_bits == 32 || flash_size <= THREE_BYTE_ADDRESS_LIMIT,
+            "The flash is larger than 16MiB, so it needs 32-bit addresses"
+        );
+        NorFlashDevice::new(self.lookup_table())
+            .flash_size(flash_size)
+            .page_size(self.page_size)
+            .sector_size(self.sector_size)
+            .pad_type(self.read.data_pads)
+            .max_frequency_mhz(self.max_frequency_mhz)
+            .dqs(self.dqs)
+            .ddr(self.read.ddr)
+    }
+
+    /// Generate a configuration block for a part with `flash_size` bytes.
+    ///
+    /// This is [`recommended_fcb`](super::recommended_fcb) for the
+    /// [`device`](Self::device), with the device mode configuration, and the
+    /// configuration command that enters 4-byte address mode. On chips
+    /// with `blockSize` and `serialNorType` fields, the block also has the
+    /// command set's block size, and it's an xSPI part if the read command
+    /// sends its opcode on eight pads.
+    ///
+    /// # Panics
+    ///
+    /// Panics for the same reasons as `recommended_fcb` and `device`.
+    pub const fn fcb(&self, flash_size: u32) -> ConfigurationBlock {
+        let fcb = super::recommended_fcb(&self.device(flash_size));
+        #[cfg(any(
+            feature = "imxrt1170",
+            feature = "imxrt1180",
+            feature = "imxrt500",
+            feature = "imxrt600"
+        ))]
+        let fcb = {
+            let fcb = fcb.block_size(self.block_size);
+            if matches!(self.read.command_pads, FlashPadType::Octal) {
+                fcb.serial_nor_type(SerialNorType::Xspi)
+            } else {
+                fcb
+            }
+        };
+        #[cfg(any(
+            feature = "imxrt1170",
+            feature = "imxrt1180",
+            feature = "imxrt500",
+            feature = "imxrt600"
+        ))]
+        let fcb = match self.continuous_read {
+            Some(_) => fcb
+                .serial_nor_type(SerialNorType::NoCmd)
+                .need_exit_no_cmd_mode(true)
+                .need_restore_no_cmd_mode(true)
+                .flash_state_ctx(
+                    FlashStateContext::new()
+                        .exit_no_cmd_sequence(EXIT_CONTINUOUS_READ_SEQ_INDEX as u8)
+                        .restore_sequence(RESTORE_CONTINUOUS_READ_SEQ_INDEX as u8),
+                ),
+            None => fcb,
+        };
+        let fcb = match self.device_mode {
+            Some(device_mode) => {
+                let mem_cfg =
+                    fcb.mem_cfg()
+                        .device_mode_configuration(DeviceModeConfiguration::Enabled {
+                            device_mode_arg: device_mode.arg,
+                            device_mode_seq: DeviceModeSequence::new(
+                                1,
+                                DEVICE_MODE_SEQ_INDEX as u8,
+                            ),
+                        });
+                #[cfg(any(
+                    feature = "imxrt1170",
+                    feature = "imxrt1180",
+                    feature = "imxrt500",
+                    feature = "imxrt600"
+                ))]
+                let mem_cfg = mem_cfg.device_mode_type(device_mode.device_mode_type);
+                fcb.with_mem_cfg(mem_cfg)
+            }
+            None => fcb,
+        };
+        match self.enter_four_byte_addresses {
+            Some(_) => fcb.with_mem_cfg(fcb.mem_cfg().config_command(
+                0,
+                ConfigurationCommand::new(1, FOUR_BYTE_ADDRESSES_SEQ_INDEX as u8, 0),
+            )),
+            None => fcb,
+        }
+    }
+
+    /// Generate a configuration block for two of the part in parallel mode,
+    /// each with `flash_size` bytes.
+    ///
+    /// This is [`fcb`](Self::fcb) for one of the parts, with the flash on B1
+    /// paired with the flash on A1 in
+    /// [parallel mode](crate::flexspi::ConfigurationBlock::parallel_flash_size).
+    /// The page, sector, and block sizes are twice the part's sizes, since
+    /// each program and erase covers both parts.
+    ///
+    /// ```
+    /// use imxrt_boot_gen::serial_flash::nor::{self, presets::winbond};
+    ///
+    /// // Two W25Q64JV, 16MiB in total.
+    /// const FCB: nor::ConfigurationBlock = winbond::W25Q_JV.parallel_fcb(winbond::W25Q64JV);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics for the same reasons as [`fcb`](Self::fcb).
+    pub const fn parallel_fcb(&self, flash_size: u32) -> ConfigurationBlock {
+        let fcb = self.fcb(flash_size);
+        let mem_cfg = fcb
+            .mem_cfg()
+            .parallel_flash_size(Density::from_bytes(flash_size));
+        let fcb = fcb
+            .with_mem_cfg(mem_cfg)
+            .page_size(2 * self.page_size)
+            .sector_size(2 * self.sector_size);
+        #[cfg(any(
+            feature = "imxrt1170",
+            feature = "imxrt1180",
+            feature = "imxrt500",
+            feature = "imxrt600"
+        ))]
+        let fcb = fcb.block_size(2 * self.block_size);
+        fcb
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{ContinuousRead, NorCommand, NorCommandSet, QuadEnable};
+    use crate::flexspi::{
+        opcodes::{ddr, sdr::*},
+        Command, FlashPadType, Instr, LookupTable, Pads, SequenceBuilder,
+    };
+
+    const QUAD: NorCommandSet = NorCommandSet::new().read(
+        NorCommand::new(0xEB)
+            .address_pads(FlashPadType::Quad)
+            .data_pads(FlashPadType::Quad)
+            .dummy_cycles(6),
+    );
+
+    /// The lookup table from the 1060EVK's FCB.
+    #[test]
+    fn matches_manual_lookup_table() {
+        let expected = LookupTable::new()
+            .command(
+                Command::Read,
+                SequenceBuilder::new()
+                    .instr(Instr::new(CMD, Pads::One, 0xEB))
+                    .instr(Instr::new(RADDR, Pads::Four, 0x18))
+                    .instr(Instr::new(DUMMY, Pads::Four, 0x06))
+                    .instr(Instr::new(READ, Pads::Four, 0x04))
+                    .build(),
+            )
+            .command(
+                Command::ReadStatus,
+                SequenceBuilder::new()
+                    .instr(Instr::new(CMD, Pads::One, 0x05))
+                    .instr(Instr::new(READ, Pads::One, 0x04))
+                    .build(),
+            )
+            .command(
+                Command::WriteEnable,
+                SequenceBuilder::new()
+                    .instr(Instr::new(CMD, Pads::One, 0x06))
+                    .build(),
+            )
+            .command(
+                Command::EraseSector,
+                SequenceBuilder::new()
+                    .instr(Instr::new(CMD, Pads::One, 0x20))
+                    .instr(Instr::new(RADDR, Pads::One, 0x18))
+                    .build(),
+            )
+            .command(
+                Command::PageProgram,
+                SequenceBuilder::new()
+                    .instr(Instr::new(CMD, Pads::One, 0x02))
+                    .instr(Instr::new(RADDR, Pads::One, 0x18))
+                    .instr(Instr::new(WRITE, Pads::One, 0x04))
+                    .build(),
+            )
+            .command(
+                Command::ChipErase,
+                SequenceBuilder::new()
+                    .instr(Instr::new(CMD, Pads::One, 0x60))
+                    .build(),
+            );
+        assert_eq!(QUAD.lookup_table(), expected);
+    }
+
+    #[test]
+    fn ddr_and_four_byte_addresses() {
+        let commands = NorCommandSet::new()
+            .read(
+                NorCommand::new(0xEE)
+                    .command_pads(FlashPadType::Octal)
+                    .address_pads(FlashPadType::Octal)
+                    .data_pads(FlashPadType::Octal)
+                    .dummy_cycles(20)
+                    .ddr(true),
+            )
+            .address_bits(32);
+        let read = commands.lookup_table().get_command(Command::Read);
+        assert_eq!(read.0[0], Instr::new(ddr::CMD, Pads::Eight, 0xEE));
+        assert_eq!(read.0[1], Instr::new(ddr::RADDR, Pads::Eight, 32));
+        assert_eq!(read.0[2], Instr::new(ddr::DUMMY, Pads::Eight, 20));
+        assert_eq!(read.0[3], Instr::new(ddr::READ, Pads::Eight, 0x04));
+
+        let erase = commands.lookup_table().get_command(Command::EraseSector);
+        assert_eq!(erase.0[1], Instr::new(RADDR, Pads::One, 32));
+    }
+
+    #[test]
+    fn fcb() {
+        let fcb = QUAD.max_frequency_mhz(100).fcb(0x80_0000);
+        let mem_cfg = fcb.mem_cfg();
+        assert_eq!(mem_cfg.get_serial_flash_pad_type(), FlashPadType::Quad);
+        assert_eq!(mem_cfg.get_lookup_table(), &QUAD.lookup_table());
+        assert_eq!(fcb.get_page_size(), 256);
+        assert_eq!(fcb.get_sector_size(), 4096);
+    }
+
+    #[test]
+    #[should_panic(expected = "24 or 32 bits")]
+    fn address_bits() {
+        let _ = NorCommandSet::new().address_bits(16);
+    }
+
+    #[test]
+    fn quad_enable_schemes() {
+        for (scheme, opcode, arg, data_bytes) in [
+            (QuadEnable::StatusRegister1Bit6, 0x01, 0x40, 1),
+            (QuadEnable::StatusRegister2Bit1, 0x31, 0x02, 1),
+            (QuadEnable::StatusRegisters1And2Bit1, 0x01, 0x0200, 2),
+            (QuadEnable::StatusRegister2Bit7, 0x3E, 0x80, 1),
+        ] {
+            let commands = QUAD.quad_enable(scheme);
+            let device_mode = commands.get_device_mode().unwrap();
+            assert_eq!(device_mode.get_arg(), arg, "{scheme:?}");
+            assert_eq!(
+                commands.lookup_table().get_sequence(6),
+                SequenceBuilder::new()
+                    .instr(Instr::new(CMD, Pads::One, opcode))
+                    .instr(Instr::new(WRITE, Pads::One, data_bytes))
+                    .build(),
+                "{scheme:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn enter_four_byte_addresses() {
+        use crate::flexspi::{ConfigurationCommand, DeviceModeConfiguration};
+
+        let commands = QUAD
+            .quad_enable(QuadEnable::StatusRegister1Bit6)
+            .enter_four_byte_addresses(NorCommand::new(0xB7));
+        let lookup_table = commands.lookup_table();
+        assert_eq!(
+            lookup_table.get_command(Command::Read),
+            SequenceBuilder::new()
+                .instr(Instr::new(CMD, Pads::One, 0xEB))
+                .instr(Instr::new(RADDR, Pads::Four, 0x20))
+                .instr(Instr::new(DUMMY, Pads::Four, 0x06))
+                .instr(Instr::new(READ, Pads::Four, 0x04))
+                .build()
+        );
+        assert_eq!(
+            lookup_table.get_command(Command::PageProgram).0[1],
+            Instr::new(RADDR, Pads::One, 0x20)
+        );
+        assert_eq!(
+            lookup_table.get_sequence(7),
+            SequenceBuilder::new()
+                .instr(Instr::new(CMD, Pads::One, 0xB7))
+                .build()
+        );
+
+        let fcb = commands.fcb(32 * 1024 * 1024);
+        assert_eq!(
+            fcb.mem_cfg().get_config_command(0),
+            Some(ConfigurationCommand::new(1, 7, 0))
+        );
+        // The quad enable bit is still the device mode configuration.
+        assert!(matches!(
+            fcb.mem_cfg().get_device_mode_configuration(),
+            DeviceModeConfiguration::Enabled { .. }
+        ));
+    }
+
+    #[test]
+    #[should_panic(expected = "needs 32-bit addresses")]
+    fn three_byte_addresses_too_small() {
+        let _ = QUAD.fcb(32 * 1024 * 1024);
+    }
+
+    #[test]
+    fn continuous_read() {
+        let commands = QUAD.continuous_read(ContinuousRead::new(0xA5));
+        let lookup_table = commands.lookup_table();
+        // The mode bits take two of the six dummy cycles. Later reads start
+        // at the address.
+        assert_eq!(
+            lookup_table.get_command(Command::Read),
+            SequenceBuilder::new()
+                .instr(Instr::new(CMD, Pads::One, 0xEB))
+                .instr(Instr::new(RADDR, Pads::Four, 0x18))
+                .instr(Instr::new(MODE8, Pads::Four, 0xA5))
+                .instr(Instr::new(DUMMY, Pads::Four, 0x04))
+                .instr(Instr::new(READ, Pads::Four, 0x04))
+                .instr(Instr::jump_on_cs(1))
+                .build()
+        );
+        assert_eq!(
+            lookup_table.get_sequence(14),
+            SequenceBuilder::new()
+                .instr(Instr::new(CMD, Pads::One, 0xEB))
+                .instr(Instr::new(RADDR, Pads::Four, 0x18))
+                .instr(Instr::new(MODE8, Pads::Four, 0xA5))
+                .instr(Instr::new(DUMMY, Pads::Four, 0x04))
+                .instr(Instr::new(READ, Pads::Four, 0x04))
+                .build()
+        );
+        assert_eq!(
+            lookup_table.get_command(Command::Dummy),
+            SequenceBuilder::new()
+                .instr(Instr::new(RADDR, Pads::Four, 0x18))
+                .instr(Instr::new(MODE8, Pads::Four, 0xFF))
+                .instr(Instr::new(DUMMY, Pads::Four, 0x04))
+                .instr(Instr::new(READ, Pads::Four, 0x04))
+                .build()
+        );
+
+        // Without continuous read mode, the read doesn't jump.
+        assert_eq!(
+            QUAD.lookup_table().get_command(Command::Read).0[4],
+            crate::flexspi::STOP
+        );
+        let _ = commands.fcb(16 * 1024 * 1024);
+
+        #[cfg(any(
+            feature = "imxrt1170",
+            feature = "imxrt1180",
+            feature = "imxrt500",
+            feature = "imxrt600"
+        ))]
+        {
+            use crate::serial_flash::nor::{FlashStateContext, SerialNorType};
+
+            let extras = commands.fcb(16 * 1024 * 1024).get_extras();
+            assert_eq!(extras.get_serial_nor_type(), Some(SerialNorType::NoCmd));
+            assert!(extras.get_need_exit_no_cmd_mode());
+            assert!(extras.get_need_restore_no_cmd_mode());
+            assert_eq!(
+                extras.get_flash_state_ctx(),
+                FlashStateContext::new()
+                    .exit_no_cmd_sequence(15)
+                    .restore_sequence(14)
+            );
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "include the mode bit cycles")]
+    fn mode_bits_without_dummy_cycles() {
+        let _ = NorCommandSet::new()
+            .continuous_read(ContinuousRead::new(0xA5))
+            .lookup_table();
+    }
+
+    #[test]
+    fn parallel_fcb() {
+        use crate::flexspi::{ControllerMiscOptions, Density, SerialFlashRegion};
+
+        let fcb = QUAD.parallel_fcb(8 * 1024 * 1024);
+        let mem_cfg = fcb.mem_cfg();
+        assert!(mem_cfg
+            .get_controller_misc_options()
+            .contains(ControllerMiscOptions::PARALLEL));
+        assert_eq!(
+            mem_cfg.get_flash_size(SerialFlashRegion::A1),
+            Density::from_mebibytes(8)
+        );
+        assert_eq!(
+            mem_cfg.get_flash_size(SerialFlashRegion::B1),
+            Density::from_mebibytes(8)
+        );
+        assert_eq!(
+            mem_cfg.get_flash_size(SerialFlashRegion::A2),
+            Density::from_bytes(0)
+        );
+        // The sequences still describe one quad part.
+        assert_eq!(mem_cfg.get_serial_flash_pad_type(), FlashPadType::Quad);
+        assert_eq!(fcb.get_page_size(), 512);
+        assert_eq!(fcb.get_sector_size(), 8 * 1024);
+    }
+}
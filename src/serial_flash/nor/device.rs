@@ -0,0 +1,414 @@
+//! Serial NOR flash device descriptions, and recommended configuration blocks
+
+use super::{ConfigurationBlock, SerialClockFrequency as IpSerialClockFrequency};
+use crate::{
+    flexspi::{
+        self,
+        opcodes::{ddr, sdr},
+        ColumnAddressWidth, Command, ControllerMiscOptions, DeviceModeConfiguration, FlashPadType,
+        Instr, LookupTable, Pads, Profile, ReadSampleClockSource, SequenceBuilder,
+        SerialClockFrequency, SerialFlashRegion,
+    },
+    Imxrt,
+};
+
+/// A description of a serial NOR flash device
+///
+/// Use a `NorFlashDevice` with [`recommended_fcb`] to generate a configuration
+/// block. Unless otherwise specified, the description assumes a single-pad, SDR
+/// device that can't drive DQS, and that runs at 30MHz.
+///
+/// ```
+/// use imxrt_boot_gen::flexspi::{FlashPadType, LookupTable};
+/// use imxrt_boot_gen::serial_flash::nor::NorFlashDevice;
+///
+/// # const LUT: LookupTable = LookupTable::new();
+/// const IS25WP064: NorFlashDevice = NorFlashDevice::new(LUT)
+///     .flash_size(8 * 1024 * 1024)
+///     .page_size(256)
+///     .sector_size(4096)
+///     .pad_type(FlashPadType::Quad)
+///     .max_frequency_mhz(133);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct NorFlashDevice {
+    lookup_table: LookupTable,
+    flash_size: u32,
+    page_size: u32,
+    sector_size: u32,
+    pad_type: FlashPadType,
+    max_frequency_mhz: u32,
+    dqs: bool,
+    ddr: bool,
+}
+
+impl NorFlashDevice {
+    /// Describe a device that's accessed using the sequences in `lookup_table`
+    pub const fn new(lookup_table: LookupTable) -> Self {
+        NorFlashDevice {
+            lookup_table,
+            flash_size: 0,
+            page_size: 0,
+            sector_size: 0,
+            pad_type: FlashPadType::Single,
+            max_frequency_mhz: 30,
+            dqs: false,
+            ddr: false,
+        }
+    }
+    /// Set the device's capacity, in bytes
+    pub const fn flash_size(mut self, flash_size: u32) -> Self {
+        self.flash_size = flash_size;
+        self
+    }
+    /// Set the device's page size, in bytes
+    pub const fn page_size(mut self, page_size: u32) -> Self {
+        self.page_size = page_size;
+        self
+    }
+    /// Set the device's sector size, in bytes
+    pub const fn sector_size(mut self, sector_size: u32) -> Self {
+        self.sector_size = sector_size;
+        self
+    }
+    /// Set the number of data pads used by the device's read sequence
+    pub const fn pad_type(mut self, pad_type: FlashPadType) -> Self {
+        self.pad_type = pad_type;
+        self
+    }
+    /// Set the device's maximum serial clock frequency, in MHz
+    pub const fn max_frequency_mhz(mut self, max_frequency_mhz: u32) -> Self {
+        self.max_frequency_mhz = max_frequency_mhz;
+        self
+    }
+    /// Indicate that the device drives a DQS (data strobe) signal
+    pub const fn dqs(mut self, dqs: bool) -> Self {
+        self.dqs = dqs;
+        self
+    }
+    /// Indicate that the device's read sequence uses DDR instructions
+    pub const fn ddr(mut self, ddr: bool) -> Self {
+        self.ddr = ddr;
+        self
+    }
+}
+
+/// Returns the fastest serial clock frequency that doesn't exceed `max_frequency_mhz`.
+const fn serial_clk_freq(max_frequency_mhz: u32) -> SerialClockFrequency {
+    let fastest = SerialClockFrequency::nearest(u32::MAX).mhz();
+    let mut mhz = if max_frequency_mhz > fastest {
+        fastest
+    } else {
+        max_frequency_mhz
+    };
+    while mhz > 0 {
+        if let Some(frequency) = SerialClockFrequency::from_mhz(mhz) {
+            return frequency;
+        }
+        mhz -= 1;
+    }
+    panic!("The device can't run at the slowest FlexSPI serial clock frequency")
+}
+
+/// Generate a serial NOR configuration block with sensible defaults for the
+/// `device`
+///
+/// The configuration block
+///
+/// - starts from the [`Profile`] for the chip selected by the crate's feature.
+/// - samples reads using the flash-provided DQS if the device drives DQS.
+/// - runs at the fastest serial clock frequency supported by both the chip
+///   and the device.
+/// - enables DDR mode for DDR devices.
+/// - runs IP commands at 30MHz.
+///
+/// Use the returned configuration block's methods to tune these values.
+///
+/// ```
+/// use imxrt_boot_gen::flexspi::{FlashPadType, LookupTable};
+/// use imxrt_boot_gen::serial_flash::nor::{self, NorFlashDevice};
+///
+/// # const LUT: LookupTable = LookupTable::new();
+/// const DEVICE: NorFlashDevice = NorFlashDevice::new(LUT)
+///     .flash_size(8 * 1024 * 1024)
+///     .page_size(256)
+///     .sector_size(4096)
+///     .pad_type(FlashPadType::Quad)
+///     .max_frequency_mhz(133);
+///
+/// const FCB: nor::ConfigurationBlock = nor::recommended_fcb(&DEVICE);
+/// ```
+///
+/// # Panics
+///
+/// Panics if the device's size, page size, or sector size is zero. When
+/// evaluated in a `const` or `static` initializer, the panic is a compile-time
+/// error.
+pub const fn recommended_fcb(device: &NorFlashDevice) -> ConfigurationBlock {
+    assert!(device.flash_size != 0, "Serial NOR flash size is not set");
+
+    let mut profile = Profile::for_chip(Imxrt::SELECTED);
+    if device.dqs {
+        profile.read_sample_clk_src = ReadSampleClockSource::FlashProvidedDQS;
+    }
+    if device.ddr {
+        profile.controller_misc_options |= ControllerMiscOptions::DDR_MODE.bits();
+    }
+
+    let mem_cfg = flexspi::ConfigurationBlock::new(device.lookup_table)
+        .profile(profile)
+        .serial_flash_pad_type(device.pad_type)
+        .serial_clk_freq(serial_clk_freq(device.max_frequency_mhz))
+        .flash_size_bytes(SerialFlashRegion::A1, device.flash_size);
+
+    ConfigurationBlock::new(mem_cfg)
+        .page_size(device.page_size)
+        .sector_size(device.sector_size)
+        .ip_cmd_serial_clk_freq(IpSerialClockFrequency::MHz30)
+        .finish()
+}
+
+/// Returns the read sequence's address width, or 24 if the read doesn't send
+/// an address.
+const fn read_address_bits(lookup_table: &LookupTable) -> u8 {
+    let read = lookup_table.get_command(Command::Read);
+    let mut idx = 0;
+    while idx < read.0.len() {
+        let opcode = read.0[idx].opcode();
+        if opcode.const_eq(sdr::RADDR) || opcode.const_eq(ddr::RADDR) {
+            return read.0[idx].operand();
+        }
+        idx += 1;
+    }
+    24
+}
+
+/// Derive a conservative boot configuration block from `fcb`
+///
+/// The derived configuration block reads with the single-pad `0x03` read
+/// command, or the `0x13` read command if `fcb`'s read sends a 32-bit
+/// address. It
+///
+/// - runs the serial clock at `serial_clk_freq`, 30MHz or 50MHz.
+/// - samples reads with the internal loopback.
+/// - disables DDR mode and the device mode configuration.
+/// - enables the safe configuration frequency on chips whose [`Profile`]
+///   enables it. It's left alone on the 1010, 1015, 1020, and 1024.
+/// - on chips with a `serialNorType` field, describes a standard SPI part
+///   that the boot ROM doesn't switch out of, or back into, a no-command mode.
+///
+/// Everything else, including the flash sizes, page and sector sizes, and
+/// the lookup table's other sequences, comes from `fcb`. Ship the derived
+/// configuration block so that the boot ROM reliably loads your image, then
+/// configure the fast reads in your firmware.
+///
+/// ```
+/// use imxrt_boot_gen::flexspi::SerialClockFrequency;
+/// use imxrt_boot_gen::serial_flash::nor::{self, presets::winbond};
+///
+/// const FAST: nor::ConfigurationBlock = winbond::W25Q_JV.fcb(winbond::W25Q64JV);
+/// const BOOT: nor::ConfigurationBlock = nor::to_safe_boot(FAST, SerialClockFrequency::MHz30);
+/// ```
+///
+/// # Panics
+///
+/// Panics if `serial_clk_freq` isn't 30MHz or 50MHz, or if `fcb` describes a
+/// HyperFlash part, which doesn't have a single-pad read.
+pub const fn to_safe_boot(
+    fcb: ConfigurationBlock,
+    serial_clk_freq: SerialClockFrequency,
+) -> ConfigurationBlock {
+    assert!(
+        matches!(
+            serial_clk_freq,
+            SerialClockFrequency::MHz30 | SerialClockFrequency::MHz50
+        ),
+        "A safe boot configuration block runs at 30MHz or 50MHz"
+    );
+    let mem_cfg = *fcb.mem_cfg();
+    assert!(
+        matches!(
+            mem_cfg.get_column_address_width(),
+            ColumnAddressWidth::OtherDevices
+        ),
+        "HyperFlash doesn't have a single-pad read"
+    );
+
+    let lookup_table = *mem_cfg.get_lookup_table();
+    let address_bits = read_address_bits(&lookup_table);
+    let opcode = if address_bits == 32 { 0x13 } else { 0x03 };
+    let read = SequenceBuilder::new()
+        .instr(Instr::new(sdr::CMD, Pads::One, opcode))
+        .instr(Instr::new(sdr::RADDR, Pads::One, address_bits))
+        .instr(Instr::new(sdr::READ, Pads::One, 0x04))
+        .build();
+
+    let safe_config_freq = ControllerMiscOptions::from_bits_retain(
+        Profile::for_chip(Imxrt::SELECTED).controller_misc_options
+            & ControllerMiscOptions::SAFE_CONFIG_FREQ.bits(),
+    );
+    let options = mem_cfg
+        .get_controller_misc_options()
+        .difference(ControllerMiscOptions::DDR_MODE)
+        .union(safe_config_freq);
+    let mem_cfg = mem_cfg
+        .with_lookup_table(lookup_table.command(Command::Read, read))
+        .device_mode_configuration(DeviceModeConfiguration::Disabled)
+        .serial_flash_pad_type(FlashPadType::Single)
+        .serial_clk_freq(serial_clk_freq)
+        .read_sample_clk_src(ReadSampleClockSource::InternalLoopback)
+        .controller_misc_option_flags(options);
+    let fcb = fcb.with_mem_cfg(mem_cfg);
+
+    #[cfg(any(
+        feature = "imxrt1170",
+        feature = "imxrt1180",
+        feature = "imxrt500",
+        feature = "imxrt600"
+    ))]
+    let fcb = fcb
+        .serial_nor_type(super::SerialNorType::StandardSpi)
+        .need_exit_no_cmd_mode(false)
+        .need_restore_no_cmd_mode(false)
+        .flash_state_ctx(super::FlashStateContext::new());
+
+    fcb
+}
+
+#[cfg(test)]
+mod test {
+    use super::{recommended_fcb, serial_clk_freq, to_safe_boot, NorFlashDevice};
+    use crate::{
+        flexspi::{self, FlashPadType, LookupTable, SerialClockFrequency, SerialFlashRegion},
+        serial_flash::nor,
+        Imxrt,
+    };
+
+    #[test]
+    fn serial_clk_freq_never_exceeds_device() {
+        assert_eq!(serial_clk_freq(30), SerialClockFrequency::MHz30);
+        assert_eq!(serial_clk_freq(59), SerialClockFrequency::MHz50);
+        assert_eq!(serial_clk_freq(100), SerialClockFrequency::MHz100);
+        assert_eq!(serial_clk_freq(104), SerialClockFrequency::MHz100);
+    }
+
+    #[test]
+    fn matches_manual_configuration() {
+        const DEVICE: NorFlashDevice = NorFlashDevice::new(LookupTable::new())
+            .flash_size(16 * 1024 * 1024)
+            .page_size(256)
+            .sector_size(4096)
+            .pad_type(FlashPadType::Quad)
+            .max_frequency_mhz(104);
+
+        // Each chip's baseline, written out rather than taken from flexspi::Profile.
+        let (version, misc_options) = match Imxrt::SELECTED {
+            Imxrt::Imxrt1010 => (flexspi::Version::new(1, 0, 0), 0),
+            Imxrt::Imxrt1015 | Imxrt::Imxrt1020 | Imxrt::Imxrt1024 => {
+                (flexspi::Version::new(1, 4, 0), 0)
+            }
+            _ => (flexspi::Version::new(1, 4, 0), 0x10),
+        };
+        let expected = nor::ConfigurationBlock::new(
+            flexspi::ConfigurationBlock::new(LookupTable::new())
+                .version(version)
+                .read_sample_clk_src(flexspi::ReadSampleClockSource::LoopbackFromDQSPad)
+                .cs_hold_time(3)
+                .cs_setup_time(3)
+                .controller_misc_options(misc_options)
+                .serial_flash_pad_type(FlashPadType::Quad)
+                .serial_clk_freq(SerialClockFrequency::MHz100)
+                .flash_size(SerialFlashRegion::A1, flexspi::Density::from_mebibytes(16)),
+        )
+        .page_size(256)
+        .sector_size(4096)
+        .ip_cmd_serial_clk_freq(nor::SerialClockFrequency::MHz30);
+
+        let actual = recommended_fcb(&DEVICE);
+        assert_eq!(actual.to_bytes(), expected.to_bytes());
+    }
+
+    #[test]
+    fn safe_boot_keeps_geometry() {
+        use crate::flexspi::{
+            opcodes::sdr::*, Command, ControllerMiscOptions, DeviceModeConfiguration, Instr, Pads,
+            ReadSampleClockSource, SequenceBuilder,
+        };
+        use crate::serial_flash::nor::presets::winbond;
+
+        let fast = winbond::W25Q_JV.fcb(winbond::W25Q64JV);
+        let boot = to_safe_boot(fast, SerialClockFrequency::MHz50);
+        let mem_cfg = boot.mem_cfg();
+        assert_eq!(
+            mem_cfg.get_lookup_table().get_command(Command::Read),
+            SequenceBuilder::new()
+                .instr(Instr::new(CMD, Pads::One, 0x03))
+                .instr(Instr::new(RADDR, Pads::One, 0x18))
+                .instr(Instr::new(READ, Pads::One, 0x04))
+                .build()
+        );
+        assert_eq!(
+            mem_cfg.get_lookup_table().get_command(Command::PageProgram),
+            fast.mem_cfg()
+                .get_lookup_table()
+                .get_command(Command::PageProgram)
+        );
+        assert_eq!(mem_cfg.get_serial_flash_pad_type(), FlashPadType::Single);
+        assert_eq!(mem_cfg.get_serial_clk_freq(), SerialClockFrequency::MHz50);
+        assert_eq!(
+            mem_cfg.get_read_sample_clk_src(),
+            ReadSampleClockSource::InternalLoopback
+        );
+        assert!(mem_cfg.get_device_mode_configuration() == DeviceModeConfiguration::Disabled);
+        assert!(!mem_cfg
+            .get_controller_misc_options()
+            .contains(ControllerMiscOptions::DDR_MODE));
+        assert_eq!(
+            mem_cfg.get_flash_size(SerialFlashRegion::A1),
+            fast.mem_cfg().get_flash_size(SerialFlashRegion::A1)
+        );
+        assert_eq!(boot.get_page_size(), fast.get_page_size());
+        assert_eq!(boot.get_sector_size(), fast.get_sector_size());
+    }
+
+    #[test]
+    fn safe_boot_four_byte_addresses() {
+        use crate::flexspi::Command;
+        use crate::serial_flash::nor::presets::micron;
+
+        let fast = micron::MT25Q_4B.fcb(micron::MT25QL512);
+        let boot = to_safe_boot(fast, SerialClockFrequency::MHz30);
+        let read = boot.mem_cfg().get_lookup_table().get_command(Command::Read);
+        assert_eq!(read.0[0].operand(), 0x13);
+        assert_eq!(read.0[1].operand(), 32);
+        assert_eq!(read.0[2].pads(), Some(0), "Single pad read");
+    }
+
+    #[test]
+    fn safe_boot_safe_config_freq() {
+        use crate::flexspi::ControllerMiscOptions;
+        use crate::serial_flash::nor::presets::winbond;
+
+        let fast = winbond::W25Q_JV.fcb(winbond::W25Q64JV);
+        let boot = to_safe_boot(fast, SerialClockFrequency::MHz30);
+        let expected = !matches!(
+            Imxrt::SELECTED,
+            Imxrt::Imxrt1010 | Imxrt::Imxrt1015 | Imxrt::Imxrt1020 | Imxrt::Imxrt1024
+        );
+        assert_eq!(
+            boot.mem_cfg()
+                .get_controller_misc_options()
+                .contains(ControllerMiscOptions::SAFE_CONFIG_FREQ),
+            expected
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "30MHz or 50MHz")]
+    fn safe_boot_too_fast() {
+        use crate::serial_flash::nor::presets::winbond;
+
+        let fast = winbond::W25Q_JV.fcb(winbond::W25Q64JV);
+        let _ = to_safe_boot(fast, SerialClockFrequency::MHz100);
+    }
+}
@@ -0,0 +1,78 @@
+//! The serial NOR configuration block layout
+
+use super::{ConfigurationBlock, Tail};
+use crate::flexspi::{self, Field};
+use core::mem::{offset_of, size_of};
+
+macro_rules! field {
+    ($name:literal, $($field:ident).+, $ty:ty) => {
+        Field {
+            name: $name,
+            offset: offset_of!(ConfigurationBlock, tail) + offset_of!(Tail, $($field).+),
+            size: size_of::<$ty>(),
+        }
+    };
+}
+
+#[cfg(not(any(
+    feature = "imxrt1170",
+    feature = "imxrt1180",
+    feature = "imxrt500",
+    feature = "imxrt600"
+)))]
+const TAIL_FIELDS: [Field; 3] = [
+    field!("pageSize", page_size, u32),
+    field!("sectorSize", sector_size, u32),
+    field!("ipcmdSerialClkFreq", ip_cmd_serial_clk_freq, u8),
+];
+
+#[cfg(any(
+    feature = "imxrt1170",
+    feature = "imxrt1180",
+    feature = "imxrt500",
+    feature = "imxrt600"
+))]
+const TAIL_FIELDS: [Field; 11] = [
+    field!("pageSize", page_size, u32),
+    field!("sectorSize", sector_size, u32),
+    field!("ipcmdSerialClkFreq", ip_cmd_serial_clk_freq, u8),
+    field!("isUniformBlockSize", extras.is_uniform_block_size, u8),
+    field!("isDataOrderSwapped", extras.is_data_order_swapped, u8),
+    field!("serialNorType", extras.serial_nor_type, u8),
+    field!("needExitNoCmdMode", extras.need_exit_no_cmd_mode, u8),
+    field!("halfClkForNonReadCmd", extras.half_clk_for_non_read_cmd, u8),
+    field!("needRestoreNoCmdMode", extras.need_restore_no_cmd_mode, u8),
+    field!("blockSize", extras.block_size, u32),
+    field!("flashStateCtx", extras.flash_state_ctx, u32),
+];
+
+/// The named fields of the serial NOR configuration block, in offset order
+///
+/// The table starts with the FlexSPI configuration block's [`FIELDS`](flexspi::FIELDS),
+/// and it excludes reserved fields. The serial NOR fields depend on the selected chip.
+pub const FIELDS: [Field; flexspi::FIELDS.len() + TAIL_FIELDS.len()] = {
+    let mut fields = [flexspi::FIELDS[0]; flexspi::FIELDS.len() + TAIL_FIELDS.len()];
+    let mut idx = 0;
+    while idx < flexspi::FIELDS.len() {
+        fields[idx] = flexspi::FIELDS[idx];
+        idx += 1;
+    }
+    while idx < fields.len() {
+        fields[idx] = TAIL_FIELDS[idx - flexspi::FIELDS.len()];
+        idx += 1;
+    }
+    fields
+};
+
+#[cfg(test)]
+mod test {
+    use super::FIELDS;
+    use crate::flexspi::layout::check_fields;
+
+    #[test]
+    fn fields() {
+        check_fields(&FIELDS, 512);
+        let page_size = FIELDS.iter().find(|f| f.name == "pageSize").unwrap();
+        assert_eq!((page_size.offset, page_size.size), (0x1C0, 4));
+    }
+}
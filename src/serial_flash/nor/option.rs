@@ -0,0 +1,580 @@
+//! Serial NOR configuration option words
+//!
+//! NXP's flashloader, `blhost`, and the ROM API configure FlexSPI NOR flash
+//! from one or two option words, instead of a complete configuration block.
+//! The boot ROM probes the flash with the options, and generates the
+//! configuration block. The first option word starts with `0xC`, so you'll
+//! often see options like `0xC000_0007`.
+//!
+//! [`ConfigurationOption`] builds and decodes the option words. Use
+//! [`from_fcb`](ConfigurationOption::from_fcb) to derive the options from a
+//! configuration block, and [`device`](ConfigurationOption::device) to expand
+//! options into a [`NorFlashDevice`] that starts a configuration block.
+//!
+//! ```
+//! use imxrt_boot_gen::flexspi::SerialClockFrequency;
+//! use imxrt_boot_gen::serial_flash::nor::option::{ConfigurationOption, DeviceType, QuadMode};
+//!
+//! const OPTION: ConfigurationOption =
+//!     ConfigurationOption::new(DeviceType::QuadSpiSdr, SerialClockFrequency::MHz100)
+//!         .quad_mode(QuadMode::StatusReg2Bit1);
+//!
+//! let option0 = OPTION.option0();
+//! assert_eq!(option0 & 0xFFFF_FFF0, 0xC000_0200);
+//! assert_eq!(ConfigurationOption::from_raw(option0, None), Some(OPTION));
+//! ```
+
+use super::{
+    sfdp::{FastRead, Parameters},
+    ConfigurationBlock, NorFlashDevice,
+};
+use crate::flexspi::{ControllerMiscOptions, FlashPadType, SerialClockFrequency};
+
+const TAG: u32 = 0xC;
+
+/// The kind of flash device
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum DeviceType {
+    /// Quad SPI flash, SDR.
+    QuadSpiSdr = 0,
+    /// Quad SPI flash, DDR.
+    QuadSpiDdr = 1,
+    /// HyperFlash, 1.8V.
+    HyperFlash1V8 = 2,
+    /// HyperFlash, 3V.
+    HyperFlash3V = 3,
+    /// Macronix octal flash, DDR.
+    MacronixOctalDdr = 4,
+    /// Macronix octal flash, SDR.
+    MacronixOctalSdr = 5,
+    /// Micron octal flash, DDR.
+    MicronOctalDdr = 6,
+    /// Micron octal flash, SDR.
+    MicronOctalSdr = 7,
+    /// Adesto octal flash, DDR.
+    AdestoOctalDdr = 8,
+    /// Adesto octal flash, SDR.
+    AdestoOctalSdr = 9,
+}
+
+impl DeviceType {
+    const fn from_raw(raw: u32) -> Option<Self> {
+        Some(match raw {
+            0 => DeviceType::QuadSpiSdr,
+            1 => DeviceType::QuadSpiDdr,
+            2 => DeviceType::HyperFlash1V8,
+            3 => DeviceType::HyperFlash3V,
+            4 => DeviceType::MacronixOctalDdr,
+            5 => DeviceType::MacronixOctalSdr,
+            6 => DeviceType::MicronOctalDdr,
+            7 => DeviceType::MicronOctalSdr,
+            8 => DeviceType::AdestoOctalDdr,
+            9 => DeviceType::AdestoOctalSdr,
+            _ => return None,
+        })
+    }
+}
+
+/// How the boot ROM enables quad mode
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum QuadMode {
+    /// The boot ROM doesn't configure quad mode.
+    NotConfigured = 0,
+    /// Set bit 6 in status register 1.
+    StatusReg1Bit6 = 1,
+    /// Set bit 1 in status register 2.
+    StatusReg2Bit1 = 2,
+    /// Set bit 7 in status register 2.
+    StatusReg2Bit7 = 3,
+    /// Set bit 1 in status register 2, using the `0x31` command.
+    StatusReg2Bit1Via31 = 4,
+}
+
+impl QuadMode {
+    const fn from_raw(raw: u32) -> Option<Self> {
+        Some(match raw {
+            0 => QuadMode::NotConfigured,
+            1 => QuadMode::StatusReg1Bit6,
+            2 => QuadMode::StatusReg2Bit1,
+            3 => QuadMode::StatusReg2Bit7,
+            4 => QuadMode::StatusReg2Bit1Via31,
+            _ => return None,
+        })
+    }
+}
+
+/// Miscellaneous modes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum MiscMode {
+    /// No miscellaneous mode.
+    Disabled = 0,
+    /// Continuous read in 0-4-4 mode.
+    Mode044 = 1,
+    /// Continuous read in 0-8-8 mode.
+    Mode088 = 2,
+    /// Swap the order of data bytes.
+    DataOrderSwapped = 3,
+    /// Use the second pin mux group.
+    SecondPinMux = 4,
+    /// Sample reads with the internal loopback.
+    InternalLoopback = 5,
+    /// Stay in SPI mode, without switching an octal flash to its octal mode.
+    SpiMode = 6,
+    /// Sample reads with an external DQS signal.
+    ExtDqs = 8,
+}
+
+impl MiscMode {
+    const fn from_raw(raw: u32) -> Option<Self> {
+        Some(match raw {
+            0 => MiscMode::Disabled,
+            1 => MiscMode::Mode044,
+            2 => MiscMode::Mode088,
+            3 => MiscMode::DataOrderSwapped,
+            4 => MiscMode::SecondPinMux,
+            5 => MiscMode::InternalLoopback,
+            6 => MiscMode::SpiMode,
+            8 => MiscMode::ExtDqs,
+            _ => return None,
+        })
+    }
+}
+
+/// How the flash connects to FlexSPI
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum FlashConnection {
+    /// One flash on port A.
+    SinglePortA = 0,
+    /// Two flashes in parallel, on ports A and B.
+    Parallel = 1,
+    /// One flash on port B.
+    SinglePortB = 2,
+    /// One flash on each port.
+    BothPorts = 3,
+}
+
+impl FlashConnection {
+    const fn from_raw(raw: u32) -> Self {
+        match raw {
+            0 => FlashConnection::SinglePortA,
+            1 => FlashConnection::Parallel,
+            2 => FlashConnection::SinglePortB,
+            _ => FlashConnection::BothPorts,
+        }
+    }
+}
+
+/// The fields of the second option word
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Option1 {
+    flash_connection: FlashConnection,
+    drive_strength: u8,
+    dqs_pinmux_group: u8,
+    pinmux_group: u8,
+    status_override: u8,
+    dummy_cycles: u8,
+}
+
+impl Option1 {
+    const DEFAULT: Self = Option1 {
+        flash_connection: FlashConnection::SinglePortA,
+        drive_strength: 0,
+        dqs_pinmux_group: 0,
+        pinmux_group: 0,
+        status_override: 0,
+        dummy_cycles: 0,
+    };
+}
+
+const fn pads(pad_type: FlashPadType) -> u32 {
+    match pad_type {
+        FlashPadType::Single => 0,
+        FlashPadType::Dual => 1,
+        FlashPadType::Quad => 2,
+        FlashPadType::Octal => 3,
+    }
+}
+
+const fn pad_type(raw: u32) -> Option<FlashPadType> {
+    Some(match raw {
+        0 => FlashPadType::Single,
+        1 => FlashPadType::Dual,
+        2 => FlashPadType::Quad,
+        3 => FlashPadType::Octal,
+        _ => return None,
+    })
+}
+
+/// Serial NOR configuration options
+///
+/// Unless otherwise specified, the boot ROM queries the flash and sends
+/// commands on one pad, doesn't configure quad mode, and doesn't use a
+/// miscellaneous mode. Setting any field of the second option word includes
+/// the second option word.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ConfigurationOption {
+    device_type: DeviceType,
+    query_pads: FlashPadType,
+    cmd_pads: FlashPadType,
+    quad_mode: QuadMode,
+    misc_mode: MiscMode,
+    max_frequency: SerialClockFrequency,
+    option1: Option<Option1>,
+}
+
+impl ConfigurationOption {
+    /// Create options for a device that runs up to `max_frequency`.
+    pub const fn new(device_type: DeviceType, max_frequency: SerialClockFrequency) -> Self {
+        ConfigurationOption {
+            device_type,
+            query_pads: FlashPadType::Single,
+            cmd_pads: FlashPadType::Single,
+            quad_mode: QuadMode::NotConfigured,
+            misc_mode: MiscMode::Disabled,
+            max_frequency,
+            option1: None,
+        }
+    }
+    /// Set the pads that the boot ROM uses to query SFDP.
+    pub const fn query_pads(mut self, query_pads: FlashPadType) -> Self {
+        self.query_pads = query_pads;
+        self
+    }
+    /// Set the pads that the boot ROM uses for commands.
+    pub const fn cmd_pads(mut self, cmd_pads: FlashPadType) -> Self {
+        self.cmd_pads = cmd_pads;
+        self
+    }
+    /// Set how the boot ROM enables quad mode.
+    pub const fn quad_mode(mut self, quad_mode: QuadMode) -> Self {
+        self.quad_mode = quad_mode;
+        self
+    }
+    /// Set the miscellaneous mode.
+    pub const fn misc_mode(mut self, misc_mode: MiscMode) -> Self {
+        self.misc_mode = misc_mode;
+        self
+    }
+    /// Set how the flash connects to FlexSPI.
+    pub const fn flash_connection(mut self, flash_connection: FlashConnection) -> Self {
+        self.option1_mut().flash_connection = flash_connection;
+        self
+    }
+    /// Set the pad drive strength, from 0 through 15.
+    ///
+    /// Zero keeps the boot ROM's default.
+    pub const fn drive_strength(mut self, drive_strength: u8) -> Self {
+        assert!(
+            drive_strength < 16,
+            "The drive strength must be less than 16"
+        );
+        self.option1_mut().drive_strength = drive_strength;
+        self
+    }
+    /// Set the DQS pin mux group, from 0 through 15.
+    pub const fn dqs_pinmux_group(mut self, dqs_pinmux_group: u8) -> Self {
+        assert!(
+            dqs_pinmux_group < 16,
+            "The DQS pin mux group must be less than 16"
+        );
+        self.option1_mut().dqs_pinmux_group = dqs_pinmux_group;
+        self
+    }
+    /// Set the pin mux group, from 0 through 15.
+    pub const fn pinmux_group(mut self, pinmux_group: u8) -> Self {
+        assert!(pinmux_group < 16, "The pin mux group must be less than 16");
+        self.option1_mut().pinmux_group = pinmux_group;
+        self
+    }
+    /// Override the status register value that enables quad mode.
+    pub const fn status_override(mut self, status_override: u8) -> Self {
+        self.option1_mut().status_override = status_override;
+        self
+    }
+    /// Set the read dummy cycles.
+    ///
+    /// Zero keeps the dummy cycles that the boot ROM discovers.
+    pub const fn dummy_cycles(mut self, dummy_cycles: u8) -> Self {
+        self.option1_mut().dummy_cycles = dummy_cycles;
+        self
+    }
+    /// Returns the device type.
+    pub const fn get_device_type(&self) -> DeviceType {
+        self.device_type
+    }
+    /// Returns the pads that the boot ROM uses to query SFDP.
+    pub const fn get_query_pads(&self) -> FlashPadType {
+        self.query_pads
+    }
+    /// Returns the pads that the boot ROM uses for commands.
+    pub const fn get_cmd_pads(&self) -> FlashPadType {
+        self.cmd_pads
+    }
+    /// Returns how the boot ROM enables quad mode.
+    pub const fn get_quad_mode(&self) -> QuadMode {
+        self.quad_mode
+    }
+    /// Returns the miscellaneous mode.
+    pub const fn get_misc_mode(&self) -> MiscMode {
+        self.misc_mode
+    }
+    /// Returns the maximum frequency.
+    pub const fn get_max_frequency(&self) -> SerialClockFrequency {
+        self.max_frequency
+    }
+    /// Returns how the flash connects to FlexSPI.
+    pub const fn get_flash_connection(&self) -> FlashConnection {
+        match self.option1 {
+            Some(option1) => option1.flash_connection,
+            None => FlashConnection::SinglePortA,
+        }
+    }
+    /// Returns the read dummy cycles, or zero if the boot ROM discovers them.
+    pub const fn get_dummy_cycles(&self) -> u8 {
+        match self.option1 {
+            Some(option1) => option1.dummy_cycles,
+            None => 0,
+        }
+    }
+    /// Returns the first option word.
+    pub const fn option0(&self) -> u32 {
+        (TAG << 28)
+            | ((self.option1.is_some() as u32) << 24)
+            | ((self.device_type as u32) << 20)
+            | (pads(self.query_pads) << 16)
+            | (pads(self.cmd_pads) << 12)
+            | ((self.quad_mode as u32) << 8)
+            | ((self.misc_mode as u32) << 4)
+            | self.max_frequency as u32
+    }
+    /// Returns the second option word, or `None` if the options don't need it.
+    pub const fn option1(&self) -> Option<u32> {
+        match self.option1 {
+            Some(option1) => Some(
+                ((option1.flash_connection as u32) << 28)
+                    | ((option1.drive_strength as u32) << 24)
+                    | ((option1.dqs_pinmux_group as u32) << 20)
+                    | ((option1.pinmux_group as u32) << 16)
+                    | ((option1.status_override as u32) << 8)
+                    | option1.dummy_cycles as u32,
+            ),
+            None => None,
+        }
+    }
+    /// Decode option words.
+    ///
+    /// Returns `None` if `option0` doesn't have the `0xC` tag, if a field has a
+    /// value that this module doesn't know, or if `option1` doesn't match the
+    /// option size in `option0`. The values aren't checked against the
+    /// selected chip; not every boot ROM supports every device type and
+    /// miscellaneous mode.
+    pub fn from_raw(option0: u32, option1: Option<u32>) -> Option<Self> {
+        if option0 >> 28 != TAG {
+            return None;
+        }
+        let field = |offset: u32| (option0 >> offset) & 0xF;
+        let option1 = match (field(24), option1) {
+            (0, None) => None,
+            (1, Some(option1)) => Some(Option1 {
+                flash_connection: FlashConnection::from_raw(option1 >> 28),
+                drive_strength: ((option1 >> 24) & 0xF) as u8,
+                dqs_pinmux_group: ((option1 >> 20) & 0xF) as u8,
+                pinmux_group: ((option1 >> 16) & 0xF) as u8,
+                status_override: (option1 >> 8) as u8,
+                dummy_cycles: option1 as u8,
+            }),
+            _ => return None,
+        };
+        Some(ConfigurationOption {
+            device_type: DeviceType::from_raw(field(20))?,
+            query_pads: pad_type(field(16))?,
+            cmd_pads: pad_type(field(12))?,
+            quad_mode: QuadMode::from_raw(field(8))?,
+            misc_mode: MiscMode::from_raw(field(4))?,
+            max_frequency: SerialClockFrequency::from_raw(field(0) as u8)?,
+            option1,
+        })
+    }
+    /// Derive options from a configuration block.
+    ///
+    /// The options describe a quad SPI device with the block's serial clock
+    /// frequency, and with DDR if the block enables DDR mode. The boot ROM
+    /// queries the flash on one pad. Returns `None` for octal configuration
+    /// blocks, since the block doesn't say which vendor's octal protocol the
+    /// flash uses.
+    pub const fn from_fcb(fcb: &ConfigurationBlock) -> Option<Self> {
+        let mem_cfg = fcb.mem_cfg();
+        let device_type = if mem_cfg
+            .get_controller_misc_options()
+            .contains(ControllerMiscOptions::DDR_MODE)
+        {
+            DeviceType::QuadSpiDdr
+        } else {
+            DeviceType::QuadSpiSdr
+        };
+        match mem_cfg.get_serial_flash_pad_type() {
+            FlashPadType::Octal => None,
+            _ => Some(Self::new(device_type, mem_cfg.get_serial_clk_freq())),
+        }
+    }
+    /// Expand the options into a device description.
+    ///
+    /// The device reads with the 1-4-4 `0xEB` command, and uses the common
+    /// status, write enable, 4KiB sector erase, page program, and chip erase
+    /// commands. Unless the options override the dummy cycles, the read uses
+    /// six dummy cycles. The device doesn't have a size; set it with
+    /// [`flash_size`](NorFlashDevice::flash_size) before you generate a
+    /// configuration block.
+    ///
+    /// Returns `None` if the device isn't a quad SPI SDR device.
+    pub fn device(&self) -> Option<NorFlashDevice> {
+        if self.device_type != DeviceType::QuadSpiSdr {
+            return None;
+        }
+        let dummy_cycles = match self.get_dummy_cycles() {
+            0 => 6,
+            dummy_cycles => dummy_cycles,
+        };
+        let parameters = Parameters {
+            flash_size: 0,
+            page_size: 256,
+            sector_size: 4096,
+            erase_sector_opcode: 0x20,
+            address_bits: 24,
+            read: FastRead {
+                opcode: 0xEB,
+                address_pads: FlashPadType::Quad,
+                data_pads: FlashPadType::Quad,
+                dummy_cycles,
+            },
+        };
+        Some(
+            parameters
+                .device()
+                .max_frequency_mhz(self.max_frequency.mhz()),
+        )
+    }
+
+    const fn option1_mut(&mut self) -> &mut Option1 {
+        if self.option1.is_none() {
+            self.option1 = Some(Option1::DEFAULT);
+        }
+        match &mut self.option1 {
+            Some(option1) => option1,
+            None => unreachable!(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{ConfigurationOption, DeviceType, FlashConnection, MiscMode, QuadMode};
+    use crate::flexspi::{
+        self, ControllerMiscOptions, FlashPadType, LookupTable, SerialClockFrequency,
+    };
+    use crate::serial_flash::nor;
+
+    #[test]
+    fn option0() {
+        let option = ConfigurationOption::new(DeviceType::QuadSpiSdr, SerialClockFrequency::MHz30)
+            .quad_mode(QuadMode::StatusReg1Bit6)
+            .misc_mode(MiscMode::Mode044);
+        assert_eq!(option.option0(), 0xC000_0111);
+        assert_eq!(option.option1(), None);
+
+        let octal =
+            ConfigurationOption::new(DeviceType::MacronixOctalDdr, SerialClockFrequency::MHz30)
+                .cmd_pads(FlashPadType::Octal);
+        assert_eq!(octal.option0(), 0xC040_3001);
+    }
+
+    #[test]
+    fn option1() {
+        let option = ConfigurationOption::new(DeviceType::QuadSpiSdr, SerialClockFrequency::MHz50)
+            .flash_connection(FlashConnection::SinglePortB)
+            .drive_strength(3)
+            .dummy_cycles(8);
+        assert_eq!(option.option0(), 0xC100_0002);
+        assert_eq!(option.option1(), Some(0x2300_0008));
+        assert_eq!(
+            ConfigurationOption::from_raw(0xC100_0002, Some(0x2300_0008)),
+            Some(option)
+        );
+    }
+
+    #[test]
+    fn from_raw_rejects() {
+        assert_eq!(ConfigurationOption::from_raw(0xB000_0001, None), None);
+        assert_eq!(ConfigurationOption::from_raw(0xC100_0001, None), None);
+        assert_eq!(ConfigurationOption::from_raw(0xC000_0001, Some(0)), None);
+        assert_eq!(ConfigurationOption::from_raw(0xC0A0_0001, None), None);
+        assert_eq!(ConfigurationOption::from_raw(0xC000_0071, None), None);
+        assert_eq!(ConfigurationOption::from_raw(0xC000_0000, None), None);
+    }
+
+    #[test]
+    fn from_raw_octal_sdr() {
+        // A Macronix octal SDR flash, queried and commanded on eight pads.
+        let option = ConfigurationOption::from_raw(0xC053_3001, None).unwrap();
+        assert_eq!(option.get_device_type(), DeviceType::MacronixOctalSdr);
+        assert_eq!(option.get_query_pads(), FlashPadType::Octal);
+        assert_eq!(
+            ConfigurationOption::from_raw(0xC050_0001, None)
+                .unwrap()
+                .get_device_type(),
+            DeviceType::MacronixOctalSdr
+        );
+        assert_eq!(
+            ConfigurationOption::from_raw(0xC000_0081, None)
+                .unwrap()
+                .get_misc_mode(),
+            MiscMode::ExtDqs
+        );
+        for (raw, device_type) in [
+            (7, DeviceType::MicronOctalSdr),
+            (9, DeviceType::AdestoOctalSdr),
+        ] {
+            let option = ConfigurationOption::new(device_type, SerialClockFrequency::MHz30);
+            assert_eq!(option.option0(), 0xC000_0001 | raw << 20);
+        }
+    }
+
+    #[test]
+    fn from_fcb() {
+        let mem_cfg = flexspi::ConfigurationBlock::new(LookupTable::new())
+            .serial_flash_pad_type(FlashPadType::Quad)
+            .serial_clk_freq(SerialClockFrequency::MHz100)
+            .controller_misc_option_flags(ControllerMiscOptions::DDR_MODE);
+        let fcb = nor::ConfigurationBlock::new(mem_cfg);
+        let option = ConfigurationOption::from_fcb(&fcb).unwrap();
+        assert_eq!(option.get_device_type(), DeviceType::QuadSpiDdr);
+        assert_eq!(option.get_max_frequency(), SerialClockFrequency::MHz100);
+
+        let octal =
+            nor::ConfigurationBlock::new(mem_cfg.serial_flash_pad_type(FlashPadType::Octal));
+        assert_eq!(ConfigurationOption::from_fcb(&octal), None);
+    }
+
+    #[test]
+    fn device() {
+        let option = ConfigurationOption::new(DeviceType::QuadSpiSdr, SerialClockFrequency::MHz100);
+        let device = option.device().unwrap().flash_size(8 * 1024 * 1024);
+        let fcb = nor::recommended_fcb(&device);
+        assert_eq!(
+            fcb.mem_cfg().get_serial_clk_freq(),
+            SerialClockFrequency::MHz100
+        );
+        assert_eq!(
+            fcb.mem_cfg().get_serial_flash_pad_type(),
+            FlashPadType::Quad
+        );
+        assert_eq!(fcb.get_sector_size(), 4096);
+
+        let ddr = ConfigurationOption::new(DeviceType::QuadSpiDdr, SerialClockFrequency::MHz100);
+        assert!(ddr.device().is_none());
+    }
+}
@@ -0,0 +1,136 @@
+//! Command sets for common serial NOR flash parts
+//!
+//! Each module describes one vendor's parts as [`NorCommandSet`](super::NorCommandSet)
+//! constants. Combine a command set with your part's capacity to generate a
+//! configuration block. HyperFlash doesn't fit a command set, so [`infineon`]
+//! provides its lookup table and configuration block directly.
+//!
+//! ```
+//! use imxrt_boot_gen::serial_flash::nor::{self, presets::winbond};
+//!
+//! const FCB: nor::ConfigurationBlock = winbond::W25Q_JV.fcb(winbond::W25Q64JV);
+//! ```
+//!
+//! For a board that pairs two of the same quad part in parallel mode, generate
+//! the configuration block with
+//! [`parallel_fcb`](super::NorCommandSet::parallel_fcb) instead.
+//!
+//! The quad presets read with the 1-4-4 `0xEB` command. The command sends two
+//! mode byte cycles, then four dummy cycles, so the read has six dummy cycles.
+//!
+//! Before a quad part responds to quad reads, the boot ROM sets the part's quad
+//! enable bit with the preset's [`QuadEnable`](super::QuadEnable) scheme. The
+//! scheme writes whole status registers, and it writes zeros to their other
+//! writable bits. Those bits reset to zero, so the write
+//! keeps their defaults, but it clears any protection that you've set.
+//!
+//! The presets are a starting point. Check them against your part's data sheet,
+//! and adjust the command set, or the configuration block, for your board.
+
+pub mod adesto;
+pub mod gigadevice;
+pub mod infineon;
+pub mod issi;
+pub mod macronix;
+pub mod micron;
+pub mod winbond;
+
+#[cfg(test)]
+mod test {
+    use super::{adesto, gigadevice, winbond};
+    use crate::flexspi::{
+        opcodes::sdr::*, Command, DeviceModeConfiguration, DeviceModeSequence, FlashPadType, Instr,
+        Pads, SequenceBuilder,
+    };
+    use crate::serial_flash::nor::NorCommandSet;
+
+    /// A quad part, and how the boot ROM sets its quad enable bit.
+    struct QuadPart {
+        name: &'static str,
+        command_set: NorCommandSet,
+        capacity: u32,
+        /// The write status register opcode.
+        opcode: u8,
+        /// The status register bytes, and their value.
+        bytes: u8,
+        argument: u32,
+        max_mhz: u32,
+    }
+
+    const QUAD_PARTS: &[QuadPart] = &[
+        QuadPart {
+            name: "W25Q_JV",
+            command_set: winbond::W25Q_JV,
+            capacity: winbond::W25Q64JV,
+            opcode: 0x31,
+            bytes: 1,
+            argument: 0x02,
+            max_mhz: 133,
+        },
+        QuadPart {
+            name: "AT25SF",
+            command_set: adesto::AT25SF,
+            capacity: adesto::AT25SF128A,
+            opcode: 0x31,
+            bytes: 1,
+            argument: 0x02,
+            max_mhz: 104,
+        },
+        QuadPart {
+            name: "GD25Q",
+            command_set: gigadevice::GD25Q,
+            capacity: gigadevice::GD25Q64,
+            opcode: 0x01,
+            bytes: 2,
+            argument: 0x0200,
+            max_mhz: 104,
+        },
+    ];
+
+    #[test]
+    fn quad_parts() {
+        for part in QUAD_PARTS {
+            let name = part.name;
+            let lookup_table = part.command_set.lookup_table();
+            assert_eq!(
+                lookup_table.get_command(Command::Read),
+                SequenceBuilder::new()
+                    .instr(Instr::new(CMD, Pads::One, 0xEB))
+                    .instr(Instr::new(RADDR, Pads::Four, 0x18))
+                    .instr(Instr::new(DUMMY, Pads::Four, 0x06))
+                    .instr(Instr::new(READ, Pads::Four, 0x04))
+                    .build(),
+                "{name}"
+            );
+            assert_eq!(
+                lookup_table.get_sequence(6),
+                SequenceBuilder::new()
+                    .instr(Instr::new(CMD, Pads::One, part.opcode))
+                    .instr(Instr::new(WRITE, Pads::One, part.bytes))
+                    .build(),
+                "{name}"
+            );
+
+            let fcb = part.command_set.fcb(part.capacity);
+            let mem_cfg = fcb.mem_cfg();
+            assert!(
+                mem_cfg.get_device_mode_configuration()
+                    == DeviceModeConfiguration::Enabled {
+                        device_mode_arg: part.argument,
+                        device_mode_seq: DeviceModeSequence::new(1, 6),
+                    },
+                "{name}"
+            );
+            assert_eq!(
+                mem_cfg.get_serial_flash_pad_type(),
+                FlashPadType::Quad,
+                "{name}"
+            );
+            assert!(
+                mem_cfg.get_serial_clk_freq().mhz() <= part.max_mhz,
+                "{name}"
+            );
+            assert_eq!(fcb.get_sector_size(), 4096, "{name}");
+        }
+    }
+}
@@ -0,0 +1,34 @@
+//! Adesto (Renesas) serial NOR flash
+//!
+//! [`AT25SF`] describes the AT25SF quad SPI parts, like the AT25SF128A on the
+//! 1010 EVK. The parts read with the 1-4-4 `0xEB` command. Before the part
+//! responds to quad reads, the boot ROM sets the quad enable bit, bit 1 of
+//! status register 2, with the `0x31` command.
+//!
+//! The other writable bits of status register 2 select the complement protect
+//! mode and lock the security registers.
+
+use crate::flexspi::FlashPadType;
+use crate::serial_flash::nor::{NorCommand, NorCommandSet, QuadEnable};
+
+/// The AT25SF321B capacity, in bytes.
+pub const AT25SF321B: u32 = 4 * 1024 * 1024;
+/// The AT25SF641B capacity, in bytes.
+pub const AT25SF641B: u32 = 8 * 1024 * 1024;
+/// The AT25SF128A capacity, in bytes.
+pub const AT25SF128A: u32 = 16 * 1024 * 1024;
+
+/// The AT25SF command set
+///
+/// 256 byte pages, 4KiB sectors, and 64KiB blocks.
+pub const AT25SF: NorCommandSet = NorCommandSet::new()
+    .read(
+        NorCommand::new(0xEB)
+            .address_pads(FlashPadType::Quad)
+            .data_pads(FlashPadType::Quad)
+            .dummy_cycles(6),
+    )
+    .erase_block(NorCommand::new(0xD8))
+    .block_size(64 * 1024)
+    .quad_enable(QuadEnable::StatusRegister2Bit1)
+    .max_frequency_mhz(104);
@@ -0,0 +1,36 @@
+//! GigaDevice serial NOR flash
+//!
+//! [`GD25Q`] describes the GD25Q quad SPI parts. The parts read with the
+//! 1-4-4 `0xEB` command.
+//!
+//! The quad enable bit is bit 1 of status register 2, but not every GD25Q
+//! part has a command that writes only status register 2. Instead, the boot
+//! ROM writes both status registers with the `0x01` command: status
+//! register 1 is zero, and status register 2 sets the quad enable bit.
+
+use crate::flexspi::FlashPadType;
+use crate::serial_flash::nor::{NorCommand, NorCommandSet, QuadEnable};
+
+/// The GD25Q16 capacity, in bytes.
+pub const GD25Q16: u32 = 2 * 1024 * 1024;
+/// The GD25Q32 capacity, in bytes.
+pub const GD25Q32: u32 = 4 * 1024 * 1024;
+/// The GD25Q64 capacity, in bytes.
+pub const GD25Q64: u32 = 8 * 1024 * 1024;
+/// The GD25Q128 capacity, in bytes.
+pub const GD25Q128: u32 = 16 * 1024 * 1024;
+
+/// The GD25Q command set
+///
+/// 256 byte pages, 4KiB sectors, and 64KiB blocks.
+pub const GD25Q: NorCommandSet = NorCommandSet::new()
+    .read(
+        NorCommand::new(0xEB)
+            .address_pads(FlashPadType::Quad)
+            .data_pads(FlashPadType::Quad)
+            .dummy_cycles(6),
+    )
+    .erase_block(NorCommand::new(0xD8))
+    .block_size(64 * 1024)
+    .quad_enable(QuadEnable::StatusRegisters1And2Bit1)
+    .max_frequency_mhz(104);
@@ -0,0 +1,255 @@
+//! Infineon (Cypress) HyperFlash
+//!
+//! HyperFlash doesn't use SPI opcodes, so it can't be described by a
+//! [`NorCommandSet`](super::super::NorCommandSet). Every HyperBus transaction
+//! starts with six command-address bytes, transferred on eight pads at double
+//! data rate. Reads and writes to the memory array use the command-address
+//! bytes directly. Status reads, programs, and erases write command words to
+//! fixed addresses, like a parallel NOR flash.
+//!
+//! [`S26KS_LUT`] describes the 1.8V S26KS parts, and [`s26ks_fcb`] uses it to
+//! generate a configuration block. The configuration block enables
+//! word-addressable mode, since HyperFlash addresses 16-bit words, and it
+//! samples reads with the part's RWDS signal. It also enables the
+//! differential clock. The 3V S26KL parts use the same lookup table, but they
+//! have a single-ended clock; remove
+//! [`DIFFERENTIAL_CLOCK`](crate::flexspi::ControllerMiscOptions::DIFFERENTIAL_CLOCK)
+//! from the configuration block for those parts.
+//!
+//! Status reads, programs, and erases need more than one sequence. The
+//! configuration block describes them with custom LUT sequences. The custom
+//! sequence slots follow the 1050 boot ROM; check your chip's reference
+//! manual before you use the preset on another chip.
+
+use crate::flexspi::{
+    opcodes::ddr::*, BusyBit, BusyBitPolarity, ColumnAddressWidth, Command, ControllerMiscOptions,
+    DataValidTime, FlashPadType, Instr, LookupTable, LutCustomSequence, Pads, Sequence,
+    SequenceBuilder,
+};
+use crate::serial_flash::nor::{self, NorFlashDevice};
+
+/// The S26KS512S capacity, in bytes.
+pub const S26KS512S: u32 = 64 * 1024 * 1024;
+/// The S26KS256S capacity, in bytes.
+pub const S26KS256S: u32 = 32 * 1024 * 1024;
+/// The S26KS128S capacity, in bytes.
+pub const S26KS128S: u32 = 16 * 1024 * 1024;
+
+/// The write buffer size, in bytes.
+const PAGE_SIZE: u32 = 512;
+/// The uniform sector size, in bytes.
+const SECTOR_SIZE: u32 = 256 * 1024;
+
+/// The first word address of the command unlock cycles.
+const UNLOCK_1: u32 = 0x555;
+/// The second word address of the command unlock cycles.
+const UNLOCK_2: u32 = 0x2AA;
+
+/// A transaction that writes `data` to the word `address`.
+///
+/// The sequence is six command-address bytes, then the two data bytes. The
+/// first command-address byte selects a write to the memory space.
+const fn write_word(address: u32, data: u16) -> Sequence {
+    let row = address >> 3;
+    let bytes = [
+        ((row >> 24) & 0x1F) as u8,
+        (row >> 16) as u8,
+        (row >> 8) as u8,
+        row as u8,
+        0,
+        (address & 0x7) as u8,
+        (data >> 8) as u8,
+        data as u8,
+    ];
+    let mut builder = SequenceBuilder::new();
+    let mut idx = 0;
+    while idx < bytes.len() {
+        builder = builder.instr(Instr::new(CMD, Pads::Eight, bytes[idx]));
+        idx += 1;
+    }
+    builder.build()
+}
+
+/// Reads the memory array.
+const READ_SEQ: Sequence = SequenceBuilder::new()
+    .instr(Instr::new(CMD, Pads::Eight, 0xA0))
+    .instr(Instr::new(RADDR, Pads::Eight, 0x18))
+    .instr(Instr::new(CADDR, Pads::Eight, 0x10))
+    .instr(Instr::new(READ, Pads::Eight, 0x04))
+    .build();
+
+/// Reads the status register, after the status read command.
+const READ_STATUS_SEQ: Sequence = SequenceBuilder::new()
+    .instr(Instr::new(CMD, Pads::Eight, 0x80))
+    .instr(Instr::new(RADDR, Pads::Eight, 0x18))
+    .instr(Instr::new(CADDR, Pads::Eight, 0x10))
+    .instr(Instr::new(DUMMY_RWDS, Pads::Eight, 0x0B))
+    .instr(Instr::new(READ, Pads::Eight, 0x04))
+    .build();
+
+/// Writes the sector erase command to the sector address.
+const ERASE_SECTOR_SEQ: Sequence = SequenceBuilder::new()
+    .instr(Instr::new(CMD, Pads::Eight, 0x00))
+    .instr(Instr::new(RADDR, Pads::Eight, 0x18))
+    .instr(Instr::new(CADDR, Pads::Eight, 0x10))
+    .instr(Instr::new(CMD, Pads::Eight, 0x00))
+    .instr(Instr::new(CMD, Pads::Eight, 0x30))
+    .build();
+
+/// Writes the program data to the page address.
+const PAGE_PROGRAM_SEQ: Sequence = SequenceBuilder::new()
+    .instr(Instr::new(CMD, Pads::Eight, 0x00))
+    .instr(Instr::new(RADDR, Pads::Eight, 0x18))
+    .instr(Instr::new(CADDR, Pads::Eight, 0x10))
+    .instr(Instr::new(WRITE, Pads::Eight, 0x80))
+    .build();
+
+/// The S26KS lookup table
+///
+/// | Index | Sequence |
+/// | ----- | -------- |
+/// | 0 | Read |
+/// | 1, 2 | Read status: status read command, then the status read |
+/// | 3, 4 | Write enable: the unlock cycles |
+/// | 5 - 8 | Erase sector: the erase command, the unlock cycles, then the sector erase command |
+/// | 9, 10 | Page program: the program command, then the data |
+/// | 11 - 14 | Chip erase: the erase command, the unlock cycles, then the chip erase command |
+pub const S26KS_LUT: LookupTable = LookupTable::new()
+    .command(Command::Read, READ_SEQ)
+    .command(Command::ReadStatus, write_word(UNLOCK_1, 0x70))
+    .sequence(2, READ_STATUS_SEQ)
+    .command(Command::WriteEnable, write_word(UNLOCK_1, 0xAA))
+    .sequence(4, write_word(UNLOCK_2, 0x55))
+    .command(Command::EraseSector, write_word(UNLOCK_1, 0x80))
+    .sequence(6, write_word(UNLOCK_1, 0xAA))
+    .sequence(7, write_word(UNLOCK_2, 0x55))
+    .sequence(8, ERASE_SECTOR_SEQ)
+    .command(Command::PageProgram, write_word(UNLOCK_1, 0xA0))
+    .sequence(10, PAGE_PROGRAM_SEQ)
+    .command(Command::ChipErase, write_word(UNLOCK_1, 0x80))
+    .sequence(12, write_word(UNLOCK_1, 0xAA))
+    .sequence(13, write_word(UNLOCK_2, 0x55))
+    .sequence(14, write_word(UNLOCK_1, 0x10));
+
+/// Generate a configuration block for an S26KS part with `flash_size` bytes
+///
+/// The configuration block runs at 133MHz, with 512 byte pages and 256KiB
+/// sectors. The busy flag is the status register's device ready bit, bit 15,
+/// which is 0 when the part is busy.
+///
+/// ```
+/// use imxrt_boot_gen::serial_flash::nor::{self, presets::infineon};
+///
+/// const FCB: nor::ConfigurationBlock = infineon::s26ks_fcb(infineon::S26KS512S);
+/// ```
+///
+/// # Panics
+///
+/// Panics if `flash_size` is zero.
+pub const fn s26ks_fcb(flash_size: u32) -> nor::ConfigurationBlock {
+    let device = NorFlashDevice::new(S26KS_LUT)
+        .flash_size(flash_size)
+        .page_size(PAGE_SIZE)
+        .sector_size(SECTOR_SIZE)
+        .pad_type(FlashPadType::Octal)
+        .max_frequency_mhz(133)
+        .dqs(true)
+        .ddr(true);
+    let fcb = nor::recommended_fcb(&device);
+
+    let mem_cfg = *fcb.mem_cfg();
+    let options = mem_cfg.get_controller_misc_options().union(
+        ControllerMiscOptions::WORD_ADDRESSABLE
+            .union(ControllerMiscOptions::SAFE_CONFIG_FREQ)
+            .union(ControllerMiscOptions::DIFFERENTIAL_CLOCK),
+    );
+    let mem_cfg = mem_cfg
+        .column_address_width(ColumnAddressWidth::Hyperflash)
+        .controller_misc_option_flags(options)
+        .cs_hold_time(3)
+        .cs_setup_time(3)
+        // The SDK's `.dataValidTime = {16u, 16u}` sets port A's time and
+        // delay cells.
+        .data_valid_time(DataValidTime::new(16, 0).delay_cells(16, 0))
+        .busy_bit(BusyBit::new(15, BusyBitPolarity::ActiveLow))
+        .lut_custom_seq(1, LutCustomSequence::new(2, Command::ReadStatus as u8))
+        .lut_custom_seq(2, LutCustomSequence::new(2, Command::WriteEnable as u8))
+        .lut_custom_seq(3, LutCustomSequence::new(4, Command::EraseSector as u8))
+        .lut_custom_seq(4, LutCustomSequence::new(2, Command::PageProgram as u8))
+        .lut_custom_seq(5, LutCustomSequence::new(4, Command::ChipErase as u8));
+    let fcb = fcb.with_mem_cfg(mem_cfg);
+
+    #[cfg(any(
+        feature = "imxrt1170",
+        feature = "imxrt1180",
+        feature = "imxrt500",
+        feature = "imxrt600"
+    ))]
+    let fcb = fcb
+        .block_size(SECTOR_SIZE)
+        .serial_nor_type(nor::SerialNorType::HyperBus);
+
+    fcb
+}
+
+#[cfg(test)]
+mod test {
+    use super::{s26ks_fcb, write_word, S26KS512S, S26KS_LUT};
+    use crate::flexspi::{
+        opcodes::ddr::*, BusyBit, BusyBitPolarity, ColumnAddressWidth, Command,
+        ControllerMiscOptions, FlashPadType, Instr, LutCustomSequence, Pads, SequenceBuilder,
+    };
+
+    #[test]
+    fn write_word_command_address() {
+        // Write 0x00AA to word 0x555: CA bytes 00 00 00 AA 00 05.
+        let bytes = [0x00, 0x00, 0x00, 0xAA, 0x00, 0x05, 0x00, 0xAA];
+        let mut expected = SequenceBuilder::new();
+        for byte in bytes {
+            expected = expected.instr(Instr::new(CMD, Pads::Eight, byte));
+        }
+        assert_eq!(write_word(0x555, 0xAA), expected.build());
+        assert_eq!(
+            S26KS_LUT.get_sequence(4),
+            write_word(0x2AA, 0x55),
+            "Second unlock cycle"
+        );
+    }
+
+    #[test]
+    fn read() {
+        assert_eq!(
+            S26KS_LUT.get_command(Command::Read),
+            SequenceBuilder::new()
+                .instr(Instr::new(CMD, Pads::Eight, 0xA0))
+                .instr(Instr::new(RADDR, Pads::Eight, 0x18))
+                .instr(Instr::new(CADDR, Pads::Eight, 0x10))
+                .instr(Instr::new(READ, Pads::Eight, 0x04))
+                .build()
+        );
+    }
+
+    #[test]
+    fn fcb() {
+        let fcb = s26ks_fcb(S26KS512S);
+        let mem_cfg = fcb.mem_cfg();
+        assert_eq!(
+            mem_cfg.get_column_address_width(),
+            ColumnAddressWidth::Hyperflash
+        );
+        assert_eq!(mem_cfg.get_serial_flash_pad_type(), FlashPadType::Octal);
+        assert!(mem_cfg.get_controller_misc_options().contains(
+            ControllerMiscOptions::WORD_ADDRESSABLE.union(ControllerMiscOptions::DDR_MODE)
+        ));
+        assert_eq!(
+            mem_cfg.get_busy_bit(),
+            BusyBit::new(15, BusyBitPolarity::ActiveLow)
+        );
+        assert_eq!(
+            mem_cfg.get_lut_custom_seq(3),
+            Some(LutCustomSequence::new(4, 5))
+        );
+        assert_eq!(fcb.get_page_size(), 512);
+        assert_eq!(fcb.get_sector_size(), 256 * 1024);
+    }
+}
@@ -0,0 +1,242 @@
+//! ISSI serial NOR flash
+//!
+//! [`IS25LP_WP`] describes the IS25LP and IS25WP quad SPI parts up to 16MiB,
+//! like the IS25LP064A on the 1020 EVK and the IS25WP064A on the 1050 and
+//! 1060 EVKs. The parts read with the 1-4-4 `0xEB` command. The lookup table
+//! matches the NXP SDK's configuration blocks for those boards.
+//!
+//! [`IS25LP_WP_4B`] describes the larger parts, which need 4-byte addresses.
+//! It uses the 4-byte read, program, and erase commands, so the part can stay
+//! in its default 3-byte address mode.
+//!
+//! Before the part responds to quad reads, the boot ROM sets the quad enable
+//! bit, bit 6 of the status register, which also has the block protection
+//! bits.
+//!
+//! [`IS25WX_OCTAL_DTR`] describes the IS25WX256 octal part in its octal DDR
+//! mode. The part uses the Xccela command set, like Micron's MT35X parts. The
+//! part starts in extended SPI mode. The device mode configuration writes the
+//! I/O mode volatile configuration register to switch the part into octal DDR
+//! mode with DQS, then the boot ROM uses the octal commands, and samples reads
+//! with the part's DQS. In octal DDR mode, the part transfers each opcode
+//! twice, once on each clock edge. The read uses the part's default 16 dummy
+//! cycles. The preset is only available for chips whose boot ROM understands
+//! the switch into an xSPI mode.
+
+#[cfg(any(
+    feature = "imxrt1170",
+    feature = "imxrt1180",
+    feature = "imxrt500",
+    feature = "imxrt600"
+))]
+use crate::flexspi::DeviceModeType;
+use crate::flexspi::FlashPadType;
+#[cfg(any(
+    feature = "imxrt1170",
+    feature = "imxrt1180",
+    feature = "imxrt500",
+    feature = "imxrt600"
+))]
+use crate::serial_flash::nor::DeviceMode;
+use crate::serial_flash::nor::{NorCommand, NorCommandSet, QuadEnable};
+
+/// The IS25LP064A capacity, in bytes.
+pub const IS25LP064A: u32 = 8 * 1024 * 1024;
+/// The IS25WP064A capacity, in bytes.
+pub const IS25WP064A: u32 = 8 * 1024 * 1024;
+/// The IS25LP128 capacity, in bytes.
+pub const IS25LP128: u32 = 16 * 1024 * 1024;
+/// The IS25WP128 capacity, in bytes.
+pub const IS25WP128: u32 = 16 * 1024 * 1024;
+/// The IS25LP256D capacity, in bytes.
+pub const IS25LP256D: u32 = 32 * 1024 * 1024;
+/// The IS25WP256D capacity, in bytes.
+pub const IS25WP256D: u32 = 32 * 1024 * 1024;
+/// The IS25WX256 capacity, in bytes.
+pub const IS25WX256: u32 = 32 * 1024 * 1024;
+
+/// The IS25LP and IS25WP command set, with 3-byte addresses
+///
+/// 256 byte pages, 4KiB sectors, and 64KiB blocks.
+pub const IS25LP_WP: NorCommandSet = NorCommandSet::new()
+    .read(
+        NorCommand::new(0xEB)
+            .address_pads(FlashPadType::Quad)
+            .data_pads(FlashPadType::Quad)
+            .dummy_cycles(6),
+    )
+    .erase_block(NorCommand::new(0xD8))
+    .block_size(64 * 1024)
+    .quad_enable(QuadEnable::StatusRegister1Bit6)
+    .max_frequency_mhz(133);
+
+/// The IS25LP and IS25WP command set, with 4-byte addresses
+///
+/// The same as [`IS25LP_WP`], using the `0xEC` read, `0x12` page program,
+/// `0x21` sector erase, and `0xDC` block erase commands.
+pub const IS25LP_WP_4B: NorCommandSet = IS25LP_WP
+    .read(
+        NorCommand::new(0xEC)
+            .address_pads(FlashPadType::Quad)
+            .data_pads(FlashPadType::Quad)
+            .dummy_cycles(6),
+    )
+    .page_program(NorCommand::new(0x12))
+    .erase_sector(NorCommand::new(0x21))
+    .erase_block(NorCommand::new(0xDC))
+    .address_bits(32);
+
+/// An octal DDR command, which repeats the opcode.
+#[cfg(any(
+    feature = "imxrt1170",
+    feature = "imxrt1180",
+    feature = "imxrt500",
+    feature = "imxrt600"
+))]
+const fn octal_ddr(opcode: u8) -> NorCommand {
+    NorCommand::new(opcode)
+        .extension(opcode)
+        .command_pads(FlashPadType::Octal)
+        .address_pads(FlashPadType::Octal)
+        .data_pads(FlashPadType::Octal)
+        .ddr(true)
+}
+
+/// The IS25WX256 command set, in octal DDR mode
+///
+/// 256 byte pages, 4KiB sectors, and 128KiB blocks, with 4-byte addresses.
+/// Before the device mode configuration, the boot ROM uses the SPI read
+/// status and write enable commands.
+///
+/// The preset runs at 166MHz. The part's default dummy cycles support 200MHz;
+/// check your board before you run faster.
+#[cfg(any(
+    feature = "imxrt1170",
+    feature = "imxrt1180",
+    feature = "imxrt500",
+    feature = "imxrt600"
+))]
+pub const IS25WX_OCTAL_DTR: NorCommandSet = NorCommandSet::new()
+    .read(octal_ddr(0xFD).dummy_cycles(16))
+    .read_status_xpi(octal_ddr(0x05).dummy_cycles(8))
+    .write_enable_xpi(octal_ddr(0x06))
+    .page_program(octal_ddr(0x12))
+    .erase_sector(octal_ddr(0x21))
+    .erase_block(octal_ddr(0xDC))
+    .chip_erase(octal_ddr(0x60))
+    .address_bits(32)
+    .block_size(128 * 1024)
+    .device_mode(
+        // Write 0xE7, octal DDR with DQS, to the I/O mode register at address 0.
+        DeviceMode::new(NorCommand::new(0x81).address_bits(24), 0xE7)
+            .device_mode_type(DeviceModeType::Spi2Xpi),
+    )
+    .dqs(true)
+    .max_frequency_mhz(166);
+
+#[cfg(test)]
+mod test {
+    use super::{IS25LP_WP, IS25LP_WP_4B};
+    use crate::flexspi::Command;
+
+    /// The lookup table words in the NXP SDK's configuration block for the
+    /// 1060 EVK, by word index.
+    const SDK_LUT: &[(usize, u32)] = &[
+        (0, 0x0A18_04EB),
+        (1, 0x2604_3206),
+        (4, 0x2404_0405),
+        (12, 0x0000_0406),
+        (20, 0x0818_0420),
+        (32, 0x0818_04D8),
+        (36, 0x0818_0402),
+        (37, 0x0000_2004),
+        (44, 0x0000_0460),
+    ];
+
+    #[test]
+    fn matches_sdk_lookup_table() {
+        let bytes = IS25LP_WP.lookup_table().to_bytes();
+        let words: [u32; 64] = core::array::from_fn(|idx| {
+            u32::from_le_bytes(bytes[idx * 4..][..4].try_into().unwrap())
+        });
+        for (idx, word) in words.into_iter().enumerate() {
+            match idx / 4 {
+                // The SDK doesn't configure quad mode.
+                6 => continue,
+                // Only some boot ROMs use the block erase sequence.
+                8 if cfg!(not(any(
+                    feature = "imxrt1170",
+                    feature = "imxrt1180",
+                    feature = "imxrt500",
+                    feature = "imxrt600"
+                ))) =>
+                {
+                    continue
+                }
+                _ => {}
+            }
+            let expected = SDK_LUT
+                .iter()
+                .find(|(sdk, _)| *sdk == idx)
+                .map_or(0, |(_, word)| *word);
+            assert_eq!(word, expected, "lookupTable[{idx}]");
+        }
+    }
+
+    #[test]
+    fn four_byte_addresses() {
+        let lookup_table = IS25LP_WP_4B.lookup_table();
+        for (command, opcode) in [
+            (Command::Read, 0xEC),
+            (Command::EraseSector, 0x21),
+            (Command::PageProgram, 0x12),
+        ] {
+            let sequence = lookup_table.get_command(command);
+            assert_eq!(sequence.0[0].operand(), opcode);
+            assert_eq!(sequence.0[1].operand(), 32);
+        }
+    }
+
+    #[cfg(any(
+        feature = "imxrt1170",
+        feature = "imxrt1180",
+        feature = "imxrt500",
+        feature = "imxrt600"
+    ))]
+    #[test]
+    fn octal_ddr() {
+        use super::{IS25WX256, IS25WX_OCTAL_DTR};
+        use crate::flexspi::{
+            opcodes::ddr, DeviceModeConfiguration, DeviceModeSequence, Instr, Pads,
+            SequenceBuilder, SerialClockFrequency,
+        };
+        use crate::serial_flash::nor::SerialNorType;
+
+        assert_eq!(
+            IS25WX_OCTAL_DTR
+                .lookup_table()
+                .get_command(Command::PageProgram),
+            SequenceBuilder::new()
+                .instr(Instr::new(ddr::CMD, Pads::Eight, 0x12))
+                .instr(Instr::new(ddr::CMD, Pads::Eight, 0x12))
+                .instr(Instr::new(ddr::RADDR, Pads::Eight, 32))
+                .instr(Instr::new(ddr::WRITE, Pads::Eight, 0x04))
+                .build()
+        );
+
+        let fcb = IS25WX_OCTAL_DTR.fcb(IS25WX256);
+        let mem_cfg = fcb.mem_cfg();
+        assert!(
+            mem_cfg.get_device_mode_configuration()
+                == DeviceModeConfiguration::Enabled {
+                    device_mode_arg: 0xE7,
+                    device_mode_seq: DeviceModeSequence::new(1, 6),
+                }
+        );
+        assert_eq!(mem_cfg.get_serial_clk_freq(), SerialClockFrequency::MHz166);
+        assert_eq!(
+            fcb.get_extras().get_serial_nor_type(),
+            Some(SerialNorType::Xspi)
+        );
+    }
+}
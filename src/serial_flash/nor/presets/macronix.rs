@@ -0,0 +1,251 @@
+//! Macronix serial NOR flash
+//!
+//! [`MX25L_U`] describes the MX25L (3V) and MX25U (1.8V) quad SPI parts up to
+//! 16MiB. The parts read with the 1-4-4 `0xEB` command, using their default
+//! six dummy cycles. [`MX25L_U_4B`] describes the larger parts, using the
+//! 4-byte read, program, and erase commands.
+//!
+//! Before the part responds to quad reads, the boot ROM sets the quad enable
+//! bit, bit 6 of the status register, which also has the block protection
+//! bits.
+//!
+//! The presets run at 80MHz. Depending on the part, six dummy cycles support a
+//! faster clock, and more dummy cycles support a faster clock still. To run
+//! faster, set the dummy cycles in the part's configuration register, then
+//! adjust the read command's dummy cycles and the maximum frequency.
+//!
+//! [`MX25UM_OPI_DTR`] describes the MX25UM51345G octal part in its octal DTR
+//! (OPI DDR) mode. The part starts in SPI mode. The device mode configuration
+//! writes configuration register 2 to switch the part into octal DTR mode,
+//! then the boot ROM uses the octal commands, and samples reads with the
+//! part's DQS. Every octal command is followed by its inverted opcode. The
+//! read uses the part's default 20 dummy cycles, which support 200MHz. The
+//! preset is only available for chips whose boot ROM understands the switch
+//! into an xSPI mode.
+
+#[cfg(any(
+    feature = "imxrt1170",
+    feature = "imxrt1180",
+    feature = "imxrt500",
+    feature = "imxrt600"
+))]
+use crate::flexspi::DeviceModeType;
+use crate::flexspi::FlashPadType;
+#[cfg(any(
+    feature = "imxrt1170",
+    feature = "imxrt1180",
+    feature = "imxrt500",
+    feature = "imxrt600"
+))]
+use crate::serial_flash::nor::DeviceMode;
+use crate::serial_flash::nor::{NorCommand, NorCommandSet, QuadEnable};
+
+/// The MX25L6433F capacity, in bytes.
+pub const MX25L6433F: u32 = 8 * 1024 * 1024;
+/// The MX25L12833F capacity, in bytes.
+pub const MX25L12833F: u32 = 16 * 1024 * 1024;
+/// The MX25L25645G capacity, in bytes.
+pub const MX25L25645G: u32 = 32 * 1024 * 1024;
+/// The MX25U6432F capacity, in bytes.
+pub const MX25U6432F: u32 = 8 * 1024 * 1024;
+/// The MX25U12832F capacity, in bytes.
+pub const MX25U12832F: u32 = 16 * 1024 * 1024;
+/// The MX25U25645G capacity, in bytes.
+pub const MX25U25645G: u32 = 32 * 1024 * 1024;
+/// The MX25UM51345G capacity, in bytes.
+pub const MX25UM51345G: u32 = 64 * 1024 * 1024;
+
+/// The MX25L and MX25U command set, with 3-byte addresses
+///
+/// 256 byte pages, 4KiB sectors, and 64KiB blocks.
+pub const MX25L_U: NorCommandSet = NorCommandSet::new()
+    .read(
+        NorCommand::new(0xEB)
+            .address_pads(FlashPadType::Quad)
+            .data_pads(FlashPadType::Quad)
+            // Two performance enhance cycles, then four dummy cycles.
+            .dummy_cycles(6),
+    )
+    .erase_block(NorCommand::new(0xD8))
+    .block_size(64 * 1024)
+    .quad_enable(QuadEnable::StatusRegister1Bit6)
+    .max_frequency_mhz(80);
+
+/// The MX25L and MX25U command set, with 4-byte addresses
+///
+/// The same as [`MX25L_U`], using the `0xEC` read, `0x12` page program,
+/// `0x21` sector erase, and `0xDC` block erase commands.
+pub const MX25L_U_4B: NorCommandSet = MX25L_U
+    .read(
+        NorCommand::new(0xEC)
+            .address_pads(FlashPadType::Quad)
+            .data_pads(FlashPadType::Quad)
+            .dummy_cycles(6),
+    )
+    .page_program(NorCommand::new(0x12))
+    .erase_sector(NorCommand::new(0x21))
+    .erase_block(NorCommand::new(0xDC))
+    .address_bits(32);
+
+/// An octal DTR command, followed by its inverted opcode.
+#[cfg(any(
+    feature = "imxrt1170",
+    feature = "imxrt1180",
+    feature = "imxrt500",
+    feature = "imxrt600"
+))]
+const fn octal_dtr(opcode: u8) -> NorCommand {
+    NorCommand::new(opcode)
+        .extension(!opcode)
+        .command_pads(FlashPadType::Octal)
+        .address_pads(FlashPadType::Octal)
+        .data_pads(FlashPadType::Octal)
+        .ddr(true)
+}
+
+/// The MX25UM51345G command set, in octal DTR mode
+///
+/// 256 byte pages, 4KiB sectors, and 64KiB blocks, with 4-byte addresses.
+/// Before the device mode configuration, the boot ROM uses the SPI read
+/// status and write enable commands.
+#[cfg(any(
+    feature = "imxrt1170",
+    feature = "imxrt1180",
+    feature = "imxrt500",
+    feature = "imxrt600"
+))]
+pub const MX25UM_OPI_DTR: NorCommandSet = NorCommandSet::new()
+    .read(octal_dtr(0xEE).dummy_cycles(20))
+    .read_status_xpi(octal_dtr(0x05).address_bits(32).dummy_cycles(4))
+    .write_enable_xpi(octal_dtr(0x06))
+    .page_program(octal_dtr(0x12))
+    .erase_sector(octal_dtr(0x21))
+    .erase_block(octal_dtr(0xDC))
+    .chip_erase(octal_dtr(0x60))
+    .address_bits(32)
+    .block_size(64 * 1024)
+    .device_mode(
+        // Write 0x02, octal DTR mode, to configuration register 2 address 0.
+        DeviceMode::new(NorCommand::new(0x72).address_bits(32), 0x02)
+            .device_mode_type(DeviceModeType::Spi2Xpi),
+    )
+    .dqs(true)
+    .max_frequency_mhz(200);
+
+#[cfg(test)]
+mod test {
+    use super::{MX25L12833F, MX25L_U, MX25L_U_4B};
+    use crate::flexspi::{
+        opcodes::sdr::*, Command, DeviceModeConfiguration, DeviceModeSequence, Instr, Pads,
+        SequenceBuilder, SerialClockFrequency,
+    };
+
+    #[test]
+    fn quad_enable() {
+        assert_eq!(
+            MX25L_U.lookup_table().get_sequence(6),
+            SequenceBuilder::new()
+                .instr(Instr::new(CMD, Pads::One, 0x01))
+                .instr(Instr::new(WRITE, Pads::One, 0x01))
+                .build()
+        );
+        let fcb = MX25L_U.fcb(MX25L12833F);
+        assert!(
+            fcb.mem_cfg().get_device_mode_configuration()
+                == DeviceModeConfiguration::Enabled {
+                    device_mode_arg: 0x40,
+                    device_mode_seq: DeviceModeSequence::new(1, 6),
+                }
+        );
+        assert_eq!(
+            fcb.mem_cfg().get_serial_clk_freq(),
+            SerialClockFrequency::MHz80
+        );
+    }
+
+    #[cfg(any(
+        feature = "imxrt1170",
+        feature = "imxrt1180",
+        feature = "imxrt500",
+        feature = "imxrt600"
+    ))]
+    #[test]
+    fn octal_dtr() {
+        use super::{MX25UM51345G, MX25UM_OPI_DTR};
+        use crate::flexspi::{
+            opcodes::ddr, ControllerMiscOptions, DeviceModeType, FlashPadType,
+            ReadSampleClockSource,
+        };
+        use crate::serial_flash::nor::SerialNorType;
+
+        let lookup_table = MX25UM_OPI_DTR.lookup_table();
+        assert_eq!(
+            lookup_table.get_command(Command::Read),
+            SequenceBuilder::new()
+                .instr(Instr::new(ddr::CMD, Pads::Eight, 0xEE))
+                .instr(Instr::new(ddr::CMD, Pads::Eight, 0x11))
+                .instr(Instr::new(ddr::RADDR, Pads::Eight, 32))
+                .instr(Instr::new(ddr::DUMMY, Pads::Eight, 20))
+                .instr(Instr::new(ddr::READ, Pads::Eight, 0x04))
+                .build()
+        );
+        assert_eq!(
+            lookup_table.get_sequence(2),
+            SequenceBuilder::new()
+                .instr(Instr::new(ddr::CMD, Pads::Eight, 0x05))
+                .instr(Instr::new(ddr::CMD, Pads::Eight, 0xFA))
+                .instr(Instr::new(ddr::RADDR, Pads::Eight, 32))
+                .instr(Instr::new(ddr::DUMMY, Pads::Eight, 4))
+                .instr(Instr::new(ddr::READ, Pads::Eight, 0x04))
+                .build()
+        );
+        assert_eq!(
+            lookup_table.get_sequence(6),
+            SequenceBuilder::new()
+                .instr(Instr::new(CMD, Pads::One, 0x72))
+                .instr(Instr::new(RADDR, Pads::One, 32))
+                .instr(Instr::new(WRITE, Pads::One, 0x01))
+                .build()
+        );
+        // The SPI commands stay in place for before the mode switch.
+        assert_eq!(
+            lookup_table.get_command(Command::WriteEnable),
+            SequenceBuilder::new()
+                .instr(Instr::new(CMD, Pads::One, 0x06))
+                .build()
+        );
+
+        let fcb = MX25UM_OPI_DTR.fcb(MX25UM51345G);
+        let mem_cfg = fcb.mem_cfg();
+        assert_eq!(mem_cfg.get_serial_flash_pad_type(), FlashPadType::Octal);
+        assert_eq!(
+            mem_cfg.get_read_sample_clk_src(),
+            ReadSampleClockSource::FlashProvidedDQS
+        );
+        assert!(mem_cfg
+            .get_controller_misc_options()
+            .contains(ControllerMiscOptions::DDR_MODE));
+        assert_eq!(
+            mem_cfg.get_device_mode_type(),
+            Some(DeviceModeType::Spi2Xpi)
+        );
+        assert_eq!(
+            fcb.get_extras().get_serial_nor_type(),
+            Some(SerialNorType::Xspi)
+        );
+    }
+
+    #[test]
+    fn four_byte_addresses() {
+        let read = MX25L_U_4B.lookup_table().get_command(Command::Read);
+        assert_eq!(
+            read,
+            SequenceBuilder::new()
+                .instr(Instr::new(CMD, Pads::One, 0xEC))
+                .instr(Instr::new(RADDR, Pads::Four, 32))
+                .instr(Instr::new(DUMMY, Pads::Four, 6))
+                .instr(Instr::new(READ, Pads::Four, 0x04))
+                .build()
+        );
+    }
+}
@@ -0,0 +1,247 @@
+//! Micron serial NOR flash
+//!
+//! [`MT25Q`] describes the MT25QL (3V) and MT25QU (1.8V) quad SPI parts up to
+//! 16MiB. The parts read with the 1-4-4 `0xEB` command. [`MT25Q_4B`]
+//! describes the 32MiB and 64MiB parts, using the 4-byte read, program, and
+//! erase commands, so the part can stay in its default 3-byte address mode.
+//!
+//! The MT25Q parts don't have a quad enable bit; the quad commands work out
+//! of reset. The presets don't need a device mode configuration.
+//!
+//! The reads use the part's default ten dummy cycles, which include the XIP
+//! confirmation bit. Ten dummy cycles support 133MHz. If you change the
+//! dummy cycles in the part's nonvolatile configuration register, adjust the
+//! read command.
+//!
+//! The 1Gb and larger parts stack more than one die, and they erase with the
+//! die erase command instead of the chip erase command. The presets don't
+//! describe those parts.
+//!
+//! [`MT35XU_OCTAL_DTR`] describes the MT35XU512 Xccela octal part in its octal
+//! DDR mode. The part starts in extended SPI mode. The device mode
+//! configuration writes the I/O mode volatile configuration register to
+//! switch the part into octal DDR mode with DQS, then the boot ROM uses the
+//! octal commands, and samples reads with the part's DQS. In octal DDR mode,
+//! the part transfers each opcode twice, once on each clock edge. The read
+//! uses the part's default 16 dummy cycles. The preset is only available for
+//! chips whose boot ROM understands the switch into an xSPI mode.
+
+#[cfg(any(
+    feature = "imxrt1170",
+    feature = "imxrt1180",
+    feature = "imxrt500",
+    feature = "imxrt600"
+))]
+use crate::flexspi::DeviceModeType;
+use crate::flexspi::FlashPadType;
+#[cfg(any(
+    feature = "imxrt1170",
+    feature = "imxrt1180",
+    feature = "imxrt500",
+    feature = "imxrt600"
+))]
+use crate::serial_flash::nor::DeviceMode;
+use crate::serial_flash::nor::{NorCommand, NorCommandSet};
+
+/// The MT25QL128 capacity, in bytes.
+pub const MT25QL128: u32 = 16 * 1024 * 1024;
+/// The MT25QU128 capacity, in bytes.
+pub const MT25QU128: u32 = 16 * 1024 * 1024;
+/// The MT25QL256 capacity, in bytes.
+pub const MT25QL256: u32 = 32 * 1024 * 1024;
+/// The MT25QU256 capacity, in bytes.
+pub const MT25QU256: u32 = 32 * 1024 * 1024;
+/// The MT25QL512 capacity, in bytes.
+pub const MT25QL512: u32 = 64 * 1024 * 1024;
+/// The MT25QU512 capacity, in bytes.
+pub const MT25QU512: u32 = 64 * 1024 * 1024;
+/// The MT35XU512 capacity, in bytes.
+pub const MT35XU512: u32 = 64 * 1024 * 1024;
+
+/// The MT25Q command set, with 3-byte addresses
+///
+/// 256 byte pages, 4KiB sectors, and 64KiB blocks.
+pub const MT25Q: NorCommandSet = NorCommandSet::new()
+    .read(
+        NorCommand::new(0xEB)
+            .address_pads(FlashPadType::Quad)
+            .data_pads(FlashPadType::Quad)
+            .dummy_cycles(10),
+    )
+    .erase_block(NorCommand::new(0xD8))
+    .block_size(64 * 1024)
+    .max_frequency_mhz(133);
+
+/// The MT25Q command set, with 4-byte addresses
+///
+/// The same as [`MT25Q`], using the `0xEC` read, `0x12` page program, `0x21`
+/// sector erase, and `0xDC` block erase commands.
+pub const MT25Q_4B: NorCommandSet = MT25Q
+    .read(
+        NorCommand::new(0xEC)
+            .address_pads(FlashPadType::Quad)
+            .data_pads(FlashPadType::Quad)
+            .dummy_cycles(10),
+    )
+    .page_program(NorCommand::new(0x12))
+    .erase_sector(NorCommand::new(0x21))
+    .erase_block(NorCommand::new(0xDC))
+    .address_bits(32);
+
+/// An octal DDR command, which repeats the opcode.
+#[cfg(any(
+    feature = "imxrt1170",
+    feature = "imxrt1180",
+    feature = "imxrt500",
+    feature = "imxrt600"
+))]
+const fn octal_ddr(opcode: u8) -> NorCommand {
+    NorCommand::new(opcode)
+        .extension(opcode)
+        .command_pads(FlashPadType::Octal)
+        .address_pads(FlashPadType::Octal)
+        .data_pads(FlashPadType::Octal)
+        .ddr(true)
+}
+
+/// The MT35XU512 command set, in octal DDR mode
+///
+/// 256 byte pages, 4KiB sectors, and 128KiB blocks, with 4-byte addresses.
+/// Before the device mode configuration, the boot ROM uses the SPI read
+/// status and write enable commands.
+///
+/// The preset runs at 166MHz. The part's default dummy cycles support 200MHz;
+/// check your board before you run faster.
+#[cfg(any(
+    feature = "imxrt1170",
+    feature = "imxrt1180",
+    feature = "imxrt500",
+    feature = "imxrt600"
+))]
+pub const MT35XU_OCTAL_DTR: NorCommandSet = NorCommandSet::new()
+    .read(octal_ddr(0xFD).dummy_cycles(16))
+    .read_status_xpi(octal_ddr(0x05).dummy_cycles(8))
+    .write_enable_xpi(octal_ddr(0x06))
+    .page_program(octal_ddr(0x12))
+    .erase_sector(octal_ddr(0x21))
+    .erase_block(octal_ddr(0xDC))
+    .chip_erase(octal_ddr(0x60))
+    .address_bits(32)
+    .block_size(128 * 1024)
+    .device_mode(
+        // Write 0xE7, octal DDR with DQS, to the I/O mode register at address 0.
+        DeviceMode::new(NorCommand::new(0x81).address_bits(24), 0xE7)
+            .device_mode_type(DeviceModeType::Spi2Xpi),
+    )
+    .dqs(true)
+    .max_frequency_mhz(166);
+
+#[cfg(test)]
+mod test {
+    use super::{MT25Q, MT25QL128, MT25QL512, MT25Q_4B};
+    use crate::flexspi::{
+        opcodes::sdr::*, Command, DeviceModeConfiguration, Instr, Pads, SequenceBuilder,
+    };
+
+    #[test]
+    fn lookup_table() {
+        let lookup_table = MT25Q.lookup_table();
+        assert_eq!(
+            lookup_table.get_command(Command::Read),
+            SequenceBuilder::new()
+                .instr(Instr::new(CMD, Pads::One, 0xEB))
+                .instr(Instr::new(RADDR, Pads::Four, 24))
+                .instr(Instr::new(DUMMY, Pads::Four, 10))
+                .instr(Instr::new(READ, Pads::Four, 0x04))
+                .build()
+        );
+        // No quad enable sequence.
+        assert_eq!(lookup_table.get_sequence(6), SequenceBuilder::new().build());
+
+        let fcb = MT25Q.fcb(MT25QL128);
+        assert!(fcb.mem_cfg().get_device_mode_configuration() == DeviceModeConfiguration::Disabled);
+    }
+
+    #[test]
+    fn four_byte_addresses() {
+        let lookup_table = MT25Q_4B.lookup_table();
+        assert_eq!(
+            lookup_table.get_command(Command::Read),
+            SequenceBuilder::new()
+                .instr(Instr::new(CMD, Pads::One, 0xEC))
+                .instr(Instr::new(RADDR, Pads::Four, 32))
+                .instr(Instr::new(DUMMY, Pads::Four, 10))
+                .instr(Instr::new(READ, Pads::Four, 0x04))
+                .build()
+        );
+        assert_eq!(
+            lookup_table.get_command(Command::EraseSector),
+            SequenceBuilder::new()
+                .instr(Instr::new(CMD, Pads::One, 0x21))
+                .instr(Instr::new(RADDR, Pads::One, 32))
+                .build()
+        );
+
+        let fcb = MT25Q_4B.fcb(MT25QL512);
+        assert_eq!(fcb.get_sector_size(), 4 * 1024);
+    }
+
+    #[cfg(any(
+        feature = "imxrt1170",
+        feature = "imxrt1180",
+        feature = "imxrt500",
+        feature = "imxrt600"
+    ))]
+    #[test]
+    fn octal_ddr() {
+        use super::{MT35XU512, MT35XU_OCTAL_DTR};
+        use crate::flexspi::{opcodes::ddr, DeviceModeType, ReadSampleClockSource};
+        use crate::serial_flash::nor::SerialNorType;
+
+        let lookup_table = MT35XU_OCTAL_DTR.lookup_table();
+        assert_eq!(
+            lookup_table.get_command(Command::Read),
+            SequenceBuilder::new()
+                .instr(Instr::new(ddr::CMD, Pads::Eight, 0xFD))
+                .instr(Instr::new(ddr::CMD, Pads::Eight, 0xFD))
+                .instr(Instr::new(ddr::RADDR, Pads::Eight, 32))
+                .instr(Instr::new(ddr::DUMMY, Pads::Eight, 16))
+                .instr(Instr::new(ddr::READ, Pads::Eight, 0x04))
+                .build()
+        );
+        // The xSPI read status doesn't send an address.
+        assert_eq!(
+            lookup_table.get_sequence(2),
+            SequenceBuilder::new()
+                .instr(Instr::new(ddr::CMD, Pads::Eight, 0x05))
+                .instr(Instr::new(ddr::CMD, Pads::Eight, 0x05))
+                .instr(Instr::new(ddr::DUMMY, Pads::Eight, 8))
+                .instr(Instr::new(ddr::READ, Pads::Eight, 0x04))
+                .build()
+        );
+        assert_eq!(
+            lookup_table.get_sequence(6),
+            SequenceBuilder::new()
+                .instr(Instr::new(CMD, Pads::One, 0x81))
+                .instr(Instr::new(RADDR, Pads::One, 24))
+                .instr(Instr::new(WRITE, Pads::One, 0x01))
+                .build()
+        );
+
+        let fcb = MT35XU_OCTAL_DTR.fcb(MT35XU512);
+        let mem_cfg = fcb.mem_cfg();
+        assert_eq!(
+            mem_cfg.get_device_mode_type(),
+            Some(DeviceModeType::Spi2Xpi)
+        );
+        assert_eq!(
+            mem_cfg.get_read_sample_clk_src(),
+            ReadSampleClockSource::FlashProvidedDQS
+        );
+        assert_eq!(
+            fcb.get_extras().get_serial_nor_type(),
+            Some(SerialNorType::Xspi)
+        );
+        assert_eq!(fcb.get_extras().get_block_size(), Some(128 * 1024));
+    }
+}
@@ -0,0 +1,34 @@
+//! Winbond serial NOR flash
+//!
+//! [`W25Q_JV`] describes the W25Q16JV, W25Q64JV, and W25Q128JV. The parts
+//! read with the 1-4-4 `0xEB` command, and run up to 133MHz. Before the part
+//! responds to quad reads, the boot ROM sets the quad enable bit, bit 1 of
+//! status register 2, with the `0x31` command.
+//!
+//! The other writable bits of status register 2 protect the status registers
+//! and select the complement protect mode.
+
+use crate::flexspi::FlashPadType;
+use crate::serial_flash::nor::{NorCommand, NorCommandSet, QuadEnable};
+
+/// The W25Q16JV capacity, in bytes.
+pub const W25Q16JV: u32 = 2 * 1024 * 1024;
+/// The W25Q64JV capacity, in bytes.
+pub const W25Q64JV: u32 = 8 * 1024 * 1024;
+/// The W25Q128JV capacity, in bytes.
+pub const W25Q128JV: u32 = 16 * 1024 * 1024;
+
+/// The W25Q JV command set
+///
+/// 256 byte pages, 4KiB sectors, and 64KiB blocks.
+pub const W25Q_JV: NorCommandSet = NorCommandSet::new()
+    .read(
+        NorCommand::new(0xEB)
+            .address_pads(FlashPadType::Quad)
+            .data_pads(FlashPadType::Quad)
+            .dummy_cycles(6),
+    )
+    .erase_block(NorCommand::new(0xD8))
+    .block_size(64 * 1024)
+    .quad_enable(QuadEnable::StatusRegister2Bit1)
+    .max_frequency_mhz(133);
@@ -0,0 +1,370 @@
+//! Serial flash discoverable parameters (SFDP)
+//!
+//! JEDEC JESD216 flash parts describe themselves with SFDP tables, which you
+//! read from the part with the `0x5A` command. [`parse`] decodes the basic
+//! flash parameter table, and the [`Parameters`] describe a
+//! [`NorFlashDevice`] that you can pass to [`recommended_fcb`](super::recommended_fcb).
+//!
+//! The parser picks the fastest read mode that sends the command on one pad:
+//! 1-4-4, 1-1-4, 1-2-2, 1-1-2, then 1-1-1. It doesn't enable quad mode; some
+//! parts need their quad enable bit set before they respond to quad reads.
+//!
+//! ```
+//! use imxrt_boot_gen::serial_flash::nor::sfdp;
+//!
+//! # fn read_sfdp() -> [u8; 256] { [0; 256] }
+//! let bytes = read_sfdp();
+//! match sfdp::parse(&bytes) {
+//!     Ok(parameters) => {
+//!         let device = parameters.device().max_frequency_mhz(104);
+//!         // Use the device with recommended_fcb...
+//!     }
+//!     Err(err) => { /* Not an SFDP table */ }
+//! }
+//! ```
+
+use super::NorFlashDevice;
+use crate::flexspi::{
+    opcodes::sdr::{CMD, DUMMY, RADDR, READ, WRITE},
+    Command, FlashPadType, Instr, LookupTable, Pads, SequenceBuilder,
+};
+use core::fmt;
+
+/// The `"SFDP"` signature.
+const SIGNATURE: u32 = u32::from_le_bytes(*b"SFDP");
+
+/// An error produced when parsing SFDP tables
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Error {
+    /// The tables don't start with the `"SFDP"` signature.
+    Signature(u32),
+    /// The tables don't have a basic flash parameter table.
+    NoBasicTable,
+    /// A table extends beyond the end of the bytes.
+    Truncated,
+    /// The density doesn't fit in a `u32` number of bytes.
+    Density,
+    /// The part is larger than 16MiB, and it starts up in 3-byte address mode.
+    AddressMode,
+    /// The part doesn't have an erase command smaller than 4GiB.
+    Erase,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Error::Signature(signature) => write!(f, "signature {signature:#010X} is not 'SFDP'"),
+            Error::NoBasicTable => write!(f, "no basic flash parameter table"),
+            Error::Truncated => write!(f, "a table extends beyond the end of the bytes"),
+            Error::Density => write!(f, "the density is too large"),
+            Error::AddressMode => write!(f, "the part needs 4-byte addresses"),
+            Error::Erase => write!(f, "no supported erase command"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Error {}
+
+/// A fast read command
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FastRead {
+    /// The read opcode.
+    pub opcode: u8,
+    /// The pads for the address.
+    pub address_pads: FlashPadType,
+    /// The pads for the mode bits, dummy cycles, and data.
+    pub data_pads: FlashPadType,
+    /// The dummy cycles, including the mode bit cycles.
+    pub dummy_cycles: u8,
+}
+
+/// Parameters from the basic flash parameter table
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Parameters {
+    /// The capacity, in bytes.
+    pub flash_size: u32,
+    /// The page size, in bytes.
+    pub page_size: u32,
+    /// The size erased by `erase_sector_opcode`, in bytes.
+    pub sector_size: u32,
+    /// The opcode that erases the smallest sector.
+    pub erase_sector_opcode: u8,
+    /// The number of address bits, 24 or 32.
+    pub address_bits: u8,
+    /// The fastest supported read.
+    pub read: FastRead,
+}
+
+const fn pads(pad_type: FlashPadType) -> Pads {
+    match pad_type {
+        FlashPadType::Single => Pads::One,
+        FlashPadType::Dual => Pads::Two,
+        FlashPadType::Quad => Pads::Four,
+        FlashPadType::Octal => Pads::Eight,
+    }
+}
+
+impl Parameters {
+    /// Returns a lookup table with read, read status, write enable, sector
+    /// erase, page program, and chip erase sequences.
+    ///
+    /// Everything other than reads uses one pad, and the common status,
+    /// write enable, program, and chip erase opcodes.
+    pub fn lookup_table(&self) -> LookupTable {
+        let read = self.read;
+        let mut read_seq = SequenceBuilder::new()
+            .instr(Instr::new(CMD, Pads::One, read.opcode))
+            .instr(Instr::new(
+                RADDR,
+                pads(read.address_pads),
+                self.address_bits,
+            ));
+        if read.dummy_cycles != 0 {
+            read_seq = read_seq.instr(Instr::new(DUMMY, pads(read.data_pads), read.dummy_cycles));
+        }
+        let read_seq = read_seq
+            .instr(Instr::new(READ, pads(read.data_pads), 0x04))
+            .build();
+
+        LookupTable::new()
+            .command(Command::Read, read_seq)
+            .command(
+                Command::ReadStatus,
+                SequenceBuilder::new()
+                    .instr(Instr::new(CMD, Pads::One, 0x05))
+                    .instr(Instr::new(READ, Pads::One, 0x04))
+                    .build(),
+            )
+            .command(
+                Command::WriteEnable,
+                SequenceBuilder::new()
+                    .instr(Instr::new(CMD, Pads::One, 0x06))
+                    .build(),
+            )
+            .command(
+                Command::EraseSector,
+                SequenceBuilder::new()
+                    .instr(Instr::new(CMD, Pads::One, self.erase_sector_opcode))
+                    .instr(Instr::new(RADDR, Pads::One, self.address_bits))
+                    .build(),
+            )
+            .command(
+                Command::PageProgram,
+                SequenceBuilder::new()
+                    .instr(Instr::new(CMD, Pads::One, 0x02))
+                    .instr(Instr::new(RADDR, Pads::One, self.address_bits))
+                    .instr(Instr::new(WRITE, Pads::One, 0x04))
+                    .build(),
+            )
+            .command(
+                Command::ChipErase,
+                SequenceBuilder::new()
+                    .instr(Instr::new(CMD, Pads::One, 0x60))
+                    .build(),
+            )
+    }
+
+    /// Describe the part as a [`NorFlashDevice`].
+    ///
+    /// SFDP doesn't describe the part's maximum frequency, so the device
+    /// runs at the default 30MHz. Use
+    /// [`max_frequency_mhz`](NorFlashDevice::max_frequency_mhz) to go faster.
+    pub fn device(&self) -> NorFlashDevice {
+        NorFlashDevice::new(self.lookup_table())
+            .flash_size(self.flash_size)
+            .page_size(self.page_size)
+            .sector_size(self.sector_size)
+            .pad_type(self.read.data_pads)
+    }
+}
+
+/// Returns the dword at `index` in the table at `table`.
+fn dword(bytes: &[u8], table: usize, index: usize) -> Result<u32, Error> {
+    let offset = table + index * 4;
+    let word = bytes.get(offset..offset + 4).ok_or(Error::Truncated)?;
+    Ok(u32::from_le_bytes(word.try_into().unwrap()))
+}
+
+/// Returns the read for a fast read field, the upper or lower half of a dword.
+fn fast_read(field: u32, address_pads: FlashPadType, data_pads: FlashPadType) -> FastRead {
+    let dummy = field & 0x1F;
+    let mode = (field >> 5) & 0x7;
+    FastRead {
+        opcode: (field >> 8) as u8,
+        address_pads,
+        data_pads,
+        dummy_cycles: (dummy + mode) as u8,
+    }
+}
+
+/// Parse the SFDP tables, starting with the SFDP header.
+pub fn parse(bytes: &[u8]) -> Result<Parameters, Error> {
+    let signature = dword(bytes, 0, 0)?;
+    if signature != SIGNATURE {
+        return Err(Error::Signature(signature));
+    }
+    let headers = usize::from(*bytes.get(6).ok_or(Error::Truncated)?) + 1;
+    let table = (0..headers)
+        .map(|header| 8 + header * 8)
+        .find_map(|header| {
+            let header = bytes.get(header..header + 8)?;
+            // The basic flash parameter table's ID is 0xFF00.
+            (header[0] == 0x00 && header[7] == 0xFF).then_some(header)
+        })
+        .ok_or(Error::NoBasicTable)?;
+    let length = usize::from(table[3]);
+    let table = usize::from(table[4]) | usize::from(table[5]) << 8 | usize::from(table[6]) << 16;
+    if length < 9 || bytes.len() < table + length * 4 {
+        return Err(Error::Truncated);
+    }
+    let dword = |index: usize| dword(bytes, table, index - 1);
+
+    let dword1 = dword(1)?;
+    let density = dword(2)?;
+    let flash_size = if density & (1 << 31) == 0 {
+        (u64::from(density) + 1) / 8
+    } else {
+        1u64.checked_shl(density & 0x7FFF_FFFF)
+            .ok_or(Error::Density)?
+            / 8
+    };
+    let flash_size = u32::try_from(flash_size).map_err(|_| Error::Density)?;
+
+    let address_bits = match (dword1 >> 17) & 0x3 {
+        0b10 => 32,
+        _ if flash_size > 16 * 1024 * 1024 => return Err(Error::AddressMode),
+        _ => 24,
+    };
+
+    use FlashPadType::{Dual, Quad, Single};
+    let (dword3, dword4) = (dword(3)?, dword(4)?);
+    let read = if dword1 & (1 << 21) != 0 {
+        fast_read(dword3, Quad, Quad)
+    } else if dword1 & (1 << 22) != 0 {
+        fast_read(dword3 >> 16, Single, Quad)
+    } else if dword1 & (1 << 20) != 0 {
+        fast_read(dword4 >> 16, Dual, Dual)
+    } else if dword1 & (1 << 16) != 0 {
+        fast_read(dword4, Single, Dual)
+    } else {
+        FastRead {
+            opcode: 0x0B,
+            address_pads: Single,
+            data_pads: Single,
+            dummy_cycles: 8,
+        }
+    };
+
+    // Erase types 1 through 4 are in dwords 8 and 9. Pick the smallest.
+    let erase_types = [dword(8)?, dword(9)?];
+    let (sector_size, erase_sector_opcode) = erase_types
+        .iter()
+        .flat_map(|dword| [*dword as u16, (*dword >> 16) as u16])
+        .filter(|erase| erase & 0xFF != 0 && erase & 0xFF < 32)
+        .map(|erase| (1u32 << (erase & 0xFF), (erase >> 8) as u8))
+        .min()
+        .or_else(|| (dword1 & 0x3 == 0b01).then_some((4096, (dword1 >> 8) as u8)))
+        .ok_or(Error::Erase)?;
+
+    // JESD216A and later describe the page size in dword 11.
+    let page_size = if length >= 11 {
+        1 << ((dword(11)? >> 4) & 0xF)
+    } else {
+        256
+    };
+
+    Ok(Parameters {
+        flash_size,
+        page_size,
+        sector_size,
+        erase_sector_opcode,
+        address_bits,
+        read,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::{parse, Error, FastRead};
+    use crate::flexspi::{Command, FlashPadType};
+
+    /// A 64Mbit quad SPI part with 4KiB sectors.
+    fn tables() -> [u8; 0x80 + 16 * 4] {
+        let mut bytes = [0; 0x80 + 16 * 4];
+        bytes[..16].copy_from_slice(&[
+            b'S', b'F', b'D', b'P', 0x06, 0x01, 0x00, 0xFF, // SFDP header
+            0x00, 0x06, 0x01, 0x10, 0x80, 0x00, 0x00, 0xFF, // Basic table header
+        ]);
+        let dwords: [u32; 16] = [
+            0xFF71_20E5, // 4KiB erase 0x20; 1-1-2, 1-2-2, 1-4-4, 1-1-4 reads
+            0x03FF_FFFF, // 64Mbit
+            0x6B08_EB44, // 1-1-4 0x6B, 8 dummy; 1-4-4 0xEB, 2 mode + 4 dummy
+            0xBB42_3B08, // 1-2-2 0xBB; 1-1-2 0x3B
+            0xFFFF_FFEE, // No 2-2-2 or 4-4-4
+            0xFFFF_0000,
+            0xFFFF_0000,
+            0x520F_200C, // 4KiB 0x20; 32KiB 0x52
+            0x00FF_D810, // 64KiB 0xD8
+            0,
+            0x0000_0080, // 256 byte pages
+            0,
+            0,
+            0,
+            0,
+            0,
+        ];
+        for (index, dword) in dwords.iter().enumerate() {
+            bytes[0x80 + index * 4..][..4].copy_from_slice(&dword.to_le_bytes());
+        }
+        bytes
+    }
+
+    #[test]
+    fn quad_part() {
+        let parameters = parse(&tables()).unwrap();
+        assert_eq!(parameters.flash_size, 8 * 1024 * 1024);
+        assert_eq!(parameters.page_size, 256);
+        assert_eq!(parameters.sector_size, 4096);
+        assert_eq!(parameters.erase_sector_opcode, 0x20);
+        assert_eq!(parameters.address_bits, 24);
+        assert_eq!(
+            parameters.read,
+            FastRead {
+                opcode: 0xEB,
+                address_pads: FlashPadType::Quad,
+                data_pads: FlashPadType::Quad,
+                dummy_cycles: 6,
+            }
+        );
+        let sequence = parameters
+            .lookup_table()
+            .get_sequence(Command::Read as usize);
+        assert_eq!(sequence.0[2].operand(), 6);
+    }
+
+    #[test]
+    fn read_modes() {
+        let mut bytes = tables();
+        // Only 1-1-4 reads.
+        bytes[0x82] = 0x41;
+        let parameters = parse(&bytes).unwrap();
+        assert_eq!(parameters.read.opcode, 0x6B);
+        assert_eq!(parameters.read.address_pads, FlashPadType::Single);
+        assert_eq!(parameters.read.dummy_cycles, 8);
+        // No fast reads.
+        bytes[0x82] = 0x00;
+        assert_eq!(parse(&bytes).unwrap().read.opcode, 0x0B);
+    }
+
+    #[test]
+    fn errors() {
+        let mut bytes = tables();
+        assert!(matches!(parse(&bytes[..0x90]), Err(Error::Truncated)));
+        bytes[0x87] = 0x0F; // 256Mbit, 3-byte addresses
+        assert_eq!(parse(&bytes), Err(Error::AddressMode));
+        bytes[0] = 0;
+        assert!(matches!(parse(&bytes), Err(Error::Signature(_))));
+    }
+}
@@ -0,0 +1,114 @@
+//! UF2 output
+//!
+//! UF2 bootloaders accept files made of 512-byte blocks, each carrying up to
+//! 256 bytes of flash data and the address where it belongs. Each block also
+//! names the chip family, so a bootloader can reject an image that's built
+//! for another chip.
+//!
+//! ```
+//! use imxrt_boot_gen::hex::Segment;
+//! use imxrt_boot_gen::flexspi::{self, LookupTable};
+//! use imxrt_boot_gen::serial_flash::nor;
+//! use imxrt_boot_gen::{uf2, Imxrt};
+//!
+//! let block = nor::ConfigurationBlock::new(flexspi::ConfigurationBlock::new(LookupTable::new()));
+//! # #[cfg(not(any(feature = "imxrt1170", feature = "imxrt1180", feature = "imxrt500", feature = "imxrt600")))] {
+//! let family_id = Imxrt::SELECTED.uf2_family_id().unwrap();
+//! let uf2 = uf2::to_uf2(&[Segment::serial_nor_fcb(&block)], family_id);
+//! assert_eq!(uf2.len(), 2 * uf2::BLOCK_SIZE);
+//! # }
+//! ```
+//!
+//! This module requires the `"std"` feature.
+
+use crate::hex::Segment;
+
+/// The size of a UF2 block, in bytes.
+pub const BLOCK_SIZE: usize = 512;
+
+/// The most flash data in one block.
+const PAYLOAD_SIZE: u32 = 256;
+
+const MAGIC_START_0: u32 = 0x0A32_4655;
+const MAGIC_START_1: u32 = 0x9E5D_5157;
+const MAGIC_END: u32 = 0x0AB1_6F30;
+/// The block's `fileSize` field is a family ID.
+const FLAG_FAMILY_ID_PRESENT: u32 = 0x0000_2000;
+
+/// Encode the segments as UF2 blocks for `family_id`.
+///
+/// A block's data never crosses a 256-byte address boundary, so blocks
+/// may have less than 256 bytes of data. Use
+/// [`Imxrt::uf2_family_id`](crate::Imxrt::uf2_family_id) for the family ID,
+/// or your bootloader's ID if the chip doesn't have one.
+pub fn to_uf2(segments: &[Segment], family_id: u32) -> Vec<u8> {
+    let mut payloads = Vec::new();
+    for segment in segments {
+        let mut address = segment.address();
+        let mut data = segment.data();
+        while !data.is_empty() {
+            let to_boundary = PAYLOAD_SIZE - address % PAYLOAD_SIZE;
+            let (payload, rest) = data.split_at(data.len().min(to_boundary as usize));
+            payloads.push((address, payload));
+            address = address.wrapping_add(payload.len() as u32);
+            data = rest;
+        }
+    }
+
+    let count = payloads.len() as u32;
+    let mut out = Vec::with_capacity(payloads.len() * BLOCK_SIZE);
+    for (number, (address, payload)) in payloads.into_iter().enumerate() {
+        let header = [
+            MAGIC_START_0,
+            MAGIC_START_1,
+            FLAG_FAMILY_ID_PRESENT,
+            address,
+            payload.len() as u32,
+            number as u32,
+            count,
+            family_id,
+        ];
+        for word in header {
+            out.extend_from_slice(&word.to_le_bytes());
+        }
+        let data_start = out.len();
+        out.extend_from_slice(payload);
+        out.resize(data_start + 476, 0);
+        out.extend_from_slice(&MAGIC_END.to_le_bytes());
+    }
+    out
+}
+
+#[cfg(test)]
+mod test {
+    use super::{to_uf2, BLOCK_SIZE};
+    use crate::hex::Segment;
+
+    fn word(block: &[u8], index: usize) -> u32 {
+        u32::from_le_bytes(block[index * 4..][..4].try_into().unwrap())
+    }
+
+    #[test]
+    fn blocks() {
+        let uf2 = to_uf2(&[Segment::new(0x6000_0080, [0xAA; 300])], 0x4FB2_D5BD);
+        let blocks: Vec<_> = uf2.chunks_exact(BLOCK_SIZE).collect();
+        assert_eq!(blocks.len(), 2);
+
+        let addresses: Vec<_> = blocks.iter().map(|block| word(block, 3)).collect();
+        assert_eq!(addresses, [0x6000_0080, 0x6000_0100]);
+        let sizes: Vec<_> = blocks.iter().map(|block| word(block, 4)).collect();
+        assert_eq!(sizes, [128, 172]);
+
+        for (number, block) in blocks.iter().enumerate() {
+            assert_eq!(word(block, 0), 0x0A32_4655);
+            assert_eq!(word(block, 1), 0x9E5D_5157);
+            assert_eq!(word(block, 2), 0x2000);
+            assert_eq!(word(block, 5), number as u32);
+            assert_eq!(word(block, 6), 2);
+            assert_eq!(word(block, 7), 0x4FB2_D5BD);
+            assert_eq!(word(block, 127), 0x0AB1_6F30);
+        }
+        assert_eq!(blocks[0][32 + 127], 0xAA);
+        assert_eq!(blocks[0][32 + 128], 0);
+    }
+}
@@ -0,0 +1,232 @@
+//! SD and eMMC boot configuration
+//!
+//! When a chip boots from uSDHC, the boot ROM takes the uSDHC instance and the
+//! card type from the boot configuration fuses or pins. An eMMC device also
+//! keeps its own boot configuration in its `EXT_CSD` register: the partition
+//! that holds the boot image, and the bus width and timing that the device uses
+//! during boot. An SD card doesn't have a boot configuration area.
+//!
+//! Use [`EmmcBootConfiguration`] to compute the `EXT_CSD` values, and the
+//! `CMD6` (`SWITCH`) arguments that program them. Provision the eMMC device
+//! with these commands before you write the boot image.
+//!
+//! ```
+//! use imxrt_boot_gen::usdhc::{BootPartition, BusWidth, EmmcBootConfiguration, Timing};
+//!
+//! const EMMC: EmmcBootConfiguration = EmmcBootConfiguration::new()
+//!     .boot_partition(BootPartition::Boot1)
+//!     .boot_ack(true)
+//!     .bus_width(BusWidth::Eight)
+//!     .timing(Timing::HighSpeed)
+//!     .finish();
+//!
+//! assert_eq!(EMMC.partition_config(), 0x48);
+//! assert_eq!(EMMC.boot_bus_conditions(), 0x0A);
+//! ```
+
+/// The data bus width during boot
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(u8)]
+pub enum BusWidth {
+    /// One data line. This is the default.
+    One = 0,
+    /// Four data lines.
+    Four = 1,
+    /// Eight data lines.
+    Eight = 2,
+}
+
+/// The bus timing during boot
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(u8)]
+pub enum Timing {
+    /// Single data rate, backwards-compatible timing. This is the default.
+    Normal = 0,
+    /// Single data rate, high-speed timing.
+    HighSpeed = 1,
+    /// Dual data rate. Requires a four or eight line bus.
+    Ddr = 2,
+}
+
+/// The eMMC partition that holds the boot image
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(u8)]
+pub enum BootPartition {
+    /// Boot isn't enabled. This is the default.
+    Disabled = 0,
+    /// Boot partition 1.
+    Boot1 = 1,
+    /// Boot partition 2.
+    Boot2 = 2,
+    /// The user data area.
+    User = 7,
+}
+
+/// The `EXT_CSD` byte index of `BOOT_BUS_CONDITIONS`.
+pub const BOOT_BUS_CONDITIONS: u8 = 177;
+/// The `EXT_CSD` byte index of `PARTITION_CONFIG`.
+pub const PARTITION_CONFIG: u8 = 179;
+
+/// An eMMC device's boot configuration
+///
+/// Unless otherwise specified, the device doesn't boot, and it boots with a one
+/// line bus and backwards-compatible timing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EmmcBootConfiguration {
+    boot_partition: BootPartition,
+    boot_ack: bool,
+    bus_width: BusWidth,
+    timing: Timing,
+    retain_bus_conditions: bool,
+}
+
+impl Default for EmmcBootConfiguration {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl EmmcBootConfiguration {
+    /// Create a boot configuration that doesn't boot.
+    pub const fn new() -> Self {
+        EmmcBootConfiguration {
+            boot_partition: BootPartition::Disabled,
+            boot_ack: false,
+            bus_width: BusWidth::One,
+            timing: Timing::Normal,
+            retain_bus_conditions: false,
+        }
+    }
+    /// Set the partition that holds the boot image.
+    pub const fn boot_partition(mut self, boot_partition: BootPartition) -> Self {
+        self.boot_partition = boot_partition;
+        self
+    }
+    /// Set `BOOT_ACK`, so that the device acknowledges the boot operation.
+    pub const fn boot_ack(mut self, boot_ack: bool) -> Self {
+        self.boot_ack = boot_ack;
+        self
+    }
+    /// Set the data bus width during boot.
+    pub const fn bus_width(mut self, bus_width: BusWidth) -> Self {
+        self.bus_width = bus_width;
+        self
+    }
+    /// Set the bus timing during boot.
+    pub const fn timing(mut self, timing: Timing) -> Self {
+        self.timing = timing;
+        self
+    }
+    /// Keep the boot bus width and timing after boot.
+    ///
+    /// By default, the device returns to a one line bus and backwards-compatible
+    /// timing after boot.
+    pub const fn retain_bus_conditions(mut self, retain_bus_conditions: bool) -> Self {
+        self.retain_bus_conditions = retain_bus_conditions;
+        self
+    }
+    /// Returns the partition that holds the boot image.
+    pub const fn get_boot_partition(&self) -> BootPartition {
+        self.boot_partition
+    }
+    /// Returns `true` if the device acknowledges the boot operation.
+    pub const fn get_boot_ack(&self) -> bool {
+        self.boot_ack
+    }
+    /// Returns the data bus width during boot.
+    pub const fn get_bus_width(&self) -> BusWidth {
+        self.bus_width
+    }
+    /// Returns the bus timing during boot.
+    pub const fn get_timing(&self) -> Timing {
+        self.timing
+    }
+    /// Returns `true` if the device keeps the boot bus conditions after boot.
+    pub const fn get_retain_bus_conditions(&self) -> bool {
+        self.retain_bus_conditions
+    }
+    /// Returns the `PARTITION_CONFIG` value.
+    ///
+    /// The value leaves `PARTITION_ACCESS` at zero, so that the host accesses
+    /// the user data area.
+    pub const fn partition_config(&self) -> u8 {
+        ((self.boot_ack as u8) << 6) | ((self.boot_partition as u8) << 3)
+    }
+    /// Returns the `BOOT_BUS_CONDITIONS` value.
+    pub const fn boot_bus_conditions(&self) -> u8 {
+        ((self.timing as u8) << 3)
+            | ((self.retain_bus_conditions as u8) << 2)
+            | (self.bus_width as u8)
+    }
+    /// Returns the `CMD6` arguments that write the boot configuration.
+    ///
+    /// Each argument writes one `EXT_CSD` byte. Send the commands in order.
+    pub const fn switch_arguments(&self) -> [u32; 2] {
+        [
+            switch_argument(BOOT_BUS_CONDITIONS, self.boot_bus_conditions()),
+            switch_argument(PARTITION_CONFIG, self.partition_config()),
+        ]
+    }
+    /// Check that the bus width supports the timing.
+    ///
+    /// Call `finish` at the end of your builder chain.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the timing is DDR with a one line bus. When evaluated in a
+    /// `const` or `static` initializer, the panic is a compile-time error.
+    pub const fn finish(self) -> Self {
+        assert!(
+            !matches!((self.timing, self.bus_width), (Timing::Ddr, BusWidth::One)),
+            "eMMC DDR boot requires a four or eight line bus"
+        );
+        self
+    }
+}
+
+/// Returns the `CMD6` argument that writes `value` into the `EXT_CSD` byte at
+/// `index`.
+pub const fn switch_argument(index: u8, value: u8) -> u32 {
+    const WRITE_BYTE: u32 = 0b11;
+    (WRITE_BYTE << 24) | ((index as u32) << 16) | ((value as u32) << 8)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{switch_argument, BootPartition, BusWidth, EmmcBootConfiguration, Timing};
+
+    #[test]
+    fn defaults() {
+        let config = EmmcBootConfiguration::new();
+        assert_eq!(config.partition_config(), 0);
+        assert_eq!(config.boot_bus_conditions(), 0);
+    }
+
+    #[test]
+    fn values() {
+        let config = EmmcBootConfiguration::new()
+            .boot_partition(BootPartition::User)
+            .bus_width(BusWidth::Four)
+            .timing(Timing::Ddr)
+            .retain_bus_conditions(true)
+            .finish();
+        assert_eq!(config.partition_config(), 0x38);
+        assert_eq!(config.boot_bus_conditions(), 0x15);
+        assert_eq!(config.switch_arguments(), [0x03B1_1500, 0x03B3_3800]);
+    }
+
+    #[test]
+    fn switch() {
+        assert_eq!(switch_argument(179, 0x48), 0x03B3_4800);
+    }
+
+    #[test]
+    #[should_panic(expected = "four or eight line bus")]
+    fn ddr_one_line() {
+        let _ = EmmcBootConfiguration::new().timing(Timing::Ddr).finish();
+    }
+}
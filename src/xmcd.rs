@@ -0,0 +1,199 @@
+//! External memory configuration data (XMCD)
+//!
+//! The 1170 and 1180 boot ROMs can configure external RAM before they load
+//! your program. The XMCD tells the boot ROM which memory interface to
+//! configure, and how to configure it. The XMCD starts with a header, followed by
+//! the configuration in one of two formats:
+//!
+//! - [`BlockType::Simplified`] configurations are one or more option words. The
+//!   boot ROM derives the complete configuration from the options.
+//! - [`BlockType::Full`] configurations are complete configuration blocks, like
+//!   the SEMC SDRAM configuration, or a FlexSPI configuration block.
+//!
+//! The option words are raw `u32`s. This module doesn't have typed builders
+//! for the FlexSPI RAM or SEMC SDRAM option fields, because their layouts
+//! couldn't be checked against the reference manuals. See your chip's
+//! reference manual for the option word fields, and for the configuration
+//! block layouts. Like the other boot data structures, you're
+//! responsible for placing the XMCD where the boot ROM expects it.
+//!
+//! ```
+//! use imxrt_boot_gen::xmcd::{MemoryInterface, Xmcd};
+//!
+//! const XMCD: Xmcd = Xmcd::options(MemoryInterface::FlexSpi, 2, &[0xC000_0000]);
+//!
+//! #[link_section = ".xmcd"]
+//! static XMCD_BYTES: [u8; XMCD.len()] = XMCD.to_array();
+//! # assert_eq!(&XMCD_BYTES[..4], &0xC002_0008u32.to_le_bytes());
+//! ```
+
+/// The largest XMCD, including its header, in bytes.
+///
+/// This is the largest size that the header can describe.
+pub const MAX_SIZE: usize = 0xFFF;
+
+const HEADER_SIZE: usize = 4;
+const TAG: u32 = 0xC;
+const VERSION: u32 = 0;
+
+/// The memory interface that the XMCD configures
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum MemoryInterface {
+    /// FlexSPI RAM, like HyperRAM or octal PSRAM.
+    FlexSpi = 0,
+    /// SEMC SDRAM.
+    Semc = 1,
+}
+
+/// The format of the configuration that follows the XMCD header
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum BlockType {
+    /// One or more option words.
+    Simplified = 0,
+    /// A complete configuration block.
+    Full = 1,
+}
+
+/// External memory configuration data
+///
+/// Use [`options`](Xmcd::options) for a simplified configuration, or
+/// [`full`](Xmcd::full) for a complete configuration block.
+#[derive(Debug, Clone, Copy)]
+pub struct Xmcd {
+    interface: MemoryInterface,
+    instance: u8,
+    block_type: BlockType,
+    bytes: [u8; MAX_SIZE],
+    len: usize,
+}
+
+impl Xmcd {
+    const fn new(interface: MemoryInterface, instance: u8, block_type: BlockType) -> Self {
+        assert!(instance < 16, "The XMCD instance must be less than 16");
+        Xmcd {
+            interface,
+            instance,
+            block_type,
+            bytes: [0; MAX_SIZE],
+            len: HEADER_SIZE,
+        }
+        .with_header()
+    }
+    /// Create a simplified configuration from option words.
+    ///
+    /// `instance` is the memory interface instance, like `2` for FlexSPI2.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are no option words, or if `instance` is 16 or more.
+    pub const fn options(interface: MemoryInterface, instance: u8, options: &[u32]) -> Self {
+        assert!(
+            !options.is_empty(),
+            "The XMCD needs at least one option word"
+        );
+        let mut xmcd = Self::new(interface, instance, BlockType::Simplified);
+        let mut idx = 0;
+        while idx < options.len() {
+            xmcd = xmcd.append(&options[idx].to_le_bytes());
+            idx += 1;
+        }
+        xmcd
+    }
+    /// Create a full configuration from a complete configuration block.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the XMCD exceeds [`MAX_SIZE`] bytes, or if `instance` is 16
+    /// or more.
+    pub const fn full(interface: MemoryInterface, instance: u8, config: &[u8]) -> Self {
+        Self::new(interface, instance, BlockType::Full).append(config)
+    }
+    /// Returns the memory interface.
+    pub const fn get_interface(&self) -> MemoryInterface {
+        self.interface
+    }
+    /// Returns the memory interface instance.
+    pub const fn get_instance(&self) -> u8 {
+        self.instance
+    }
+    /// Returns the configuration format.
+    pub const fn get_block_type(&self) -> BlockType {
+        self.block_type
+    }
+    /// Returns the XMCD header.
+    pub const fn header(&self) -> u32 {
+        (TAG << 28)
+            | (VERSION << 24)
+            | ((self.interface as u32) << 20)
+            | ((self.instance as u32) << 16)
+            | ((self.block_type as u32) << 12)
+            | self.len as u32
+    }
+    /// Returns the size of the XMCD, including its header, in bytes.
+    #[allow(clippy::len_without_is_empty)]
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+    /// Returns the XMCD's bytes.
+    pub const fn as_bytes(&self) -> &[u8] {
+        self.bytes.split_at(self.len).0
+    }
+    /// Copy the XMCD into an array that's exactly [`len`](Self::len) bytes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `N` isn't the XMCD's length.
+    pub const fn to_array<const N: usize>(&self) -> [u8; N] {
+        assert!(N == self.len, "The array length must equal the XMCD length");
+        let mut array = [0; N];
+        crate::raw::put(&mut array, 0, self.as_bytes());
+        array
+    }
+
+    const fn append(mut self, bytes: &[u8]) -> Self {
+        assert!(
+            self.len + bytes.len() <= MAX_SIZE,
+            "The XMCD exceeds its maximum size"
+        );
+        crate::raw::put(&mut self.bytes, self.len, bytes);
+        self.len += bytes.len();
+        self.with_header()
+    }
+    const fn with_header(mut self) -> Self {
+        let header = self.header();
+        crate::raw::put(&mut self.bytes, 0, &header.to_le_bytes());
+        self
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{BlockType, MemoryInterface, Xmcd};
+
+    #[test]
+    fn simplified() {
+        let xmcd = Xmcd::options(MemoryInterface::FlexSpi, 1, &[0xC000_0700, 0x0000_0001]);
+        assert_eq!(xmcd.header(), 0xC001_000C);
+        assert_eq!(
+            xmcd.as_bytes(),
+            &[0x0C, 0x00, 0x01, 0xC0, 0x00, 0x07, 0x00, 0xC0, 0x01, 0x00, 0x00, 0x00]
+        );
+        assert_eq!(xmcd.get_block_type(), BlockType::Simplified);
+    }
+
+    #[test]
+    fn full() {
+        let xmcd = Xmcd::full(MemoryInterface::Semc, 0, &[0xA1; 60]);
+        assert_eq!(xmcd.header(), 0xC010_1040);
+        assert_eq!(xmcd.len(), 64);
+        assert_eq!(&xmcd.as_bytes()[4..], &[0xA1; 60]);
+    }
+
+    #[test]
+    #[should_panic(expected = "maximum size")]
+    fn too_large() {
+        let _ = Xmcd::full(MemoryInterface::Semc, 0, &[0; 4092]);
+    }
+}
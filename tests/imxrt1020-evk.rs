@@ -40,7 +40,7 @@ const FLEXSPI_CONFIGURATION_BLOCK: flexspi::ConfigurationBlock =
         .column_address_width(ColumnAddressWidth::OtherDevices)
         .device_mode_configuration(DeviceModeConfiguration::Disabled)
         .wait_time_cfg_commands(WaitTimeConfigurationCommands::disable())
-        .flash_size(SerialFlashRegion::A1, 0x0080_0000)
+        .flash_size(SerialFlashRegion::A1, Density::from_mebibytes(8))
         .serial_clk_freq(SerialClockFrequency::MHz100)
         .serial_flash_pad_type(FlashPadType::Quad);
 
@@ -68,6 +68,12 @@ fn imxrt1020_evk() {
     }
 }
 
+#[test]
+fn issi_preset_read_sequence() {
+    let preset = nor::presets::issi::IS25LP_WP.lookup_table();
+    assert_eq!(preset.get_command(Command::Read), SEQ_READ);
+}
+
 // A known, working FCB for the MIMXRT1020-EVK.
 #[rustfmt::skip]
 const EXPECTED: [u32; 128] = [
@@ -32,3 +32,43 @@ fn ip_serial_clock_frequency() {
     assert_eq!(SerialClockFrequency::MHz133 as u32, 7);
     assert_eq!(SerialClockFrequency::MHz166 as u32, 8);
 }
+
+#[test]
+fn hyperflash_preset() {
+    use imxrt_boot_gen::serial_flash::nor::presets::infineon;
+
+    let fcb = infineon::s26ks_fcb(infineon::S26KS512S).to_bytes();
+    let word = |offset: usize| u32::from_le_bytes(fcb[offset..offset + 4].try_into().unwrap());
+
+    // readSampleClkSrc from DQS, then columnAddressWidth.
+    assert_eq!(fcb[0x0C], 3);
+    assert_eq!(fcb[0x0F], 3);
+    // DDR, word-addressable, safe configuration, and differential clock.
+    assert_eq!(word(0x40), 0x59);
+    assert_eq!(word(0x50), 64 * 1024 * 1024);
+    // dataValidTime: port A time_100ps and delay_cells.
+    assert_eq!(word(0x78), 0x0000_1010);
+    // busyBitPolarity, busyOffset.
+    assert_eq!(word(0x7C), 0x0001_000F);
+
+    // The read and status read sequences from the MIMXRT1050-EVKB HyperFlash FCB.
+    #[rustfmt::skip]
+    const LUT: [u32; 12] = [
+        0x8B18_87A0, 0xA704_8F10, 0, 0,
+        0x8700_8700, 0x87AA_8700, 0x8705_8700, 0x8770_8700,
+        0x8B18_8780, 0xB70B_8F10, 0x0000_A704, 0,
+    ];
+    for (idx, expected) in LUT.iter().enumerate() {
+        assert_eq!(word(0x80 + idx * 4), *expected, "lookupTable[{idx}]");
+    }
+
+    // lutCustomSeq: read status, write enable, erase sector, page program, chip erase.
+    assert_eq!(word(0x184), 0x0000_0102);
+    assert_eq!(word(0x188), 0x0000_0302);
+    assert_eq!(word(0x18C), 0x0000_0504);
+    assert_eq!(word(0x190), 0x0000_0902);
+    assert_eq!(word(0x194), 0x0000_0B04);
+
+    assert_eq!(word(0x1C0), 512);
+    assert_eq!(word(0x1C4), 256 * 1024);
+}
@@ -14,6 +14,7 @@ fn serial_clock_frequency() {
     assert_eq!(SerialClockFrequency::MHz120 as u32, 6);
     assert_eq!(SerialClockFrequency::MHz133 as u32, 7);
     assert_eq!(SerialClockFrequency::MHz166 as u32, 8);
+    assert_eq!(SerialClockFrequency::MHz200 as u32, 9);
 }
 
 #[test]
@@ -30,3 +31,16 @@ fn ip_serial_clock_frequency() {
     assert_eq!(SerialClockFrequency::MHz120 as u32, 6);
     assert_eq!(SerialClockFrequency::MHz133 as u32, 7);
 }
+
+#[test]
+fn preset_device_mode_type() {
+    use imxrt_boot_gen::flexspi::DeviceModeType;
+    use imxrt_boot_gen::serial_flash::nor::presets::winbond;
+
+    let fcb = winbond::W25Q_JV.fcb(winbond::W25Q128JV);
+    assert_eq!(
+        fcb.mem_cfg().get_device_mode_type(),
+        Some(DeviceModeType::QuadEnable)
+    );
+    assert_eq!(fcb.get_extras().get_block_size(), Some(64 * 1024));
+}
@@ -14,6 +14,7 @@ fn serial_clock_frequency() {
     assert_eq!(SerialClockFrequency::MHz120 as u32, 6);
     assert_eq!(SerialClockFrequency::MHz133 as u32, 7);
     assert_eq!(SerialClockFrequency::MHz166 as u32, 8);
+    assert_eq!(SerialClockFrequency::MHz200 as u32, 9);
 }
 
 #[test]
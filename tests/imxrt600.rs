@@ -0,0 +1,62 @@
+//! Tests specific to 500 and 600 families.
+
+#![cfg(any(feature = "imxrt500", feature = "imxrt600"))]
+
+#[test]
+fn serial_clock_frequency() {
+    use imxrt_boot_gen::flexspi::SerialClockFrequency;
+
+    assert_eq!(SerialClockFrequency::MHz30 as u32, 1);
+    assert_eq!(SerialClockFrequency::MHz50 as u32, 2);
+    assert_eq!(SerialClockFrequency::MHz60 as u32, 3);
+    assert_eq!(SerialClockFrequency::MHz80 as u32, 4);
+    assert_eq!(SerialClockFrequency::MHz100 as u32, 5);
+    assert_eq!(SerialClockFrequency::MHz120 as u32, 6);
+    assert_eq!(SerialClockFrequency::MHz133 as u32, 7);
+    assert_eq!(SerialClockFrequency::MHz166 as u32, 8);
+    assert_eq!(SerialClockFrequency::MHz200 as u32, 9);
+}
+
+#[test]
+fn ip_serial_clock_frequency() {
+    use imxrt_boot_gen::serial_flash::nor::SerialClockFrequency;
+
+    assert_eq!(SerialClockFrequency::NoChange as u32, 0);
+
+    assert_eq!(SerialClockFrequency::MHz30 as u32, 1);
+    assert_eq!(SerialClockFrequency::MHz50 as u32, 2);
+    assert_eq!(SerialClockFrequency::MHz60 as u32, 3);
+    assert_eq!(SerialClockFrequency::MHz80 as u32, 4);
+    assert_eq!(SerialClockFrequency::MHz100 as u32, 5);
+    assert_eq!(SerialClockFrequency::MHz120 as u32, 6);
+    assert_eq!(SerialClockFrequency::MHz133 as u32, 7);
+}
+
+#[test]
+fn dll_cr_val() {
+    use imxrt_boot_gen::flexspi::{ConfigurationBlock, LookupTable, FIELDS};
+
+    let block = ConfigurationBlock::new(LookupTable::new())
+        .dll0_cr_val(0x0000_0100)
+        .dll1_cr_val(0x0000_0079);
+    let bytes = block.to_bytes();
+    assert_eq!(&bytes[0x1B0..0x1B4], &[0x00, 0x01, 0x00, 0x00]);
+    assert_eq!(&bytes[0x1B4..0x1B8], &[0x79, 0x00, 0x00, 0x00]);
+    assert!(bytes[0x1B8..].iter().all(|byte| *byte == 0));
+
+    let parsed = ConfigurationBlock::from_bytes(&bytes).unwrap();
+    assert_eq!(parsed.get_dll0_cr_val(), 0x100);
+    assert_eq!(parsed.get_dll1_cr_val(), 0x79);
+
+    let field = FIELDS.iter().find(|f| f.name == "dll1CrVal").unwrap();
+    assert_eq!((field.offset, field.size), (0x1B4, 4));
+}
+
+#[test]
+fn boot_addresses() {
+    use imxrt_boot_gen::Imxrt;
+
+    assert_eq!(Imxrt::SELECTED.fcb_address(), 0x0800_0400);
+    assert_eq!(Imxrt::SELECTED.ivt_offset(), None);
+    assert_eq!(Imxrt::SELECTED.uf2_family_id(), None);
+}
@@ -77,7 +77,7 @@ const FLEXSPI_CONFIGURATION_BLOCK: flexspi::ConfigurationBlock =
         .column_address_width(ColumnAddressWidth::OtherDevices)
         .device_mode_configuration(DeviceModeConfiguration::Disabled)
         .wait_time_cfg_commands(WaitTimeConfigurationCommands::disable())
-        .flash_size(SerialFlashRegion::A1, 0x0020_0000)
+        .flash_size(SerialFlashRegion::A1, Density::from_mebibytes(2))
         .serial_clk_freq(SerialClockFrequency::MHz60)
         .serial_flash_pad_type(FlashPadType::Quad);
 